@@ -1,6 +1,8 @@
 use crossterm::event::KeyEvent;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use crate::state::annotation_state::MergeResolution;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum QuitCombo {
     CtrlC,
     CtrlD,
@@ -16,7 +18,7 @@ impl QuitCombo {
 }
 
 /// Central action enum — all state mutations flow through here.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Action {
     // Lifecycle
     Quit,
@@ -29,27 +31,48 @@ pub enum Action {
     NavigatorTop,
     NavigatorBottom,
     SelectFile(usize),
+    NextFile,
+    PrevFile,
 
     // Diff view
     ScrollUp,
     ScrollDown,
     ScrollToTop,
     ScrollToBottom,
+    /// First key of a two-key chord (`zc`, `gd`, `yy`, ...) in the diff view.
+    StartChord(char),
+    /// A key that only ends a pending chord.
+    Noop,
+    GoToDefinition,
+    CopyCursorLineContent,
     ScrollPageUp,
     ScrollPageDown,
     ToggleViewMode,
     ToggleWhitespace,
+    ToggleLineWrap,
+    FoldAllHunks,
+    UnfoldAllHunks,
+    ToggleAnnotationsVisible,
+    ToggleInlineAnnotations,
+    ScrollLeft,
+    ScrollRight,
 
     ExpandContext,
 
     // Hunk navigation
     JumpNextHunk,
     JumpPrevHunk,
+    NextConflict,
+    PrevConflict,
 
     // Focus
     FocusNavigator,
     FocusDiffView,
 
+    // External editor / file manager
+    OpenInEditor,
+    OpenFileInExplorer,
+
     // File search (navigator)
     StartSearch,
     ConfirmSearch,
@@ -76,24 +99,107 @@ pub enum Action {
     // Git mutations
     StageFile,
     UnstageFile,
+    ToggleFileMark,
+    CollapseFile,
+    ExpandFile,
+    BulkStage,
+    BulkUnstage,
     RestoreFile,
     OpenCommitDialog,
     ConfirmCommit,
     CancelCommit,
     CommitChar(char),
+    ToggleCommitAmend,
     CommitBackspace,
     CommitNewline,
 
+    // Annotation export / import
+    ExportAnnotations,
+    ImportGithubReviews,
+
     // Restore confirm
     ConfirmRestore,
     CancelRestore,
 
+    // Single-hunk apply/revert
+    ApplyHunk,
+    RevertHunk,
+    /// Show how many of the selected file's changed lines are staged.
+    DiffCurrentFilePatch,
+    ClosePatchSummary,
+    /// Stage (or unstage) the hunk under the cursor, from any row in it.
+    StageHunk,
+    UnstageHunk,
+    ConfirmHunkPatch,
+    CancelHunkPatch,
+
     // Target change
     OpenTargetDialog,
+    NewTab,
+    CloseTab,
+    NextTab,
+    PrevTab,
     ConfirmTarget,
     CancelTarget,
     TargetChar(char),
     TargetBackspace,
+    /// Insert the first branch completion.
+    TargetComplete,
+
+    // Push
+    Push,
+    ConfirmPushUpstream,
+    CancelPushUpstream,
+    PushDialogChar(char),
+    PushDialogBackspace,
+
+    // Interactive rebase
+    /// Ask how many commits to rebase, then run `git rebase -i HEAD~N` in a PTY.
+    RebaseInteractive,
+    ConfirmRebaseInteractive,
+    CancelRebaseInteractive,
+    RebaseDialogChar(char),
+    RebaseDialogBackspace,
+
+    // Glob file filter
+    FilterFiles,
+    ConfirmFilter,
+    CancelFilter,
+    FilterChar(char),
+    FilterBackspace,
+    OpenJumpToHunk,
+    JumpToHunk,
+    CancelJumpToHunk,
+    JumpToHunkChar(char),
+    JumpToHunkBackspace,
+    SplitHunk,
+    IncreaseContext,
+    DecreaseContext,
+    ToggleFileLog,
+    FileLogUp,
+    FileLogDown,
+    FileLogSelect,
+    /// Cycle the navigator between all files and one status at a time.
+    ToggleFileStatus,
+    /// Revert the selected file log commit into the working tree.
+    RevertCommit,
+    ConfirmRevertCommit,
+    CancelRevertCommit,
+    ReplaceInDiff,
+    ReplaceChar(char),
+    ReplaceBackspace,
+    ReplaceSwitchField,
+    ConfirmReplace,
+    CancelReplace,
+    ApplyReplace,
+    CancelApplyReplace,
+
+    // Worktree creation
+    OpenWorktreeDialog,
+    ConfirmWorktreeDialog,
+    CancelWorktreeDialog,
+    WorktreeDialogChar(char),
+    WorktreeDialogBackspace,
 
     // Worktree
     ToggleWorktreeBrowser,
@@ -102,7 +208,14 @@ pub enum Action {
     WorktreeSelect,
     WorktreeRefresh,
     WorktreeFreeze,
+    LockWorktree,
+    UnlockWorktree,
+    WorktreeFetch,
+    WorktreePull,
     WorktreeBack,
+    DeleteWorktree,
+    ConfirmDeleteWorktree,
+    CancelDeleteWorktree,
 
     // Visual selection
     EnterVisualMode,
@@ -119,6 +232,8 @@ pub enum Action {
     CommentNewline,
     // Annotations
     DeleteAnnotation,
+    UndoAnnotation,
+    RedoAnnotation,
     NextAnnotation,
     PrevAnnotation,
     OpenAnnotationMenu,
@@ -127,10 +242,44 @@ pub enum Action {
     AnnotationMenuEdit,
     AnnotationMenuDelete,
     CancelAnnotationMenu,
+    // Bookmarks
+    ToggleBookmark,
+    NextBookmark,
+    PrevBookmark,
+
+    // Right-click menu in the diff view
+    OpenContextMenu(u16, u16),
+    ContextMenuUp,
+    ContextMenuDown,
+    ContextMenuSelect,
+    ContextMenuClick(usize),
+    CloseContextMenu,
 
     // Prompt / clipboard
     CopyPromptToClipboard,
+    CopyFileDiff,
+    CopyHunkPatch,
+    /// Copy the changed files, one `M path` line each, to the clipboard.
+    CopyFileList,
+    /// Copy every file's diff to the clipboard as JSON, for LLM tool calls.
+    DiffFormatJson,
     TogglePromptPreview,
+    ToggleFocusMode,
+    FocusPromptPreview,
+    PromptPreviewGrow,
+    PromptPreviewShrink,
+    PromptPreviewScrollUp,
+    PromptPreviewScrollDown,
+    PromptPreviewPageUp,
+    PromptPreviewPageDown,
+    PromptPreviewTop,
+    PromptPreviewBottom,
+    StartPreviewSearch,
+    EndPreviewSearch,
+    PreviewSearchChar(char),
+    PreviewSearchBackspace,
+    PreviewSearchNext,
+    PreviewSearchPrev,
 
     // Agent selector
     OpenAgentSelector,
@@ -139,6 +288,7 @@ pub enum Action {
     AgentSelectorFilter(char),
     AgentSelectorBackspace,
     AgentSelectorCycleModel,
+    FavoriteAgent,
     SelectAgent,
     CancelAgentSelector,
 
@@ -146,7 +296,10 @@ pub enum Action {
     SwitchToAgentOutputs,
     AgentOutputsUp,
     AgentOutputsDown,
+    AgentOutputsListScrollUp,
+    AgentOutputsListScrollDown,
     AgentOutputsCopyPrompt,
+    RerunAgent,
     KillAgentProcess,
     AgentOutputsSwitchWorktree,
 
@@ -179,6 +332,17 @@ pub enum Action {
     SettingsDown,
     SettingsLeft,
     SettingsRight,
+    SettingsActivate,
+
+    // Session file export/import
+    ExportSession,
+    ImportSession,
+    SessionDialogChar(char),
+    SessionDialogBackspace,
+    ConfirmSessionDialog,
+    CancelSessionDialog,
+    ResolveSessionImport(MergeResolution),
+    CancelSessionImport,
 
     // Feedback summary
     ToggleFeedbackSummary,
@@ -187,6 +351,15 @@ pub enum Action {
     FeedbackSummaryCopyJson,
     FeedbackSummaryCopyPrompt,
 
+    // Review summary
+    ToggleReviewSummary,
+    ReviewSummaryUp,
+    ReviewSummaryDown,
+    ReviewSummarySelect,
+    ReviewSummaryDeleteFile,
+    /// Copy a Markdown checklist of the reviewed files and their annotations.
+    ExportChecklist,
+
     // Generic text input navigation
     TextCursorLeft,
     TextCursorRight,
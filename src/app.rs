@@ -1,8 +1,9 @@
 use anyhow::Result;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use std::cell::Cell;
-use std::path::PathBuf;
-use std::time::Duration;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use crate::action::{Action, QuitCombo};
 use crate::async_diff::{DiffRequest, DiffWorker};
@@ -14,40 +15,114 @@ use crate::components::checklist_panel::ChecklistPanel;
 use crate::components::comment_editor::render_comment_editor;
 use crate::components::commit_dialog::render_commit_dialog;
 use crate::components::context_bar::ContextBar;
+use crate::components::context_menu::{context_menu_rect, render_context_menu, CONTEXT_MENU_ITEMS};
 use crate::components::diff_view::{
-    compute_split_visual_row_metrics, compute_unified_visual_row_metrics, DiffView,
+    compute_split_visual_row_metrics, compute_unified_visual_row_metrics, DiffView, MINIMAP_WIDTH,
 };
+use crate::components::file_log::{render_file_log, FILE_LOG_HEIGHT};
+use crate::components::filter_dialog::render_filter_dialog;
 use crate::components::global_search_bar::render_global_search_bar;
+use crate::components::hunk_patch_confirm::render_hunk_patch_confirm;
+use crate::components::jump_hunk_dialog::render_jump_hunk_dialog;
 use crate::components::navigator::Navigator;
+use crate::components::patch_summary::render_patch_summary;
 use crate::components::prompt_preview::render_prompt_preview;
+use crate::components::push_dialog::render_push_dialog;
+use crate::components::rebase_dialog::render_rebase_dialog;
+use crate::components::replace_dialog::{render_replace_confirm, render_replace_dialog};
 use crate::components::restore_confirm::render_restore_confirm;
+use crate::components::revert_confirm::render_revert_confirm;
+use crate::components::review_summary::{review_summary_rows, ReviewSummaryRow};
+use crate::components::session_dialog::{render_session_dialog, render_session_import_confirm};
 use crate::components::settings_modal::render_settings_modal;
-use crate::components::target_dialog::render_target_dialog;
+use crate::components::target_dialog::{branch_completions, render_target_dialog};
 use crate::components::which_key;
 use crate::components::worktree_browser::WorktreeBrowser;
+use crate::components::worktree_dialog::render_worktree_dialog;
+use crate::components::worktree_remove_confirm::render_worktree_remove_confirm;
 use crate::components::Component;
 use crate::config::{
-    self, checklist_config_to_items, load_checklist_config, MdiffConfig, PersistentSettings,
+    self, checklist_config_to_items, load_checklist_config, AgentProviderConfig, MdiffConfig,
+    PersistentSettings, MAX_CONTEXT, MAX_CONTEXT_PADDING, MIN_CONTEXT, PROMPT_PREVIEW_RATIO_MAX,
+    PROMPT_PREVIEW_RATIO_MIN,
 };
-use crate::display_map::{build_display_map, DisplayRowInfo};
+use crate::diff_stat;
+use crate::display_map::{build_display_map, change_block_rows, conflict_rows, DisplayRowInfo};
+use crate::editor;
 use crate::event::{
     map_key_to_action, map_mouse_to_action, Event, EventReader, KeyContext, MouseContext,
 };
+use crate::export;
 use crate::git::commands::GitCli;
-use crate::git::types::{ComparisonTarget, DiffLineOrigin, FileDelta};
+use crate::git::patch;
+use crate::git::types::{CommitSummary, ComparisonTarget, DiffLineOrigin, FileDelta, FileStatus};
 use crate::git::worktree;
+use crate::git::DiffEngine;
+use crate::github::{self, ReviewComment};
 use crate::highlight::HighlightEngine;
+use crate::image_preview::{is_image_path, ImagePreview, ImageSide};
+use crate::keybindings::KeySpec;
+use crate::platform;
 use crate::pty_runner::{key_event_to_bytes, PtyEvent, PtyRunner};
 use crate::session;
-use crate::state::agent_state::{AgentRun, AgentRunStatus};
-use crate::state::annotation_state::{Annotation, LineAnchor};
-use crate::state::app_state::{ActiveView, FocusPanel};
-use crate::state::review_state::compute_diff_hashes;
+use crate::state::agent_state::{parse_tokens_used, AgentRun, AgentRunStatus};
+use crate::state::annotation_state::MergeResolution;
+use crate::state::annotation_state::{
+    format_with_tags, parse_tags, Annotation, AnnotationUndoEntry, LineAnchor,
+};
+use crate::state::app_state::{
+    ActiveView, Bookmark, ContextMenuState, FocusPanel, HunkPatchRequest, PatchSummary,
+    PendingSessionImport, ReplacePlan, SessionDialogMode,
+};
+use crate::state::navigator_state::NavigatorStatusFilter;
+use crate::state::review_state::{change_blocks, compute_diff_hashes, FileReviewStatus};
 use crate::state::settings_state::SETTINGS_ROW_COUNT;
-use crate::state::{AppState, ChecklistState, DiffOptions, DiffViewMode};
-use crate::theme::{next_theme, prev_theme, Theme};
-use crate::tui::Tui;
+use crate::state::{
+    AppState, ChecklistState, DiffOptions, DiffViewMode, GlobalSearchState, TabState,
+};
+use crate::tags::{self, TagEntry};
+use crate::theme::{next_theme, prev_theme, ColorSupport, Theme};
+use crate::tui::{self, Tui};
 use crossterm::event::MouseEventKind;
+use tokio::sync::mpsc;
+
+/// Columns shifted per horizontal scroll step when line wrap is off.
+const HSCROLL_STEP: usize = 8;
+/// Runs moved per PageUp/PageDown in the agent outputs run list.
+const RUN_LIST_PAGE_STEP: usize = 5;
+/// Lines moved per PageUp/PageDown in the prompt preview.
+const PROMPT_PREVIEW_PAGE_STEP: usize = 10;
+/// Percentage points moved per `Alt+↑`/`Alt+↓` when resizing the prompt preview.
+const PROMPT_PREVIEW_RATIO_STEP: u16 = 5;
+/// Lines kept above a search match scrolled to in the prompt preview.
+const PROMPT_PREVIEW_MATCH_MARGIN: usize = 2;
+/// How long the first key of a chord waits for the second before it runs
+/// its own action (`z` wraps lines, `g` scrolls to the top, `y` copies the prompt).
+const CHORD_TICKS: u32 = 10;
+/// Context lines added or removed per `+`/`-` press.
+const CONTEXT_STEP: usize = 3;
+/// How long safe mode shows "Would stage: …" before asking for confirmation (2s).
+const SAFE_MODE_PREVIEW_TICKS: u32 = 40;
+/// How long the safe-mode confirm prompt stays in the status bar.
+const SAFE_MODE_PROMPT_TICKS: u32 = 100;
+
+/// Instruction preamble used when neither the agent nor the config sets `prompt_template`.
+const DEFAULT_PROMPT_TEMPLATE: &str = "You are reviewing a code change. A reviewer has left \
+    comments on the diff below. Address each review comment by making the necessary code \
+    changes. If a comment asks a question, answer it and make any implied fixes. Keep changes \
+    minimal and focused on what the reviewer asked for.";
+
+/// Outcome of a background `git fetch` / `git pull` / `git push`.
+struct RemoteOpResult {
+    verb: &'static str,
+    /// Worktree (or, for a push, branch) name for the status message.
+    label: String,
+    workdir: PathBuf,
+    result: Result<(), String>,
+}
+
+/// A finished background tags build, with the `tags_epoch` it started in.
+type TagsBuild = (u64, Result<HashMap<String, Vec<TagEntry>>>);
 
 pub struct App {
     state: AppState,
@@ -59,6 +134,16 @@ pub struct App {
     status_clear_countdown: u32,
     hud_collapse_countdown: u32,
     quit_confirm_countdown: u32,
+    /// First key of a pending chord and the ticks left for its second key.
+    chord_prefix: Option<(char, u32)>,
+    /// Symbol definitions from ctags, built in the background on the first `gd`
+    /// after a refresh.
+    tags_cache: Option<HashMap<String, Vec<TagEntry>>>,
+    /// Bumped on each refresh, so a tags build started before it is thrown away.
+    tags_epoch: u64,
+    tags_building: bool,
+    /// The `gd` waiting for the tags build: cursor line content, file and line.
+    pending_definition: Option<(String, (PathBuf, u32))>,
     last_quit_combo: Option<QuitCombo>,
     repo_path: PathBuf,
     nav_area: Cell<Rect>,
@@ -67,6 +152,25 @@ pub struct App {
     pty_runner: Option<PtyRunner>,
     last_navigator_rect: Rect,
     last_diff_view_rect: Rect,
+    /// Stage/unstage/restore waiting for a second press in safe mode.
+    pending_action: Option<PendingAction>,
+    /// Patterns from the repository's `.mdiffignore`, applied with `exclude_paths`.
+    ignore_patterns: Vec<config::IgnorePattern>,
+    /// File and line to open in `$EDITOR` once the current batch of actions is applied.
+    pending_editor: Option<(PathBuf, u32)>,
+    /// Short id of a conflicting revert, reported once the refreshed diff shows its conflicts.
+    pending_revert_conflicts: Option<String>,
+    /// Diff generation, file, scroll offset, height and row count last marked
+    /// seen; the viewport is only re-marked when one of them changes.
+    seen_viewport: Option<(u64, Option<usize>, usize, usize, usize)>,
+    remote_tx: mpsc::UnboundedSender<RemoteOpResult>,
+    remote_rx: mpsc::UnboundedReceiver<RemoteOpResult>,
+    github_tx: mpsc::UnboundedSender<Result<Vec<ReviewComment>>>,
+    github_rx: mpsc::UnboundedReceiver<Result<Vec<ReviewComment>>>,
+    tags_tx: mpsc::UnboundedSender<TagsBuild>,
+    tags_rx: mpsc::UnboundedReceiver<TagsBuild>,
+    image_tx: mpsc::UnboundedSender<ImagePreview>,
+    image_rx: mpsc::UnboundedReceiver<ImagePreview>,
 }
 
 impl App {
@@ -74,31 +178,34 @@ impl App {
         diff_options: DiffOptions,
         open_worktree_browser: bool,
         target: ComparisonTarget,
+        target_label: String,
         repo_path: PathBuf,
-        config: MdiffConfig,
+        mut config: MdiffConfig,
         context_lines: Option<usize>,
     ) -> Self {
         let theme = config.theme.clone();
         let mut state = AppState::new(diff_options, theme);
-        state.target_label = match &target {
-            ComparisonTarget::HeadVsWorkdir => "HEAD".to_string(),
-            ComparisonTarget::Branch(name) => name.clone(),
-            ComparisonTarget::Commit(oid) => format!("{:.7}", oid),
-        };
+        state.target_label = target_label;
         if open_worktree_browser {
             state.active_view = ActiveView::WorktreeBrowser;
         }
         if let Some(ctx) = context_lines {
             state.diff.display_context = ctx;
         }
-
-        // Load session annotations and checklist state
-        let (annotations, saved_checklist) =
-            session::load_session_data(&repo_path, &state.target_label);
-        state.annotations = annotations;
+        state.export_format = config.export_format;
+        state.context_padding = config.context_padding;
+        state.prompt_preview_ratio = config.prompt_preview_ratio;
+        state.hunk_stat_bar_width = config.hunk_stat_bar_width;
+        state.accessible = config.accessible;
+        state.max_line_length = config.max_line_length;
+
+        // Load session annotations, checklist and review state
+        let session = session::load_session_data(&repo_path, &state.target_label);
+        state.annotations = session.annotations;
+        state.review.restore_reviewed(session.reviewed);
 
         // Load checklist configuration or use saved state
-        if let Some(saved) = saved_checklist {
+        if let Some(saved) = session.checklist {
             // Use saved checklist state (preserves checked items and notes)
             state.checklist = saved;
         } else if let Some(checklist_config) = load_checklist_config(&repo_path) {
@@ -108,8 +215,20 @@ impl App {
         }
 
         let worker = DiffWorker::new(repo_path.clone());
+        state.tabs.push(TabState::new(
+            ComparisonTarget::HeadVsWorkdir,
+            String::new(),
+            DiffWorker::new(repo_path.clone()),
+            state.diff.options.clone(),
+        ));
         let highlight_engine = HighlightEngine::new();
         let git_cli = GitCli::new(&repo_path);
+        let (remote_tx, remote_rx) = mpsc::unbounded_channel();
+        let (ignore_patterns, ignore_warnings) = config::load_ignore_file(&repo_path);
+        config.config_warnings.extend(ignore_warnings);
+        let (github_tx, github_rx) = mpsc::unbounded_channel();
+        let (tags_tx, tags_rx) = mpsc::unbounded_channel();
+        let (image_tx, image_rx) = mpsc::unbounded_channel();
         Self {
             state,
             worker,
@@ -120,6 +239,11 @@ impl App {
             status_clear_countdown: 0,
             hud_collapse_countdown: 0,
             quit_confirm_countdown: 0,
+            chord_prefix: None,
+            tags_cache: None,
+            tags_epoch: 0,
+            tags_building: false,
+            pending_definition: None,
             last_quit_combo: None,
             repo_path,
             nav_area: Cell::new(Rect::default()),
@@ -128,11 +252,168 @@ impl App {
             pty_runner: None,
             last_navigator_rect: Rect::default(),
             last_diff_view_rect: Rect::default(),
+            pending_action: None,
+            ignore_patterns,
+            pending_editor: None,
+            pending_revert_conflicts: None,
+            seen_viewport: None,
+            remote_tx,
+            remote_rx,
+            github_tx,
+            github_rx,
+            tags_tx,
+            tags_rx,
+            image_tx,
+            image_rx,
+        }
+    }
+
+    /// Compute the diff without the TUI, failing if it couldn't be loaded.
+    async fn load_diff_headless(&mut self) -> Result<()> {
+        self.request_diff();
+        while self.state.diff.loading {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            self.poll_diff_results();
+        }
+        match &self.state.diff.load_error {
+            Some(err) => anyhow::bail!("failed to load the diff: {err}"),
+            None => Ok(()),
+        }
+    }
+
+    /// `--stat`: print a `git diff --stat`-style summary of the diff to stdout.
+    pub async fn run_stat(&mut self, color: ColorSupport) -> Result<()> {
+        self.load_diff_headless().await?;
+        let width = crossterm::terminal::size().map_or(80, |(cols, _)| cols as usize);
+        let colors = (color != ColorSupport::None).then(|| {
+            let theme = &self.state.theme;
+            (
+                color.adapt_color(theme.diff_add_fg).into(),
+                color.adapt_color(theme.diff_del_fg).into(),
+            )
+        });
+        print!(
+            "{}",
+            diff_stat::format_diff_stat(&self.state.diff.deltas, width, colors)
+        );
+        Ok(())
+    }
+
+    /// `--json`: print the diff and its target as JSON to stdout.
+    pub async fn run_json(&mut self) -> Result<()> {
+        self.load_diff_headless().await?;
+        let json = export::render_diff_json(&self.state.target_label, &self.state.diff.deltas)?;
+        println!("{json}");
+        Ok(())
+    }
+
+    /// `--check`: list the files that aren't fully reviewed on stderr. Returns the
+    /// exit code, 1 if there are any.
+    pub async fn run_check(&mut self) -> Result<i32> {
+        self.load_diff_headless().await?;
+        let pending: Vec<(String, FileReviewStatus)> = self
+            .state
+            .diff
+            .deltas
+            .iter()
+            .map(|d| d.path.to_string_lossy().into_owned())
+            .map(|path| {
+                let status = self.state.review.status(&path);
+                (path, status)
+            })
+            .filter(|(_, status)| *status != FileReviewStatus::Reviewed)
+            .collect();
+        if pending.is_empty() {
+            return Ok(0);
+        }
+
+        eprintln!(
+            "mdiff: {} of {} files not reviewed:",
+            pending.len(),
+            self.state.diff.deltas.len()
+        );
+        for (path, status) in pending {
+            let why = match status {
+                FileReviewStatus::Unreviewed => "unreviewed".to_string(),
+                FileReviewStatus::New => "new".to_string(),
+                FileReviewStatus::ChangedSinceReview => "changed since review".to_string(),
+                FileReviewStatus::PartiallyChanged { changed_hunks, .. } => {
+                    format!("{changed_hunks} hunks not reviewed")
+                }
+                FileReviewStatus::Reviewed => unreachable!("filtered out above"),
+            };
+            eprintln!("  {path} ({why})");
+        }
+        Ok(1)
+    }
+
+    /// `--headless`: wait for the diff, run `agent_name` on the prompt for every
+    /// annotated file in a PTY with its output copied to stderr, and return the
+    /// agent's exit code.
+    pub async fn run_headless(&mut self, agent_name: &str, model: Option<String>) -> Result<i32> {
+        self.load_diff_headless().await?;
+
+        let Some(agent) = self
+            .config
+            .agents_by_name
+            .get(agent_name)
+            .map(|&i| self.config.agents[i].clone())
+        else {
+            let names: Vec<&str> = self.config.agents.iter().map(|a| a.name.as_str()).collect();
+            anyhow::bail!(
+                "unknown agent `{agent_name}` (configured: {})",
+                if names.is_empty() {
+                    "none".to_string()
+                } else {
+                    names.join(", ")
+                }
+            );
+        };
+        let model = model
+            .or_else(|| self.config.agent_models.get(&agent.name).cloned())
+            .unwrap_or_else(|| {
+                if agent.default_model.is_empty() {
+                    agent.models.first().cloned().unwrap_or_default()
+                } else {
+                    agent.default_model.clone()
+                }
+            });
+        let Some(prompt) = self.render_prompt_for_all_files(Some(&agent)) else {
+            anyhow::bail!("no annotated changes to send to the agent");
+        };
+
+        let (command, env) =
+            build_agent_command(&agent, &model, &prompt).map_err(anyhow::Error::msg)?;
+        let (cols, rows) = crossterm::terminal::size().unwrap_or((120, 40));
+        let mut runner = PtyRunner::spawn(0, &command, &env, rows, cols, &self.repo_path);
+        eprintln!("mdiff: running {}/{model}", agent.name);
+
+        use std::io::Write;
+        let mut stderr = std::io::stderr();
+        loop {
+            let exit_code = runner.try_wait();
+            while let Some(PtyEvent::Output(_, bytes)) = runner.try_recv() {
+                stderr.write_all(&bytes)?;
+            }
+            if let Some(code) = exit_code {
+                // Give the reader thread a moment to pass on the last output
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                while let Some(PtyEvent::Output(_, bytes)) = runner.try_recv() {
+                    stderr.write_all(&bytes)?;
+                }
+                stderr.flush()?;
+                return Ok(code);
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
         }
     }
 
     pub async fn run(&mut self, terminal: &mut Tui) -> Result<()> {
         self.request_diff();
+        if !self.config.config_warnings.is_empty() {
+            let msg = self.config.config_warnings.join("; ");
+            self.set_status_for_ticks(msg, true, 120);
+        }
         if self.state.active_view == ActiveView::WorktreeBrowser {
             self.refresh_worktrees();
         }
@@ -140,7 +421,7 @@ impl App {
         let mut events = EventReader::new(Duration::from_millis(50));
 
         let context_bar = ContextBar;
-        let navigator = Navigator;
+        let navigator = Navigator::default();
         let diff_view = DiffView;
         let action_hud = ActionHud;
         let worktree_browser = WorktreeBrowser;
@@ -150,6 +431,10 @@ impl App {
         loop {
             self.poll_diff_results();
             self.poll_pty_output();
+            self.poll_remote_ops();
+            self.poll_github_import();
+            self.poll_image_preview();
+            self.poll_tags();
 
             terminal.draw(|frame| {
                 let hud_h = hud_height(&self.state, frame.area().width);
@@ -170,7 +455,17 @@ impl App {
                         let show_checklist =
                             self.state.checklist.panel_open && !self.state.checklist.is_empty();
 
-                        let main = if show_checklist {
+                        let main = if self.state.focus_mode {
+                            // Focus mode: the navigator gets no width
+                            let mut cols = vec![Constraint::Length(0), Constraint::Min(0)];
+                            if show_checklist {
+                                cols.push(Constraint::Percentage(20));
+                            }
+                            Layout::default()
+                                .direction(Direction::Horizontal)
+                                .constraints(cols)
+                                .split(outer[1])
+                        } else if show_checklist {
                             // Three-column layout: navigator | diff | checklist
                             Layout::default()
                                 .direction(Direction::Horizontal)
@@ -195,14 +490,26 @@ impl App {
                         self.last_navigator_rect = main[0];
                         navigator.render(frame, main[0], &self.state);
 
-                        let diff_area = main[1];
+                        let diff_area = if self.state.file_log_open {
+                            let vsplit = Layout::default()
+                                .direction(Direction::Vertical)
+                                .constraints([
+                                    Constraint::Min(1),
+                                    Constraint::Length(FILE_LOG_HEIGHT),
+                                ])
+                                .split(main[1]);
+                            render_file_log(frame, vsplit[1], &self.state);
+                            vsplit[0]
+                        } else {
+                            main[1]
+                        };
 
                         if self.state.prompt_preview_visible {
                             let vsplit = Layout::default()
                                 .direction(Direction::Vertical)
                                 .constraints([
-                                    Constraint::Percentage(60),
-                                    Constraint::Percentage(40),
+                                    Constraint::Percentage(self.state.prompt_preview_ratio),
+                                    Constraint::Percentage(100 - self.state.prompt_preview_ratio),
                                 ])
                                 .split(diff_area);
 
@@ -237,6 +544,10 @@ impl App {
                         use crate::components::feedback_summary::FeedbackSummary;
                         FeedbackSummary.render(frame, outer[1], &self.state);
                     }
+                    ActiveView::ReviewSummary => {
+                        use crate::components::review_summary::ReviewSummary;
+                        ReviewSummary.render(frame, outer[1], &self.state);
+                    }
                 }
 
                 action_hud.render(frame, outer[2], &self.state);
@@ -245,6 +556,31 @@ impl App {
                 if self.state.target_dialog_open {
                     render_target_dialog(frame, &self.state);
                 }
+                if self.state.filter_dialog_open {
+                    render_filter_dialog(frame, &self.state);
+                }
+                if self.state.push_dialog_open {
+                    render_push_dialog(frame, &self.state);
+                }
+                if self.state.rebase_dialog_open {
+                    render_rebase_dialog(frame, &self.state);
+                }
+                if self.state.jump_hunk_dialog_open {
+                    let total = self.state.diff.selected_delta().map_or(0, |delta| {
+                        change_block_rows(delta, &self.current_display_map()).len()
+                    });
+                    render_jump_hunk_dialog(frame, &self.state, total);
+                }
+                if self.state.replace_dialog_open {
+                    render_replace_dialog(frame, &self.state);
+                }
+                if self.state.replace_confirm.is_some() {
+                    render_replace_confirm(frame, &self.state);
+                }
+                if self.state.worktree_dialog_open {
+                    let path = self.new_worktree_path();
+                    render_worktree_dialog(frame, &self.state, &path.to_string_lossy());
+                }
                 if self.state.commit_dialog_open {
                     render_commit_dialog(frame, &self.state);
                 }
@@ -254,22 +590,43 @@ impl App {
                 if self.state.annotation_menu_open {
                     render_annotation_menu(frame, &self.state);
                 }
+                render_context_menu(frame, &self.state);
                 if self.state.agent_selector.open {
                     render_agent_selector(frame, &self.state.agent_selector);
                 }
                 if self.state.restore_confirm_open {
                     render_restore_confirm(frame, &self.state);
                 }
+                if let Some(wt) = &self.state.worktree_remove_confirm {
+                    render_worktree_remove_confirm(frame, &self.state, wt);
+                }
+                if let Some(commit) = &self.state.revert_confirm {
+                    render_revert_confirm(frame, &self.state, commit);
+                }
+                if let Some(summary) = &self.state.patch_summary {
+                    render_patch_summary(frame, &self.state, summary);
+                }
+                if self.state.hunk_patch_confirm.is_some() {
+                    render_hunk_patch_confirm(frame, &self.state);
+                }
                 if self.state.settings.open {
                     render_settings_modal(frame, &self.state);
                 }
+                if let Some(mode) = self.state.session_dialog {
+                    render_session_dialog(frame, &self.state, mode);
+                }
+                if self.state.session_import.is_some() {
+                    render_session_import_confirm(frame, &self.state);
+                }
                 if self.state.global_search.active {
                     render_global_search_bar(frame, &self.state);
                 }
                 which_key::render_which_key(frame, frame.area(), &self.state);
+                self.config.color_support.adapt_buffer(frame.buffer_mut());
             })?;
 
             self.state.diff.viewport_height = self.diff_viewport_height.get();
+            self.mark_viewport_seen();
 
             // Wait for at least one event, then drain all pending events
             // to avoid input lag from buffered scroll/key events.
@@ -291,18 +648,38 @@ impl App {
                     focus: self.state.focus,
                     search_active: self.state.navigator.search_active,
                     diff_search_active: self.state.diff.search_active,
+                    preview_search_active: self.state.preview_search_active,
                     global_search_active: self.state.global_search.active,
                     commit_dialog_open: self.state.commit_dialog_open,
                     target_dialog_open: self.state.target_dialog_open,
+                    worktree_dialog_open: self.state.worktree_dialog_open,
+                    filter_dialog_open: self.state.filter_dialog_open,
+                    jump_hunk_dialog_open: self.state.jump_hunk_dialog_open,
+                    rebase_dialog_open: self.state.rebase_dialog_open,
+                    push_dialog_open: self.state.push_dialog_open,
+                    replace_dialog_open: self.state.replace_dialog_open,
+                    file_log_open: self.state.file_log_open,
+                    replace_confirm_open: self.state.replace_confirm.is_some(),
                     comment_editor_open: self.state.comment_editor_open,
                     agent_selector_open: self.state.agent_selector.open,
                     annotation_menu_open: self.state.annotation_menu_open,
+                    context_menu_open: self.state.context_menu.is_some(),
+                    chord_prefix: self.chord_prefix.map(|(c, _)| c),
                     restore_confirm_open: self.state.restore_confirm_open,
+                    worktree_remove_confirm_open: self.state.worktree_remove_confirm.is_some(),
+                    revert_confirm_open: self.state.revert_confirm.is_some(),
+                    hunk_patch_confirm_open: self.state.hunk_patch_confirm.is_some(),
+                    patch_summary_open: self.state.patch_summary.is_some(),
+                    session_dialog_open: self.state.session_dialog.is_some(),
+                    session_import_open: self.state.session_import.is_some(),
                     settings_open: self.state.settings.open,
                     visual_mode_active: self.state.selection.active,
                     active_view: self.state.active_view,
                     pty_focus: self.state.pty_focus,
                     checklist_panel_open: self.state.checklist.panel_open,
+                    file_collapsed: self.state.navigator.selected_collapsed(),
+                    focus_mode: self.state.focus_mode,
+                    keybindings: &self.config.keybindings,
                 };
                 let action = match event {
                     Event::Key(key) => map_key_to_action(key, &ctx),
@@ -312,13 +689,27 @@ impl App {
                         if !self.config.mouse.enabled
                             || ctx.commit_dialog_open
                             || ctx.target_dialog_open
+                            || ctx.worktree_dialog_open
+                            || ctx.filter_dialog_open
+                            || ctx.jump_hunk_dialog_open
+                            || ctx.rebase_dialog_open
+                            || ctx.push_dialog_open
+                            || ctx.replace_dialog_open
+                            || ctx.replace_confirm_open
                             || ctx.comment_editor_open
                             || ctx.agent_selector_open
                             || ctx.annotation_menu_open
                             || ctx.restore_confirm_open
+                            || ctx.worktree_remove_confirm_open
+                            || ctx.revert_confirm_open
+                            || ctx.hunk_patch_confirm_open
+                            || ctx.patch_summary_open
+                            || ctx.session_dialog_open
+                            || ctx.session_import_open
                             || ctx.settings_open
                             || ctx.search_active
                             || ctx.diff_search_active
+                            || ctx.preview_search_active
                         {
                             None
                         } else {
@@ -330,22 +721,19 @@ impl App {
                                     _ => None,
                                 }
                             } else {
-                                let visible_entries = self.state.navigator.visible_entries();
-                                let inner_height =
-                                    self.last_navigator_rect.height.saturating_sub(2) as usize;
-                                let selected = self.state.navigator.selected;
-                                let scroll_offset = if selected >= inner_height {
-                                    selected - inner_height + 1
-                                } else {
-                                    0
-                                };
-
                                 let mouse_ctx = MouseContext {
                                     navigator_rect: self.last_navigator_rect,
                                     diff_view_rect: self.last_diff_view_rect,
-                                    navigator_scroll_offset: scroll_offset,
-                                    navigator_item_count: visible_entries.len(),
-                                    navigator_visible_entries: &visible_entries,
+                                    navigator_hit: navigator.hit_test(mouse.column, mouse.row),
+                                    context_menu_rect: self.state.context_menu.map(|menu| {
+                                        let (cols, rows) =
+                                            crossterm::terminal::size().unwrap_or((120, 40));
+                                        context_menu_rect(
+                                            Rect::new(0, 0, cols, rows),
+                                            menu.x,
+                                            menu.y,
+                                        )
+                                    }),
                                 };
                                 map_mouse_to_action(mouse, &mouse_ctx)
                             }
@@ -385,12 +773,37 @@ impl App {
                 self.update(action);
             }
 
+            if let Some((file, line)) = self.pending_editor.take() {
+                // Suspend the TUI while the editor owns the terminal
+                drop(events);
+                tui::restore()?;
+                let editor = editor::detect_editor();
+                let status = editor::editor_command(
+                    &editor,
+                    self.config.editor_open_args.as_deref(),
+                    &file,
+                    line,
+                )
+                .current_dir(&self.repo_path)
+                .status();
+                *terminal = tui::init()?;
+                terminal.clear()?;
+                events = EventReader::new(Duration::from_millis(50));
+
+                match status {
+                    Ok(s) if s.success() => {}
+                    Ok(s) => self.set_status(format!("{editor} exited with {s}"), true),
+                    Err(e) => self.set_status(format!("Failed to run {editor}: {e}"), true),
+                }
+                self.request_diff();
+            }
+
             if self.state.should_quit {
                 break;
             }
         }
 
-        // Save session on quit
+        // Save session on quit, for background tabs too
         session::save_session_data(
             &self.repo_path,
             &self.state.target_label,
@@ -400,7 +813,24 @@ impl App {
             } else {
                 Some(&self.state.checklist)
             },
+            &self.state.review,
         );
+        for (idx, tab) in self.state.tabs.iter().enumerate() {
+            if idx == self.state.active_tab {
+                continue;
+            }
+            session::save_session_data(
+                &self.repo_path,
+                &tab.target_label,
+                &tab.annotations,
+                if tab.checklist.is_empty() {
+                    None
+                } else {
+                    Some(&tab.checklist)
+                },
+                &tab.review,
+            );
+        }
 
         Ok(())
     }
@@ -408,6 +838,8 @@ impl App {
     fn request_diff(&mut self) {
         self.generation += 1;
         self.state.diff.loading = true;
+        // A refresh while loading keeps counting from the first request
+        self.state.diff.load_start.get_or_insert_with(Instant::now);
         self.worker.request(DiffRequest {
             generation: self.generation,
             target: self.target.clone(),
@@ -415,17 +847,45 @@ impl App {
         });
     }
 
+    /// Re-read `.mdiffignore` after switching to another worktree.
+    fn reload_ignore_file(&mut self) {
+        let (patterns, warnings) = config::load_ignore_file(&self.repo_path);
+        self.ignore_patterns = patterns;
+        if !warnings.is_empty() {
+            self.set_status(warnings.join("; "), true);
+        }
+    }
+
     fn poll_diff_results(&mut self) {
         while let Some(result) = self.worker.try_recv() {
             if result.generation < self.generation {
                 continue;
             }
             self.state.diff.loading = false;
+            self.state.diff.load_start = None;
             match result.deltas {
-                Ok(deltas) => {
+                Ok(mut deltas) => {
+                    self.state.diff.load_error = None;
+                    self.tags_cache = None;
+                    self.tags_epoch += 1;
+                    let before = deltas.len();
+                    deltas.retain(|d| {
+                        !config::is_ignored(&self.config.exclude_paths, &d.path)
+                            && !config::is_ignored(&self.ignore_patterns, &d.path)
+                    });
+                    self.state.diff.excluded_count = before - deltas.len();
                     let new_hashes = compute_diff_hashes(&deltas);
                     self.state.review.on_diff_refresh(new_hashes);
                     self.state.navigator.update_from_deltas(&deltas);
+                    if let Some(short) = self.pending_revert_conflicts.take() {
+                        self.report_revert_conflicts(&short, &deltas);
+                    }
+                    self.reanchor_annotations(&deltas);
+                    self.state.diff.max_changes = deltas
+                        .iter()
+                        .map(|d| d.additions + d.deletions)
+                        .max()
+                        .unwrap_or(0);
                     self.state.diff.deltas = deltas;
                     if !self.state.diff.deltas.is_empty() && self.state.diff.selected_file.is_none()
                     {
@@ -433,7 +893,8 @@ impl App {
                         self.update_highlights();
                     }
                 }
-                Err(_e) => {
+                Err(e) => {
+                    self.state.diff.load_error = Some(format!("{e:#}"));
                     self.state.diff.deltas.clear();
                     self.state.navigator.update_from_deltas(&[]);
                 }
@@ -441,6 +902,187 @@ impl App {
         }
     }
 
+    /// Move annotations to follow their surrounding context after a re-diff.
+    fn reanchor_annotations(&mut self, deltas: &[FileDelta]) {
+        for delta in deltas {
+            let path = delta.path.to_string_lossy();
+            if !self
+                .state
+                .annotations
+                .annotations
+                .contains_key(path.as_ref())
+            {
+                continue;
+            }
+            let (old_lines, new_lines) = self.source_lines(delta);
+            self.state.annotations.reanchor(
+                &path,
+                &old_lines.iter().map(String::as_str).collect::<Vec<_>>(),
+                &new_lines.iter().map(String::as_str).collect::<Vec<_>>(),
+            );
+        }
+    }
+
+    fn poll_remote_ops(&mut self) {
+        while let Ok(done) = self.remote_rx.try_recv() {
+            self.state.remote_op = None;
+            match done.result {
+                Ok(()) => self.set_status(format!("{}: {}", done.verb, done.label), false),
+                Err(e) if e.contains("has no upstream branch") => {
+                    let branch = GitCli::new(&self.repo_path)
+                        .current_branch()
+                        .unwrap_or_default();
+                    self.state
+                        .push_dialog_input
+                        .set(&format!("origin {branch}"));
+                    self.state.push_dialog_open = true;
+                }
+                Err(e) => self.set_status(format!("{} failed: {e}", done.verb), true),
+            }
+            self.refresh_worktrees();
+            if done.workdir == self.repo_path {
+                self.request_diff();
+            }
+        }
+    }
+
+    /// Run `git fetch --all` or `git pull --rebase` for the selected worktree
+    /// in the background; the result arrives through `poll_remote_ops`.
+    fn start_remote_op(&mut self, pull: bool) {
+        if self.state.remote_op.is_some() {
+            self.set_status("A fetch or pull is already running".to_string(), true);
+            return;
+        }
+        let Some(wt) = self.state.worktree.selected_worktree().cloned() else {
+            return;
+        };
+        let (progress, verb) = if pull {
+            ("Pulling", "Pulled")
+        } else {
+            ("Fetching", "Fetched")
+        };
+        self.state.remote_op = Some(format!("{progress} {}\u{2026}", wt.name));
+
+        let git = GitCli::new(&wt.path);
+        let tx = self.remote_tx.clone();
+        tokio::spawn(async move {
+            let result = if pull {
+                git.pull_rebase().await
+            } else {
+                git.fetch().await
+            };
+            let _ = tx.send(RemoteOpResult {
+                verb,
+                label: wt.name,
+                workdir: wt.path,
+                result: result.map_err(|e| e.to_string()),
+            });
+        });
+    }
+
+    /// Push the current branch in the background, with `git push -u <remote>
+    /// <branch>` when `upstream` is given. A push that finds no upstream opens
+    /// the push dialog from `poll_remote_ops`.
+    fn start_push(&mut self, upstream: Option<(String, String)>) {
+        if self.state.remote_op.is_some() {
+            self.set_status("Another remote operation is running".to_string(), true);
+            return;
+        }
+        let git = GitCli::new(&self.repo_path);
+        let label = match &upstream {
+            Some((remote, branch)) => format!("{remote}/{branch}"),
+            None => git.current_branch().unwrap_or_else(|_| "HEAD".to_string()),
+        };
+        self.state.remote_op = Some(format!("Pushing {label}\u{2026}"));
+
+        let workdir = self.repo_path.clone();
+        let tx = self.remote_tx.clone();
+        tokio::spawn(async move {
+            let result = match upstream {
+                Some((remote, branch)) => git.push_upstream(&remote, &branch).await,
+                None => git.push().await,
+            };
+            let _ = tx.send(RemoteOpResult {
+                verb: "Pushed",
+                label,
+                workdir,
+                result: result.map_err(|e| e.to_string()),
+            });
+        });
+    }
+
+    /// Fetch the pull request's review comments with `gh` in the background;
+    /// the result arrives through `poll_github_import`.
+    fn start_github_import(&mut self) {
+        if self.state.remote_op.is_some() {
+            self.set_status("Another remote operation is running".to_string(), true);
+            return;
+        }
+        if !github::gh_available() {
+            self.set_status(
+                "GitHub import needs the gh CLI (https://cli.github.com)".to_string(),
+                true,
+            );
+            return;
+        }
+        self.state.remote_op = Some("Importing GitHub review comments\u{2026}".to_string());
+
+        let workdir = self.repo_path.clone();
+        let tx = self.github_tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let _ = tx.send(github::fetch_review_comments(&workdir));
+        });
+    }
+
+    fn poll_github_import(&mut self) {
+        while let Ok(result) = self.github_rx.try_recv() {
+            self.state.remote_op = None;
+            let comments = match result {
+                Ok(comments) => comments,
+                Err(e) => {
+                    self.set_status(format!("GitHub import failed: {e}"), true);
+                    continue;
+                }
+            };
+
+            let mut imported = 0;
+            let mut unanchored = 0;
+            for mut ann in github::to_annotations(comments, &self.state.diff.deltas) {
+                let duplicate = self
+                    .state
+                    .annotations
+                    .annotations
+                    .get(&ann.anchor.file_path)
+                    .is_some_and(|anns| anns.iter().any(|a| a.comment == ann.comment));
+                if duplicate {
+                    continue;
+                }
+                if !ann.unanchored {
+                    if let Some(delta) = self
+                        .state
+                        .diff
+                        .deltas
+                        .iter()
+                        .find(|d| d.path.to_string_lossy() == ann.anchor.file_path)
+                    {
+                        let (old_lines, new_lines) = self.source_lines(delta);
+                        ann.anchor.capture_context(
+                            &old_lines.iter().map(String::as_str).collect::<Vec<_>>(),
+                            &new_lines.iter().map(String::as_str).collect::<Vec<_>>(),
+                        );
+                    }
+                }
+                imported += 1;
+                unanchored += usize::from(ann.unanchored);
+                self.state.annotations.add(ann);
+            }
+            self.set_status(
+                format!("Imported {imported} GitHub comments ({unanchored} unanchored)"),
+                false,
+            );
+        }
+    }
+
     fn poll_pty_output(&mut self) {
         let Some(runner) = self.pty_runner.as_mut() else {
             return;
@@ -486,6 +1128,8 @@ impl App {
                 } else {
                     AgentRunStatus::Failed { exit_code: code }
                 };
+                run.token_count = parse_tokens_used(&run.terminal.screen().contents());
+                run.finished_at = Some(Instant::now());
             }
             self.state.pty_focus = false;
             self.pty_runner = None;
@@ -495,6 +1139,7 @@ impl App {
     }
 
     fn update_highlights(&mut self) {
+        self.update_image_preview();
         let Some(delta) = self.state.diff.selected_delta() else {
             self.state.diff.old_highlights.clear();
             self.state.diff.new_highlights.clear();
@@ -518,24 +1163,116 @@ impl App {
             .unwrap_or_else(|| vec![Vec::new(); new_line_count + 1]);
     }
 
+    /// Read and decode both sides of the selected binary image in the
+    /// background; the result arrives through `poll_image_preview`.
+    fn update_image_preview(&mut self) {
+        let Some(delta) = self
+            .state
+            .diff
+            .selected_delta()
+            .filter(|d| d.binary && is_image_path(&d.path))
+        else {
+            self.state.diff.image_preview = None;
+            return;
+        };
+        let path = delta.path.clone();
+        let old_path = delta.old_path.clone().unwrap_or_else(|| path.clone());
+        let has_old = !matches!(delta.status, FileStatus::Added | FileStatus::Untracked);
+        let has_new = delta.status != FileStatus::Deleted;
+        // Keep showing the old preview of the same file until the new one is ready
+        if self
+            .state
+            .diff
+            .image_preview
+            .as_ref()
+            .is_some_and(|p| p.path != path)
+        {
+            self.state.diff.image_preview = None;
+        }
+
+        let git = GitCli::new(&self.repo_path);
+        let repo_path = self.repo_path.clone();
+        let target = self.target.clone();
+        let tx = self.image_tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let (old_rev, new_rev) = comparison_revs(&git, &target);
+            let old = old_rev
+                .filter(|_| has_old)
+                .and_then(|rev| git.show_blob(&rev, &old_path).ok());
+            let new = match new_rev {
+                _ if !has_new => None,
+                Some(rev) => git.show_blob(&rev, &path).ok(),
+                None => std::fs::read(repo_path.join(&path)).ok(),
+            };
+            let _ = tx.send(ImagePreview {
+                path,
+                old: ImageSide::from_bytes(old),
+                new: ImageSide::from_bytes(new),
+            });
+        });
+    }
+
+    fn poll_image_preview(&mut self) {
+        while let Ok(preview) = self.image_rx.try_recv() {
+            // Drop previews of a file that's no longer selected
+            if self
+                .state
+                .diff
+                .selected_delta()
+                .is_some_and(|d| d.path == preview.path)
+            {
+                self.state.diff.image_preview = Some(preview);
+            }
+        }
+    }
+
+    /// Both sides of a file's contents as lists of lines, where index 0 is
+    /// line 1. Read from git and the working tree; a side that can't be read
+    /// falls back to the lines the diff shows.
+    fn source_lines(&self, delta: &FileDelta) -> (Vec<String>, Vec<String>) {
+        let (old_rev, new_rev) = comparison_revs(&self.git_cli, &self.target);
+        let old_path = delta.old_path.as_ref().unwrap_or(&delta.path);
+        let old = match delta.status {
+            FileStatus::Added | FileStatus::Untracked => Some(Vec::new()),
+            _ => old_rev.and_then(|rev| self.git_cli.show_blob(&rev, old_path).ok()),
+        };
+        let new = match new_rev {
+            _ if delta.status == FileStatus::Deleted => Some(Vec::new()),
+            Some(rev) => self.git_cli.show_blob(&rev, &delta.path).ok(),
+            None => std::fs::read(self.repo_path.join(&delta.path)).ok(),
+        };
+        let lines = |bytes: Option<Vec<u8>>, side| match bytes.map(String::from_utf8) {
+            Some(Ok(text)) => text.lines().map(str::to_string).collect(),
+            _ => file_lines(delta, side),
+        };
+        (lines(old, ContentSide::Old), lines(new, ContentSide::New))
+    }
+
     /// Build the display map for the currently selected file.
+    /// Collapsed files have no rows.
     fn current_display_map(&self) -> Vec<DisplayRowInfo> {
         let Some(delta) = self.state.diff.selected_delta() else {
             return Vec::new();
         };
+        if self.selected_file_collapsed() {
+            return Vec::new();
+        }
         build_display_map(
             delta,
             self.state.diff.options.view_mode,
             self.state.diff.display_context,
             &self.state.diff.gap_expansions,
+            &self.state.diff.hunk_splits,
         )
     }
 
     fn update_diff_visual_metrics(&mut self, area: Rect) {
-        let Some(delta) = self.state.diff.selected_delta() else {
+        let collapsed = self.selected_file_collapsed();
+        let Some(delta) = self.state.diff.selected_delta().filter(|_| !collapsed) else {
             self.state.diff.visual_row_offsets.clear();
             self.state.diff.visual_row_heights.clear();
             self.state.diff.visual_total_rows = 0;
+            self.state.diff.change_block_rows.clear();
             self.state.diff.scroll_offset = 0;
             self.state.diff.cursor_row = 0;
             return;
@@ -544,7 +1281,7 @@ impl App {
         let inner = Rect {
             x: area.x.saturating_add(1),
             y: area.y.saturating_add(1),
-            width: area.width.saturating_sub(2),
+            width: area.width.saturating_sub(2 + MINIMAP_WIDTH),
             height: area.height.saturating_sub(2),
         };
 
@@ -574,6 +1311,12 @@ impl App {
 
     fn clamp_diff_view_state(&mut self) {
         let display_map = self.current_display_map();
+        self.state.diff.change_block_rows = self
+            .state
+            .diff
+            .selected_delta()
+            .map(|delta| change_block_rows(delta, &display_map))
+            .unwrap_or_default();
         let max_cursor_row = display_map.len().saturating_sub(1);
         let vh = self.state.diff.viewport_height.max(1);
         let max_scroll = self.state.diff.visual_total_rows.saturating_sub(vh);
@@ -591,6 +1334,26 @@ impl App {
         self.ensure_cursor_visible();
     }
 
+    /// Move the cursor to display row `row` and scroll it to the top of the
+    /// diff view. A jump can follow a file switch or a wrap toggle in the same
+    /// update, so the wrapped-row offsets are recomputed first rather than
+    /// waiting for the next render.
+    fn scroll_row_to_top(&mut self, row: usize) {
+        self.state.diff.cursor_row = row;
+        if self.last_diff_view_rect.width > 0 {
+            self.update_diff_visual_metrics(self.last_diff_view_rect);
+        }
+        self.state.diff.scroll_offset = self.visual_offset_for_row(row);
+    }
+
+    /// Whether the file shown in the diff pane is collapsed in the navigator.
+    fn selected_file_collapsed(&self) -> bool {
+        self.state
+            .diff
+            .selected_file
+            .is_some_and(|idx| self.state.navigator.collapsed.contains(&idx))
+    }
+
     fn visual_offset_for_row(&self, row: usize) -> usize {
         self.state
             .diff
@@ -612,6 +1375,67 @@ impl App {
         idx.min(offsets.len().saturating_sub(1))
     }
 
+    /// Record the diff rows currently on screen as seen, for the minimap.
+    fn mark_viewport_seen(&mut self) {
+        if self.state.active_view != ActiveView::DiffExplorer {
+            return;
+        }
+        let scroll = self.state.diff.scroll_offset;
+        let viewport = (
+            self.generation,
+            self.state.diff.selected_file,
+            scroll,
+            self.state.diff.viewport_height,
+            self.state.diff.visual_total_rows,
+        );
+        if self.seen_viewport == Some(viewport) {
+            return;
+        }
+        self.seen_viewport = Some(viewport);
+        let Some(delta) = self.state.diff.selected_delta() else {
+            return;
+        };
+        let path = delta.path.to_string_lossy().into_owned();
+        let end = scroll + self.state.diff.viewport_height.max(1);
+        let offsets = &self.state.diff.visual_row_offsets;
+        let first = offsets.partition_point(|&o| o < scroll);
+        let last = offsets.partition_point(|&o| o < end);
+        let display_map = self.current_display_map();
+        for info in display_map.iter().take(last).skip(first) {
+            if info.line_index.is_some() {
+                self.state
+                    .review
+                    .mark_seen(&path, info.old_lineno, info.new_lineno);
+            }
+        }
+    }
+
+    /// After the display map changes shape, put the cursor back on the row for
+    /// the same line, or the closest row when that line is now folded away.
+    fn restore_cursor_to_line(&mut self, old: Option<u32>, new: Option<u32>) {
+        let display_map = self.current_display_map();
+        let distance = |info: &DisplayRowInfo| match (new, info.new_lineno, old, info.old_lineno) {
+            (Some(a), Some(b), _, _) | (_, _, Some(a), Some(b)) => Some(a.abs_diff(b)),
+            _ => None,
+        };
+        if let Some(row) = display_map
+            .iter()
+            .enumerate()
+            .filter_map(|(row, info)| distance(info).map(|d| (d, row)))
+            .min()
+            .map(|(_, row)| row)
+        {
+            self.state.diff.cursor_row = row;
+        } else {
+            self.state.diff.cursor_row = self
+                .state
+                .diff
+                .cursor_row
+                .min(display_map.len().saturating_sub(1));
+        }
+        self.ensure_cursor_visible();
+    }
+
     fn ensure_cursor_visible(&mut self) {
         if self.state.diff.visual_row_offsets.is_empty() {
             return;
@@ -625,45 +1449,6 @@ impl App {
         }
     }
 
-    fn find_next_hunk_row(
-        &self,
-        current_row: usize,
-        display_map: &[DisplayRowInfo],
-    ) -> Option<usize> {
-        display_map
-            .iter()
-            .enumerate()
-            .skip(current_row + 1)
-            .find(|(_, info)| info.is_header)
-            .map(|(idx, _)| idx)
-            .or_else(|| {
-                display_map
-                    .iter()
-                    .enumerate()
-                    .take(current_row + 1)
-                    .find(|(_, info)| info.is_header)
-                    .map(|(idx, _)| idx)
-            })
-    }
-
-    fn find_prev_hunk_row(
-        &self,
-        current_row: usize,
-        display_map: &[DisplayRowInfo],
-    ) -> Option<usize> {
-        if current_row > 0 {
-            if let Some(idx) = (0..current_row)
-                .rev()
-                .find(|&idx| display_map[idx].is_header)
-            {
-                return Some(idx);
-            }
-        }
-        (0..display_map.len())
-            .rev()
-            .find(|&idx| display_map[idx].is_header)
-    }
-
     /// Convert the current visual selection to a LineAnchor using the display map.
     /// Collects old and new line numbers separately to preserve side information.
     fn selection_to_anchor(&self) -> Option<LineAnchor> {
@@ -695,11 +1480,11 @@ impl App {
             return None;
         }
 
-        Some(LineAnchor {
+        Some(LineAnchor::new(
             file_path,
-            old_range: old_min.zip(old_max),
-            new_range: new_min.zip(new_max),
-        })
+            old_min.zip(old_max),
+            new_min.zip(new_max),
+        ))
     }
 
     /// Convert the cursor row to a single-line LineAnchor (used when no visual selection is active).
@@ -713,11 +1498,39 @@ impl App {
         if info.old_lineno.is_none() && info.new_lineno.is_none() {
             return None;
         }
-        Some(LineAnchor {
+        Some(LineAnchor::new(
             file_path,
-            old_range: info.old_lineno.map(|n| (n, n)),
-            new_range: info.new_lineno.map(|n| (n, n)),
-        })
+            info.old_lineno.map(|n| (n, n)),
+            info.new_lineno.map(|n| (n, n)),
+        ))
+    }
+
+    /// Safe mode gate, see [`safe_mode_step`]. Returns whether `action` should run.
+    fn confirm_pending_action(&mut self, action: &Action) -> bool {
+        match safe_mode_step(&mut self.pending_action, action) {
+            SafeModeStep::Run => true,
+            SafeModeStep::Cancel => {
+                self.set_status("Cancelled".to_string(), false);
+                true
+            }
+            SafeModeStep::Preview => {
+                let Some(path) = self.selected_file_path() else {
+                    self.pending_action = None;
+                    return false;
+                };
+                let verb = match action {
+                    Action::StageFile => "stage",
+                    Action::UnstageFile => "unstage",
+                    _ => "restore",
+                };
+                self.set_status_for_ticks(
+                    format!("Would {verb}: {}", path.display()),
+                    false,
+                    SAFE_MODE_PREVIEW_TICKS,
+                );
+                false
+            }
+        }
     }
 
     fn update(&mut self, action: Action) {
@@ -732,7 +1545,8 @@ impl App {
                 | Action::SettingsUp
                 | Action::SettingsDown
                 | Action::SettingsLeft
-                | Action::SettingsRight => {}
+                | Action::SettingsRight
+                | Action::SettingsActivate => {}
                 _ => {
                     self.state.hud_expanded = false;
                     self.hud_collapse_countdown = 0;
@@ -740,6 +1554,33 @@ impl App {
             }
         }
 
+        // A chord prefix followed by anything but its second key runs on its own
+        if let Some((prefix, _)) = self.chord_prefix {
+            if !matches!(
+                action,
+                Action::Tick | Action::Resize | Action::StartChord(_)
+            ) {
+                self.chord_prefix = None;
+                if !matches!(
+                    action,
+                    Action::FoldAllHunks
+                        | Action::UnfoldAllHunks
+                        | Action::GoToDefinition
+                        | Action::CopyCursorLineContent
+                        | Action::NextBookmark
+                        | Action::PrevBookmark
+                        | Action::Noop
+                ) && !chord_is_eager(prefix)
+                {
+                    self.update(chord_fallback(prefix));
+                }
+            }
+        }
+
+        if self.config.safe_mode && !self.confirm_pending_action(&action) {
+            return;
+        }
+
         match action {
             Action::Quit => {
                 self.state.should_quit = true;
@@ -765,6 +1606,16 @@ impl App {
                 self.state.navigator.select_down();
                 self.sync_selection();
             }
+            Action::NextFile => {
+                self.state.navigator.select_down();
+                self.sync_selection();
+                self.state.focus = FocusPanel::DiffView;
+            }
+            Action::PrevFile => {
+                self.state.navigator.select_up();
+                self.sync_selection();
+                self.state.focus = FocusPanel::DiffView;
+            }
             Action::NavigatorTop => {
                 self.state.navigator.selected = 0;
                 self.sync_selection();
@@ -792,6 +1643,9 @@ impl App {
                 }
                 self.state.focus = FocusPanel::Navigator;
                 self.update_highlights();
+                if self.state.file_log_open {
+                    self.load_file_log();
+                }
             }
             Action::ScrollUp => {
                 self.state.diff.cursor_row = self.state.diff.cursor_row.saturating_sub(1);
@@ -856,8 +1710,114 @@ impl App {
                 self.request_diff();
             }
 
+            Action::ToggleLineWrap => {
+                self.state.diff.line_wrap = !self.state.diff.line_wrap;
+                self.state.diff.scroll_x = 0;
+                let label = if self.state.diff.line_wrap {
+                    "Line wrap on"
+                } else {
+                    "Line wrap off (</> to scroll)"
+                };
+                self.set_status(label.to_string(), false);
+            }
+            Action::StartChord(c) => {
+                let pending = self.chord_prefix.take().map(|(prefix, _)| prefix);
+                let (actions, next) = start_chord(pending, c);
+                self.chord_prefix = next.map(|prefix| (prefix, CHORD_TICKS));
+                for action in actions {
+                    self.update(action);
+                }
+            }
+            Action::Noop => {}
+            Action::GoToDefinition => self.go_to_definition(),
+            Action::CopyCursorLineContent => self.copy_cursor_line(),
+            Action::FoldAllHunks | Action::UnfoldAllHunks => {
+                let display_map = self.current_display_map();
+                let cursor = display_map
+                    .get(self.state.diff.cursor_row)
+                    .map(|info| (info.old_lineno, info.new_lineno));
+                if matches!(action, Action::FoldAllHunks) {
+                    self.state.diff.gap_expansions.clear();
+                } else {
+                    for info in display_map.iter().filter(|r| r.is_collapsed_indicator) {
+                        if let Some(gap_id) = info.gap_id {
+                            *self.state.diff.gap_expansions.entry(gap_id).or_insert(0) +=
+                                info.hidden_count;
+                        }
+                    }
+                }
+                if let Some((old, new)) = cursor {
+                    self.restore_cursor_to_line(old, new);
+                }
+                let label = if matches!(action, Action::FoldAllHunks) {
+                    "Folded all context"
+                } else {
+                    "Unfolded all context"
+                };
+                self.set_status(label.to_string(), false);
+            }
+            Action::ToggleAnnotationsVisible => {
+                self.state.diff.annotations_visible = !self.state.diff.annotations_visible;
+                let label = if self.state.diff.annotations_visible {
+                    "Annotations visible"
+                } else {
+                    "Annotations hidden"
+                };
+                self.set_status(label.to_string(), false);
+            }
+            Action::ToggleInlineAnnotations => {
+                self.state.diff.show_inline_annotations = !self.state.diff.show_inline_annotations;
+                let label = if self.state.diff.show_inline_annotations {
+                    "Inline comments on"
+                } else {
+                    "Inline comments off"
+                };
+                self.set_status(label.to_string(), false);
+            }
+            Action::ScrollLeft => {
+                self.state.diff.scroll_x = self.state.diff.scroll_x.saturating_sub(HSCROLL_STEP);
+            }
+            Action::ScrollRight => {
+                if !self.state.diff.line_wrap {
+                    self.state.diff.scroll_x += HSCROLL_STEP;
+                }
+            }
+            Action::OpenInEditor => {
+                if let Some(path) = self.selected_file_path() {
+                    let display_map = self.current_display_map();
+                    let line = display_map
+                        .get(self.state.diff.cursor_row)
+                        .and_then(|info| info.new_lineno.or(info.old_lineno))
+                        .unwrap_or(1);
+                    self.pending_editor = Some((self.repo_path.join(path), line));
+                }
+            }
+            Action::OpenFileInExplorer => {
+                if let Some(path) = self.selected_file_path() {
+                    let full_path = self.repo_path.join(&path);
+                    match platform::open_in_explorer(&full_path) {
+                        Ok(()) => self.set_status(
+                            format!("Opened file manager at {}", path.display()),
+                            false,
+                        ),
+                        Err(e) => self.set_status(e.to_string(), true),
+                    }
+                }
+            }
             Action::FocusNavigator => {
                 self.state.focus = FocusPanel::Navigator;
+                // The navigator can't take focus while it's hidden
+                self.state.focus_mode = false;
+            }
+            Action::ToggleFocusMode => {
+                self.state.focus_mode = !self.state.focus_mode;
+                if self.state.focus_mode {
+                    self.state.focus = FocusPanel::DiffView;
+                    self.set_status(
+                        "Focus mode: Ctrl+N/Ctrl+P switch files, Alt+f or Esc to exit".to_string(),
+                        false,
+                    );
+                }
             }
             Action::FocusDiffView => {
                 self.state.focus = FocusPanel::DiffView;
@@ -991,7 +1951,8 @@ impl App {
                 self.state.active_view = match self.state.active_view {
                     ActiveView::DiffExplorer
                     | ActiveView::AgentOutputs
-                    | ActiveView::FeedbackSummary => {
+                    | ActiveView::FeedbackSummary
+                    | ActiveView::ReviewSummary => {
                         self.refresh_worktrees();
                         ActiveView::WorktreeBrowser
                     }
@@ -1010,6 +1971,7 @@ impl App {
                     self.repo_path = new_path.clone();
                     self.worker = DiffWorker::new(new_path.clone());
                     self.git_cli = GitCli::new(&new_path);
+                    self.reload_ignore_file();
                     self.generation = 0;
                     self.state.diff.deltas.clear();
                     self.state.diff.selected_file = None;
@@ -1025,6 +1987,84 @@ impl App {
             Action::WorktreeRefresh => {
                 self.refresh_worktrees();
             }
+            Action::LockWorktree | Action::UnlockWorktree => {
+                let lock = matches!(action, Action::LockWorktree);
+                if let Some(wt) = self.state.worktree.selected_worktree().cloned() {
+                    if wt.is_main {
+                        self.set_status("The main worktree can't be locked".to_string(), true);
+                        return;
+                    }
+                    let (result, verb) = if lock {
+                        (self.git_cli.lock_worktree(&wt.path, None), "Locked")
+                    } else {
+                        (self.git_cli.unlock_worktree(&wt.path), "Unlocked")
+                    };
+                    match result {
+                        Ok(()) => {
+                            self.set_status(format!("{verb}: {}", wt.name), false);
+                            self.refresh_worktrees();
+                        }
+                        Err(e) => self.set_status(e.to_string(), true),
+                    }
+                }
+            }
+            Action::DeleteWorktree => {
+                if let Some(wt) = self.state.worktree.selected_worktree().cloned() {
+                    if wt.is_main {
+                        self.set_status("Can't remove the main worktree".to_string(), true);
+                    } else if wt.path == self.repo_path {
+                        self.set_status(
+                            "Can't remove the worktree being reviewed; switch away first"
+                                .to_string(),
+                            true,
+                        );
+                    } else {
+                        self.state.worktree_remove_confirm = Some(wt);
+                    }
+                }
+            }
+            Action::ConfirmDeleteWorktree => {
+                if let Some(wt) = self.state.worktree_remove_confirm.take() {
+                    match self.git_cli.remove_worktree(&wt.path) {
+                        Ok(()) => {
+                            self.set_status(format!("Removed worktree: {}", wt.name), false);
+                            self.refresh_worktrees();
+                            self.state.worktree.selected = self
+                                .state
+                                .worktree
+                                .selected
+                                .min(self.state.worktree.worktrees.len().saturating_sub(1));
+                        }
+                        Err(e) => {
+                            self.set_status(format!("Remove failed: {e}"), true);
+                        }
+                    }
+                }
+            }
+            Action::CancelDeleteWorktree => {
+                self.state.worktree_remove_confirm = None;
+            }
+            Action::WorktreeFetch => self.start_remote_op(false),
+            Action::WorktreePull => self.start_remote_op(true),
+            Action::Push => self.start_push(None),
+            Action::CancelPushUpstream => {
+                self.state.push_dialog_open = false;
+                self.state.push_dialog_input.clear();
+            }
+            Action::PushDialogChar(c) => self.state.push_dialog_input.insert_char(c),
+            Action::PushDialogBackspace => self.state.push_dialog_input.delete_back(),
+            Action::ConfirmPushUpstream => {
+                let input = self.state.push_dialog_input.text().to_string();
+                let mut words = input.split_whitespace();
+                match (words.next(), words.next(), words.next()) {
+                    (Some(remote), Some(branch), None) => {
+                        self.state.push_dialog_open = false;
+                        self.state.push_dialog_input.clear();
+                        self.start_push(Some((remote.to_string(), branch.to_string())));
+                    }
+                    _ => self.set_status("Enter a remote and a branch".to_string(), true),
+                }
+            }
             Action::WorktreeFreeze => {
                 if let Some(wt) = self.state.worktree.selected_worktree().cloned() {
                     let freeze_cli = GitCli::new(&wt.path);
@@ -1042,6 +2082,300 @@ impl App {
                     }
                 }
             }
+            Action::FilterFiles => {
+                self.state.filter_dialog_open = true;
+                let current = self.state.navigator.filter_glob.clone().unwrap_or_default();
+                self.state.filter_dialog_input.set(&current);
+            }
+            Action::ToggleFileStatus => {
+                self.state.navigator.cycle_status_filter();
+                self.sync_selection();
+                let filter = self.state.navigator.status_filter.label();
+                self.set_status(format!("Showing {filter} files"), false);
+            }
+            Action::CancelFilter => {
+                self.state.filter_dialog_open = false;
+                self.state.filter_dialog_input.clear();
+                let _ = self.state.navigator.set_filter_glob(None);
+                self.sync_selection();
+            }
+            Action::FilterChar(c) => {
+                self.state.filter_dialog_input.insert_char(c);
+            }
+            Action::FilterBackspace => {
+                self.state.filter_dialog_input.delete_back();
+            }
+            Action::ConfirmFilter => {
+                let input = self.state.filter_dialog_input.text().to_string();
+                match self.state.navigator.set_filter_glob(Some(&input)) {
+                    Ok(()) => {
+                        self.state.filter_dialog_open = false;
+                        self.state.filter_dialog_input.clear();
+                        self.sync_selection();
+                    }
+                    Err(e) => {
+                        self.set_status(format!("Invalid pattern '{}': {}", input.trim(), e), true);
+                    }
+                }
+            }
+            Action::RebaseInteractive => {
+                if self.pty_runner.is_some() {
+                    self.set_status("Wait for the running agent to finish".to_string(), true);
+                } else {
+                    self.state.rebase_dialog_open = true;
+                    self.state.rebase_dialog_input.clear();
+                }
+            }
+            Action::CancelRebaseInteractive => {
+                self.state.rebase_dialog_open = false;
+                self.state.rebase_dialog_input.clear();
+            }
+            Action::RebaseDialogChar(c) => self.state.rebase_dialog_input.insert_char(c),
+            Action::RebaseDialogBackspace => self.state.rebase_dialog_input.delete_back(),
+            Action::ConfirmRebaseInteractive => {
+                let n = self
+                    .state
+                    .rebase_dialog_input
+                    .text()
+                    .parse::<usize>()
+                    .unwrap_or(0);
+                if n == 0 {
+                    self.set_status("Enter how many commits to rebase".to_string(), true);
+                    return;
+                }
+                self.state.rebase_dialog_open = false;
+                self.state.rebase_dialog_input.clear();
+                self.launch_rebase_interactive(n);
+            }
+            Action::OpenJumpToHunk => {
+                if self.state.diff.selected_delta().is_some() {
+                    self.state.jump_hunk_dialog_open = true;
+                    self.state.jump_hunk_input.clear();
+                }
+            }
+            Action::CancelJumpToHunk => {
+                self.state.jump_hunk_dialog_open = false;
+                self.state.jump_hunk_input.clear();
+            }
+            Action::JumpToHunkChar(c) => {
+                self.state.jump_hunk_input.insert_char(c);
+            }
+            Action::JumpToHunkBackspace => {
+                self.state.jump_hunk_input.delete_back();
+            }
+            Action::JumpToHunk => {
+                let display_map = self.current_display_map();
+                let rows = self
+                    .state
+                    .diff
+                    .selected_delta()
+                    .map(|delta| change_block_rows(delta, &display_map))
+                    .unwrap_or_default();
+                let n = self
+                    .state
+                    .jump_hunk_input
+                    .text()
+                    .parse::<usize>()
+                    .unwrap_or(0);
+                let Some(&row) = n.checked_sub(1).and_then(|i| rows.get(i)) else {
+                    self.set_status(format!("No hunk {n} (1-{})", rows.len()), true);
+                    return;
+                };
+                self.state.jump_hunk_dialog_open = false;
+                self.state.jump_hunk_input.clear();
+                self.state.focus = FocusPanel::DiffView;
+                self.scroll_row_to_top(row);
+                self.set_status(format!("Hunk {n}/{}", rows.len()), false);
+            }
+            Action::SplitHunk => {
+                let display_map = self.current_display_map();
+                let row = self.state.diff.cursor_row;
+                let split = display_map.get(row).and_then(|info| {
+                    let line_index = info.line_index.or(info.split_line)?;
+                    Some((info.hunk_index, line_index))
+                });
+                match split {
+                    Some(pos) if self.state.diff.hunk_splits.contains(&pos) => {
+                        self.state.diff.hunk_splits.retain(|&p| p != pos);
+                        // Stay on the line the header was above
+                        if display_map[row].is_header {
+                            self.state.diff.cursor_row = row;
+                        } else {
+                            self.state.diff.cursor_row = row.saturating_sub(1);
+                        }
+                        self.set_status("Split removed".to_string(), false);
+                    }
+                    Some((_, 0)) | None => {
+                        self.set_status("Move the cursor to a line inside a hunk".to_string(), true)
+                    }
+                    Some(pos) => {
+                        self.state.diff.hunk_splits.push(pos);
+                        // The new header takes this row; keep the cursor on the line
+                        self.state.diff.cursor_row = row + 1;
+                        self.set_status("Hunk split".to_string(), false);
+                    }
+                }
+            }
+            Action::IncreaseContext | Action::DecreaseContext => {
+                let context = self.state.diff.display_context;
+                self.state.diff.display_context = if matches!(action, Action::IncreaseContext) {
+                    (context + CONTEXT_STEP).min(MAX_CONTEXT)
+                } else {
+                    context.saturating_sub(CONTEXT_STEP).max(MIN_CONTEXT)
+                };
+                let max = self.current_display_map().len().saturating_sub(1);
+                self.state.diff.cursor_row = self.state.diff.cursor_row.min(max);
+                self.ensure_cursor_visible();
+                let lines = self.state.diff.display_context;
+                self.set_status_for_ticks(format!("Context: {lines} lines"), false, 40);
+            }
+            Action::ToggleFileLog => {
+                self.state.file_log_open = !self.state.file_log_open;
+                if self.state.file_log_open {
+                    self.load_file_log();
+                }
+            }
+            Action::FileLogUp => {
+                self.state.file_log_selected = self.state.file_log_selected.saturating_sub(1);
+            }
+            Action::FileLogDown => {
+                if self.state.file_log_selected + 1 < self.state.file_log.len() {
+                    self.state.file_log_selected += 1;
+                }
+            }
+            Action::FileLogSelect => {
+                if let Some(commit) = self.state.file_log.get(self.state.file_log_selected) {
+                    let target = ComparisonTarget::Commit(commit.oid);
+                    let label = target.label();
+                    self.state.file_log_open = false;
+                    self.apply_new_target(target, label);
+                }
+            }
+            Action::RevertCommit => {
+                if let Some(commit) = self.state.file_log.get(self.state.file_log_selected) {
+                    self.state.revert_confirm = Some(commit.clone());
+                }
+            }
+            Action::CancelRevertCommit => self.state.revert_confirm = None,
+            Action::ConfirmRevertCommit => {
+                if let Some(commit) = self.state.revert_confirm.take() {
+                    self.revert_commit(&commit);
+                }
+            }
+            Action::ReplaceInDiff => {
+                if matches!(self.target, ComparisonTarget::Range { .. }) {
+                    self.set_status(
+                        "Replace edits the working tree; not available for two-ref diffs"
+                            .to_string(),
+                        true,
+                    );
+                } else {
+                    self.state.replace_dialog_open = true;
+                    self.state.replace_editing_with = false;
+                }
+            }
+            Action::ReplaceChar(c) => {
+                if let Some(buf) = self.active_text_buffer() {
+                    buf.insert_char(c);
+                }
+            }
+            Action::ReplaceBackspace => {
+                if let Some(buf) = self.active_text_buffer() {
+                    buf.delete_back();
+                }
+            }
+            Action::ReplaceSwitchField => {
+                self.state.replace_editing_with = !self.state.replace_editing_with;
+            }
+            Action::CancelReplace => {
+                self.state.replace_dialog_open = false;
+            }
+            Action::ConfirmReplace => {
+                let search = self.state.replace_search.text().to_string();
+                let replace = self.state.replace_with.text().to_string();
+                if search.is_empty() {
+                    self.set_status("Enter a search string".to_string(), true);
+                    return;
+                }
+                let mut plan = ReplacePlan {
+                    search,
+                    replace,
+                    patch: String::new(),
+                    substitutions: 0,
+                    files: 0,
+                };
+                for delta in &self.state.diff.deltas {
+                    if let Some((file_patch, count)) =
+                        patch::replace_patch(delta, &plan.search, &plan.replace)
+                    {
+                        plan.patch.push_str(&file_patch);
+                        plan.substitutions += count;
+                        plan.files += 1;
+                    }
+                }
+                if plan.substitutions == 0 {
+                    self.set_status(format!("No added lines contain '{}'", plan.search), true);
+                } else {
+                    self.state.replace_dialog_open = false;
+                    self.state.replace_confirm = Some(plan);
+                }
+            }
+            Action::ApplyReplace => {
+                if let Some(plan) = self.state.replace_confirm.take() {
+                    match self.git_cli.apply_patch(&plan.patch, false, false) {
+                        Ok(()) => {
+                            self.state.replace_search.clear();
+                            self.state.replace_with.clear();
+                            self.set_status(
+                                format!(
+                                    "Replaced {} occurrence(s) in {} file(s)",
+                                    plan.substitutions, plan.files
+                                ),
+                                false,
+                            );
+                            self.request_diff();
+                        }
+                        Err(e) => {
+                            self.set_status(format!("Replace failed: {e}"), true);
+                        }
+                    }
+                }
+            }
+            Action::CancelApplyReplace => {
+                self.state.replace_confirm = None;
+            }
+            Action::OpenWorktreeDialog => {
+                self.state.worktree_dialog_open = true;
+                self.state.worktree_dialog_input.clear();
+            }
+            Action::CancelWorktreeDialog => {
+                self.state.worktree_dialog_open = false;
+                self.state.worktree_dialog_input.clear();
+            }
+            Action::WorktreeDialogChar(c) => {
+                self.state.worktree_dialog_input.insert_char(c);
+            }
+            Action::WorktreeDialogBackspace => {
+                self.state.worktree_dialog_input.delete_back();
+            }
+            Action::ConfirmWorktreeDialog => {
+                let branch = self.state.worktree_dialog_input.text().trim().to_string();
+                if branch.is_empty() {
+                    return;
+                }
+                let path = self.new_worktree_path();
+                match self.git_cli.create_worktree(&branch, &path) {
+                    Ok(()) => {
+                        self.state.worktree_dialog_open = false;
+                        self.state.worktree_dialog_input.clear();
+                        self.set_status(format!("Created worktree: {}", path.display()), false);
+                        self.refresh_worktrees();
+                    }
+                    Err(e) => {
+                        self.set_status(format!("Worktree creation failed: {e}"), true);
+                    }
+                }
+            }
             Action::WorktreeBack => {
                 self.state.active_view = ActiveView::DiffExplorer;
             }
@@ -1071,6 +2405,67 @@ impl App {
                     }
                 }
             }
+            Action::CollapseFile | Action::ExpandFile => {
+                let collapse = matches!(action, Action::CollapseFile);
+                if let Some(idx) = self.state.navigator.selected_delta_index() {
+                    if collapse {
+                        self.state.navigator.collapsed.insert(idx);
+                        self.state.diff.cursor_row = 0;
+                        self.state.diff.scroll_offset = 0;
+                        self.state.selection.active = false;
+                    } else {
+                        self.state.navigator.collapsed.remove(&idx);
+                    }
+                }
+            }
+            Action::ToggleFileMark => {
+                self.state.navigator.toggle_mark();
+                self.state.navigator.select_down();
+                self.sync_selection();
+            }
+            Action::BulkStage | Action::BulkUnstage => {
+                let unstage = matches!(action, Action::BulkUnstage);
+                if self.state.navigator.marked.is_empty() {
+                    self.set_status("No files marked (Space to mark)".to_string(), true);
+                } else {
+                    let mut indices: Vec<usize> =
+                        self.state.navigator.marked.iter().copied().collect();
+                    indices.sort_unstable();
+                    let paths: Vec<PathBuf> = indices
+                        .iter()
+                        .filter_map(|&i| self.state.diff.deltas.get(i))
+                        .map(|d| d.path.clone())
+                        .collect();
+
+                    let mut done = 0;
+                    let mut first_error = None;
+                    for path in &paths {
+                        let result = if unstage {
+                            self.git_cli.unstage_file(path)
+                        } else {
+                            self.git_cli.stage_file(path)
+                        };
+                        match result {
+                            Ok(()) => done += 1,
+                            Err(e) => {
+                                first_error.get_or_insert(e);
+                            }
+                        }
+                    }
+
+                    let verb = if unstage { "Unstaged" } else { "Staged" };
+                    let noun = if done == 1 { "file" } else { "files" };
+                    match first_error {
+                        Some(e) => self.set_status(
+                            format!("{verb} {done}/{} files; failed: {e}", paths.len()),
+                            true,
+                        ),
+                        None => self.set_status(format!("{verb} {done} {noun}"), false),
+                    }
+                    self.state.navigator.marked.clear();
+                    self.request_diff();
+                }
+            }
             Action::RestoreFile => {
                 if self.selected_file_path().is_some() {
                     self.state.restore_confirm_open = true;
@@ -1093,23 +2488,177 @@ impl App {
             Action::CancelRestore => {
                 self.state.restore_confirm_open = false;
             }
+            Action::ApplyHunk | Action::RevertHunk => {
+                let reverse = matches!(action, Action::RevertHunk);
+                let display_map = self.current_display_map();
+                // The run of changed lines under the cursor, as for staging
+                let block = self.state.diff.selected_delta().and_then(|delta| {
+                    let info = display_map
+                        .get(self.state.diff.cursor_row)
+                        .filter(|info| !info.is_collapsed_indicator)?;
+                    let line = info.line_index?;
+                    change_blocks(delta)
+                        .into_iter()
+                        .enumerate()
+                        .find(|(_, b)| b.hunk_index == info.hunk_index && b.lines.contains(&line))
+                });
+                match (self.state.diff.selected_file, block) {
+                    (Some(file_index), Some((block_index, block))) => {
+                        self.state.hunk_patch_confirm = Some(HunkPatchRequest {
+                            file_index,
+                            hunk_index: block.hunk_index,
+                            block_index,
+                            lines: block.lines,
+                            reverse,
+                        });
+                    }
+                    _ => {
+                        self.set_status("No hunk under the cursor".to_string(), true);
+                    }
+                }
+            }
+            Action::DiffCurrentFilePatch => {
+                let Some(path) = self.selected_file_path() else {
+                    return;
+                };
+                let counts = git2::Repository::open(&self.repo_path)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|repo| DiffEngine::staged_unstaged_lines(&repo, &path));
+                match counts {
+                    Ok((staged, unstaged)) => {
+                        self.state.patch_summary = Some(PatchSummary {
+                            path: path.display().to_string(),
+                            staged,
+                            unstaged,
+                        });
+                    }
+                    Err(e) => self.set_status(format!("Staged summary failed: {e}"), true),
+                }
+            }
+            Action::ClosePatchSummary => self.state.patch_summary = None,
+            Action::StageHunk | Action::UnstageHunk => {
+                let unstage = matches!(action, Action::UnstageHunk);
+                let display_map = self.current_display_map();
+                // Only the run of changed lines under the cursor, not the whole
+                // full-context hunk
+                let patch = self.state.diff.selected_delta().and_then(|delta| {
+                    let info = display_map
+                        .get(self.state.diff.cursor_row)
+                        .filter(|info| !info.is_collapsed_indicator)?;
+                    let line = info.line_index?;
+                    let (n, block) =
+                        change_blocks(delta)
+                            .into_iter()
+                            .enumerate()
+                            .find(|(_, b)| {
+                                b.hunk_index == info.hunk_index && b.lines.contains(&line)
+                            })?;
+                    Some((
+                        n,
+                        patch::format_patch_for_hunk(delta, block.hunk_index, block.lines)?,
+                    ))
+                });
+                let Some((block_index, patch)) = patch else {
+                    self.set_status("No hunk under the cursor".to_string(), true);
+                    return;
+                };
+                let (result, verb) = if unstage {
+                    (self.git_cli.unstage_patch(&patch), "Unstage")
+                } else {
+                    (self.git_cli.stage_patch(&patch), "Stage")
+                };
+                match result {
+                    Ok(()) => {
+                        self.set_status(format!("{verb}d: hunk {}", block_index + 1), false);
+                        self.request_diff();
+                    }
+                    Err(e) => self.set_status(format!("{verb} failed: {e}"), true),
+                }
+            }
+            Action::ConfirmHunkPatch => {
+                if let Some(req) = self.state.hunk_patch_confirm.take() {
+                    let patch = self
+                        .state
+                        .diff
+                        .deltas
+                        .get(req.file_index)
+                        .and_then(|delta| {
+                            patch::format_patch_for_hunk(delta, req.hunk_index, req.lines.clone())
+                        });
+                    match patch {
+                        Some(patch) => {
+                            let (cached, verb) = if req.reverse {
+                                (false, "Reverted")
+                            } else {
+                                (true, "Applied to index")
+                            };
+                            match self.git_cli.apply_patch(&patch, cached, req.reverse) {
+                                Ok(()) => {
+                                    self.set_status(
+                                        format!("{verb}: hunk {}", req.block_index + 1),
+                                        false,
+                                    );
+                                    self.request_diff();
+                                }
+                                Err(e) => {
+                                    self.set_status(format!("Hunk apply failed: {e}"), true);
+                                }
+                            }
+                        }
+                        None => {
+                            self.set_status("Cannot build a patch for this hunk".to_string(), true);
+                        }
+                    }
+                }
+            }
+            Action::CancelHunkPatch => {
+                self.state.hunk_patch_confirm = None;
+            }
             Action::OpenCommitDialog => {
                 self.state.commit_dialog_open = true;
+                self.state.commit_amend = false;
                 self.state.commit_message.clear();
             }
             Action::CancelCommit => {
                 self.state.commit_dialog_open = false;
+                self.state.commit_amend = false;
                 self.state.commit_message.clear();
             }
+            Action::ToggleCommitAmend => {
+                if self.state.commit_amend {
+                    self.state.commit_amend = false;
+                    // Drop the pre-filled message unless it was edited
+                    let last = self.git_cli.last_commit_message().unwrap_or_default();
+                    if self.state.commit_message.text() == last {
+                        self.state.commit_message.clear();
+                    }
+                } else {
+                    match self.git_cli.last_commit_message() {
+                        Ok(last) => {
+                            self.state.commit_amend = true;
+                            if self.state.commit_message.text().trim().is_empty() {
+                                self.state.commit_message.set(&last);
+                            }
+                        }
+                        Err(e) => self.set_status(format!("Can't amend: {e}"), true),
+                    }
+                }
+            }
             Action::ConfirmCommit => {
                 if self.state.commit_message.text().trim().is_empty() {
                     self.set_status("Commit message cannot be empty".to_string(), true);
                 } else {
                     let msg = self.state.commit_message.text().to_string();
-                    match self.git_cli.commit(&msg) {
+                    let (result, done) = if self.state.commit_amend {
+                        (self.git_cli.commit_amend(&msg), "Amended last commit")
+                    } else {
+                        (self.git_cli.commit(&msg), "Committed successfully")
+                    };
+                    match result {
                         Ok(()) => {
-                            self.set_status("Committed successfully".to_string(), false);
+                            self.set_status(done.to_string(), false);
                             self.state.commit_dialog_open = false;
+                            self.state.commit_amend = false;
                             self.state.commit_message.clear();
                             self.request_diff();
                         }
@@ -1130,33 +2679,66 @@ impl App {
             }
 
             // Target dialog
-            Action::OpenTargetDialog => {
+            Action::OpenTargetDialog | Action::NewTab => {
                 self.state.target_dialog_open = true;
+                self.state.target_dialog_new_tab = matches!(action, Action::NewTab);
                 self.state.target_dialog_input.clear();
+                self.state.target_dialog_branches =
+                    self.git_cli.list_branches().unwrap_or_default();
             }
             Action::CancelTarget => {
                 self.state.target_dialog_open = false;
+                self.state.target_dialog_new_tab = false;
                 self.state.target_dialog_input.clear();
             }
+            Action::CloseTab => self.close_tab(),
+            Action::NextTab | Action::PrevTab => {
+                let len = self.state.tabs.len();
+                let next = if matches!(action, Action::NextTab) {
+                    (self.state.active_tab + 1) % len
+                } else {
+                    (self.state.active_tab + len - 1) % len
+                };
+                if next == self.state.active_tab {
+                    self.set_status(
+                        "Only one tab is open (Ctrl+T opens another)".to_string(),
+                        false,
+                    );
+                } else {
+                    self.switch_tab(next);
+                }
+            }
             Action::TargetChar(c) => {
                 self.state.target_dialog_input.insert_char(c);
             }
             Action::TargetBackspace => {
                 self.state.target_dialog_input.delete_back();
             }
+            Action::TargetComplete => {
+                let input = self.state.target_dialog_input.text();
+                if let Some(branch) =
+                    branch_completions(&self.state.target_dialog_branches, input).first()
+                {
+                    let completed = match input.rfind("..") {
+                        Some(i) => format!("{}..{branch}", &input[..i]),
+                        None => branch.to_string(),
+                    };
+                    self.state.target_dialog_input.set(&completed);
+                }
+            }
             Action::ConfirmTarget => {
                 let input = self.state.target_dialog_input.text().trim().to_string();
                 if input.is_empty() {
                     // Reset to HEAD vs workdir
                     self.state.target_dialog_open = false;
                     self.state.target_dialog_input.clear();
-                    self.apply_new_target(ComparisonTarget::HeadVsWorkdir, "HEAD".to_string());
+                    self.confirm_target(ComparisonTarget::HeadVsWorkdir, "HEAD".to_string());
                 } else {
                     match self.validate_ref(&input) {
                         Ok((target, label)) => {
                             self.state.target_dialog_open = false;
                             self.state.target_dialog_input.clear();
-                            self.apply_new_target(target, label);
+                            self.confirm_target(target, label);
                         }
                         Err(e) => {
                             self.set_status(format!("Invalid ref '{}': {}", input, e), true);
@@ -1215,28 +2797,54 @@ impl App {
                 if !self.state.comment_editor_text.text().trim().is_empty() {
                     if let Some(editing) = self.state.editing_annotation.take() {
                         // Editing an existing annotation from the annotation menu
-                        let comment_text = self.state.comment_editor_text.text().to_string();
+                        let (comment_text, tags) =
+                            parse_tags(self.state.comment_editor_text.text());
                         self.state.annotations.update_comment(
                             &editing.file_path,
                             editing.old_range,
                             editing.new_range,
                             &editing.old_comment,
                             &comment_text,
+                            &tags,
                         );
+                        self.state
+                            .annotation_history
+                            .record(AnnotationUndoEntry::Edited {
+                                file: editing.file_path,
+                                ranges: (editing.old_range, editing.new_range),
+                                old_comment: editing.old_comment,
+                                new_comment: comment_text,
+                                old_tags: editing.old_tags,
+                                new_tags: tags,
+                            });
                         self.set_status("Comment updated".to_string(), false);
                     } else if self.state.checklist.panel_open {
                         // Adding/editing a checklist note
                         let note_text = self.state.comment_editor_text.text().to_string();
                         self.state.checklist.set_current_note(note_text);
                         self.set_status("Checklist note updated".to_string(), false);
-                    } else if let Some(anchor) = self.selection_to_anchor() {
+                    } else if let Some(mut anchor) = self.selection_to_anchor() {
                         // Creating a new annotation from visual mode
+                        if let Some(delta) = self.state.diff.selected_delta() {
+                            let (old_lines, new_lines) = self.source_lines(delta);
+                            anchor.capture_context(
+                                &old_lines.iter().map(String::as_str).collect::<Vec<_>>(),
+                                &new_lines.iter().map(String::as_str).collect::<Vec<_>>(),
+                            );
+                        }
                         let now = chrono::Utc::now().to_rfc3339();
-                        self.state.annotations.add(Annotation {
+                        let (comment, tags) = parse_tags(self.state.comment_editor_text.text());
+                        let annotation = Annotation {
                             anchor,
-                            comment: self.state.comment_editor_text.text().to_string(),
+                            comment,
                             created_at: now,
-                        });
+                            tags,
+                            unanchored: false,
+                        };
+                        self.state.annotations.add(annotation.clone());
+                        self.state
+                            .annotation_history
+                            .record(AnnotationUndoEntry::Added(annotation));
                         self.set_status("Comment added".to_string(), false);
                     }
                 }
@@ -1257,14 +2865,41 @@ impl App {
             // Annotations
             Action::DeleteAnnotation => {
                 if let Some(anchor) = self.selection_to_anchor() {
-                    self.state.annotations.delete_at(
+                    let removed = self.state.annotations.delete_at(
                         &anchor.file_path,
                         anchor.old_range,
                         anchor.new_range,
                     );
+                    for ann in removed {
+                        self.state
+                            .annotation_history
+                            .record(AnnotationUndoEntry::Deleted(ann));
+                    }
                     self.set_status("Annotation deleted".to_string(), false);
                 }
             }
+            Action::UndoAnnotation => {
+                if self
+                    .state
+                    .annotation_history
+                    .undo(&mut self.state.annotations)
+                {
+                    self.set_status("Undid annotation change".to_string(), false);
+                } else {
+                    self.set_status("Nothing to undo".to_string(), false);
+                }
+            }
+            Action::RedoAnnotation => {
+                if self
+                    .state
+                    .annotation_history
+                    .redo(&mut self.state.annotations)
+                {
+                    self.set_status("Redid annotation change".to_string(), false);
+                } else {
+                    self.set_status("Nothing to redo".to_string(), false);
+                }
+            }
             Action::NextAnnotation => {
                 let file_path = self
                     .state
@@ -1312,6 +2947,37 @@ impl App {
                 }
             }
 
+            Action::ToggleBookmark => {
+                let Some(delta) = self.state.diff.selected_delta() else {
+                    return;
+                };
+                let display_map = self.current_display_map();
+                let Some(info) = display_map
+                    .get(self.state.diff.cursor_row)
+                    .filter(|info| info.old_lineno.is_some() || info.new_lineno.is_some())
+                else {
+                    self.set_status("Move the cursor to a diff line first".to_string(), true);
+                    return;
+                };
+                let bookmark = Bookmark {
+                    file_path: delta.path.to_string_lossy().into_owned(),
+                    old_lineno: info.old_lineno,
+                    new_lineno: info.new_lineno,
+                };
+                if let Some(idx) = self.state.bookmarks.iter().position(|b| *b == bookmark) {
+                    self.state.bookmarks.remove(idx);
+                    self.set_status("Bookmark removed".to_string(), false);
+                } else {
+                    self.state.bookmarks.push(bookmark);
+                    self.set_status(
+                        format!("Bookmarked ({} total)", self.state.bookmarks.len()),
+                        false,
+                    );
+                }
+            }
+            Action::NextBookmark => self.jump_to_bookmark(true),
+            Action::PrevBookmark => self.jump_to_bookmark(false),
+
             // Annotation menu
             Action::OpenAnnotationMenu => {
                 if let Some(anchor) = self.cursor_to_anchor() {
@@ -1330,6 +2996,7 @@ impl App {
                                 old_range: a.anchor.old_range,
                                 new_range: a.anchor.new_range,
                                 comment: a.comment.clone(),
+                                tags: a.tags.clone(),
                             })
                             .collect();
                         self.state.annotation_menu_selected = 0;
@@ -1360,12 +3027,16 @@ impl App {
                     .get(self.state.annotation_menu_selected)
                     .cloned()
                 {
-                    self.state.annotations.delete_annotation(
+                    if let Some(ann) = self.state.annotations.delete_annotation(
                         &item.file_path,
                         item.old_range,
                         item.new_range,
                         &item.comment,
-                    );
+                    ) {
+                        self.state
+                            .annotation_history
+                            .record(AnnotationUndoEntry::Deleted(ann));
+                    }
                     self.state
                         .annotation_menu_items
                         .remove(self.state.annotation_menu_selected);
@@ -1396,10 +3067,13 @@ impl App {
                             old_range: item.old_range,
                             new_range: item.new_range,
                             old_comment: item.comment.clone(),
+                            old_tags: item.tags.clone(),
                         });
                     self.state.annotation_menu_open = false;
                     self.state.comment_editor_open = true;
-                    self.state.comment_editor_text.set(&item.comment);
+                    self.state
+                        .comment_editor_text
+                        .set(&format_with_tags(&item.comment, &item.tags));
                 }
             }
             Action::CancelAnnotationMenu => {
@@ -1407,9 +3081,65 @@ impl App {
                 self.state.annotation_menu_items.clear();
             }
 
+            // Right-click menu
+            Action::OpenContextMenu(x, y) => {
+                if self.state.diff.selected_delta().is_some() {
+                    self.state.focus = FocusPanel::DiffView;
+                    self.state.context_menu = Some(ContextMenuState { x, y, selected: 0 });
+                }
+            }
+            Action::ContextMenuUp => {
+                if let Some(menu) = self.state.context_menu.as_mut() {
+                    menu.selected = menu
+                        .selected
+                        .checked_sub(1)
+                        .unwrap_or(CONTEXT_MENU_ITEMS.len() - 1);
+                }
+            }
+            Action::ContextMenuDown => {
+                if let Some(menu) = self.state.context_menu.as_mut() {
+                    menu.selected = (menu.selected + 1) % CONTEXT_MENU_ITEMS.len();
+                }
+            }
+            Action::ContextMenuClick(idx) => {
+                if let Some(menu) = self.state.context_menu.as_mut() {
+                    menu.selected = idx;
+                }
+                self.update(Action::ContextMenuSelect);
+            }
+            Action::ContextMenuSelect => {
+                if let Some(menu) = self.state.context_menu.take() {
+                    if let Some((_, action)) = CONTEXT_MENU_ITEMS.get(menu.selected) {
+                        self.update(action.clone());
+                    }
+                }
+            }
+            Action::CloseContextMenu => {
+                self.state.context_menu = None;
+            }
+
             // Prompt / clipboard
+            Action::ExportAnnotations => {
+                if self.state.annotations.count() == 0 {
+                    self.set_status("No annotations to export".to_string(), true);
+                } else {
+                    match export::export_annotations(
+                        &self.repo_path,
+                        &self.state.target_label,
+                        &self.state.annotations,
+                        self.state.export_format,
+                    ) {
+                        Ok(path) => {
+                            self.set_status(format!("Exported to {}", path.display()), false)
+                        }
+                        Err(e) => self.set_status(format!("Export failed: {e}"), true),
+                    }
+                }
+            }
+            Action::ImportGithubReviews => self.start_github_import(),
             Action::CopyPromptToClipboard => {
-                if let Some(rendered) = self.render_prompt_for_all_files() {
+                let agent = self.state.agent_selector.selected_agent_config();
+                if let Some(rendered) = self.render_prompt_for_all_files(agent) {
                     match arboard::Clipboard::new().and_then(|mut cb| cb.set_text(&rendered)) {
                         Ok(()) => self.set_status("Prompt copied to clipboard".to_string(), false),
                         Err(e) => {
@@ -1420,10 +3150,170 @@ impl App {
                     self.set_status("No diff to copy".to_string(), true);
                 }
             }
+            Action::CopyFileDiff => {
+                let Some(delta) = self.state.diff.selected_delta() else {
+                    self.set_status("No diff to copy".to_string(), true);
+                    return;
+                };
+                let Some(diff) = patch::file_patch(delta) else {
+                    self.set_status("Binary file — no text diff to copy".to_string(), true);
+                    return;
+                };
+                match arboard::Clipboard::new().and_then(|mut cb| cb.set_text(&diff)) {
+                    Ok(()) => self.set_status("File diff copied to clipboard".to_string(), false),
+                    Err(e) => {
+                        self.set_status(format!("Clipboard error: {e}"), true);
+                    }
+                }
+            }
+            Action::CopyFileList => {
+                if self.state.diff.deltas.is_empty() {
+                    self.set_status("No diff to copy".to_string(), true);
+                    return;
+                }
+                let list = export::render_file_list(&self.state.diff.deltas);
+                match arboard::Clipboard::new().and_then(|mut cb| cb.set_text(&list)) {
+                    Ok(()) => self.set_status(
+                        format!(
+                            "List of {} files copied to clipboard",
+                            self.state.diff.deltas.len()
+                        ),
+                        false,
+                    ),
+                    Err(e) => {
+                        self.set_status(format!("Clipboard error: {e}"), true);
+                    }
+                }
+            }
+            Action::DiffFormatJson => {
+                if self.state.diff.deltas.is_empty() {
+                    self.set_status("No diff to copy".to_string(), true);
+                    return;
+                }
+                let json = export::serialize_deltas_to_json(&self.state.diff.deltas);
+                match arboard::Clipboard::new().and_then(|mut cb| cb.set_text(&json)) {
+                    Ok(()) => self.set_status(
+                        format!(
+                            "Diff of {} files copied as JSON",
+                            self.state.diff.deltas.len()
+                        ),
+                        false,
+                    ),
+                    Err(e) => {
+                        self.set_status(format!("Clipboard error: {e}"), true);
+                    }
+                }
+            }
+            Action::CopyHunkPatch => {
+                let Some(delta) = self.state.diff.selected_delta() else {
+                    self.set_status("No diff to copy".to_string(), true);
+                    return;
+                };
+                let blocks = change_blocks(delta);
+                let display_map = self.current_display_map();
+                let block_of = |info: &DisplayRowInfo| {
+                    let line = info.line_index?;
+                    blocks
+                        .iter()
+                        .find(|b| b.hunk_index == info.hunk_index && b.lines.contains(&line))
+                };
+                // The block under the cursor, else the nearest one above or below it
+                let cursor = self.state.diff.cursor_row;
+                let block = display_map
+                    .iter()
+                    .take(cursor + 1)
+                    .rev()
+                    .find_map(block_of)
+                    .or_else(|| display_map.iter().skip(cursor).find_map(block_of));
+                let patch = block.and_then(|b| {
+                    patch::format_patch_for_hunk(delta, b.hunk_index, b.lines.clone())
+                });
+                let Some(patch) = patch else {
+                    self.set_status("No hunk to copy here".to_string(), true);
+                    return;
+                };
+                match arboard::Clipboard::new().and_then(|mut cb| cb.set_text(&patch)) {
+                    Ok(()) => self.set_status("Hunk patch copied to clipboard".to_string(), false),
+                    Err(e) => {
+                        self.set_status(format!("Clipboard error: {e}"), true);
+                    }
+                }
+            }
             Action::TogglePromptPreview => {
                 self.state.prompt_preview_visible = !self.state.prompt_preview_visible;
                 if self.state.prompt_preview_visible {
                     self.update_prompt_preview();
+                } else if self.state.focus == FocusPanel::PromptPreview {
+                    self.state.focus = FocusPanel::DiffView;
+                }
+            }
+            Action::PromptPreviewGrow | Action::PromptPreviewShrink => {
+                if !self.state.prompt_preview_visible {
+                    return;
+                }
+                // The ratio is the diff's share, so growing the preview lowers it
+                let ratio = if matches!(action, Action::PromptPreviewGrow) {
+                    self.state
+                        .prompt_preview_ratio
+                        .saturating_sub(PROMPT_PREVIEW_RATIO_STEP)
+                } else {
+                    self.state.prompt_preview_ratio + PROMPT_PREVIEW_RATIO_STEP
+                };
+                self.state.prompt_preview_ratio =
+                    ratio.clamp(PROMPT_PREVIEW_RATIO_MIN, PROMPT_PREVIEW_RATIO_MAX);
+                self.config.prompt_preview_ratio = self.state.prompt_preview_ratio;
+                config::save_prompt_preview_ratio(self.state.prompt_preview_ratio);
+            }
+            Action::FocusPromptPreview => {
+                if self.state.prompt_preview_visible {
+                    self.state.focus = FocusPanel::PromptPreview;
+                }
+            }
+            Action::PromptPreviewScrollUp => self.scroll_prompt_preview(-1),
+            Action::PromptPreviewScrollDown => self.scroll_prompt_preview(1),
+            Action::PromptPreviewPageUp => {
+                self.scroll_prompt_preview(-(PROMPT_PREVIEW_PAGE_STEP as isize));
+            }
+            Action::PromptPreviewPageDown => {
+                self.scroll_prompt_preview(PROMPT_PREVIEW_PAGE_STEP as isize);
+            }
+            Action::PromptPreviewTop => self.state.preview_scroll_offset = 0,
+            Action::PromptPreviewBottom => self.scroll_prompt_preview(isize::MAX),
+            Action::StartPreviewSearch => {
+                self.state.preview_search_active = true;
+                self.state.preview_search_query.clear();
+                self.state.preview_search_matches.clear();
+                self.state.preview_search_match_index = None;
+            }
+            Action::EndPreviewSearch => {
+                self.state.preview_search_active = false;
+            }
+            Action::PreviewSearchChar(c) => {
+                self.state.preview_search_query.insert_char(c);
+                self.recompute_preview_search_matches();
+            }
+            Action::PreviewSearchBackspace => {
+                self.state.preview_search_query.delete_back();
+                self.recompute_preview_search_matches();
+            }
+            Action::PreviewSearchNext => {
+                let len = self.state.preview_search_matches.len();
+                if len > 0 {
+                    let next = self
+                        .state
+                        .preview_search_match_index
+                        .map_or(0, |idx| (idx + 1) % len);
+                    self.jump_to_preview_match(next);
+                }
+            }
+            Action::PreviewSearchPrev => {
+                let len = self.state.preview_search_matches.len();
+                if len > 0 {
+                    let prev = match self.state.preview_search_match_index {
+                        Some(0) | None => len - 1,
+                        Some(idx) => idx - 1,
+                    };
+                    self.jump_to_preview_match(prev);
                 }
             }
 
@@ -1436,6 +3326,10 @@ impl App {
                         .agent_selector
                         .last_models
                         .clone_from(&self.config.agent_models);
+                    self.state
+                        .agent_selector
+                        .favorites
+                        .clone_from(&self.config.agent_favorites);
                     self.state.agent_selector.populate(&self.config.agents);
                     self.state.agent_selector.rerun_prompt = None;
                     self.state.agent_selector.open = true;
@@ -1462,6 +3356,16 @@ impl App {
             Action::AgentSelectorCycleModel => {
                 self.state.agent_selector.cycle_model();
             }
+            Action::FavoriteAgent => {
+                if let Some((name, favorite)) = self.state.agent_selector.toggle_favorite() {
+                    self.config
+                        .agent_favorites
+                        .clone_from(&self.state.agent_selector.favorites);
+                    config::save_agent_favorites(&self.config.agent_favorites);
+                    let verb = if favorite { "Starred" } else { "Unstarred" };
+                    self.set_status(format!("{verb} {name}"), false);
+                }
+            }
             Action::SelectAgent => {
                 let agent = self.state.agent_selector.selected_agent_config().cloned();
                 let model = self.state.agent_selector.selected_model_name();
@@ -1470,73 +3374,11 @@ impl App {
                 if let (Some(agent), Some(model)) = (agent, model) {
                     // Always use all files + all annotations for the prompt
                     let rendered_prompt =
-                        rerun_prompt.or_else(|| self.render_prompt_for_all_files());
+                        rerun_prompt.or_else(|| self.render_prompt_for_all_files(Some(&agent)));
 
                     if let Some(prompt) = rendered_prompt {
-                        let command = build_agent_command(&agent.command, &model, &prompt);
-                        let run_id = self.state.agent_outputs.next_id;
-
-                        // Size PTY to match the actual rendered inner area:
-                        // Layout: 30% left sidebar | 70% detail pane, with borders
-                        let (term_cols, term_rows) =
-                            crossterm::terminal::size().unwrap_or((120, 40));
-                        // Detail pane is 70% width minus 2 for block borders
-                        let pty_cols = (term_cols * 70 / 100).saturating_sub(2).max(40);
-                        // Height: full terminal minus context_bar(1) - hud(1) - block borders(2)
-                        let pty_rows = term_rows.saturating_sub(4).max(10);
-
-                        let worktree_name = self
-                            .repo_path
-                            .file_name()
-                            .map(|n| n.to_string_lossy().into_owned())
-                            .unwrap_or_else(|| "unknown".to_string());
-                        let worktree_path = self.repo_path.clone();
-
-                        let run = AgentRun {
-                            id: run_id,
-                            agent_name: agent.name.clone(),
-                            model: model.clone(),
-                            command: command.clone(),
-                            rendered_prompt: prompt,
-                            terminal: vt100::Parser::new(pty_rows, pty_cols, 10000),
-                            status: AgentRunStatus::Running,
-                            started_at: chrono::Utc::now().format("%H:%M").to_string(),
-                            worktree_name,
-                            worktree_path,
-                        };
-
-                        self.state.agent_outputs.add_run(run);
-                        self.pty_runner = Some(PtyRunner::spawn(
-                            run_id,
-                            &command,
-                            pty_rows,
-                            pty_cols,
-                            &self.repo_path,
-                        ));
                         self.state.agent_selector.open = false;
-                        self.state.active_view = ActiveView::AgentOutputs;
-                        self.state.pty_focus = true;
-
-                        // Clear annotations — they've been captured in the prompt
-                        self.state.annotations = Default::default();
-                        session::save_session_data(
-                            &self.repo_path,
-                            &self.state.target_label,
-                            &self.state.annotations,
-                            if self.state.checklist.is_empty() {
-                                None
-                            } else {
-                                Some(&self.state.checklist)
-                            },
-                        );
-
-                        // Persist last-used model for this agent
-                        self.config
-                            .agent_models
-                            .insert(agent.name.clone(), model.clone());
-                        config::save_agent_model(&agent.name, &model);
-
-                        self.set_status(format!("Running {}/{}", agent.name, model), false);
+                        self.launch_agent(&agent, model, prompt);
                     } else {
                         self.set_status("No diff to review".to_string(), true);
                     }
@@ -1557,6 +3399,14 @@ impl App {
             Action::AgentOutputsDown => {
                 self.state.agent_outputs.select_down();
             }
+            Action::AgentOutputsListScrollUp => {
+                self.state.agent_outputs.scroll_list_up(RUN_LIST_PAGE_STEP);
+            }
+            Action::AgentOutputsListScrollDown => {
+                self.state
+                    .agent_outputs
+                    .scroll_list_down(RUN_LIST_PAGE_STEP);
+            }
             Action::AgentOutputsCopyPrompt => {
                 if let Some(run) = self.state.agent_outputs.selected() {
                     let prompt = run.rendered_prompt.clone();
@@ -1568,6 +3418,25 @@ impl App {
                     }
                 }
             }
+            Action::RerunAgent => {
+                let Some(run) = self.state.agent_outputs.selected() else {
+                    return;
+                };
+                if matches!(run.status, AgentRunStatus::Running) {
+                    self.set_status("Agent is still running".to_string(), true);
+                    return;
+                }
+                let (name, model) = (run.agent_name.clone(), run.model.clone());
+                let Some(agent) = self.config.agents.iter().find(|a| a.name == name).cloned()
+                else {
+                    self.set_status(format!("Agent {name} is no longer configured"), true);
+                    return;
+                };
+                match self.render_prompt_for_all_files(Some(&agent)) {
+                    Some(prompt) => self.launch_agent(&agent, model, prompt),
+                    None => self.set_status("No diff to review".to_string(), true),
+                }
+            }
             Action::KillAgentProcess => {
                 if let Some(run) = self.state.agent_outputs.selected() {
                     if matches!(run.status, AgentRunStatus::Running) {
@@ -1586,6 +3455,7 @@ impl App {
                     self.repo_path = new_path.clone();
                     self.worker = DiffWorker::new(new_path.clone());
                     self.git_cli = GitCli::new(&new_path);
+                    self.reload_ignore_file();
                     self.generation = 0;
                     self.state.diff.deltas.clear();
                     self.state.diff.selected_file = None;
@@ -1609,7 +3479,6 @@ impl App {
                 }
             }
             Action::NextUnreviewed => {
-                use crate::state::review_state::FileReviewStatus;
                 let visible = self.state.navigator.visible_entries();
                 if visible.is_empty() {
                     return;
@@ -1625,6 +3494,7 @@ impl App {
                         status,
                         FileReviewStatus::Unreviewed
                             | FileReviewStatus::ChangedSinceReview
+                            | FileReviewStatus::PartiallyChanged { .. }
                             | FileReviewStatus::New
                     ) {
                         self.state.navigator.selected = idx;
@@ -1691,6 +3561,7 @@ impl App {
             }
 
             Action::Tick => {
+                self.state.spinner_tick = self.state.spinner_tick.wrapping_add(1);
                 if self.quit_confirm_countdown > 0 {
                     self.quit_confirm_countdown -= 1;
                     if self.quit_confirm_countdown == 0 {
@@ -1703,12 +3574,32 @@ impl App {
                         self.state.status_message = None;
                     }
                 }
+                if let Some((prefix, ticks)) = self.chord_prefix.as_mut() {
+                    *ticks -= 1;
+                    if *ticks == 0 {
+                        let prefix = *prefix;
+                        self.chord_prefix = None;
+                        if !chord_is_eager(prefix) {
+                            self.update(chord_fallback(prefix));
+                        }
+                    }
+                }
                 if self.hud_collapse_countdown > 0 {
                     self.hud_collapse_countdown -= 1;
                     if self.hud_collapse_countdown == 0 {
                         self.state.hud_expanded = false;
                     }
                 }
+                if tick_pending_action(&mut self.pending_action) {
+                    if let Some(pending) = &self.pending_action {
+                        let key = safe_mode_key(&pending.action, &self.config.keybindings);
+                        self.set_status_for_ticks(
+                            format!("Press {key} again to confirm or any other key to cancel"),
+                            false,
+                            SAFE_MODE_PROMPT_TICKS,
+                        );
+                    }
+                }
             }
             Action::ExpandContext => {
                 let display_map = self.current_display_map();
@@ -1727,28 +3618,54 @@ impl App {
                     }
                 }
             }
-            Action::JumpNextHunk => {
+            Action::JumpNextHunk | Action::JumpPrevHunk => {
+                let forward = matches!(action, Action::JumpNextHunk);
                 let display_map = self.current_display_map();
-                if let Some(row) = self.find_next_hunk_row(self.state.diff.cursor_row, &display_map)
-                {
-                    self.state.diff.cursor_row = row;
-                    self.state.diff.scroll_offset = self.visual_offset_for_row(row);
-                    let total_hunks = display_map.iter().filter(|r| r.is_header).count();
-                    let current_hunk = display_map[..=row].iter().filter(|r| r.is_header).count();
+                let rows = self
+                    .state
+                    .diff
+                    .selected_delta()
+                    .map(|delta| change_block_rows(delta, &display_map))
+                    .unwrap_or_default();
+                let cursor = self.state.diff.cursor_row;
+                let next = if forward {
+                    rows.iter()
+                        .position(|&r| r > cursor)
+                        .or((!rows.is_empty()).then_some(0))
+                } else {
+                    rows.iter()
+                        .rposition(|&r| r < cursor)
+                        .or(rows.len().checked_sub(1))
+                };
+                if let Some(i) = next {
+                    self.scroll_row_to_top(rows[i]);
                     self.state.status_message =
-                        Some((format!("Hunk {}/{}", current_hunk, total_hunks), false));
+                        Some((format!("Hunk {}/{}", i + 1, rows.len()), false));
                 }
             }
-            Action::JumpPrevHunk => {
+            Action::NextConflict | Action::PrevConflict => {
+                let forward = matches!(action, Action::NextConflict);
                 let display_map = self.current_display_map();
-                if let Some(row) = self.find_prev_hunk_row(self.state.diff.cursor_row, &display_map)
-                {
-                    self.state.diff.cursor_row = row;
-                    self.state.diff.scroll_offset = self.visual_offset_for_row(row);
-                    let total_hunks = display_map.iter().filter(|r| r.is_header).count();
-                    let current_hunk = display_map[..=row].iter().filter(|r| r.is_header).count();
-                    self.state.status_message =
-                        Some((format!("Hunk {}/{}", current_hunk, total_hunks), false));
+                let rows = self
+                    .state
+                    .diff
+                    .selected_delta()
+                    .map(|delta| conflict_rows(delta, &display_map))
+                    .unwrap_or_default();
+                let cursor = self.state.diff.cursor_row;
+                let next = if forward {
+                    rows.iter().find(|&&r| r > cursor).or(rows.first())
+                } else {
+                    rows.iter().rev().find(|&&r| r < cursor).or(rows.last())
+                };
+                match next {
+                    Some(&row) => {
+                        self.scroll_row_to_top(row);
+                        let current = rows.iter().position(|&r| r == row).unwrap_or(0) + 1;
+                        self.state.status_message =
+                            Some((format!("Conflict marker {current}/{}", rows.len()), false));
+                    }
+                    None => self.set_status("No conflict markers in this file".to_string(), false),
                 }
             }
             // Settings modal
@@ -1758,13 +3675,7 @@ impl App {
             }
             Action::CloseSettings => {
                 self.state.settings.open = false;
-                // Persist all settings to config.toml
-                config::save_settings(&PersistentSettings {
-                    theme: self.state.theme.name.clone(),
-                    unified: self.state.diff.options.view_mode == DiffViewMode::Unified,
-                    ignore_whitespace: self.state.diff.options.ignore_whitespace,
-                    context_lines: self.state.diff.display_context,
-                });
+                self.save_settings();
             }
             Action::SettingsUp => {
                 if self.state.settings.selected_row > 0 {
@@ -1776,12 +3687,97 @@ impl App {
                     self.state.settings.selected_row += 1;
                 }
             }
+            Action::SettingsActivate => match self.state.settings.selected_row {
+                6 => {
+                    self.update(Action::CloseSettings);
+                    self.update(Action::ExportSession);
+                }
+                7 => {
+                    self.update(Action::CloseSettings);
+                    self.update(Action::ImportSession);
+                }
+                _ => {}
+            },
+            Action::ExportSession | Action::ImportSession => {
+                let mode = if action == Action::ExportSession {
+                    SessionDialogMode::Export
+                } else {
+                    SessionDialogMode::Import
+                };
+                self.state.session_dialog = Some(mode);
+                let path = session::default_session_file(&self.repo_path, &self.state.target_label);
+                self.state.session_dialog_input.set(&path.to_string_lossy());
+            }
+            Action::SessionDialogChar(c) => {
+                self.state.session_dialog_input.insert_char(c);
+            }
+            Action::SessionDialogBackspace => {
+                self.state.session_dialog_input.delete_back();
+            }
+            Action::CancelSessionDialog => {
+                self.state.session_dialog = None;
+                self.state.session_dialog_input.clear();
+            }
+            Action::ConfirmSessionDialog => {
+                let input = self.state.session_dialog_input.text().trim().to_string();
+                let Some(mode) = self.state.session_dialog else {
+                    return;
+                };
+                if input.is_empty() {
+                    self.set_status("Enter a file name".to_string(), true);
+                    return;
+                }
+                self.state.session_dialog = None;
+                self.state.session_dialog_input.clear();
+                let path = self.repo_path.join(&input);
+                match mode {
+                    SessionDialogMode::Export => match session::export_session_file(
+                        &path,
+                        &self.state.target_label,
+                        &self.state.annotations,
+                        &self.state.review,
+                    ) {
+                        Ok(count) => self
+                            .set_status(format!("Exported {count} annotations to {input}"), false),
+                        Err(e) => self.set_status(format!("{e:#}"), true),
+                    },
+                    SessionDialogMode::Import => match session::read_session_file(&path) {
+                        Ok(session) => {
+                            let conflicts =
+                                self.state.annotations.count_conflicts(&session.annotations);
+                            let pending = PendingSessionImport {
+                                path,
+                                session,
+                                conflicts,
+                            };
+                            if conflicts == 0 {
+                                self.apply_session_import(pending, MergeResolution::KeepBoth);
+                            } else {
+                                self.state.session_import = Some(pending);
+                            }
+                        }
+                        Err(e) => self.set_status(format!("{e:#}"), true),
+                    },
+                }
+            }
+            Action::ResolveSessionImport(resolution) => {
+                if let Some(pending) = self.state.session_import.take() {
+                    self.apply_session_import(pending, resolution);
+                }
+            }
+            Action::CancelSessionImport => {
+                self.state.session_import = None;
+                self.set_status("Session import cancelled".to_string(), false);
+            }
             Action::SettingsLeft => {
                 match self.state.settings.selected_row {
                     0 => {
                         // Prev theme
                         let new_name = prev_theme(&self.state.theme.name);
-                        self.state.theme = Theme::from_name(new_name);
+                        self.state.theme = self
+                            .config
+                            .color_support
+                            .adapt_theme(Theme::from_name(new_name));
                         self.update_highlights();
                     }
                     1 => {
@@ -1802,11 +3798,20 @@ impl App {
                         self.request_diff();
                     }
                     3 => {
-                        // Decrease context lines (min 1)
-                        if self.state.diff.display_context > 1 {
+                        // Decrease context lines
+                        if self.state.diff.display_context > MIN_CONTEXT {
                             self.state.diff.display_context -= 1;
                         }
                     }
+                    4 => {
+                        self.state.context_padding = self.state.context_padding.saturating_sub(1);
+                        if self.state.prompt_preview_visible {
+                            self.update_prompt_preview();
+                        }
+                    }
+                    5 => {
+                        self.state.export_format = self.state.export_format.toggle();
+                    }
                     _ => {}
                 }
             }
@@ -1815,7 +3820,10 @@ impl App {
                     0 => {
                         // Next theme
                         let new_name = next_theme(&self.state.theme.name);
-                        self.state.theme = Theme::from_name(new_name);
+                        self.state.theme = self
+                            .config
+                            .color_support
+                            .adapt_theme(Theme::from_name(new_name));
                         self.update_highlights();
                     }
                     1 => {
@@ -1836,11 +3844,22 @@ impl App {
                         self.request_diff();
                     }
                     3 => {
-                        // Increase context lines (max 20)
-                        if self.state.diff.display_context < 20 {
+                        // Increase context lines
+                        if self.state.diff.display_context < MAX_CONTEXT {
                             self.state.diff.display_context += 1;
                         }
                     }
+                    4 => {
+                        if self.state.context_padding < MAX_CONTEXT_PADDING {
+                            self.state.context_padding += 1;
+                        }
+                        if self.state.prompt_preview_visible {
+                            self.update_prompt_preview();
+                        }
+                    }
+                    5 => {
+                        self.state.export_format = self.state.export_format.toggle();
+                    }
                     _ => {}
                 }
             }
@@ -1878,6 +3897,50 @@ impl App {
                 }
             }
 
+            // Review summary
+            Action::ToggleReviewSummary => {
+                if self.state.active_view == ActiveView::ReviewSummary {
+                    self.state.active_view = ActiveView::DiffExplorer;
+                } else {
+                    self.state.active_view = ActiveView::ReviewSummary;
+                    self.state.review_summary_selected = 0;
+                }
+            }
+            Action::ReviewSummaryUp => {
+                self.state.review_summary_selected =
+                    self.state.review_summary_selected.saturating_sub(1);
+            }
+            Action::ReviewSummaryDown => {
+                let len = review_summary_rows(&self.state.annotations).len();
+                if self.state.review_summary_selected + 1 < len {
+                    self.state.review_summary_selected += 1;
+                }
+            }
+            Action::ReviewSummarySelect => self.jump_to_review_summary_row(),
+            Action::ReviewSummaryDeleteFile => {
+                let rows = review_summary_rows(&self.state.annotations);
+                let Some(path) = rows
+                    .get(self.state.review_summary_selected)
+                    .map(|row| row.file_path().to_string())
+                else {
+                    return;
+                };
+                let removed = self.state.annotations.annotations.remove(&path);
+                let count = removed.as_ref().map_or(0, Vec::len);
+                for ann in removed.into_iter().flatten() {
+                    self.state
+                        .annotation_history
+                        .record(AnnotationUndoEntry::Deleted(ann));
+                }
+                let len = review_summary_rows(&self.state.annotations).len();
+                self.state.review_summary_selected = self
+                    .state
+                    .review_summary_selected
+                    .min(len.saturating_sub(1));
+                self.set_status(format!("Deleted {count} annotations on {path}"), false);
+            }
+            Action::ExportChecklist => self.export_review_checklist(),
+
             // Generic text input navigation
             Action::TextCursorLeft => {
                 if let Some(buf) = self.active_text_buffer() {
@@ -1909,6 +3972,8 @@ impl App {
                     self.sync_selection();
                 } else if self.state.diff.search_active {
                     self.recompute_diff_search_matches();
+                } else if self.state.preview_search_active {
+                    self.recompute_preview_search_matches();
                 } else if self.state.agent_selector.open {
                     self.state.agent_selector.refilter();
                 }
@@ -1983,12 +4048,28 @@ impl App {
             Some(&mut self.state.commit_message)
         } else if self.state.target_dialog_open {
             Some(&mut self.state.target_dialog_input)
+        } else if self.state.worktree_dialog_open {
+            Some(&mut self.state.worktree_dialog_input)
+        } else if self.state.filter_dialog_open {
+            Some(&mut self.state.filter_dialog_input)
+        } else if self.state.push_dialog_open {
+            Some(&mut self.state.push_dialog_input)
+        } else if self.state.session_dialog.is_some() {
+            Some(&mut self.state.session_dialog_input)
+        } else if self.state.replace_dialog_open {
+            if self.state.replace_editing_with {
+                Some(&mut self.state.replace_with)
+            } else {
+                Some(&mut self.state.replace_search)
+            }
         } else if self.state.comment_editor_open {
             Some(&mut self.state.comment_editor_text)
         } else if self.state.global_search.active {
             Some(&mut self.state.global_search.query)
         } else if self.state.diff.search_active {
             Some(&mut self.state.diff.search_query)
+        } else if self.state.preview_search_active {
+            Some(&mut self.state.preview_search_query)
         } else if self.state.navigator.search_active {
             Some(&mut self.state.navigator.search_query)
         } else if self.state.agent_selector.open {
@@ -1998,6 +4079,52 @@ impl App {
         }
     }
 
+    /// Merge an imported session's annotations and reviewed hunks into ours.
+    fn apply_session_import(&mut self, pending: PendingSessionImport, resolution: MergeResolution) {
+        let PendingSessionImport { path, session, .. } = pending;
+        let added = self
+            .state
+            .annotations
+            .merge(session.annotations, resolution);
+        self.state.review.merge_reviewed(session.reviewed);
+        session::save_session_data(
+            &self.repo_path,
+            &self.state.target_label,
+            &self.state.annotations,
+            if self.state.checklist.is_empty() {
+                None
+            } else {
+                Some(&self.state.checklist)
+            },
+            &self.state.review,
+        );
+        let name = path.file_name().map_or_else(
+            || path.display().to_string(),
+            |n| n.to_string_lossy().into_owned(),
+        );
+        self.set_status(format!("Imported {added} annotations from {name}"), false);
+    }
+
+    /// Where the worktree dialog will create the branch currently typed into it.
+    fn new_worktree_path(&self) -> PathBuf {
+        let repo_root = self
+            .state
+            .worktree
+            .worktrees
+            .iter()
+            .find(|wt| wt.is_main)
+            .map_or(self.repo_path.as_path(), |wt| wt.path.as_path());
+        let branch = self.state.worktree_dialog_input.text().trim();
+        worktree::default_worktree_path(
+            repo_root,
+            if branch.is_empty() {
+                "<branch>"
+            } else {
+                branch
+            },
+        )
+    }
+
     fn refresh_worktrees(&mut self) {
         match worktree::list_worktrees(&self.repo_path) {
             Ok(wts) => {
@@ -2019,19 +4146,118 @@ impl App {
         self.status_clear_countdown = ticks;
     }
 
-    /// Validate a ref string against the repo. Returns the ComparisonTarget and a display label.
-    fn validate_ref(&self, input: &str) -> Result<(ComparisonTarget, String), String> {
-        let repo =
-            git2::Repository::open(&self.repo_path).map_err(|e| format!("open repo: {e}"))?;
-        repo.revparse_single(input).map_err(|e| format!("{e}"))?;
-        // Use parse_target for consistent ComparisonTarget construction
-        let target = parse_target(Some(input));
-        let label = match &target {
-            ComparisonTarget::HeadVsWorkdir => "HEAD".to_string(),
-            ComparisonTarget::Branch(name) => name.clone(),
-            ComparisonTarget::Commit(oid) => format!("{:.7}", oid),
+    /// Validate a ref string against the repo. Returns the ComparisonTarget and a display label.
+    fn validate_ref(&self, input: &str) -> Result<(ComparisonTarget, String), String> {
+        if !input.contains("..") {
+            let repo =
+                git2::Repository::open(&self.repo_path).map_err(|e| format!("open repo: {e}"))?;
+            repo.revparse_single(input).map_err(|e| format!("{e}"))?;
+        }
+        // Use parse_target for consistent ComparisonTarget construction
+        let target = parse_target(&self.repo_path, Some(input))?;
+        let label = target.label();
+        Ok((target, label))
+    }
+
+    /// Apply the target dialog's choice, in a new tab when it was opened with `Ctrl+T`.
+    fn confirm_target(&mut self, target: ComparisonTarget, label: String) {
+        if !std::mem::take(&mut self.state.target_dialog_new_tab) {
+            self.apply_new_target(target, label);
+            return;
+        }
+        let mut tab = TabState::new(
+            target,
+            label,
+            DiffWorker::new(self.repo_path.clone()),
+            self.state.diff.options.clone(),
+        );
+        tab.diff.display_context = self.state.diff.display_context;
+        self.state.tabs.push(tab);
+        self.switch_tab(self.state.tabs.len() - 1);
+        self.state.focus = FocusPanel::Navigator;
+        self.load_target_session();
+    }
+
+    /// Exchange the live per-target state with the active tab's slot.
+    fn swap_active_tab(&mut self) {
+        let state = &mut self.state;
+        let tab = &mut state.tabs[state.active_tab];
+        std::mem::swap(&mut self.target, &mut tab.target);
+        std::mem::swap(&mut self.worker, &mut tab.worker);
+        std::mem::swap(&mut self.generation, &mut tab.generation);
+        std::mem::swap(&mut state.target_label, &mut tab.target_label);
+        std::mem::swap(&mut state.diff, &mut tab.diff);
+        std::mem::swap(&mut state.navigator, &mut tab.navigator);
+        std::mem::swap(&mut state.selection, &mut tab.selection);
+        std::mem::swap(&mut state.review, &mut tab.review);
+        std::mem::swap(&mut state.annotations, &mut tab.annotations);
+        std::mem::swap(&mut state.annotation_history, &mut tab.annotation_history);
+        std::mem::swap(&mut state.checklist, &mut tab.checklist);
+        std::mem::swap(&mut state.bookmarks, &mut tab.bookmarks);
+    }
+
+    fn switch_tab(&mut self, idx: usize) {
+        if idx == self.state.active_tab || idx >= self.state.tabs.len() {
+            return;
+        }
+        // Park the live state in its slot, then load the target tab from its slot
+        self.swap_active_tab();
+        self.state.active_tab = idx;
+        self.swap_active_tab();
+
+        // Search matches index into the previous tab's deltas
+        self.state.global_search = GlobalSearchState::default();
+        self.update_highlights();
+        if self.state.file_log_open {
+            self.load_file_log();
+        }
+        if self.state.prompt_preview_visible {
+            self.update_prompt_preview();
+        }
+        // Pick up changes made while the tab was in the background
+        self.request_diff();
+        self.set_status(
+            format!(
+                "Tab {}/{}: {}",
+                idx + 1,
+                self.state.tabs.len(),
+                self.state.target_label
+            ),
+            false,
+        );
+    }
+
+    fn close_tab(&mut self) {
+        if self.state.tabs.len() < 2 {
+            self.set_status("Can't close the last tab".to_string(), true);
+            return;
+        }
+        session::save_session_data(
+            &self.repo_path,
+            &self.state.target_label,
+            &self.state.annotations,
+            if self.state.checklist.is_empty() {
+                None
+            } else {
+                Some(&self.state.checklist)
+            },
+            &self.state.review,
+        );
+        let closing = self.state.active_tab;
+        let closed_label = self.state.target_label.clone();
+        let next = if closing + 1 < self.state.tabs.len() {
+            closing + 1
+        } else {
+            closing - 1
         };
-        Ok((target, label))
+        self.switch_tab(next);
+        // The closed tab's state is now parked in its slot; dropping it also
+        // stops its diff worker
+        self.state.tabs.remove(closing);
+        if next > closing {
+            self.state.active_tab -= 1;
+        }
+        self.set_status(format!("Closed tab {closed_label}"), false);
     }
 
     /// Switch to a new comparison target, preserving annotations per-target.
@@ -2046,18 +4272,25 @@ impl App {
             } else {
                 Some(&self.state.checklist)
             },
+            &self.state.review,
         );
 
         // Update target
         self.target = target;
-        self.state.target_label = label.clone();
+        self.state.target_label = label;
+        self.load_target_session();
+    }
 
-        // Load annotations and checklist state for the new target
-        let (annotations, saved_checklist) = session::load_session_data(&self.repo_path, &label);
-        self.state.annotations = annotations;
+    /// Load annotations, checklist and review state for the current target and
+    /// start diffing it.
+    fn load_target_session(&mut self) {
+        let label = self.state.target_label.clone();
+        let session = session::load_session_data(&self.repo_path, &label);
+        self.state.annotations = session.annotations;
+        self.state.annotation_history.clear();
 
         // Reset checklist to saved state or fresh config
-        if let Some(saved) = saved_checklist {
+        if let Some(saved) = session.checklist {
             self.state.checklist = saved;
         } else if let Some(checklist_config) = load_checklist_config(&self.repo_path) {
             let items = checklist_config_to_items(&checklist_config);
@@ -2075,26 +4308,179 @@ impl App {
         self.state.navigator.filtered_indices.clear();
         self.state.selection.active = false;
         self.state.review.reset();
+        self.state.review.restore_reviewed(session.reviewed);
 
         self.request_diff();
         self.set_status(format!("Target: {label}"), false);
     }
 
-    /// Mark the current file as reviewed if the cursor has reached the last row
-    /// and the diff view is focused.
+    /// Mark the hunk under (or just above) the cursor as reviewed once the cursor
+    /// is on or past its last row, when the diff view is focused.
     fn check_auto_review(&mut self) {
         if self.state.focus != FocusPanel::DiffView {
             return;
         }
-        let max = self.current_display_map().len().saturating_sub(1);
-        if self.state.diff.cursor_row >= max {
-            if let Some(delta) = self.state.diff.selected_delta() {
-                let path = delta.path.to_string_lossy().to_string();
-                self.state.review.mark_reviewed(&path);
+        let Some(delta) = self.state.diff.selected_delta() else {
+            return;
+        };
+        let path = delta.path.to_string_lossy().to_string();
+        let blocks = change_blocks(delta);
+        if blocks.is_empty() {
+            // Nothing to walk through (binary, or only mode changes)
+            self.state.review.mark_reviewed(&path);
+            return;
+        }
+        let block_of = |info: &DisplayRowInfo| {
+            let line = info.line_index?;
+            blocks
+                .iter()
+                .position(|b| b.hunk_index == info.hunk_index && b.lines.contains(&line))
+        };
+
+        let display_map = self.current_display_map();
+        if display_map.is_empty() {
+            return;
+        }
+        let cursor = self.state.diff.cursor_row.min(display_map.len() - 1);
+        let Some(current) = display_map[..=cursor].iter().rev().find_map(block_of) else {
+            return;
+        };
+        let more_below = display_map[cursor + 1..]
+            .iter()
+            .any(|info| block_of(info) == Some(current));
+        if !more_below {
+            self.state.review.mark_hunk_reviewed(&path, current);
+        }
+    }
+
+    /// Copy the source text of the diff line under the cursor, without its
+    /// `+`/`-` prefix or trailing newline.
+    fn copy_cursor_line(&mut self) {
+        let Some(delta) = self.state.diff.selected_delta() else {
+            return;
+        };
+        let display_map = self.current_display_map();
+        let Some(line) = display_map
+            .get(self.state.diff.cursor_row)
+            .and_then(|info| {
+                delta
+                    .hunks
+                    .get(info.hunk_index)?
+                    .lines
+                    .get(info.line_index?)
+            })
+        else {
+            self.set_status("No line to copy here".to_string(), true);
+            return;
+        };
+        let text = line.content.trim_end_matches(['\n', '\r']).to_string();
+        match arboard::Clipboard::new().and_then(|mut cb| cb.set_text(&text)) {
+            Ok(()) => self.set_status("Line copied to clipboard".to_string(), false),
+            Err(e) => self.set_status(format!("Clipboard error: {e}"), true),
+        }
+    }
+
+    /// Open `$EDITOR` at the definition of the first tagged symbol on the
+    /// cursor line, building the ctags index first if needed.
+    fn go_to_definition(&mut self) {
+        let Some(delta) = self.state.diff.selected_delta() else {
+            return;
+        };
+        let display_map = self.current_display_map();
+        let Some(info) = display_map.get(self.state.diff.cursor_row) else {
+            return;
+        };
+        let Some(line) = info
+            .line_index
+            .and_then(|i| delta.hunks.get(info.hunk_index)?.lines.get(i))
+        else {
+            self.set_status("No code on the cursor line".to_string(), true);
+            return;
+        };
+        let content = line.content.clone();
+        let here = (
+            delta.path.clone(),
+            info.new_lineno.or(info.old_lineno).unwrap_or(0),
+        );
+
+        if self.tags_cache.is_none() {
+            self.pending_definition = Some((content, here));
+            self.start_tags_build();
+            return;
+        }
+        self.jump_to_definition(&content, here);
+    }
+
+    /// Run the tags command in the background; the result arrives through
+    /// `poll_tags`, which finishes the pending `gd`.
+    fn start_tags_build(&mut self) {
+        self.set_status_for_ticks("Indexing tags\u{2026}".to_string(), false, 0);
+        if self.tags_building {
+            return;
+        }
+        self.tags_building = true;
+        let workdir = self.repo_path.clone();
+        let command = self.config.tags_command.clone();
+        let epoch = self.tags_epoch;
+        let tx = self.tags_tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let _ = tx.send((epoch, tags::load_tags(&workdir, command.as_deref())));
+        });
+    }
+
+    fn poll_tags(&mut self) {
+        while let Ok((epoch, result)) = self.tags_rx.try_recv() {
+            self.tags_building = false;
+            if epoch != self.tags_epoch {
+                // The diff was refreshed mid-build; index the current tree instead
+                if self.pending_definition.is_some() {
+                    self.start_tags_build();
+                }
+                continue;
+            }
+            match result {
+                Ok(tags) => {
+                    self.tags_cache = Some(tags);
+                    if let Some((content, here)) = self.pending_definition.take() {
+                        self.jump_to_definition(&content, here);
+                    }
+                }
+                Err(e) => {
+                    self.pending_definition = None;
+                    self.set_status(format!("{e:#}"), true);
+                }
+            }
+        }
+    }
+
+    /// Look up the first known symbol in `content` and queue `$EDITOR` at it.
+    fn jump_to_definition(&mut self, content: &str, here: (PathBuf, u32)) {
+        let Some(tags) = self.tags_cache.as_ref() else {
+            return;
+        };
+        match tags::find_definition(tags, content, (&here.0, here.1)) {
+            Some((name, entry)) => {
+                let status = format!("{name}: {}:{}", entry.file.display(), entry.line);
+                self.pending_editor = Some((self.repo_path.join(&entry.file), entry.line));
+                self.set_status(status, false);
             }
+            None => self.set_status("Symbol not found".to_string(), true),
         }
     }
 
+    /// Persist the settings modal's values and the preview split to config.toml.
+    fn save_settings(&self) {
+        config::save_settings(&PersistentSettings {
+            theme: self.state.theme.name.clone(),
+            unified: self.state.diff.options.view_mode == DiffViewMode::Unified,
+            ignore_whitespace: self.state.diff.options.ignore_whitespace,
+            context_lines: self.state.diff.display_context,
+            context_padding: self.state.context_padding,
+            export_format: self.state.export_format,
+            prompt_preview_ratio: self.state.prompt_preview_ratio,
+        });
+    }
+
     fn selected_file_path(&self) -> Option<PathBuf> {
         self.state
             .diff
@@ -2115,15 +4501,82 @@ impl App {
                 self.state.selection.active = false;
                 // Reset context expansions for the new file
                 self.state.diff.gap_expansions.clear();
+                self.state.diff.hunk_splits.clear();
+                self.state.diff.scroll_x = 0;
                 // Clear diff search state for the new file
                 self.state.diff.search_query.clear();
                 self.state.diff.search_matches.clear();
                 self.state.diff.search_match_index = None;
                 self.state.diff.search_active = false;
+                if self.state.file_log_open {
+                    self.load_file_log();
+                }
             }
         }
     }
 
+    /// Revert `commit` into the working tree. A conflicting revert narrows the
+    /// navigator to the conflicted files.
+    fn revert_commit(&mut self, commit: &CommitSummary) {
+        let short = format!("{:.7}", commit.oid);
+        match self.git_cli.revert_no_commit(&commit.oid.to_string()) {
+            Ok(()) => self.set_status(format!("Reverted {short} (not committed)"), false),
+            Err(e) => match self.git_cli.conflicted_files() {
+                Ok(files) if !files.is_empty() => {
+                    self.state.navigator.show_conflicts_only();
+                    self.pending_revert_conflicts = Some(short);
+                }
+                _ => {
+                    self.set_status(format!("Revert failed: {e}"), true);
+                    return;
+                }
+            },
+        }
+        self.state.file_log_open = false;
+        self.request_diff();
+    }
+
+    /// Count the conflicts a revert left from the same markers the conflicts
+    /// filter uses, so the message matches the files listed.
+    fn report_revert_conflicts(&mut self, short: &str, deltas: &[FileDelta]) {
+        let n = deltas.iter().filter(|d| d.has_conflicts).count();
+        if n == 0 {
+            self.state.navigator.status_filter = NavigatorStatusFilter::All;
+            self.state.navigator.refilter();
+            self.set_status(
+                format!("Reverting {short} left conflicts without markers; see git status"),
+                true,
+            );
+            return;
+        }
+        self.set_status(
+            format!(
+                "Reverting {short} left {n} conflict{}; showing conflicted files",
+                if n == 1 { "" } else { "s" }
+            ),
+            true,
+        );
+    }
+
+    /// Reload the file log panel for the selected file.
+    fn load_file_log(&mut self) {
+        self.state.file_log.clear();
+        self.state.file_log_selected = 0;
+        let Some(path) = self
+            .state
+            .diff
+            .selected_file
+            .and_then(|idx| self.state.diff.deltas.get(idx))
+            .map(|delta| delta.path.clone())
+        else {
+            return;
+        };
+        match self.git_cli.file_log(&path, self.config.log_limit) {
+            Ok(entries) => self.state.file_log = entries,
+            Err(e) => self.set_status(format!("git log failed: {e}"), true),
+        }
+    }
+
     /// Recompute diff search matches from the current display map.
     fn recompute_diff_search_matches(&mut self) {
         self.state.diff.search_matches.clear();
@@ -2145,6 +4598,7 @@ impl App {
                 self.state.diff.options.view_mode,
                 self.state.diff.display_context,
                 &self.state.diff.gap_expansions,
+                &self.state.diff.hunk_splits,
             );
             display_map
                 .iter()
@@ -2212,6 +4666,12 @@ impl App {
                 self.state.diff.options.view_mode,
                 self.state.diff.display_context,
                 &self.state.diff.gap_expansions,
+                // Splits are per file and dropped when switching to another one
+                if self.state.diff.selected_file == Some(file_index) {
+                    &self.state.diff.hunk_splits
+                } else {
+                    &[]
+                },
             );
 
             // Search through all lines in this file
@@ -2274,6 +4734,122 @@ impl App {
         }
     }
 
+    /// Open the file (and line, for an annotation row) under the review summary
+    /// cursor in the diff explorer.
+    fn jump_to_review_summary_row(&mut self) {
+        let rows = review_summary_rows(&self.state.annotations);
+        let Some(row) = rows.get(self.state.review_summary_selected) else {
+            return;
+        };
+        let path = row.file_path().to_string();
+        let line = match row {
+            ReviewSummaryRow::Annotation(ann) if !ann.unanchored => Some(ann.anchor.sort_line()),
+            ReviewSummaryRow::Annotation(_) => None,
+            ReviewSummaryRow::File { .. } => None,
+        };
+
+        let Some(vis_idx) = self
+            .state
+            .navigator
+            .visible_entries()
+            .iter()
+            .position(|(_, e)| e.path == path)
+        else {
+            self.set_status(format!("{path} is not in the current file list"), true);
+            return;
+        };
+        self.state.active_view = ActiveView::DiffExplorer;
+        self.state.navigator.selected = vis_idx;
+        self.sync_selection();
+        self.state.focus = FocusPanel::DiffView;
+        if let Some(line) = line {
+            self.scroll_to_line(line);
+        }
+    }
+
+    /// Copy the review checklist to the clipboard, and write it to
+    /// `review_checklist_path` when that's configured.
+    fn export_review_checklist(&mut self) {
+        if self.state.diff.deltas.is_empty() {
+            self.set_status("No files to export".to_string(), true);
+            return;
+        }
+        let checklist = export::render_review_checklist(
+            &self.state.diff.deltas,
+            &self.state.review,
+            &self.state.annotations,
+        );
+        let mut done = Vec::new();
+        let mut errors = Vec::new();
+        match arboard::Clipboard::new().and_then(|mut cb| cb.set_text(&checklist)) {
+            Ok(()) => done.push("copied".to_string()),
+            Err(e) => errors.push(format!("clipboard error: {e}")),
+        }
+        if let Some(path) = self.config.review_checklist_path.as_deref() {
+            let path = self.repo_path.join(config::expand_home(Path::new(path)));
+            match std::fs::write(&path, &checklist) {
+                Ok(()) => done.push(format!("written to {}", path.display())),
+                Err(e) => errors.push(format!("failed to write {}: {e}", path.display())),
+            }
+        }
+        let msg = if done.is_empty() {
+            format!("Checklist export failed: {}", errors.join("; "))
+        } else {
+            let mut msg = format!("Review checklist {}", done.join(" and "));
+            for e in &errors {
+                msg.push_str(&format!("; {e}"));
+            }
+            msg
+        };
+        self.set_status(msg, !errors.is_empty());
+    }
+
+    /// Jump to the next (or previous) bookmark after the cursor, in file list
+    /// order, wrapping around. Bookmarks on files that aren't listed are skipped.
+    fn jump_to_bookmark(&mut self, forward: bool) {
+        let entries = self.state.navigator.visible_entries();
+        let position = |path: &str| entries.iter().position(|(_, e)| e.path == path);
+        let mut marks: Vec<(usize, u32, &Bookmark)> = self
+            .state
+            .bookmarks
+            .iter()
+            .filter_map(|b| Some((position(&b.file_path)?, b.sort_line(), b)))
+            .collect();
+        if marks.is_empty() {
+            self.set_status("No bookmarks".to_string(), false);
+            return;
+        }
+        marks.sort_unstable_by_key(|&(pos, line, _)| (pos, line));
+        let cursor_line = self
+            .current_display_map()
+            .get(self.state.diff.cursor_row)
+            .and_then(|info| info.new_lineno.or(info.old_lineno))
+            .unwrap_or(0);
+        let current = (self.state.navigator.selected, cursor_line);
+        let target = if forward {
+            marks
+                .iter()
+                .find(|&&(pos, line, _)| (pos, line) > current)
+                .or(marks.first())
+        } else {
+            marks
+                .iter()
+                .rev()
+                .find(|&&(pos, line, _)| (pos, line) < current)
+                .or(marks.last())
+        };
+        let Some(&(vis_idx, _, bookmark)) = target else {
+            return;
+        };
+        let (old, new) = (bookmark.old_lineno, bookmark.new_lineno);
+
+        self.state.navigator.selected = vis_idx;
+        self.sync_selection();
+        self.state.focus = FocusPanel::DiffView;
+        self.restore_cursor_to_line(old, new);
+        self.scroll_row_to_top(self.state.diff.cursor_row);
+    }
+
     /// Scroll to the display row containing the given line number.
     fn scroll_to_line(&mut self, target_lineno: u32) {
         let display_map = self.current_display_map();
@@ -2281,23 +4857,141 @@ impl App {
             let matches =
                 info.new_lineno == Some(target_lineno) || info.old_lineno == Some(target_lineno);
             if matches {
-                self.state.diff.cursor_row = row_idx;
-                self.state.diff.scroll_offset = self.visual_offset_for_row(row_idx);
+                self.scroll_row_to_top(row_idx);
                 return;
             }
         }
     }
 
+    /// Spawn `agent` with `model` on `prompt` as a new run at the top of the
+    /// agent outputs list, then clear the annotations the prompt captured.
+    fn launch_agent(&mut self, agent: &AgentProviderConfig, model: String, prompt: String) {
+        let (command, env) = match build_agent_command(agent, &model, &prompt) {
+            Ok(command) => command,
+            Err(e) => {
+                self.set_status(format!("{}: {e}", agent.name), true);
+                return;
+            }
+        };
+        let run_id = self.state.agent_outputs.next_id;
+        let (pty_rows, pty_cols) = pty_size();
+        let worktree_name = self.worktree_name();
+        let worktree_path = self.repo_path.clone();
+
+        let run = AgentRun {
+            id: run_id,
+            agent_name: agent.name.clone(),
+            model: model.clone(),
+            command: command.clone(),
+            rendered_prompt: prompt,
+            terminal: vt100::Parser::new(pty_rows, pty_cols, 10000),
+            status: AgentRunStatus::Running,
+            started_at: chrono::Utc::now().format("%H:%M").to_string(),
+            started_at_instant: Instant::now(),
+            finished_at: None,
+            worktree_name,
+            worktree_path,
+            token_count: None,
+            cost_per_1k_tokens: agent.cost_per_1k_tokens,
+            context_window: agent.context_window,
+        };
+
+        self.state.agent_outputs.add_run(run);
+        self.pty_runner = Some(PtyRunner::spawn(
+            run_id,
+            &command,
+            &env,
+            pty_rows,
+            pty_cols,
+            &self.repo_path,
+        ));
+        self.state.agent_selector.open = false;
+        self.state.active_view = ActiveView::AgentOutputs;
+        self.state.pty_focus = true;
+
+        // Clear annotations — they've been captured in the prompt
+        self.state.annotations = Default::default();
+        self.state.annotation_history.clear();
+        session::save_session_data(
+            &self.repo_path,
+            &self.state.target_label,
+            &self.state.annotations,
+            if self.state.checklist.is_empty() {
+                None
+            } else {
+                Some(&self.state.checklist)
+            },
+            &self.state.review,
+        );
+
+        // Persist last-used model for this agent
+        self.config
+            .agent_models
+            .insert(agent.name.clone(), model.clone());
+        config::save_agent_model(&agent.name, &model);
+
+        self.set_status(format!("Running {}/{}", agent.name, model), false);
+    }
+
+    /// Run `git rebase -i HEAD~n` as a PTY session in the agent outputs tab, so
+    /// the todo list opens in the user's editor there. The diff is refreshed
+    /// when it exits, as after an agent run.
+    fn launch_rebase_interactive(&mut self, n: usize) {
+        let command = format!("git rebase -i HEAD~{n}");
+        let run_id = self.state.agent_outputs.next_id;
+        let (pty_rows, pty_cols) = pty_size();
+
+        let run = AgentRun {
+            id: run_id,
+            agent_name: "git".to_string(),
+            model: format!("rebase -i HEAD~{n}"),
+            command: command.clone(),
+            rendered_prompt: String::new(),
+            terminal: vt100::Parser::new(pty_rows, pty_cols, 10000),
+            status: AgentRunStatus::Running,
+            started_at: chrono::Utc::now().format("%H:%M").to_string(),
+            started_at_instant: Instant::now(),
+            finished_at: None,
+            worktree_name: self.worktree_name(),
+            worktree_path: self.repo_path.clone(),
+            token_count: None,
+            cost_per_1k_tokens: None,
+            context_window: None,
+        };
+
+        self.state.agent_outputs.add_run(run);
+        self.pty_runner = Some(PtyRunner::spawn(
+            run_id,
+            &command,
+            &[],
+            pty_rows,
+            pty_cols,
+            &self.repo_path,
+        ));
+        self.state.active_view = ActiveView::AgentOutputs;
+        self.state.pty_focus = true;
+        self.set_status(format!("Running {command}"), false);
+    }
+
+    /// Directory name of the current worktree, for labelling runs.
+    fn worktree_name(&self) -> String {
+        self.repo_path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "unknown".to_string())
+    }
+
     /// Render a prompt covering all annotated files.
     ///
     /// Each comment is interleaved with its surrounding diff context so the
-    /// relationship between code and comment is unambiguous.
-    fn render_prompt_for_all_files(&self) -> Option<String> {
+    /// relationship between code and comment is unambiguous. When `agent` is
+    /// given, its `prompt_template` takes precedence over the global one.
+    fn render_prompt_for_all_files(&self, agent: Option<&AgentProviderConfig>) -> Option<String> {
         if self.state.diff.deltas.is_empty() {
             return None;
         }
 
-        let padding: u32 = 5;
+        let padding = self.state.context_padding;
         let mut file_sections = Vec::new();
 
         for delta in &self.state.diff.deltas {
@@ -2311,7 +5005,8 @@ impl App {
 
             // Sort annotations by sort_line and group those whose padded
             // ranges overlap so nearby comments share one code block.
-            let mut sorted_anns: Vec<&Annotation> = anns.iter().collect();
+            // Unanchored GitHub comments have no lines to quote.
+            let mut sorted_anns: Vec<&Annotation> = anns.iter().filter(|a| !a.unanchored).collect();
             sorted_anns.sort_by_key(|a| a.anchor.sort_line());
 
             let mut groups: Vec<(u32, u32, Vec<&Annotation>)> = Vec::new();
@@ -2373,18 +5068,7 @@ impl App {
 
                 let mut section = format!("```diff\n{}\n```", diff_lines.join("\n"));
                 for ann in group_anns {
-                    // Side-aware comment labels
-                    let line_ref = match (ann.anchor.old_range, ann.anchor.new_range) {
-                        (_, Some((s, e))) if s == e => format!("Line {s}"),
-                        (_, Some((s, e))) => format!("Lines {s}-{e}"),
-                        (Some((s, e)), None) if s == e => {
-                            format!("Removed line {s} (old)")
-                        }
-                        (Some((s, e)), None) => {
-                            format!("Removed lines {s}-{e} (old)")
-                        }
-                        (None, None) => "Line ?".to_string(),
-                    };
+                    let line_ref = prompt_line_ref(&ann.anchor, delta);
                     section.push_str(&format!(
                         "\n\n> **Comment ({}):** {}",
                         line_ref, ann.comment
@@ -2407,12 +5091,9 @@ impl App {
             return None;
         }
 
-        let mut prompt = String::from(
-            "You are reviewing a code change. A reviewer has left comments on the diff below. \
-             Address each review comment by making the necessary code changes. If a comment asks \
-             a question, answer it and make any implied fixes. Keep changes minimal and focused \
-             on what the reviewer asked for.\n\n",
-        );
+        let template = prompt_template(agent, self.config.prompt_template.as_deref());
+        let mut prompt = template.trim_end().to_string();
+        prompt.push_str("\n\n");
 
         // Add checklist status if checklist is configured
         if !self.state.checklist.is_empty() {
@@ -2433,9 +5114,52 @@ impl App {
         Some(prompt)
     }
 
-    /// Update the prompt preview text from the current diff + annotations.
+    /// Update the prompt preview text from the current diff + annotations, as
+    /// the selected agent would receive it.
     fn update_prompt_preview(&mut self) {
-        self.state.prompt_preview_text = self.render_prompt_for_all_files().unwrap_or_default();
+        let agent = self.state.agent_selector.selected_agent_config();
+        self.state.prompt_preview_text =
+            self.render_prompt_for_all_files(agent).unwrap_or_default();
+        self.recompute_preview_search_matches();
+        self.scroll_prompt_preview(0);
+    }
+
+    /// Move the prompt preview by `delta` lines, keeping the last line on screen.
+    fn scroll_prompt_preview(&mut self, delta: isize) {
+        let last = self
+            .state
+            .prompt_preview_text
+            .lines()
+            .count()
+            .saturating_sub(1);
+        let offset = self
+            .state
+            .preview_scroll_offset
+            .saturating_add_signed(delta);
+        self.state.preview_scroll_offset = offset.min(last);
+    }
+
+    fn recompute_preview_search_matches(&mut self) {
+        self.state.preview_search_match_index = None;
+        let query = self.state.preview_search_query.text().to_lowercase();
+        self.state.preview_search_matches = if query.is_empty() {
+            Vec::new()
+        } else {
+            self.state
+                .prompt_preview_text
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| line.to_lowercase().contains(&query))
+                .map(|(idx, _)| idx)
+                .collect()
+        };
+    }
+
+    /// Select the `idx`th preview match and scroll it near the top of the pane.
+    fn jump_to_preview_match(&mut self, idx: usize) {
+        self.state.preview_search_match_index = Some(idx);
+        let line = self.state.preview_search_matches[idx];
+        self.state.preview_scroll_offset = line.saturating_sub(PROMPT_PREVIEW_MATCH_MARGIN);
     }
 
     /// Build a JSON summary of all feedback (annotations and scores).
@@ -2565,24 +5289,356 @@ fn reconstruct_content(delta: &FileDelta, side: ContentSide) -> (String, usize)
     (content, max_line)
 }
 
-/// Build the shell command for an agent by substituting `{model}` and `{rendered_prompt}`.
-fn build_agent_command(command_template: &str, model: &str, prompt: &str) -> String {
-    let escaped_prompt = prompt.replace('\'', "'\\''");
-    command_template
-        .replace("{model}", model)
-        .replace("{rendered_prompt}", &escaped_prompt)
+/// The revisions a comparison's old and new sides are read from; a `None`
+/// new side is the working tree.
+fn comparison_revs(git: &GitCli, target: &ComparisonTarget) -> (Option<String>, Option<String>) {
+    match target {
+        ComparisonTarget::HeadVsWorkdir => (Some("HEAD".to_string()), None),
+        ComparisonTarget::Branch(name) => (git.merge_base(name).ok(), None),
+        ComparisonTarget::Commit(oid) => (git.merge_base(&oid.to_string()).ok(), None),
+        ComparisonTarget::Range { from, to } => (Some(from.to_string()), Some(to.to_string())),
+    }
+}
+
+/// One side of a file's contents as far as the diff shows it, as a list of
+/// lines where index 0 is line 1. Lines outside the hunks are blank.
+fn file_lines(delta: &FileDelta, side: ContentSide) -> Vec<String> {
+    let (content, line_count) = reconstruct_content(delta, side);
+    if line_count == 0 {
+        return Vec::new();
+    }
+    // reconstruct_content reserves a blank entry for line 0
+    content.split('\n').skip(1).map(str::to_string).collect()
+}
+
+/// Side-aware label for the lines an annotation covers. A unified-mode
+/// selection can span both removed and added lines; the removed ones are
+/// named separately by their old line numbers so the two aren't confused.
+fn prompt_line_ref(anchor: &LineAnchor, delta: &FileDelta) -> String {
+    let removed = anchor.old_range.and_then(|(start, end)| {
+        let mut linenos = delta
+            .hunks
+            .iter()
+            .flat_map(|h| &h.lines)
+            .filter(|l| l.origin == DiffLineOrigin::Deletion)
+            .filter_map(|l| l.old_lineno)
+            .filter(|n| (start..=end).contains(n));
+        let first = linenos.next()?;
+        Some((first, linenos.next_back().unwrap_or(first)))
+    });
+    let range_ref = |noun: &str, (s, e): (u32, u32)| {
+        if s == e {
+            format!("{noun} {s}")
+        } else {
+            format!("{noun}s {s}-{e}")
+        }
+    };
+    match (anchor.new_range, removed) {
+        (Some(new), Some(old)) => format!(
+            "{}, {} (old)",
+            range_ref("Line", new),
+            range_ref("removed line", old)
+        ),
+        (Some(new), None) => range_ref("Line", new),
+        (None, _) => match anchor.old_range {
+            Some(old) => format!("{} (old)", range_ref("Removed line", old)),
+            None => "Line ?".to_string(),
+        },
+    }
+}
+
+/// Key of a safe-mode action for the confirm prompt: the user's binding from
+/// `[keybindings]`, else the default.
+fn safe_mode_key(action: &Action, keybindings: &HashMap<Action, KeySpec>) -> String {
+    if let Some(spec) = keybindings.get(action) {
+        return spec.to_string();
+    }
+    match action {
+        Action::StageFile => "s",
+        Action::UnstageFile => "u",
+        _ => "r",
+    }
+    .to_string()
+}
+
+/// Instruction preamble for a prompt sent to `agent`: its own template, else
+/// the global `prompt_template`, else the built-in one.
+fn prompt_template<'a>(agent: Option<&'a AgentProviderConfig>, global: Option<&'a str>) -> &'a str {
+    agent
+        .and_then(|a| a.prompt_template.as_deref())
+        .or(global)
+        .unwrap_or(DEFAULT_PROMPT_TEMPLATE)
+}
+
+/// A safe-mode action's first press and the ticks left in its current phase:
+/// the "Would stage: …" preview, then the confirm prompt.
+#[derive(Debug, Clone, PartialEq)]
+struct PendingAction {
+    action: Action,
+    ticks: u32,
+    prompting: bool,
+}
+
+/// What safe mode does with a key press.
+#[derive(Debug, PartialEq, Eq)]
+enum SafeModeStep {
+    Run,
+    /// Run it, but it cancelled the pending action first.
+    Cancel,
+    /// A guarded first press, now pending: show what it would do.
+    Preview,
+}
+
+/// The first stage/unstage/restore only becomes `pending`, the same action
+/// again goes ahead, and anything else cancels it.
+fn safe_mode_step(pending: &mut Option<PendingAction>, action: &Action) -> SafeModeStep {
+    if matches!(action, Action::Tick | Action::Resize) {
+        return SafeModeStep::Run;
+    }
+    let cancelled = match pending.take() {
+        Some(p) if p.action == *action => return SafeModeStep::Run,
+        Some(_) => true,
+        None => false,
+    };
+    if matches!(
+        action,
+        Action::StageFile | Action::UnstageFile | Action::RestoreFile
+    ) {
+        *pending = Some(PendingAction {
+            action: action.clone(),
+            ticks: SAFE_MODE_PREVIEW_TICKS,
+            prompting: false,
+        });
+        SafeModeStep::Preview
+    } else if cancelled {
+        SafeModeStep::Cancel
+    } else {
+        SafeModeStep::Run
+    }
+}
+
+/// Count `pending` down one tick. Returns true when the preview ends and the
+/// confirm prompt should show; when the prompt runs out too the action is
+/// dropped, so a late second press starts over.
+fn tick_pending_action(pending: &mut Option<PendingAction>) -> bool {
+    let Some(p) = pending.as_mut() else {
+        return false;
+    };
+    p.ticks = p.ticks.saturating_sub(1);
+    if p.ticks > 0 {
+        return false;
+    }
+    if p.prompting {
+        *pending = None;
+        return false;
+    }
+    p.ticks = SAFE_MODE_PROMPT_TICKS;
+    p.prompting = true;
+    true
+}
+
+/// Chord prefixes that act as soon as they are pressed, so a bare `]` jumps
+/// without waiting; their second key only ends the chord.
+fn chord_is_eager(prefix: char) -> bool {
+    matches!(prefix, ']' | '[')
+}
+
+/// Actions to run when chord prefix `c` is pressed while `pending` waits for
+/// its second key, and the prefix left waiting afterwards.
+fn start_chord(pending: Option<char>, c: char) -> (Vec<Action>, Option<char>) {
+    let mut actions = Vec::new();
+    match pending {
+        // `zz` / `gg`: the prefix's own action once, not twice
+        Some(prefix) if prefix == c && !chord_is_eager(c) => {
+            return (vec![chord_fallback(prefix)], None)
+        }
+        Some(prefix) if !chord_is_eager(prefix) => actions.push(chord_fallback(prefix)),
+        _ => {}
+    }
+    if chord_is_eager(c) {
+        actions.push(chord_fallback(c));
+    }
+    (actions, Some(c))
+}
+
+/// What the first key of a chord does when no second key follows.
+fn chord_fallback(prefix: char) -> Action {
+    match prefix {
+        'z' => Action::ToggleLineWrap,
+        'g' => Action::ScrollToTop,
+        '\'' => Action::NextBookmark,
+        ']' => Action::JumpNextHunk,
+        '[' => Action::JumpPrevHunk,
+        'y' => Action::CopyPromptToClipboard,
+        _ => Action::Noop,
+    }
+}
+
+/// Rows and columns for a new PTY, matching the agent outputs detail pane:
+/// 70% of the width beside the run list, less the block borders, and the full
+/// height less the context bar, HUD and borders.
+fn pty_size() -> (u16, u16) {
+    let (term_cols, term_rows) = crossterm::terminal::size().unwrap_or((120, 40));
+    let cols = (term_cols * 70 / 100).saturating_sub(2).max(40);
+    let rows = term_rows.saturating_sub(4).max(10);
+    (rows, cols)
+}
+
+/// A shell command line and the environment variables to run it with.
+type AgentCommand = (String, Vec<(&'static str, String)>);
+
+/// Build the shell command for an agent, plus environment for it. A `command_file`
+/// is executed directly, so its shebang picks the interpreter, with the model and
+/// prompt in `MDIFF_MODEL` / `MDIFF_PROMPT`; otherwise `{model}` and
+/// `{rendered_prompt}` are substituted into `command`.
+fn build_agent_command(
+    agent: &AgentProviderConfig,
+    model: &str,
+    prompt: &str,
+) -> Result<AgentCommand, String> {
+    let quote = |s: &str| s.replace('\'', "'\\''");
+    match &agent.command_file {
+        Some(file) => {
+            let path = config::expand_home(file);
+            if !config::is_executable(&path) {
+                return Err(format!(
+                    "command_file {} is not executable (chmod +x it)",
+                    path.display()
+                ));
+            }
+            Ok((
+                format!("'{}'", quote(&path.to_string_lossy())),
+                vec![
+                    ("MDIFF_MODEL", model.to_string()),
+                    ("MDIFF_PROMPT", prompt.to_string()),
+                ],
+            ))
+        }
+        None => Ok((
+            agent
+                .command
+                .replace("{model}", model)
+                .replace("{rendered_prompt}", &quote(prompt)),
+            Vec::new(),
+        )),
+    }
 }
 
-pub fn parse_target(target: Option<&str>) -> ComparisonTarget {
+/// Parse a CLI/dialog target. `A..B` ranges are resolved to commits in the repo
+/// at `repo_path` (an empty side means `HEAD`, as in git); anything else is
+/// resolved lazily by the diff engine.
+pub fn parse_target(repo_path: &Path, target: Option<&str>) -> Result<ComparisonTarget, String> {
     match target {
-        None => ComparisonTarget::HeadVsWorkdir,
+        None => Ok(ComparisonTarget::HeadVsWorkdir),
         Some(s) => {
+            if let Some((from, to)) = s.split_once("..") {
+                if to.starts_with('.') {
+                    return Err("three-dot ranges are not supported; use A..B".to_string());
+                }
+                let repo =
+                    git2::Repository::open(repo_path).map_err(|e| format!("open repo: {e}"))?;
+                let resolve =
+                    |rev: &str| resolve_commit(&repo, if rev.is_empty() { "HEAD" } else { rev });
+                return Ok(ComparisonTarget::Range {
+                    from: resolve(from)?,
+                    to: resolve(to)?,
+                });
+            }
             if s.len() >= 7 && s.chars().all(|c| c.is_ascii_hexdigit()) {
                 if let Ok(oid) = git2::Oid::from_str(s) {
-                    return ComparisonTarget::Commit(oid);
+                    return Ok(ComparisonTarget::Commit(oid));
                 }
             }
-            ComparisonTarget::Branch(s.to_string())
+            Ok(ComparisonTarget::Branch(s.to_string()))
         }
     }
 }
+
+/// Parse the two-ref form, `mdiff <from> <to>`, into the same range as
+/// `<from>..<to>`. Returns the target and a label that keeps the ref names.
+pub fn parse_ref_pair(
+    repo_path: &Path,
+    from: &str,
+    to: &str,
+) -> Result<(ComparisonTarget, String), String> {
+    let repo = git2::Repository::open(repo_path).map_err(|e| format!("open repo: {e}"))?;
+    let target = ComparisonTarget::Range {
+        from: resolve_commit(&repo, from)?,
+        to: resolve_commit(&repo, to)?,
+    };
+    Ok((target, format!("{from}..{to}")))
+}
+
+fn resolve_commit(repo: &git2::Repository, rev: &str) -> Result<git2::Oid, String> {
+    repo.revparse_single(rev)
+        .and_then(|obj| obj.peel_to_commit())
+        .map(|commit| commit.id())
+        .map_err(|e| format!("{rev}: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        chord_fallback, prompt_template, safe_mode_step, start_chord, tick_pending_action,
+        SafeModeStep, SAFE_MODE_PREVIEW_TICKS, SAFE_MODE_PROMPT_TICKS,
+    };
+    use crate::action::Action;
+    use crate::config::AgentProviderConfig;
+
+    #[test]
+    fn repeated_bracket_jumps_once_per_press() {
+        let (first, pending) = start_chord(None, ']');
+        assert_eq!(first, vec![Action::JumpNextHunk]);
+        let (second, _) = start_chord(pending, ']');
+        assert_eq!(second, vec![Action::JumpNextHunk]);
+
+        // `zz` still toggles wrap once
+        let (first, pending) = start_chord(None, 'z');
+        assert!(first.is_empty());
+        assert_eq!(
+            start_chord(pending, 'z'),
+            (vec![Action::ToggleLineWrap], None)
+        );
+    }
+
+    #[test]
+    fn unknown_chord_prefix_does_nothing() {
+        assert_eq!(chord_fallback('y'), Action::CopyPromptToClipboard);
+        assert_eq!(chord_fallback('q'), Action::Noop);
+    }
+
+    #[test]
+    fn safe_mode_confirm_after_timeout_starts_over() {
+        let mut pending = None;
+        assert_eq!(
+            safe_mode_step(&mut pending, &Action::StageFile),
+            SafeModeStep::Preview
+        );
+        let prompts = (0..SAFE_MODE_PREVIEW_TICKS + SAFE_MODE_PROMPT_TICKS)
+            .filter(|_| tick_pending_action(&mut pending))
+            .count();
+        assert_eq!(prompts, 1);
+        assert!(pending.is_none());
+        assert_eq!(
+            safe_mode_step(&mut pending, &Action::StageFile),
+            SafeModeStep::Preview
+        );
+
+        // In time, the second press goes ahead
+        tick_pending_action(&mut pending);
+        assert_eq!(
+            safe_mode_step(&mut pending, &Action::StageFile),
+            SafeModeStep::Run
+        );
+    }
+
+    #[test]
+    fn agent_prompt_template_overrides_global() {
+        let agent: AgentProviderConfig =
+            toml::from_str("name = \"a\"\nprompt_template = \"agent\"").unwrap();
+        let plain: AgentProviderConfig = toml::from_str("name = \"b\"").unwrap();
+        assert_eq!(prompt_template(Some(&agent), Some("global")), "agent");
+        assert_eq!(prompt_template(Some(&plain), Some("global")), "global");
+        assert_eq!(prompt_template(None, Some("global")), "global");
+    }
+}
@@ -1,4 +1,5 @@
-use clap::Parser;
+use chrono::{Duration, Local, NaiveDate, NaiveTime, TimeZone};
+use clap::{Parser, Subcommand};
 
 #[derive(Parser, Debug)]
 #[command(
@@ -7,9 +8,12 @@ use clap::Parser;
     about = "TUI git diff viewer with worktree management"
 )]
 pub struct Cli {
-    /// Target to diff against (branch, commit, or ref)
+    /// Target to diff against (branch, commit, ref, or an A..B commit range)
     pub target: Option<String>,
 
+    /// Second ref: diff TARGET's tree directly against this one (no merge-base, no working tree)
+    pub target2: Option<String>,
+
     /// Open worktree browser directly
     #[arg(long = "wt")]
     pub worktree_browser: bool,
@@ -22,7 +26,169 @@ pub struct Cli {
     #[arg(long)]
     pub unified: bool,
 
-    /// Color theme (one-dark, github-dark, dracula, catppuccin-mocha, tokyo-night, solarized-dark)
+    /// Context lines around each change, overriding `context_lines` (1-50)
+    #[arg(short = 'C', long, value_name = "N")]
+    pub context: Option<usize>,
+
+    /// Lines quoted around each annotation in agent prompts, overriding
+    /// `context_padding` (0-20)
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(0..=20))]
+    pub padding: Option<u32>,
+
+    /// Compute diffs with `git diff` instead of libgit2, passing ARGS through
+    /// (e.g. `--diff-tool=--histogram`)
+    #[arg(
+        long = "diff-tool",
+        value_name = "ARGS",
+        num_args = 0..=1,
+        default_missing_value = ""
+    )]
+    pub diff_tool: Option<String>,
+
+    /// Only show files touched by commits made on or after DATE
+    /// (`2024-05-01`, an RFC 3339 timestamp, or relative like `7d` / `2 weeks ago`)
+    #[arg(long, value_name = "DATE", value_parser = parse_since)]
+    pub since: Option<i64>,
+
+    /// Only show files touched by commits made on or before DATE (a bare date
+    /// includes the whole day)
+    #[arg(long, value_name = "DATE", value_parser = parse_until)]
+    pub until: Option<i64>,
+
+    /// Color theme (one-dark, github-dark, dracula, catppuccin-mocha, tokyo-night, solarized-dark, github-light)
     #[arg(long)]
     pub theme: Option<String>,
+
+    /// Print the built-in themes and their main colors, then exit. With --theme,
+    /// print just that theme, including `[colors]` overrides from config.toml
+    #[arg(long)]
+    pub list_themes: bool,
+
+    /// Mark added and removed lines with ▶ / ◀ as well as color, overriding
+    /// `accessible` in the config
+    #[arg(long)]
+    pub accessible: bool,
+
+    /// Disable all colors (also enabled by a non-empty NO_COLOR environment variable)
+    #[arg(long, conflicts_with = "color")]
+    pub no_color: bool,
+
+    /// Force true-color output, ignoring NO_COLOR and COLORTERM detection
+    #[arg(long)]
+    pub color: bool,
+
+    /// Run an agent on the diff and annotations without the TUI, streaming its
+    /// output to stderr and exiting with its exit code (needs --agent)
+    #[arg(long, requires = "agent")]
+    pub headless: bool,
+
+    /// Agent to run in --headless mode, by its configured name
+    #[arg(long, value_name = "NAME", requires = "headless")]
+    pub agent: Option<String>,
+
+    /// Model for the --headless agent (default: the last one picked, or the agent's default)
+    #[arg(long, value_name = "MODEL", requires = "headless")]
+    pub model: Option<String>,
+
+    /// Print a `git diff --stat`-style summary to stdout instead of opening the TUI
+    #[arg(long, conflicts_with = "headless")]
+    pub stat: bool,
+
+    /// Exit with 1, listing the files on stderr, if any changed file isn't fully
+    /// reviewed (for a pre-push hook); exit 0 otherwise
+    #[arg(long, conflicts_with_all = ["headless", "stat"])]
+    pub check: bool,
+
+    /// Print the diff as JSON (target, then each file's status, line counts and
+    /// hunks) to stdout instead of opening the TUI
+    #[arg(long, conflicts_with_all = ["headless", "stat", "check"])]
+    pub json: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Work with config.toml (~/.config/mdiff, or $XDG_CONFIG_HOME/mdiff)
+    Config {
+        #[command(subcommand)]
+        action: ConfigCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    /// Check config.toml for errors and exit with 1 if it has any
+    Validate,
+}
+
+fn parse_since(input: &str) -> Result<i64, String> {
+    parse_date(input, false, Local::now().timestamp())
+}
+
+fn parse_until(input: &str) -> Result<i64, String> {
+    parse_date(input, true, Local::now().timestamp())
+}
+
+/// Parse a date bound into Unix seconds. Bare dates are local midnight, or the
+/// last second of that day when `end_of_day` is set.
+fn parse_date(input: &str, end_of_day: bool, now: i64) -> Result<i64, String> {
+    let input = input.trim();
+    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(input) {
+        return Ok(dt.timestamp());
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+        let time = if end_of_day {
+            NaiveTime::from_hms_opt(23, 59, 59)
+        } else {
+            NaiveTime::from_hms_opt(0, 0, 0)
+        };
+        return Local
+            .from_local_datetime(&date.and_time(time.unwrap_or_default()))
+            .earliest()
+            .map(|dt| dt.timestamp())
+            .ok_or_else(|| format!("`{input}` does not exist in the local time zone"));
+    }
+
+    // Relative: `7d`, `12h`, `2w`, `3 days ago`
+    let relative = input.strip_suffix("ago").unwrap_or(input).trim();
+    let split = relative
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(relative.len());
+    let (count, unit) = relative.split_at(split);
+    let count: i64 = count
+        .parse()
+        .map_err(|_| format!("unrecognised date `{input}`"))?;
+    let unit = match unit.trim().trim_end_matches('s') {
+        "h" | "hour" => Duration::hours(count),
+        "d" | "day" => Duration::days(count),
+        "w" | "week" => Duration::weeks(count),
+        _ => return Err(format!("unrecognised date `{input}`")),
+    };
+    Ok(now - unit.num_seconds())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_date;
+
+    #[test]
+    fn parses_absolute_and_relative_dates() {
+        let now = 1_700_000_000;
+        assert_eq!(parse_date("7d", false, now), Ok(now - 7 * 86_400));
+        assert_eq!(parse_date("2 weeks ago", false, now), Ok(now - 14 * 86_400));
+        assert_eq!(parse_date("12h", false, now), Ok(now - 12 * 3_600));
+        assert_eq!(
+            parse_date("2024-05-01T12:00:00Z", false, now),
+            Ok(1_714_564_800)
+        );
+
+        let start = parse_date("2024-05-01", false, now).unwrap();
+        let end = parse_date("2024-05-01", true, now).unwrap();
+        assert_eq!(end - start, 86_399);
+
+        assert!(parse_date("yesterday-ish", false, now).is_err());
+        assert!(parse_date("3 fortnights", false, now).is_err());
+    }
 }
@@ -23,7 +23,7 @@ fn bindings_for_state(state: &AppState) -> &[(&str, &str)] {
             ("j/k", "select"),
             ("Enter", "chat"),
             ("y", "copy"),
-            ("^A", "re-run"),
+            ("r", "re-run"),
             ("^K", "kill"),
             ("Esc", "back"),
         ]
@@ -54,6 +54,7 @@ fn bindings_for_state(state: &AppState) -> &[(&str, &str)] {
             ("y", "yank"),
             ("Space", "expand"),
             ("p", "preview"),
+            ("M-f", "focus"),
             ("g/G", "top/bot"),
             ("m", "reviewed"),
             ("n", "next unrev"),
@@ -156,9 +157,11 @@ fn build_lines(
     lines
 }
 
+pub(crate) const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
 /// Calculate the number of rows the HUD needs for the given state and width.
 pub fn hud_height(state: &AppState, width: u16) -> u16 {
-    if state.status_message.is_some() {
+    if state.status_message.is_some() || state.remote_op.is_some() {
         return 1;
     }
     let bindings = bindings_for_state(state);
@@ -192,6 +195,17 @@ impl Component for ActionHud {
             return;
         }
 
+        if let Some(ref op) = state.remote_op {
+            let frame_char = SPINNER[state.spinner_tick % SPINNER.len()];
+            let bar = Paragraph::new(Line::from(vec![
+                Span::styled(format!(" {frame_char} "), Style::default().fg(theme.accent)),
+                Span::styled(op.as_str(), Style::default().fg(theme.text)),
+            ]))
+            .style(Style::default().bg(theme.surface));
+            frame.render_widget(bar, area);
+            return;
+        }
+
         let bindings = bindings_for_state(state);
         let ann_text = annotation_text(state);
         let lines = build_lines(bindings, area.width, ann_text.as_deref(), theme);
@@ -6,7 +6,7 @@ use ratatui::{
     Frame,
 };
 
-use crate::state::agent_state::{AgentOutputsState, AgentRunStatus};
+use crate::state::agent_state::{format_elapsed, AgentOutputsState, AgentRun, AgentRunStatus};
 use crate::state::AppState;
 use crate::theme::Theme;
 
@@ -54,9 +54,19 @@ fn render_run_list(frame: &mut Frame, area: Rect, outputs: &AgentOutputsState, t
     frame.render_widget(block, area);
 
     let height = inner.height as usize;
+    let (start, visible) = run_list_window(outputs, height);
+    let show_scrollbar = visible < outputs.runs.len();
+    let list_area = if show_scrollbar {
+        Rect {
+            width: inner.width.saturating_sub(1),
+            ..inner
+        }
+    } else {
+        inner
+    };
     let mut lines: Vec<Line> = Vec::new();
 
-    for (idx, run) in outputs.runs.iter().enumerate() {
+    for (idx, run) in outputs.runs.iter().enumerate().skip(start) {
         if lines.len() >= height {
             break;
         }
@@ -93,7 +103,12 @@ fn render_run_list(frame: &mut Frame, area: Rect, outputs: &AgentOutputsState, t
             ),
         ]));
 
-        // Second line: time + status detail + worktree
+        // Second line: time + status detail + worktree + token/cost estimate
+        let token_color = if run.exceeds_context_window() {
+            theme.error
+        } else {
+            theme.text_muted
+        };
         if lines.len() < height {
             lines.push(Line::from(vec![
                 Span::styled("    ", row_style),
@@ -102,15 +117,80 @@ fn render_run_list(frame: &mut Frame, area: Rect, outputs: &AgentOutputsState, t
                     row_style.fg(theme.text_muted),
                 ),
                 Span::styled(status_detail, row_style.fg(status_color)),
+                Span::styled(
+                    format!(" {}", format_elapsed(run.elapsed())),
+                    row_style.fg(theme.text_muted),
+                ),
                 Span::styled(
                     format!(" [{}]", run.worktree_name),
                     row_style.fg(theme.accent),
                 ),
+                Span::styled(token_summary(run), row_style.fg(token_color)),
             ]));
         }
     }
 
-    frame.render_widget(Paragraph::new(lines), inner);
+    frame.render_widget(Paragraph::new(lines), list_area);
+
+    if show_scrollbar {
+        let bar_area = Rect {
+            x: inner.x + inner.width.saturating_sub(1),
+            width: 1,
+            ..inner
+        };
+        let bar: Vec<Line> = scrollbar_cells(height, start, visible, outputs.runs.len())
+            .into_iter()
+            .map(|thumb| {
+                if thumb {
+                    Line::from(Span::styled("\u{2588}", Style::default().fg(theme.accent)))
+                } else {
+                    Line::from(Span::styled(
+                        "\u{2591}",
+                        Style::default().fg(theme.text_muted),
+                    ))
+                }
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(bar), bar_area);
+    }
+}
+
+/// `~1234 tokens | ~$0.02`; the `~` on the count is dropped once the agent has
+/// reported its own usage.
+fn token_summary(run: &AgentRun) -> String {
+    let approx = if run.token_count.is_some() { "" } else { "~" };
+    let mut out = format!(" {approx}{} tokens", run.tokens());
+    if let Some(cost) = run.estimated_cost() {
+        out.push_str(&format!(" | ~${cost:.2}"));
+    }
+    out
+}
+
+/// First run to draw and how many runs fit (two rows each), starting from
+/// `run_list_scroll` but shifted so the selected run is always on screen.
+fn run_list_window(outputs: &AgentOutputsState, height: usize) -> (usize, usize) {
+    let visible = (height / 2).max(1);
+    let max_start = outputs.runs.len().saturating_sub(visible);
+    let mut start = outputs.run_list_scroll.min(max_start);
+    if outputs.selected_run < start {
+        start = outputs.selected_run;
+    } else if outputs.selected_run >= start + visible {
+        start = outputs.selected_run + 1 - visible;
+    }
+    (start, visible)
+}
+
+/// For each of `height` rows, whether it is part of the scrollbar thumb.
+fn scrollbar_cells(height: usize, start: usize, visible: usize, total: usize) -> Vec<bool> {
+    if total == 0 || height == 0 {
+        return Vec::new();
+    }
+    let thumb_len = (height * visible / total).clamp(1, height);
+    let max_start = total.saturating_sub(visible).max(1);
+    let thumb_start = (height - thumb_len) * start.min(max_start) / max_start;
+    (0..height)
+        .map(|row| row >= thumb_start && row < thumb_start + thumb_len)
+        .collect()
 }
 
 fn render_run_detail(frame: &mut Frame, area: Rect, state: &AppState) {
@@ -313,3 +393,18 @@ fn vt100_color_to_ratatui(color: vt100::Color, default: Color) -> Color {
         vt100::Color::Rgb(r, g, b) => Color::Rgb(r, g, b),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::scrollbar_cells;
+
+    #[test]
+    fn scrollbar_thumb_tracks_position() {
+        let top = scrollbar_cells(10, 0, 5, 20);
+        assert_eq!(top.iter().filter(|&&t| t).count(), 2);
+        assert!(top[0] && top[1] && !top[2]);
+
+        let bottom = scrollbar_cells(10, 15, 5, 20);
+        assert!(bottom[8] && bottom[9] && !bottom[7]);
+    }
+}
@@ -10,7 +10,7 @@ use crate::state::AgentSelectorState;
 
 pub fn render_agent_selector(frame: &mut Frame, selector: &AgentSelectorState) {
     let area = frame.area();
-    let dialog_width = 50.min(area.width.saturating_sub(4));
+    let dialog_width = 56.min(area.width.saturating_sub(4));
     let dialog_height = (selector.filtered_indices.len() as u16 + 6)
         .min(area.height.saturating_sub(4))
         .max(8);
@@ -109,8 +109,15 @@ pub fn render_agent_selector(frame: &mut Frame, selector: &AgentSelectorState) {
             Style::default().fg(Color::DarkGray)
         };
 
+        let star = if selector.is_favorite(agent) {
+            "\u{2605} "
+        } else {
+            "  "
+        };
+
         lines.push(Line::from(vec![
             Span::styled(prefix, name_style),
+            Span::styled(star, Style::default().fg(Color::Yellow)),
             Span::styled(format!("{:<12}", agent.name), name_style),
             Span::styled(model_text, model_style),
         ]));
@@ -141,6 +148,13 @@ pub fn render_agent_selector(frame: &mut Frame, selector: &AgentSelectorState) {
                 .add_modifier(Modifier::BOLD),
         ),
         Span::styled("model ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            "[f]",
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("star ", Style::default().fg(Color::DarkGray)),
         Span::styled(
             "[Enter]",
             Style::default()
@@ -58,6 +58,7 @@ pub fn render_annotation_menu(frame: &mut Frame, state: &AppState) {
         let prefix = if is_selected { " \u{25b6} " } else { "   " };
 
         let range_text = item.range_text();
+        let tag_text: String = item.tags.iter().map(|t| format!("#{t} ")).collect();
 
         // Truncate comment to first line for the list view
         let first_line = item
@@ -66,7 +67,10 @@ pub fn render_annotation_menu(frame: &mut Frame, state: &AppState) {
             .next()
             .unwrap_or("")
             .chars()
-            .take((inner.width as usize).saturating_sub(prefix.len() + range_text.len() + 4))
+            .take(
+                (inner.width as usize)
+                    .saturating_sub(prefix.len() + range_text.len() + tag_text.chars().count() + 4),
+            )
             .collect::<String>();
 
         let name_style = if is_selected {
@@ -86,6 +90,7 @@ pub fn render_annotation_menu(frame: &mut Frame, state: &AppState) {
         lines.push(Line::from(vec![
             Span::styled(prefix, name_style),
             Span::styled(format!("{range_text}: "), range_style),
+            Span::styled(tag_text, Style::default().fg(theme.secondary)),
             Span::styled(first_line, name_style),
         ]));
     }
@@ -13,7 +13,7 @@ pub fn render_commit_dialog(frame: &mut Frame, state: &AppState) {
     let theme = &state.theme;
     let area = frame.area();
     let dialog_width = 60.min(area.width.saturating_sub(4));
-    let dialog_height = 10.min(area.height.saturating_sub(4));
+    let dialog_height = 11.min(area.height.saturating_sub(4));
 
     let x = (area.width.saturating_sub(dialog_width)) / 2;
     let y = (area.height.saturating_sub(dialog_height)) / 2;
@@ -22,8 +22,13 @@ pub fn render_commit_dialog(frame: &mut Frame, state: &AppState) {
 
     frame.render_widget(Clear, dialog_area);
 
+    let title = if state.commit_amend {
+        " Commit Message \u{26a0} amend "
+    } else {
+        " Commit Message "
+    };
     let block = Block::default()
-        .title(" Commit Message ")
+        .title(title)
         .borders(Borders::ALL)
         .border_style(Style::default().fg(theme.warning));
 
@@ -35,6 +40,7 @@ pub fn render_commit_dialog(frame: &mut Frame, state: &AppState) {
         .constraints([
             Constraint::Min(3),    // text area (expands)
             Constraint::Length(1), // blank
+            Constraint::Length(1), // amend checkbox
             Constraint::Length(1), // hints
         ])
         .split(inner);
@@ -48,6 +54,17 @@ pub fn render_commit_dialog(frame: &mut Frame, state: &AppState) {
         Style::default().fg(theme.text),
     );
 
+    let (checkbox, checkbox_style) = if state.commit_amend {
+        ("[x]", Style::default().fg(theme.warning))
+    } else {
+        ("[ ]", Style::default().fg(theme.text_muted))
+    };
+    let amend = Line::from(vec![
+        Span::styled(format!(" {checkbox} Amend last commit"), checkbox_style),
+        Span::styled("  (Tab)", Style::default().fg(theme.text_muted)),
+    ]);
+    frame.render_widget(Paragraph::new(amend), rows[2]);
+
     // Hints
     let hints = Line::from(vec![
         Span::styled(
@@ -56,7 +73,14 @@ pub fn render_commit_dialog(frame: &mut Frame, state: &AppState) {
                 .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         ),
-        Span::styled("commit  ", Style::default().fg(theme.text_muted)),
+        Span::styled(
+            if state.commit_amend {
+                "amend  "
+            } else {
+                "commit  "
+            },
+            Style::default().fg(theme.text_muted),
+        ),
         Span::styled(
             "[S-Enter]",
             Style::default()
@@ -72,5 +96,5 @@ pub fn render_commit_dialog(frame: &mut Frame, state: &AppState) {
         ),
         Span::styled("cancel", Style::default().fg(theme.text_muted)),
     ]);
-    frame.render_widget(Paragraph::new(hints), rows[2]);
+    frame.render_widget(Paragraph::new(hints), rows[3]);
 }
@@ -1,17 +1,44 @@
 use ratatui::{
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::Paragraph,
     Frame,
 };
 
+use crate::state::app_state::ActiveView;
+use crate::state::navigator_state::NavigatorStatusFilter;
 use crate::state::{AppState, DiffViewMode};
 
 use super::Component;
 
 pub struct ContextBar;
 
+/// `name [+N -M | hunk k/n]` for the selected file, with the change block at or
+/// above the cursor.
+fn selected_file_stats(state: &AppState) -> Option<String> {
+    let delta = state.diff.selected_delta()?;
+    let name = delta
+        .path
+        .file_name()
+        .map_or_else(|| delta.path.to_string_lossy(), |n| n.to_string_lossy());
+    let counts = format!("+{} -{}", delta.additions, delta.deletions);
+    let collapsed = state
+        .diff
+        .selected_file
+        .is_some_and(|idx| state.navigator.collapsed.contains(&idx));
+    let rows = &state.diff.change_block_rows;
+    if rows.is_empty() || collapsed {
+        return Some(format!("{name} [{counts}]"));
+    }
+    let block = rows
+        .iter()
+        .filter(|&&row| row <= state.diff.cursor_row)
+        .count()
+        .max(1);
+    Some(format!("{name} [{counts} | hunk {block}/{}]", rows.len()))
+}
+
 impl Component for ContextBar {
     fn render(&self, frame: &mut Frame, area: Rect, state: &AppState) {
         let theme = &state.theme;
@@ -27,21 +54,49 @@ impl Component for ContextBar {
             DiffViewMode::Unified => "unified",
         };
 
-        let line = Line::from(vec![
+        // Ranges compare two commits; everything else compares against the working tree
+        let (from_label, to_label) = state
+            .target_label
+            .split_once("..")
+            .unwrap_or((state.target_label.as_str(), "working tree"));
+
+        let mut spans = vec![
             Span::styled(
                 " mdiff ",
-                Style::default().fg(Color::Black).bg(theme.accent),
+                Style::default().fg(theme.on_accent_fg()).bg(theme.accent),
             ),
             Span::raw("  "),
+        ];
+        if state.tabs.len() > 1 {
+            for (idx, tab) in state.tabs.iter().enumerate() {
+                let (label, style) = if idx == state.active_tab {
+                    (
+                        state.target_label.as_str(),
+                        Style::default()
+                            .fg(theme.text)
+                            .bg(theme.selection_bg)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                } else {
+                    (
+                        tab.target_label.as_str(),
+                        Style::default().fg(theme.text_muted),
+                    )
+                };
+                spans.push(Span::styled(format!(" {}:{label} ", idx + 1), style));
+            }
+            spans.push(Span::raw("  "));
+        }
+        spans.extend([
             Span::styled(
-                &state.target_label,
+                from_label,
                 Style::default()
                     .fg(theme.success)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(" \u{2192} ", Style::default().fg(theme.text_muted)),
             Span::styled(
-                "working tree",
+                to_label,
                 Style::default()
                     .fg(theme.warning)
                     .add_modifier(Modifier::BOLD),
@@ -54,6 +109,34 @@ impl Component for ContextBar {
             Span::raw(" "),
             Span::styled(ws_label, Style::default().fg(theme.text_muted)),
         ]);
+        if state.diff.excluded_count > 0 {
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(
+                format!("(+{} hidden)", state.diff.excluded_count),
+                Style::default().fg(theme.text_muted),
+            ));
+        }
+        if state.navigator.status_filter != NavigatorStatusFilter::All {
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(
+                format!("[filter: {}]", state.navigator.status_filter.label()),
+                Style::default().fg(theme.warning),
+            ));
+        }
+        if let Some(glob) = &state.navigator.filter_glob {
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(
+                format!("[filter: {glob}]"),
+                Style::default().fg(theme.warning),
+            ));
+        }
+        if state.active_view == ActiveView::DiffExplorer {
+            if let Some(file) = selected_file_stats(state) {
+                spans.push(Span::raw("  "));
+                spans.push(Span::styled(file, Style::default().fg(theme.text)));
+            }
+        }
+        let line = Line::from(spans);
 
         let bar = Paragraph::new(line).style(Style::default().bg(theme.surface));
         frame.render_widget(bar, area);
@@ -0,0 +1,69 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::action::Action;
+use crate::state::AppState;
+
+/// Entries of the diff view's right-click menu, in display order.
+pub const CONTEXT_MENU_ITEMS: [(&str, Action); 6] = [
+    ("Stage file", Action::StageFile),
+    ("Unstage file", Action::UnstageFile),
+    ("Restore file", Action::RestoreFile),
+    ("Copy prompt", Action::CopyPromptToClipboard),
+    ("Add comment", Action::OpenCommentEditor),
+    ("Open in editor", Action::OpenInEditor),
+];
+
+const MENU_WIDTH: u16 = 20;
+
+/// Where a menu opened at `(x, y)` is drawn, shifted left or up to stay inside `area`.
+pub fn context_menu_rect(area: Rect, x: u16, y: u16) -> Rect {
+    let width = MENU_WIDTH.min(area.width);
+    let height = (CONTEXT_MENU_ITEMS.len() as u16 + 2).min(area.height);
+    Rect::new(
+        x.min(area.right().saturating_sub(width)),
+        y.min(area.bottom().saturating_sub(height)),
+        width,
+        height,
+    )
+}
+
+pub fn render_context_menu(frame: &mut Frame, state: &AppState) {
+    let Some(menu) = &state.context_menu else {
+        return;
+    };
+    let theme = &state.theme;
+    let menu_area = context_menu_rect(frame.area(), menu.x, menu.y);
+    frame.render_widget(Clear, menu_area);
+
+    let lines: Vec<Line> = CONTEXT_MENU_ITEMS
+        .iter()
+        .enumerate()
+        .map(|(idx, (label, _))| {
+            if idx == menu.selected {
+                Line::from(Span::styled(
+                    format!("\u{25b6} {label}"),
+                    Style::default()
+                        .fg(theme.accent)
+                        .bg(theme.selection_bg)
+                        .add_modifier(Modifier::BOLD),
+                ))
+            } else {
+                Line::from(Span::styled(
+                    format!("  {label}"),
+                    Style::default().fg(theme.text),
+                ))
+            }
+        })
+        .collect();
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.secondary));
+    frame.render_widget(Paragraph::new(lines).block(block), menu_area);
+}
@@ -2,18 +2,23 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Paragraph, Wrap},
     Frame,
 };
 
+use crate::diff_utils::intraline_diff;
 use crate::display_map::{
-    build_display_map, filter_hunk_lines, DisplayRowInfo, ExpandDirection, FilteredItem,
+    build_display_map, filter_hunk_lines, split_header_text, DisplayRowInfo, ExpandDirection,
+    FilteredItem,
 };
-use crate::git::types::{DiffLineOrigin, FileDelta};
+use crate::git::types::{DiffLineOrigin, FileDelta, FileStatus, Hunk};
 use crate::highlight::HighlightSpan;
+use crate::image_preview::{is_image_path, render_half_blocks, ImageSide};
+use crate::state::review_state::change_blocks;
 use crate::state::{app_state::FocusPanel, AppState, DiffViewMode};
 use crate::theme::Theme;
 
+use super::action_hud::SPINNER;
 use super::Component;
 
 pub struct DiffView;
@@ -34,6 +39,24 @@ impl Component for DiffView {
             DiffViewMode::Unified => "Unified",
         };
 
+        if let Some(err) = state
+            .diff
+            .load_error
+            .as_deref()
+            .filter(|_| !state.diff.loading)
+        {
+            let block = Block::default()
+                .title(" Error loading diff ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.error));
+            let paragraph = Paragraph::new(format!(" {err}"))
+                .style(Style::default().fg(theme.error))
+                .wrap(Wrap { trim: false })
+                .block(block);
+            frame.render_widget(paragraph, area);
+            return;
+        }
+
         let Some(delta) = state.diff.selected_delta() else {
             let block = Block::default()
                 .title(format!(" Diff [{view_label}] "))
@@ -41,11 +64,16 @@ impl Component for DiffView {
                 .border_style(border_style);
 
             let content = if state.diff.loading {
-                " Loading..."
+                let elapsed = state
+                    .diff
+                    .load_start
+                    .map_or(0.0, |start| start.elapsed().as_secs_f64());
+                let spinner = SPINNER[state.spinner_tick % SPINNER.len()];
+                format!(" {spinner} Loading\u{2026} {elapsed:.1}s")
             } else if state.diff.deltas.is_empty() {
-                " No changes detected"
+                " No changes detected".to_string()
             } else {
-                " Select a file to view diff"
+                " Select a file to view diff".to_string()
             };
 
             let paragraph = Paragraph::new(content)
@@ -55,6 +83,19 @@ impl Component for DiffView {
             return;
         };
 
+        let collapsed = state
+            .diff
+            .selected_file
+            .is_some_and(|idx| state.navigator.collapsed.contains(&idx));
+        if collapsed {
+            render_collapsed(frame, area, delta, state, border_style, view_label, theme);
+            return;
+        }
+        if delta.status == FileStatus::Submodule {
+            render_submodule(frame, area, delta, state, border_style, view_label, theme);
+            return;
+        }
+
         match state.diff.options.view_mode {
             DiffViewMode::Split => {
                 render_split(frame, area, delta, state, border_style, view_label, theme)
@@ -66,19 +107,35 @@ impl Component for DiffView {
     }
 }
 
-fn format_title(delta: &FileDelta, view_label: &str, state: &AppState) -> String {
+/// Block title for a pane `width` columns wide. A path too long to fit loses
+/// its start to an ellipsis, so the file name and view label stay visible.
+fn format_title(delta: &FileDelta, view_label: &str, state: &AppState, width: u16) -> String {
     let path_display = delta.path.to_string_lossy();
-    let base = if let Some(ref old_path) = delta.old_path {
-        if *old_path != delta.path {
-            let old_display = old_path.to_string_lossy();
-            format!(" {old_display} \u{2192} {path_display} [{view_label}]")
-        } else {
-            format!(" {path_display} [{view_label}]")
+    let path = match delta.old_path {
+        Some(ref old_path) if *old_path != delta.path => {
+            format!("{} \u{2192} {path_display}", old_path.to_string_lossy())
         }
-    } else {
-        format!(" {path_display} [{view_label}]")
+        _ => path_display.into_owned(),
     };
+    let suffix = format!(" [{view_label}]{}", search_title(state));
+    // Two border corners plus the spaces around the title
+    let room = (width as usize).saturating_sub(4 + suffix.chars().count());
+    format!(" {}{suffix} ", truncate_start(&path, room))
+}
+
+/// `text` cut to at most `max` characters by replacing its start with `…`.
+fn truncate_start(text: &str, max: usize) -> String {
+    let len = text.chars().count();
+    if len <= max {
+        return text.to_string();
+    }
+    let keep = max.saturating_sub(1);
+    let tail: String = text.chars().skip(len - keep).collect();
+    format!("\u{2026}{tail}")
+}
 
+/// The diff search prompt and match count shown in the title, if searching.
+fn search_title(state: &AppState) -> String {
     if state.diff.search_active || !state.diff.search_query.is_empty() {
         let match_info = if state.diff.search_matches.is_empty() {
             if state.diff.search_query.is_empty() {
@@ -95,10 +152,10 @@ fn format_title(delta: &FileDelta, view_label: &str, state: &AppState) -> String
             let ci = state.diff.search_query.cursor_char_index();
             let before: String = q.chars().take(ci).collect();
             let after: String = q.chars().skip(ci).collect();
-            format!("{base} /{}\u{2588}{}{match_info} ", before, after)
+            format!(" /{}\u{2588}{}{match_info}", before, after)
         }
     } else {
-        format!("{base} ")
+        String::new()
     }
 }
 
@@ -127,12 +184,16 @@ struct RowHighlight {
     gutter_fg: Option<Color>,
     /// Background override for content area. When set, replaces diff_bg.
     content_bg: Option<Color>,
+    /// Reverse-video the gutter; marks the cursor when the theme has no colors.
+    reversed: bool,
 }
 
 struct WrapConfig<'a> {
     width: u16,
     gutter_width: usize,
     wrap_enabled: bool,
+    /// Columns of content skipped on the left when wrapping is disabled.
+    scroll_x: usize,
     theme: &'a Theme,
 }
 
@@ -161,30 +222,302 @@ fn row_highlight(state: &AppState, display_row: usize) -> RowHighlight {
             gutter_bg: bg,
             gutter_fg: None,
             content_bg: bg,
+            reversed: theme.visual_select_bg == Color::Reset,
         }
     } else if is_cursor_row(state, display_row) {
+        let gutter_fg = if theme.cursor_line_bg == theme.accent {
+            theme.on_accent_fg()
+        } else {
+            theme.text
+        };
         RowHighlight {
-            gutter_bg: Some(theme.accent),
-            gutter_fg: Some(Color::Black),
+            gutter_bg: Some(theme.cursor_line_bg),
+            gutter_fg: Some(gutter_fg),
             content_bg: None,
+            reversed: theme.cursor_line_bg == Color::Reset,
         }
     } else if is_search_match(state, display_row) {
         RowHighlight {
             gutter_bg: None,
             gutter_fg: None,
             content_bg: Some(theme.search_match_bg),
+            reversed: false,
         }
     } else {
         RowHighlight::default()
     }
 }
 
-/// Check if a line has an annotation marker in the gutter.
-fn has_annotation(state: &AppState, delta: &FileDelta, row_info: &DisplayRowInfo) -> bool {
+/// Gutter marker for a line: the annotation's tag marker (`│` when untagged), or `⚠`
+/// when an annotation there lost its anchor.
+fn annotation_marker(
+    state: &AppState,
+    delta: &FileDelta,
+    row_info: &DisplayRowInfo,
+) -> &'static str {
+    if !state.diff.annotations_visible {
+        return " ";
+    }
+    let file_path = delta.path.to_string_lossy();
+    let anns = state.annotations.annotations_overlapping(
+        &file_path,
+        row_info.old_lineno,
+        row_info.new_lineno,
+    );
+    if anns.iter().any(|a| a.anchor.stale) {
+        "\u{26a0}"
+    } else {
+        anns.first().map_or(" ", |a| a.marker())
+    }
+}
+
+/// Gutter marker for display row `row`: `▸` where a bookmark is set, otherwise
+/// the annotation marker.
+fn gutter_marker(
+    state: &AppState,
+    delta: &FileDelta,
+    display_map: &[DisplayRowInfo],
+    row: usize,
+) -> &'static str {
+    let Some(info) = display_map.get(row) else {
+        return " ";
+    };
     let file_path = delta.path.to_string_lossy();
-    state
+    let has_lineno = info.old_lineno.is_some() || info.new_lineno.is_some();
+    if has_lineno
+        && state.bookmarks.iter().any(|b| {
+            b.old_lineno == info.old_lineno
+                && b.new_lineno == info.new_lineno
+                && b.file_path == file_path
+        })
+    {
+        return "\u{25b8}";
+    }
+    annotation_marker(state, delta, info)
+}
+
+/// Comment lines drawn under each display row, each tagged with whether its
+/// annotation is on the old side (the left column in split view).
+type InlineComments<'a> = Vec<Vec<(bool, Line<'a>)>>;
+
+/// Inline comment lines for every display row when `Alt+i` is on: each
+/// annotation's comment goes under the last line of its range. These are extra
+/// visual lines of that row, so the cursor never lands on them.
+fn inline_comments<'a>(
+    state: &AppState,
+    delta: &FileDelta,
+    display_map: &[DisplayRowInfo],
+) -> InlineComments<'a> {
+    let mut rows: InlineComments<'a> = vec![Vec::new(); display_map.len()];
+    if !state.diff.show_inline_annotations || !state.diff.annotations_visible {
+        return rows;
+    }
+    let Some(anns) = state
         .annotations
-        .has_annotation_at(&file_path, row_info.old_lineno, row_info.new_lineno)
+        .annotations
+        .get(delta.path.to_string_lossy().as_ref())
+    else {
+        return rows;
+    };
+    let style = Style::default()
+        .fg(state.theme.warning)
+        .add_modifier(Modifier::ITALIC);
+    for ann in anns.iter().filter(|a| !a.unanchored) {
+        let (old_side, end) = match (ann.anchor.new_range, ann.anchor.old_range) {
+            (Some((_, end)), _) => (false, end),
+            (None, Some((_, end))) => (true, end),
+            (None, None) => continue,
+        };
+        let Some(row) = display_map.iter().position(|info| {
+            let lineno = if old_side {
+                info.old_lineno
+            } else {
+                info.new_lineno
+            };
+            !info.is_header && lineno == Some(end)
+        }) else {
+            continue;
+        };
+        for (i, text) in ann.comment.lines().enumerate() {
+            let prefix = if i == 0 { "    \u{1f4ac} " } else { "       " };
+            rows[row].push((old_side, Line::styled(format!("{prefix}{text}"), style)));
+        }
+    }
+    rows
+}
+
+/// Columns reserved on the right edge of the diff area for the minimap.
+pub(crate) const MINIMAP_WIDTH: u16 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum MinimapCell {
+    Plain,
+    Reviewed,
+    Annotated,
+    Cursor,
+}
+
+/// Map `total_rows` display rows linearly onto `height` cells. Each cell shows
+/// the most significant kind among its rows; the cursor always wins.
+fn minimap_cells(
+    total_rows: usize,
+    height: usize,
+    cursor_row: usize,
+    row_kind: impl Fn(usize) -> MinimapCell,
+) -> Vec<MinimapCell> {
+    if total_rows == 0 {
+        return vec![MinimapCell::Plain; height];
+    }
+    (0..height)
+        .map(|y| {
+            let start = y * total_rows / height;
+            let end = ((y + 1) * total_rows / height)
+                .max(start + 1)
+                .min(total_rows);
+            if (start..end).contains(&cursor_row) {
+                MinimapCell::Cursor
+            } else {
+                (start..end)
+                    .map(&row_kind)
+                    .max()
+                    .unwrap_or(MinimapCell::Plain)
+            }
+        })
+        .collect()
+}
+
+/// Split the minimap column off the right edge of `inner`.
+fn split_minimap(inner: Rect) -> (Rect, Rect) {
+    let width = MINIMAP_WIDTH.min(inner.width);
+    let content = Rect {
+        width: inner.width - width,
+        ..inner
+    };
+    let minimap = Rect {
+        x: inner.x + content.width,
+        width,
+        ..inner
+    };
+    (content, minimap)
+}
+
+fn render_minimap(
+    frame: &mut Frame,
+    area: Rect,
+    delta: &FileDelta,
+    state: &AppState,
+    display_map: &[DisplayRowInfo],
+) {
+    let theme = &state.theme;
+    let file_path = delta.path.to_string_lossy();
+    let seen = state.review.seen_lines(&file_path);
+    let cells = minimap_cells(
+        display_map.len(),
+        area.height as usize,
+        state.diff.cursor_row,
+        |row| {
+            let info = &display_map[row];
+            if info.is_header || info.is_collapsed_indicator {
+                MinimapCell::Plain
+            } else if state.diff.annotations_visible
+                && !state
+                    .annotations
+                    .annotations_overlapping(&file_path, info.old_lineno, info.new_lineno)
+                    .is_empty()
+            {
+                MinimapCell::Annotated
+            } else if seen.is_some_and(|s| s.contains(info.old_lineno, info.new_lineno)) {
+                MinimapCell::Reviewed
+            } else {
+                MinimapCell::Plain
+            }
+        },
+    );
+
+    let lines: Vec<Line> = cells
+        .into_iter()
+        .map(|cell| {
+            let (symbol, color) = match cell {
+                MinimapCell::Plain => ("\u{2502}", theme.text_muted),
+                MinimapCell::Reviewed => ("\u{2503}", theme.success),
+                MinimapCell::Annotated => ("\u{2501}", theme.warning),
+                MinimapCell::Cursor => ("\u{2588}", theme.accent),
+            };
+            Line::from(Span::styled(symbol, Style::default().fg(color)))
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// A collapsed file shows only its title and how much is folded away.
+fn render_collapsed(
+    frame: &mut Frame,
+    area: Rect,
+    delta: &FileDelta,
+    state: &AppState,
+    border_style: Style,
+    view_label: &str,
+    theme: &Theme,
+) {
+    let block = Block::default()
+        .title(format_title(delta, view_label, state, area.width))
+        .borders(Borders::ALL)
+        .border_style(border_style);
+    let hunks = change_blocks(delta).len();
+    let noun = if hunks == 1 { "hunk" } else { "hunks" };
+    let msg = Paragraph::new(format!(
+        " \u{25b6} {hunks} {noun} hidden \u{2014} press x to expand"
+    ))
+    .style(Style::default().fg(theme.text_muted))
+    .block(block);
+    frame.render_widget(msg, area);
+}
+
+/// A submodule's commit change, with the commits styled like links.
+fn render_submodule(
+    frame: &mut Frame,
+    area: Rect,
+    delta: &FileDelta,
+    state: &AppState,
+    border_style: Style,
+    view_label: &str,
+    theme: &Theme,
+) {
+    let block = Block::default()
+        .title(format_title(delta, view_label, state, area.width))
+        .borders(Borders::ALL)
+        .border_style(border_style);
+    let link = Style::default()
+        .fg(theme.accent)
+        .add_modifier(Modifier::UNDERLINED);
+    let commit_line = |marker: &'static str, color: Color, oid: Option<&str>| {
+        Line::from(vec![
+            Span::styled(format!("   {marker} "), Style::default().fg(color)),
+            match oid {
+                Some(oid) => Span::styled(oid.to_string(), link),
+                None => Span::styled("(none)", Style::default().fg(theme.text_muted)),
+            },
+        ])
+    };
+    let (old, new) = delta.submodule_commits();
+    let lines = vec![
+        Line::from(vec![
+            Span::styled(
+                " \u{2295} ",
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("Submodule {}", delta.path.display()),
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(""),
+        commit_line("-", theme.diff_del_fg, old),
+        commit_line("+", theme.diff_add_fg, new),
+    ];
+    frame.render_widget(Paragraph::new(lines).block(block), area);
 }
 
 fn render_split(
@@ -196,17 +529,14 @@ fn render_split(
     view_label: &str,
     theme: &Theme,
 ) {
-    let title = format_title(delta, view_label, state);
+    let title = format_title(delta, view_label, state, area.width);
 
     if delta.binary {
         let block = Block::default()
             .title(title)
             .borders(Borders::ALL)
             .border_style(border_style);
-        let msg = Paragraph::new(" Binary file differs")
-            .style(Style::default().fg(theme.text_muted))
-            .block(block);
-        frame.render_widget(msg, area);
+        render_binary_delta(frame, area, block, delta, state, theme);
         return;
     }
 
@@ -214,7 +544,7 @@ fn render_split(
         .title(title)
         .borders(Borders::ALL)
         .border_style(border_style);
-    let inner = outer_block.inner(area);
+    let (inner, minimap_area) = split_minimap(outer_block.inner(area));
     frame.render_widget(outer_block, area);
 
     // 3-column layout: left content | center gutter | right content
@@ -238,6 +568,7 @@ fn render_split(
         DiffViewMode::Split,
         state.diff.display_context,
         &state.diff.gap_expansions,
+        &state.diff.hunk_splits,
     );
 
     let (left_lines, center_lines, right_lines) = build_split_lines(
@@ -249,7 +580,7 @@ fn render_split(
         state,
         &display_map,
         cols[0].width,
-        true,
+        state.diff.line_wrap,
         theme,
     );
 
@@ -260,6 +591,7 @@ fn render_split(
     frame.render_widget(left_para, cols[0]);
     frame.render_widget(center_para, cols[1]);
     frame.render_widget(right_para, cols[2]);
+    render_minimap(frame, minimap_area, delta, state, &display_map);
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -277,17 +609,20 @@ fn build_split_lines<'a>(
 ) -> (Vec<Line<'a>>, Vec<Line<'a>>, Vec<Line<'a>>) {
     let (left_lines, center_lines, right_lines) =
         build_split_lines_core(delta, old_hl, new_hl, state, display_map, theme);
+    let comments = inline_comments(state, delta, display_map);
 
     let config = WrapConfig {
         width,
         gutter_width: 0,
         wrap_enabled,
+        scroll_x: state.diff.scroll_x,
         theme,
     };
     wrap_split_lines_synchronized_with_scroll(
         left_lines,
         center_lines,
         right_lines,
+        comments,
         &config,
         scroll,
         height,
@@ -303,17 +638,14 @@ fn render_unified(
     view_label: &str,
     theme: &Theme,
 ) {
-    let title = format_title(delta, view_label, state);
+    let title = format_title(delta, view_label, state, area.width);
 
     if delta.binary {
         let block = Block::default()
             .title(title)
             .borders(Borders::ALL)
             .border_style(border_style);
-        let msg = Paragraph::new(" Binary file differs")
-            .style(Style::default().fg(theme.text_muted))
-            .block(block);
-        frame.render_widget(msg, area);
+        render_binary_delta(frame, area, block, delta, state, theme);
         return;
     }
 
@@ -321,7 +653,7 @@ fn render_unified(
         .title(title)
         .borders(Borders::ALL)
         .border_style(border_style);
-    let inner = block.inner(area);
+    let (inner, minimap_area) = split_minimap(block.inner(area));
     frame.render_widget(block, area);
 
     let old_hl = &state.diff.old_highlights;
@@ -333,6 +665,7 @@ fn render_unified(
         DiffViewMode::Unified,
         state.diff.display_context,
         &state.diff.gap_expansions,
+        &state.diff.hunk_splits,
     );
 
     let lines = build_unified_lines_core(delta, old_hl, new_hl, state, &display_map, theme);
@@ -340,17 +673,81 @@ fn render_unified(
     let config = WrapConfig {
         width: inner.width,
         gutter_width: 5 + 1 + 5 + 1 + 1,
-        wrap_enabled: true,
+        wrap_enabled: state.diff.line_wrap,
+        scroll_x: state.diff.scroll_x,
         theme,
     };
+    let comments = inline_comments(state, delta, &display_map);
     let wrapped = wrap_lines_for_display_with_scroll(
         lines,
+        comments,
         &config,
         state.diff.scroll_offset,
         inner.height as usize,
     );
     let paragraph = Paragraph::new(wrapped);
     frame.render_widget(paragraph, inner);
+    render_minimap(frame, minimap_area, delta, state, &display_map);
+}
+
+/// Body of a binary file: the old and new images side by side in half blocks
+/// when they could be decoded, otherwise a one-line note.
+fn render_binary_delta(
+    frame: &mut Frame,
+    area: Rect,
+    block: Block<'_>,
+    delta: &FileDelta,
+    state: &AppState,
+    theme: &Theme,
+) {
+    let muted = Style::default().fg(theme.text_muted);
+    let preview = state
+        .diff
+        .image_preview
+        .as_ref()
+        .filter(|p| p.path == delta.path);
+    let Some(preview) = preview.filter(|p| p.any_decoded()) else {
+        let msg = match preview.map(|p| p.new.bytes().or(p.old.bytes())) {
+            Some(Some(bytes)) => format!(" Binary image differs ({bytes} bytes)"),
+            None if is_image_path(&delta.path) => " Loading image\u{2026}".to_string(),
+            _ => " Binary file differs".to_string(),
+        };
+        frame.render_widget(Paragraph::new(msg).style(muted).block(block), area);
+        return;
+    };
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(inner);
+    for ((label, side), column) in [("Old", &preview.old), ("New", &preview.new)]
+        .into_iter()
+        .zip(columns.iter())
+    {
+        let caption = match side {
+            ImageSide::Missing => format!(" {label}: none"),
+            ImageSide::Decoded { image, bytes } => {
+                format!(
+                    " {label}: {}\u{d7}{} ({bytes} bytes)",
+                    image.width, image.height
+                )
+            }
+            ImageSide::Undecoded { bytes } => format!(" {label}: not shown ({bytes} bytes)"),
+        };
+        let mut lines = vec![Line::from(Span::styled(caption, muted))];
+        if let ImageSide::Decoded { image, .. } = side {
+            let cols = column.width.saturating_sub(1);
+            let rows = column.height.saturating_sub(1);
+            for line in render_half_blocks(image, cols, rows) {
+                let mut spans = vec![Span::raw(" ")];
+                spans.extend(line.spans);
+                lines.push(Line::from(spans));
+            }
+        }
+        frame.render_widget(Paragraph::new(lines), *column);
+    }
 }
 
 fn build_split_lines_core<'a>(
@@ -371,11 +768,9 @@ fn build_split_lines_core<'a>(
 
     for hunk in &delta.hunks {
         let hl = row_highlight(state, display_row);
-        let ann_marker = display_map
-            .get(display_row)
-            .is_some_and(|info| has_annotation(state, delta, info));
+        let ann_marker = gutter_marker(state, delta, display_map, display_row);
 
-        let marker = if ann_marker { "\u{2502}" } else { " " };
+        let marker = ann_marker;
         let hunk_gutter = format!("{:>gutter_width$} {:>gutter_width$}{marker}", "...", "...");
         let mut gutter_style = Style::default().fg(theme.text_muted);
         if let Some(fg) = hl.gutter_fg {
@@ -384,13 +779,23 @@ fn build_split_lines_core<'a>(
         if let Some(bg) = hl.gutter_bg {
             gutter_style = gutter_style.bg(bg);
         }
+        if hl.reversed {
+            gutter_style = gutter_style.add_modifier(Modifier::REVERSED);
+        }
         center.push(Line::from(Span::styled(hunk_gutter, gutter_style)));
 
         let mut content_style = Style::default().fg(theme.text_muted);
         if let Some(bg) = hl.content_bg {
             content_style = content_style.bg(bg);
         }
-        left.push(Line::from(Span::styled(hunk.header.clone(), content_style)));
+        let mut header_spans = vec![Span::styled(hunk.header.clone(), content_style)];
+        header_spans.extend(hunk_stat_spans(
+            hunk,
+            state.hunk_stat_bar_width,
+            hl.content_bg,
+            theme,
+        ));
+        left.push(Line::from(header_spans));
         right.push(Line::from(Span::styled("", content_style)));
         display_row += 1;
 
@@ -404,6 +809,14 @@ fn build_split_lines_core<'a>(
 
         let mut i = 0;
         while i < items.len() {
+            push_split_headers_split(
+                (&mut left, &mut center, &mut right),
+                hunk,
+                state,
+                display_map,
+                &mut display_row,
+                theme,
+            );
             match &items[i] {
                 FilteredItem::CollapsedIndicator {
                     hidden_count,
@@ -424,6 +837,9 @@ fn build_split_lines_core<'a>(
                     if let Some(bg) = hl.gutter_bg {
                         gutter_style = gutter_style.bg(bg);
                     }
+                    if hl.reversed {
+                        gutter_style = gutter_style.add_modifier(Modifier::REVERSED);
+                    }
                     center.push(Line::from(Span::styled(collapsed_gutter, gutter_style)));
 
                     let mut content_style = Style::default().fg(theme.text_muted);
@@ -444,33 +860,43 @@ fn build_split_lines_core<'a>(
                 FilteredItem::Line { line, .. } => match line.origin {
                     DiffLineOrigin::Context => {
                         let hl = row_highlight(state, display_row);
-                        let ann_marker = display_map
-                            .get(display_row)
-                            .is_some_and(|info| has_annotation(state, delta, info));
+                        let ann_marker = gutter_marker(state, delta, display_map, display_row);
 
                         let gutter_l = format_lineno(line.old_lineno, gutter_width);
                         let gutter_r = format_lineno(line.new_lineno, gutter_width);
-                        let marker = if ann_marker { "\u{2502}" } else { " " };
+                        let marker = ann_marker;
                         center.push(make_center_gutter_line(
                             &gutter_l, &gutter_r, marker, hl, theme,
                         ));
 
                         let old_spans = line.old_lineno.and_then(|n| old_hl.get(n as usize));
                         let new_spans = line.new_lineno.and_then(|n| new_hl.get(n as usize));
-                        left.push(make_content_only_line(
-                            &line.content,
-                            old_spans,
-                            None,
+                        let mut old_line =
+                            make_content_only_line(&line.content, old_spans, None, hl, theme);
+                        let mut new_line =
+                            make_content_only_line(&line.content, new_spans, None, hl, theme);
+                        overlay_line_length_warning(
+                            &mut old_line,
+                            0,
+                            state.max_line_length,
                             hl,
                             theme,
-                        ));
-                        right.push(make_content_only_line(
-                            &line.content,
-                            new_spans,
-                            None,
+                        );
+                        overlay_line_length_warning(
+                            &mut new_line,
+                            0,
+                            state.max_line_length,
                             hl,
                             theme,
-                        ));
+                        );
+                        if line.is_conflict_marker() {
+                            style_conflict_marker(&mut old_line, 0, hl, theme);
+                            style_conflict_marker(&mut new_line, 0, hl, theme);
+                        }
+                        push_origin_mark(&mut old_line, &line.origin, None, hl, state);
+                        push_origin_mark(&mut new_line, &line.origin, None, hl, state);
+                        left.push(old_line);
+                        right.push(new_line);
                         display_row += 1;
                         i += 1;
                     }
@@ -521,11 +947,17 @@ fn build_split_lines_core<'a>(
                         let max = dels.len().max(adds.len());
 
                         for j in 0..max {
+                            push_split_headers_split(
+                                (&mut left, &mut center, &mut right),
+                                hunk,
+                                state,
+                                display_map,
+                                &mut display_row,
+                                theme,
+                            );
                             let hl = row_highlight(state, display_row);
-                            let ann_marker = display_map
-                                .get(display_row)
-                                .is_some_and(|info| has_annotation(state, delta, info));
-                            let marker = if ann_marker { "\u{2502}" } else { " " };
+                            let ann_marker = gutter_marker(state, delta, display_map, display_row);
+                            let marker = ann_marker;
 
                             let old_lineno = if j < dels.len() {
                                 dels[j].old_lineno
@@ -543,16 +975,39 @@ fn build_split_lines_core<'a>(
                                 &gutter_l, &gutter_r, marker, hl, theme,
                             ));
 
+                            let (del_chars, add_chars) =
+                                if j < dels.len() && j < adds.len() && hl.content_bg.is_none() {
+                                    intraline_diff(
+                                        dels[j].content.trim_end_matches('\n'),
+                                        adds[j].content.trim_end_matches('\n'),
+                                    )
+                                    .unwrap_or_default()
+                                } else {
+                                    Default::default()
+                                };
+
                             if j < dels.len() {
                                 let line = dels[j];
                                 let spans = line.old_lineno.and_then(|n| old_hl.get(n as usize));
-                                left.push(make_content_only_line(
+                                let mut content = make_content_only_line(
                                     &line.content,
                                     spans,
                                     Some(theme.diff_del_bg),
                                     hl,
                                     theme,
-                                ));
+                                );
+                                emphasize_ranges(&mut content, &del_chars, theme.diff_del_char_bg);
+                                if line.is_conflict_marker() {
+                                    style_conflict_marker(&mut content, 0, hl, theme);
+                                }
+                                push_origin_mark(
+                                    &mut content,
+                                    &line.origin,
+                                    Some(theme.diff_del_bg),
+                                    hl,
+                                    state,
+                                );
+                                left.push(content);
                             } else {
                                 left.push(make_empty_content_line(hl, theme));
                             }
@@ -560,13 +1015,32 @@ fn build_split_lines_core<'a>(
                             if j < adds.len() {
                                 let line = adds[j];
                                 let spans = line.new_lineno.and_then(|n| new_hl.get(n as usize));
-                                right.push(make_content_only_line(
+                                let mut content = make_content_only_line(
                                     &line.content,
                                     spans,
                                     Some(theme.diff_add_bg),
                                     hl,
                                     theme,
-                                ));
+                                );
+                                emphasize_ranges(&mut content, &add_chars, theme.diff_add_char_bg);
+                                overlay_line_length_warning(
+                                    &mut content,
+                                    0,
+                                    state.max_line_length,
+                                    hl,
+                                    theme,
+                                );
+                                if line.is_conflict_marker() {
+                                    style_conflict_marker(&mut content, 0, hl, theme);
+                                }
+                                push_origin_mark(
+                                    &mut content,
+                                    &line.origin,
+                                    Some(theme.diff_add_bg),
+                                    hl,
+                                    state,
+                                );
+                                right.push(content);
                             } else {
                                 right.push(make_empty_content_line(hl, theme));
                             }
@@ -576,10 +1050,8 @@ fn build_split_lines_core<'a>(
                     }
                     DiffLineOrigin::Addition => {
                         let hl = row_highlight(state, display_row);
-                        let ann_marker = display_map
-                            .get(display_row)
-                            .is_some_and(|info| has_annotation(state, delta, info));
-                        let marker = if ann_marker { "\u{2502}" } else { " " };
+                        let ann_marker = gutter_marker(state, delta, display_map, display_row);
+                        let marker = ann_marker;
 
                         let gutter_l = " ".repeat(gutter_width);
                         let gutter_r = format_lineno(line.new_lineno, gutter_width);
@@ -589,13 +1061,31 @@ fn build_split_lines_core<'a>(
 
                         left.push(make_empty_content_line(hl, theme));
                         let spans = line.new_lineno.and_then(|n| new_hl.get(n as usize));
-                        right.push(make_content_only_line(
+                        let mut content = make_content_only_line(
                             &line.content,
                             spans,
                             Some(theme.diff_add_bg),
                             hl,
                             theme,
-                        ));
+                        );
+                        overlay_line_length_warning(
+                            &mut content,
+                            0,
+                            state.max_line_length,
+                            hl,
+                            theme,
+                        );
+                        if line.is_conflict_marker() {
+                            style_conflict_marker(&mut content, 0, hl, theme);
+                        }
+                        push_origin_mark(
+                            &mut content,
+                            &line.origin,
+                            Some(theme.diff_add_bg),
+                            hl,
+                            state,
+                        );
+                        right.push(content);
                         display_row += 1;
                         i += 1;
                     }
@@ -622,13 +1112,12 @@ fn build_unified_lines_core<'a>(
 
     for hunk in &delta.hunks {
         let hl = row_highlight(state, display_row);
-        let ann_marker = display_map
-            .get(display_row)
-            .is_some_and(|info| has_annotation(state, delta, info));
+        let ann_marker = gutter_marker(state, delta, display_map, display_row);
 
         lines.push(make_hunk_header_line_unified(
             gutter_width,
-            &hunk.header,
+            hunk,
+            state.hunk_stat_bar_width,
             hl,
             ann_marker,
             theme,
@@ -644,6 +1133,19 @@ fn build_unified_lines_core<'a>(
         gap_id_offset = next_offset;
 
         for item in &items {
+            while let Some(line_index) = display_map
+                .get(display_row)
+                .and_then(|info| info.split_line)
+            {
+                let hl = row_highlight(state, display_row);
+                lines.push(make_split_header_line_unified(
+                    gutter_width,
+                    split_header_text(hunk, line_index),
+                    hl,
+                    theme,
+                ));
+                display_row += 1;
+            }
             match item {
                 FilteredItem::CollapsedIndicator {
                     hidden_count,
@@ -662,61 +1164,73 @@ fn build_unified_lines_core<'a>(
                 }
                 FilteredItem::Line { line, .. } => {
                     let hl = row_highlight(state, display_row);
-                    let ann_marker = display_map
-                        .get(display_row)
-                        .is_some_and(|info| has_annotation(state, delta, info));
+                    let ann_marker = gutter_marker(state, delta, display_map, display_row);
 
                     let (old_g, new_g) = (
                         format_lineno(line.old_lineno, gutter_width),
                         format_lineno(line.new_lineno, gutter_width),
                     );
 
-                    match line.origin {
+                    let mut rendered = match line.origin {
                         DiffLineOrigin::Context => {
                             let spans = line.new_lineno.and_then(|n| new_hl.get(n as usize));
-                            lines.push(make_unified_highlighted(
+                            make_unified_highlighted(
                                 &old_g,
                                 &new_g,
-                                " ",
+                                origin_prefix(&line.origin, state.accessible),
                                 &line.content,
                                 spans,
                                 None,
                                 hl,
                                 ann_marker,
                                 theme,
-                            ));
+                            )
                         }
                         DiffLineOrigin::Addition => {
                             let spans = line.new_lineno.and_then(|n| new_hl.get(n as usize));
                             let blank = " ".repeat(gutter_width);
-                            lines.push(make_unified_highlighted(
+                            make_unified_highlighted(
                                 &blank,
                                 &new_g,
-                                "+",
+                                origin_prefix(&line.origin, state.accessible),
                                 &line.content,
                                 spans,
                                 Some(theme.diff_add_bg),
                                 hl,
                                 ann_marker,
                                 theme,
-                            ));
+                            )
                         }
                         DiffLineOrigin::Deletion => {
                             let spans = line.old_lineno.and_then(|n| old_hl.get(n as usize));
                             let blank = " ".repeat(gutter_width);
-                            lines.push(make_unified_highlighted(
+                            make_unified_highlighted(
                                 &old_g,
                                 &blank,
-                                "-",
+                                origin_prefix(&line.origin, state.accessible),
                                 &line.content,
                                 spans,
                                 Some(theme.diff_del_bg),
                                 hl,
                                 ann_marker,
                                 theme,
-                            ));
+                            )
                         }
+                    };
+                    if line.origin != DiffLineOrigin::Deletion {
+                        // Skip the gutter and origin prefix spans
+                        overlay_line_length_warning(
+                            &mut rendered,
+                            2,
+                            state.max_line_length,
+                            hl,
+                            theme,
+                        );
+                    }
+                    if line.is_conflict_marker() {
+                        style_conflict_marker(&mut rendered, 1, hl, theme);
                     }
+                    lines.push(rendered);
                     display_row += 1;
                 }
             }
@@ -735,15 +1249,53 @@ fn format_lineno(lineno: Option<u32>, width: usize) -> String {
     }
 }
 
+/// `+N -M` counts and an additions/deletions proportion bar for a hunk header.
+fn hunk_stat_spans<'a>(
+    hunk: &Hunk,
+    bar_width: usize,
+    bg: Option<Color>,
+    theme: &Theme,
+) -> Vec<Span<'a>> {
+    let total = hunk.additions + hunk.deletions;
+    if total == 0 {
+        return Vec::new();
+    }
+    let style = |fg: Color| {
+        let style = Style::default().fg(fg);
+        match bg {
+            Some(bg) => style.bg(bg),
+            None => style,
+        }
+    };
+    let mut spans = vec![
+        Span::styled(format!("  +{}", hunk.additions), style(theme.success)),
+        Span::styled(format!(" -{}", hunk.deletions), style(theme.error)),
+    ];
+    if bar_width > 0 {
+        let filled = (hunk.additions * bar_width + total / 2) / total;
+        spans.push(Span::styled(" ", style(theme.text_muted)));
+        spans.push(Span::styled(
+            "\u{2588}".repeat(filled),
+            style(theme.success),
+        ));
+        spans.push(Span::styled(
+            "\u{2591}".repeat(bar_width - filled),
+            style(theme.error),
+        ));
+    }
+    spans
+}
+
 /// Build a hunk header line for unified view.
 fn make_hunk_header_line_unified<'a>(
     gutter_width: usize,
-    header: &str,
+    hunk: &Hunk,
+    bar_width: usize,
     hl: RowHighlight,
-    ann_marker: bool,
+    ann_marker: &str,
     theme: &Theme,
 ) -> Line<'a> {
-    let marker = if ann_marker { "\u{2502}" } else { " " };
+    let marker = ann_marker;
     let gutter_text = format!("{:>gutter_width$}{marker}", "...");
     let mut gutter_style = Style::default().fg(theme.text_muted);
     if let Some(fg) = hl.gutter_fg {
@@ -752,18 +1304,87 @@ fn make_hunk_header_line_unified<'a>(
     if let Some(bg) = hl.gutter_bg {
         gutter_style = gutter_style.bg(bg);
     }
+    if hl.reversed {
+        gutter_style = gutter_style.add_modifier(Modifier::REVERSED);
+    }
     let mut content_style = Style::default()
         .fg(theme.diff_hunk_header_fg)
         .add_modifier(Modifier::BOLD);
     if let Some(bg) = hl.content_bg {
         content_style = content_style.bg(bg);
     }
-    Line::from(vec![
+    let mut spans = vec![
         Span::styled(gutter_text, gutter_style),
-        Span::styled(header.to_string(), content_style),
+        Span::styled(hunk.header.clone(), content_style),
+    ];
+    spans.extend(hunk_stat_spans(hunk, bar_width, hl.content_bg, theme));
+    Line::from(spans)
+}
+
+fn split_header_styles(hl: RowHighlight, theme: &Theme) -> (Style, Style) {
+    let mut gutter_style = Style::default().fg(theme.text_muted);
+    if let Some(fg) = hl.gutter_fg {
+        gutter_style = gutter_style.fg(fg);
+    }
+    if let Some(bg) = hl.gutter_bg {
+        gutter_style = gutter_style.bg(bg);
+    }
+    if hl.reversed {
+        gutter_style = gutter_style.add_modifier(Modifier::REVERSED);
+    }
+    let mut content_style = Style::default()
+        .fg(theme.diff_hunk_header_fg)
+        .add_modifier(Modifier::ITALIC);
+    if let Some(bg) = hl.content_bg {
+        content_style = content_style.bg(bg);
+    }
+    (gutter_style, content_style)
+}
+
+/// Header for a user split (`X`); italic and without stats to set it apart
+/// from real git hunks.
+fn make_split_header_line_unified<'a>(
+    gutter_width: usize,
+    text: String,
+    hl: RowHighlight,
+    theme: &Theme,
+) -> Line<'a> {
+    let (gutter_style, content_style) = split_header_styles(hl, theme);
+    Line::from(vec![
+        Span::styled(format!("{:>gutter_width$} ", "..."), gutter_style),
+        Span::styled(text, content_style),
     ])
 }
 
+/// Emit the split-view rows for any user split headers at `display_row`.
+fn push_split_headers_split<'a>(
+    (left, center, right): (&mut Vec<Line<'a>>, &mut Vec<Line<'a>>, &mut Vec<Line<'a>>),
+    hunk: &Hunk,
+    state: &AppState,
+    display_map: &[DisplayRowInfo],
+    display_row: &mut usize,
+    theme: &Theme,
+) {
+    let gutter_width = 5;
+    while let Some(line_index) = display_map
+        .get(*display_row)
+        .and_then(|info| info.split_line)
+    {
+        let hl = row_highlight(state, *display_row);
+        let (gutter_style, content_style) = split_header_styles(hl, theme);
+        center.push(Line::from(Span::styled(
+            format!("{:>gutter_width$} {:>gutter_width$} ", "...", "..."),
+            gutter_style,
+        )));
+        left.push(Line::from(Span::styled(
+            split_header_text(hunk, line_index),
+            content_style,
+        )));
+        right.push(Line::from(Span::styled("", content_style)));
+        *display_row += 1;
+    }
+}
+
 /// Apply highlight spans to a string, blending with diff background.
 fn apply_highlights<'a>(
     text: &str,
@@ -838,9 +1459,54 @@ fn make_center_gutter_line<'a>(
     if let Some(bg) = hl.gutter_bg {
         style = style.bg(bg);
     }
+    if hl.reversed {
+        style = style.add_modifier(Modifier::REVERSED);
+    }
     Line::from(Span::styled(text, style))
 }
 
+/// Origin markers for accessible mode, so additions and deletions don't rely
+/// on the background color alone.
+const ADDED_MARK: &str = "\u{25b6}";
+const REMOVED_MARK: &str = "\u{25c0}";
+
+/// The one-character origin column: `+` / `-`, or the accessible markers.
+fn origin_prefix(origin: &DiffLineOrigin, accessible: bool) -> &'static str {
+    match (origin, accessible) {
+        (DiffLineOrigin::Addition, false) => "+",
+        (DiffLineOrigin::Deletion, false) => "-",
+        (DiffLineOrigin::Addition, true) => ADDED_MARK,
+        (DiffLineOrigin::Deletion, true) => REMOVED_MARK,
+        (DiffLineOrigin::Context, _) => " ",
+    }
+}
+
+/// In accessible mode, prefix a split-view content line with its origin
+/// marker (a space for context) in the line's diff colors.
+fn push_origin_mark(
+    line: &mut Line<'_>,
+    origin: &DiffLineOrigin,
+    diff_bg: Option<Color>,
+    hl: RowHighlight,
+    state: &AppState,
+) {
+    if !state.accessible {
+        return;
+    }
+    let theme = &state.theme;
+    let fg = match origin {
+        DiffLineOrigin::Addition => theme.diff_add_fg,
+        DiffLineOrigin::Deletion => theme.diff_del_fg,
+        DiffLineOrigin::Context => theme.text_muted,
+    };
+    let mut style = Style::default().fg(fg);
+    if let Some(bg) = hl.content_bg.or(diff_bg) {
+        style = style.bg(bg);
+    }
+    line.spans
+        .insert(0, Span::styled(origin_prefix(origin, true), style));
+}
+
 /// Build a content-only line (no gutter) with syntax highlighting and diff background.
 fn make_content_only_line<'a>(
     content: &str,
@@ -878,6 +1544,107 @@ fn make_content_only_line<'a>(
     Line::from(content_spans)
 }
 
+/// Set `bg` on the parts of a content line inside the given byte ranges,
+/// splitting spans at the range edges. `ranges` must be sorted.
+fn emphasize_ranges(line: &mut Line<'_>, ranges: &[(usize, usize)], bg: Color) {
+    if ranges.is_empty() {
+        return;
+    }
+    let mut spans = Vec::with_capacity(line.spans.len() + ranges.len() * 2);
+    let mut pos = 0;
+    for span in line.spans.drain(..) {
+        let text = span.content.as_ref();
+        let (start, end) = (pos, pos + text.len());
+        pos = end;
+        let mut edges: Vec<usize> = ranges
+            .iter()
+            .flat_map(|&(s, e)| [s, e])
+            .filter(|&edge| edge > start && edge < end)
+            .collect();
+        edges.push(end);
+        let mut cut = start;
+        for edge in edges {
+            let changed = ranges.iter().any(|&(s, e)| s <= cut && cut < e);
+            let style = if changed {
+                span.style.bg(bg)
+            } else {
+                span.style
+            };
+            spans.push(Span::styled(
+                text[cut - start..edge - start].to_string(),
+                style,
+            ));
+            cut = edge;
+        }
+    }
+    line.spans = spans;
+}
+
+/// Give the part of a content line past `max_len` characters a warning
+/// background, like `apply_highlights` splitting spans where needed. The first
+/// `skip_spans` spans (gutter, origin prefix) and rows showing the cursor or
+/// selection colours are left alone.
+fn overlay_line_length_warning(
+    line: &mut Line<'_>,
+    skip_spans: usize,
+    max_len: Option<usize>,
+    hl: RowHighlight,
+    theme: &Theme,
+) {
+    let Some(max_len) = max_len else {
+        return;
+    };
+    if hl.content_bg.is_some() {
+        return;
+    }
+    let mut col = 0;
+    let mut spans = Vec::with_capacity(line.spans.len() + 1);
+    for (idx, span) in line.spans.drain(..).enumerate() {
+        let len = span.content.chars().count();
+        if idx < skip_spans || col + len <= max_len {
+            if idx >= skip_spans {
+                col += len;
+            }
+            spans.push(span);
+            continue;
+        }
+        let split = span
+            .content
+            .char_indices()
+            .nth(max_len.saturating_sub(col))
+            .map_or(span.content.len(), |(byte, _)| byte);
+        col += len;
+        if split > 0 {
+            spans.push(Span::styled(span.content[..split].to_string(), span.style));
+        }
+        spans.push(Span::styled(
+            span.content[split..].to_string(),
+            span.style.fg(theme.surface).bg(theme.warning),
+        ));
+    }
+    line.spans = spans;
+}
+
+/// Restyle a conflict marker row with a warning background and bold text,
+/// leaving the first `gutter_spans` spans and rows the cursor or selection colours.
+fn style_conflict_marker(
+    line: &mut Line<'_>,
+    gutter_spans: usize,
+    hl: RowHighlight,
+    theme: &Theme,
+) {
+    if hl.content_bg.is_some() {
+        return;
+    }
+    for span in line.spans.iter_mut().skip(gutter_spans) {
+        span.style = span
+            .style
+            .fg(theme.surface)
+            .bg(theme.warning)
+            .add_modifier(Modifier::BOLD);
+    }
+}
+
 /// Build an empty content-only line (no gutter) for split view filler.
 fn make_empty_content_line<'a>(hl: RowHighlight, theme: &Theme) -> Line<'a> {
     let mut style = Style::default().fg(theme.text_muted).bg(theme.collapsed_bg);
@@ -896,15 +1663,15 @@ fn make_unified_highlighted<'a>(
     hl_spans: Option<&Vec<HighlightSpan>>,
     diff_bg: Option<Color>,
     hl: RowHighlight,
-    ann_marker: bool,
+    ann_marker: &str,
     theme: &Theme,
 ) -> Line<'a> {
     let trimmed = content.trim_end_matches('\n');
     let content_bg = hl.content_bg.or(diff_bg);
 
-    let marker = if ann_marker { "\u{2502}" } else { " " };
+    let marker = ann_marker;
     let mut gutter_style = Style::default().fg(theme.text_muted);
-    if ann_marker {
+    if ann_marker != " " {
         gutter_style = gutter_style.fg(theme.cursor_line_fg);
     }
     if let Some(fg) = hl.gutter_fg {
@@ -913,13 +1680,16 @@ fn make_unified_highlighted<'a>(
     if let Some(bg) = hl.gutter_bg {
         gutter_style = gutter_style.bg(bg);
     }
+    if hl.reversed {
+        gutter_style = gutter_style.add_modifier(Modifier::REVERSED);
+    }
     let gutter_span = Span::styled(format!("{old_g} {new_g}{marker}"), gutter_style);
 
     let prefix_style = match prefix {
-        "+" => Style::default()
+        "+" | ADDED_MARK => Style::default()
             .fg(theme.diff_add_fg)
             .bg(content_bg.unwrap_or_default()),
-        "-" => Style::default()
+        "-" | REMOVED_MARK => Style::default()
             .fg(theme.diff_del_fg)
             .bg(content_bg.unwrap_or_default()),
         _ => {
@@ -966,6 +1736,9 @@ fn make_collapsed_indicator_line_unified<'a>(
     if let Some(bg) = hl.gutter_bg {
         gutter_style = gutter_style.bg(bg);
     }
+    if hl.reversed {
+        gutter_style = gutter_style.add_modifier(Modifier::REVERSED);
+    }
     let mut content_style = Style::default().fg(theme.text_muted);
     if let Some(bg) = hl.content_bg {
         content_style = content_style.bg(bg);
@@ -989,6 +1762,7 @@ fn wrap_split_lines_synchronized_with_scroll<'a>(
     left_lines: Vec<Line<'a>>,
     center_lines: Vec<Line<'a>>,
     right_lines: Vec<Line<'a>>,
+    comments: InlineComments<'a>,
     config: &WrapConfig<'_>,
     start_visual: usize,
     height: usize,
@@ -996,11 +1770,13 @@ fn wrap_split_lines_synchronized_with_scroll<'a>(
     if height == 0 {
         return (Vec::new(), Vec::new(), Vec::new());
     }
-    if !config.wrap_enabled || config.width == 0 {
+    // One visual line per row: skip straight to the viewport
+    if (!config.wrap_enabled || config.width == 0) && comments.iter().all(Vec::is_empty) {
         let left_visible: Vec<Line> = left_lines
             .into_iter()
             .skip(start_visual)
             .take(height)
+            .map(|line| clip_line_for_display(line, config))
             .collect();
         let center_visible: Vec<Line> = center_lines
             .into_iter()
@@ -1011,6 +1787,7 @@ fn wrap_split_lines_synchronized_with_scroll<'a>(
             .into_iter()
             .skip(start_visual)
             .take(height)
+            .map(|line| clip_line_for_display(line, config))
             .collect();
         return (left_visible, center_visible, right_visible);
     }
@@ -1021,6 +1798,7 @@ fn wrap_split_lines_synchronized_with_scroll<'a>(
     let mut center_result: Vec<Line<'a>> = Vec::new();
     let mut right_result: Vec<Line<'a>> = Vec::new();
 
+    let mut comments = comments.into_iter();
     let iter = left_lines
         .into_iter()
         .zip(center_lines)
@@ -1028,10 +1806,26 @@ fn wrap_split_lines_synchronized_with_scroll<'a>(
         .map(|((l, c), r)| (l, c, r));
 
     for (left_line, center_line, right_line) in iter {
-        let left_wrapped = wrap_single_line_for_display(left_line, config);
-        let right_wrapped = wrap_single_line_for_display(right_line, config);
+        let mut left_wrapped = wrap_single_line_for_display(left_line, config);
+        let mut right_wrapped = wrap_single_line_for_display(right_line, config);
         // Center gutter is never wrapped (fixed width)
 
+        let row_comments = comments.next().unwrap_or_default();
+        if !row_comments.is_empty() {
+            let base = left_wrapped.len().max(right_wrapped.len());
+            left_wrapped.resize(base, Line::default());
+            right_wrapped.resize(base, Line::default());
+            for (old_side, line) in row_comments {
+                let (own, other) = if old_side {
+                    (&mut left_wrapped, &mut right_wrapped)
+                } else {
+                    (&mut right_wrapped, &mut left_wrapped)
+                };
+                own.push(line);
+                other.push(Line::default());
+            }
+        }
+
         let max_height = left_wrapped.len().max(right_wrapped.len());
 
         if remaining_skip >= max_height {
@@ -1072,6 +1866,7 @@ fn wrap_split_lines_synchronized_with_scroll<'a>(
 
 fn wrap_lines_for_display_with_scroll<'a>(
     lines: Vec<Line<'a>>,
+    comments: InlineComments<'a>,
     config: &WrapConfig<'_>,
     start_visual: usize,
     height: usize,
@@ -1084,8 +1879,11 @@ fn wrap_lines_for_display_with_scroll<'a>(
     let mut remaining_height = height;
     let mut result: Vec<Line<'a>> = Vec::new();
 
+    let mut comments = comments.into_iter();
     for line in lines {
-        let wrapped = wrap_single_line_for_display(line, config);
+        let mut wrapped = wrap_single_line_for_display(line, config);
+        let row_comments = comments.next().unwrap_or_default();
+        wrapped.extend(row_comments.into_iter().map(|(_, line)| line));
         if remaining_skip >= wrapped.len() {
             remaining_skip -= wrapped.len();
             continue;
@@ -1112,10 +1910,68 @@ fn wrap_lines_for_display_with_scroll<'a>(
     result
 }
 
+/// Split a line into (char, style) pairs, keeping the first span aside as the gutter when
+/// `gutter_width > 0`.
+fn flatten_line<'a>(line: Line<'a>, has_gutter: bool) -> (Option<Span<'a>>, Vec<(char, Style)>) {
+    let mut spans_iter = line.spans.into_iter();
+    let gutter = if has_gutter { spans_iter.next() } else { None };
+    let mut chars: Vec<(char, Style)> = Vec::new();
+    for span in spans_iter {
+        let style = span.style;
+        for ch in span.content.chars() {
+            chars.push((ch, style));
+        }
+    }
+    (gutter, chars)
+}
+
+/// Rebuild spans from (char, style) pairs, coalescing adjacent chars with the same style.
+fn coalesce_chars<'a>(chars: &[(char, Style)]) -> Vec<Span<'a>> {
+    let mut spans: Vec<Span<'a>> = Vec::new();
+    let Some(&(_, first_style)) = chars.first() else {
+        return spans;
+    };
+    let mut current_text = String::new();
+    let mut current_style = first_style;
+    for &(ch, style) in chars {
+        if style != current_style && !current_text.is_empty() {
+            spans.push(Span::styled(
+                std::mem::take(&mut current_text),
+                current_style,
+            ));
+        }
+        current_style = style;
+        current_text.push(ch);
+    }
+    if !current_text.is_empty() {
+        spans.push(Span::styled(current_text, current_style));
+    }
+    spans
+}
+
+/// Horizontal-scroll mode: drop the first `scroll_x` content columns and truncate the rest
+/// to the viewport. The gutter (if any) stays fixed.
+fn clip_line_for_display<'a>(line: Line<'a>, config: &WrapConfig<'_>) -> Line<'a> {
+    if config.width == 0 {
+        return line;
+    }
+    let content_width = (config.width as usize).saturating_sub(config.gutter_width);
+    let (gutter, chars) = flatten_line(line, config.gutter_width > 0);
+    let start = config.scroll_x.min(chars.len());
+    let end = (start + content_width).min(chars.len());
+
+    let mut spans: Vec<Span<'a>> = gutter.into_iter().collect();
+    spans.extend(coalesce_chars(&chars[start..end]));
+    Line::from(spans)
+}
+
 fn wrap_single_line_for_display<'a>(line: Line<'a>, config: &WrapConfig<'_>) -> Vec<Line<'a>> {
-    if !config.wrap_enabled || config.width == 0 {
+    if config.width == 0 {
         return vec![line];
     }
+    if !config.wrap_enabled {
+        return vec![clip_line_for_display(line, config)];
+    }
     let max_width = config.width as usize;
     let content_width = max_width.saturating_sub(config.gutter_width);
     if content_width == 0 {
@@ -1261,6 +2117,7 @@ pub(crate) fn compute_split_visual_row_metrics(
         DiffViewMode::Split,
         state.diff.display_context,
         &state.diff.gap_expansions,
+        &state.diff.hunk_splits,
     );
     let (left_lines, _center_lines, right_lines) = build_split_lines_core(
         delta,
@@ -1273,23 +2130,27 @@ pub(crate) fn compute_split_visual_row_metrics(
     let left_config = WrapConfig {
         width: left_width,
         gutter_width: 0,
-        wrap_enabled: true,
+        wrap_enabled: state.diff.line_wrap,
+        scroll_x: state.diff.scroll_x,
         theme: &state.theme,
     };
     let right_config = WrapConfig {
         width: right_width,
         gutter_width: 0,
-        wrap_enabled: true,
+        wrap_enabled: state.diff.line_wrap,
+        scroll_x: state.diff.scroll_x,
         theme: &state.theme,
     };
+    let comments = inline_comments(state, delta, &display_map);
     let mut row_offsets = Vec::with_capacity(left_lines.len());
     let mut row_heights = Vec::with_capacity(left_lines.len());
     let mut total_rows = 0;
 
-    for (left, right) in left_lines.into_iter().zip(right_lines.into_iter()) {
+    for (i, (left, right)) in left_lines.into_iter().zip(right_lines).enumerate() {
         let left_height = wrap_single_line_for_display(left, &left_config).len();
         let right_height = wrap_single_line_for_display(right, &right_config).len();
-        let row_height = left_height.max(right_height).max(1);
+        let comment_lines = comments.get(i).map_or(0, Vec::len);
+        let row_height = left_height.max(right_height).max(1) + comment_lines;
         row_offsets.push(total_rows);
         row_heights.push(row_height);
         total_rows += row_height;
@@ -1312,6 +2173,7 @@ pub(crate) fn compute_unified_visual_row_metrics(
         DiffViewMode::Unified,
         state.diff.display_context,
         &state.diff.gap_expansions,
+        &state.diff.hunk_splits,
     );
     let lines = build_unified_lines_core(
         delta,
@@ -1324,15 +2186,18 @@ pub(crate) fn compute_unified_visual_row_metrics(
     let config = WrapConfig {
         width,
         gutter_width: 5 + 1 + 5 + 1 + 1,
-        wrap_enabled: true,
+        wrap_enabled: state.diff.line_wrap,
+        scroll_x: state.diff.scroll_x,
         theme: &state.theme,
     };
+    let comments = inline_comments(state, delta, &display_map);
     let mut row_offsets = Vec::with_capacity(lines.len());
     let mut row_heights = Vec::with_capacity(lines.len());
     let mut total_rows = 0;
 
-    for line in lines {
-        let row_height = wrap_single_line_for_display(line, &config).len().max(1);
+    for (i, line) in lines.into_iter().enumerate() {
+        let comment_lines = comments.get(i).map_or(0, Vec::len);
+        let row_height = wrap_single_line_for_display(line, &config).len().max(1) + comment_lines;
         row_offsets.push(total_rows);
         row_heights.push(row_height);
         total_rows += row_height;
@@ -1347,10 +2212,15 @@ pub(crate) fn compute_unified_visual_row_metrics(
 
 #[cfg(test)]
 mod tests {
-    use super::{compute_split_visual_row_metrics, compute_unified_visual_row_metrics};
+    use super::{
+        compute_split_visual_row_metrics, compute_unified_visual_row_metrics, format_title,
+        hunk_stat_spans, minimap_cells, overlay_line_length_warning, wrap_single_line_for_display,
+        MinimapCell, RowHighlight, WrapConfig,
+    };
     use crate::git::types::{DiffLine, DiffLineOrigin, FileDelta, FileStatus, Hunk};
     use crate::state::{AppState, DiffOptions};
     use crate::theme::Theme;
+    use ratatui::text::{Line, Span};
     use std::path::PathBuf;
 
     fn make_delta(lines: Vec<DiffLine>) -> FileDelta {
@@ -1361,13 +2231,32 @@ mod tests {
             hunks: vec![Hunk {
                 header: "@@ -1,1 +1,1 @@".to_string(),
                 lines,
+                additions: 0,
+                deletions: 0,
             }],
             additions: 0,
             deletions: 0,
             binary: false,
+            has_conflicts: false,
+            mode: 0o100644,
         }
     }
 
+    #[test]
+    fn long_titles_keep_the_end_of_the_path() {
+        let state = AppState::new(DiffOptions::new(false, false), Theme::from_name("one-dark"));
+        let mut delta = make_delta(Vec::new());
+        assert_eq!(
+            format_title(&delta, "Split", &state, 80),
+            " src/lib.rs [Split] "
+        );
+
+        delta.path = PathBuf::from("src/deeply/nested/module/subdirectory/component.rs");
+        let title = format_title(&delta, "Split", &state, 36);
+        assert_eq!(title, " \u{2026}bdirectory/component.rs [Split] ");
+        assert_eq!(title.chars().count(), 34);
+    }
+
     #[test]
     fn split_metrics_use_max_wrap_height() {
         let mut state = AppState::new(DiffOptions::new(false, false), Theme::from_name("one-dark"));
@@ -1425,4 +2314,96 @@ mod tests {
             metrics.row_heights.iter().sum::<usize>()
         );
     }
+
+    #[test]
+    fn warns_past_max_line_length() {
+        let theme = Theme::from_name("one-dark");
+        let mut line = Line::from(vec![
+            Span::raw("    1 "),
+            Span::raw("+"),
+            Span::raw("let "),
+            Span::raw("x = 12345;"),
+        ]);
+        overlay_line_length_warning(&mut line, 2, Some(8), RowHighlight::default(), &theme);
+        let texts: Vec<&str> = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(texts, ["    1 ", "+", "let ", "x = ", "12345;"]);
+        assert_eq!(line.spans[4].style.bg, Some(theme.warning));
+        assert_eq!(line.spans[3].style.bg, None);
+
+        let mut short = Line::from(Span::raw("short"));
+        overlay_line_length_warning(&mut short, 0, Some(8), RowHighlight::default(), &theme);
+        assert_eq!(short.spans.len(), 1);
+    }
+
+    #[test]
+    fn clip_shifts_content_and_keeps_gutter() {
+        let theme = Theme::from_name("one-dark");
+        let config = WrapConfig {
+            width: 8,
+            gutter_width: 3,
+            wrap_enabled: false,
+            scroll_x: 2,
+            theme: &theme,
+        };
+        let line = Line::from(vec![Span::raw("12 "), Span::raw("abcdefghij")]);
+
+        let wrapped = wrap_single_line_for_display(line, &config);
+
+        assert_eq!(wrapped.len(), 1);
+        let text: String = wrapped[0]
+            .spans
+            .iter()
+            .map(|s| s.content.as_ref())
+            .collect();
+        assert_eq!(text, "12 cdefg");
+    }
+
+    #[test]
+    fn minimap_scales_rows_and_prioritises_cursor() {
+        // 8 rows onto 4 cells: two rows per cell
+        let cells = minimap_cells(8, 4, 6, |row| match row {
+            1 => MinimapCell::Annotated,
+            2 | 3 => MinimapCell::Reviewed,
+            7 => MinimapCell::Annotated,
+            _ => MinimapCell::Plain,
+        });
+        assert_eq!(
+            cells,
+            [
+                MinimapCell::Annotated,
+                MinimapCell::Reviewed,
+                MinimapCell::Plain,
+                MinimapCell::Cursor,
+            ]
+        );
+
+        // Fewer rows than cells stretches each row over several cells
+        let cells = minimap_cells(2, 4, 0, |_| MinimapCell::Plain);
+        assert_eq!(cells[..2], [MinimapCell::Cursor, MinimapCell::Cursor]);
+        assert_eq!(cells[2..], [MinimapCell::Plain, MinimapCell::Plain]);
+    }
+
+    #[test]
+    fn hunk_stats_show_counts_and_proportion_bar() {
+        let mut delta = make_delta(Vec::new());
+        let hunk = &mut delta.hunks[0];
+        hunk.additions = 3;
+        hunk.deletions = 1;
+        let theme = Theme::from_name("one-dark");
+
+        let text: String = hunk_stat_spans(hunk, 8, None, &theme)
+            .iter()
+            .map(|s| s.content.as_ref())
+            .collect();
+        assert_eq!(
+            text,
+            "  +3 -1 \u{2588}\u{2588}\u{2588}\u{2588}\u{2588}\u{2588}\u{2591}\u{2591}"
+        );
+
+        let text: String = hunk_stat_spans(hunk, 0, None, &theme)
+            .iter()
+            .map(|s| s.content.as_ref())
+            .collect();
+        assert_eq!(text, "  +3 -1");
+    }
 }
@@ -0,0 +1,95 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState},
+    Frame,
+};
+
+use crate::state::AppState;
+
+/// Total height of the file log panel, borders included.
+pub const FILE_LOG_HEIGHT: u16 = 6;
+
+pub fn render_file_log(frame: &mut Frame, area: Rect, state: &AppState) {
+    let theme = &state.theme;
+    let path = state
+        .diff
+        .selected_file
+        .and_then(|idx| state.diff.deltas.get(idx))
+        .map(|d| d.path.display().to_string())
+        .unwrap_or_default();
+
+    let block = Block::default()
+        .title(format!(" Log: {path} "))
+        .title_bottom(Line::from(vec![
+            Span::styled(
+                " [Enter]",
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                "compare against commit  ",
+                Style::default().fg(theme.text_muted),
+            ),
+            Span::styled(
+                "[R]",
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("revert  ", Style::default().fg(theme.text_muted)),
+            Span::styled(
+                "[Esc/L]",
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("close ", Style::default().fg(theme.text_muted)),
+        ]))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.text_muted));
+
+    if state.file_log.is_empty() {
+        let empty = List::new([ListItem::new(Span::styled(
+            " No commits touch this file",
+            Style::default().fg(theme.text_muted),
+        ))])
+        .block(block);
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let items: Vec<ListItem> = state
+        .file_log
+        .iter()
+        .map(|commit| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!(" {:.7} ", commit.oid),
+                    Style::default().fg(theme.warning),
+                ),
+                Span::styled(
+                    format!("{} ", commit.date),
+                    Style::default().fg(theme.text_muted),
+                ),
+                Span::styled(
+                    format!("{} ", commit.author),
+                    Style::default().fg(theme.secondary),
+                ),
+                Span::styled(commit.subject.as_str(), Style::default().fg(theme.text)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items).block(block).highlight_style(
+        Style::default()
+            .bg(theme.selection_bg)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let mut list_state = ListState::default();
+    list_state.select(Some(state.file_log_selected));
+    frame.render_stateful_widget(list, area, &mut list_state);
+}
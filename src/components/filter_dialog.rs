@@ -0,0 +1,122 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::text_input::render_text_input;
+use crate::state::{AppState, TextBuffer};
+
+/// A centered box around one text input, shared by the single-field prompts.
+pub struct InputDialog<'a> {
+    pub title: &'a str,
+    pub width: u16,
+    pub border: Color,
+    /// Lines above the input, such as the current value.
+    pub above: Vec<Line<'a>>,
+    /// Lines between the input and the hint, such as completions.
+    pub below: Vec<Line<'a>>,
+    pub hint: Option<&'a str>,
+    /// `(key, label)` pairs listed on the last row.
+    pub keys: &'a [(&'a str, &'a str)],
+}
+
+/// Render `dialog` with `input` as its editable line. The box is as tall as
+/// its rows, up to the frame height.
+pub fn render_input_dialog(
+    frame: &mut Frame,
+    state: &AppState,
+    dialog: InputDialog,
+    input: &TextBuffer,
+) {
+    let theme = &state.theme;
+    let area = frame.area();
+    let hint_rows = u16::from(dialog.hint.is_some());
+    let body_rows = dialog.above.len() as u16 + 1 + dialog.below.len() as u16;
+    let dialog_width = dialog.width.min(area.width.saturating_sub(4));
+    let dialog_height = (body_rows + 2 + hint_rows + 2).min(area.height.saturating_sub(4));
+
+    let x = (area.width.saturating_sub(dialog_width)) / 2;
+    let y = (area.height.saturating_sub(dialog_height)) / 2;
+
+    let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .title(dialog.title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(dialog.border));
+
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(dialog.above.len() as u16),
+            Constraint::Min(1), // input line (wraps if needed)
+            Constraint::Length(dialog.below.len() as u16),
+            Constraint::Length(1), // blank
+            Constraint::Length(hint_rows),
+            Constraint::Length(1), // key hints
+        ])
+        .split(inner);
+
+    frame.render_widget(Paragraph::new(dialog.above), rows[0]);
+    render_text_input(
+        frame,
+        rows[1],
+        input.text(),
+        input.cursor_char_index(),
+        Style::default().fg(theme.text),
+    );
+    frame.render_widget(Paragraph::new(dialog.below), rows[2]);
+
+    if let Some(hint) = dialog.hint {
+        let hint = Paragraph::new(Line::from(Span::styled(
+            format!(" {hint}"),
+            Style::default().fg(theme.text_muted),
+        )));
+        frame.render_widget(hint, rows[4]);
+    }
+
+    let key_style = Style::default()
+        .fg(theme.accent)
+        .add_modifier(Modifier::BOLD);
+    let mut hints = vec![Span::raw(" ")];
+    for (i, (key, label)) in dialog.keys.iter().enumerate() {
+        let sep = if i + 1 < dialog.keys.len() { "  " } else { "" };
+        hints.push(Span::styled(format!("[{key}]"), key_style));
+        hints.push(Span::styled(
+            format!("{label}{sep}"),
+            Style::default().fg(theme.text_muted),
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(hints)), rows[5]);
+}
+
+pub fn render_filter_dialog(frame: &mut Frame, state: &AppState) {
+    let theme = &state.theme;
+    let current = Line::from(vec![
+        Span::styled(" current: ", Style::default().fg(theme.text_muted)),
+        Span::styled(
+            state.navigator.filter_glob.as_deref().unwrap_or("none"),
+            Style::default()
+                .fg(theme.success)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ]);
+    let dialog = InputDialog {
+        title: " Filter Files ",
+        width: 60,
+        border: theme.success,
+        above: vec![current, Line::default()],
+        below: Vec::new(),
+        hint: Some("glob pattern, e.g. *.rs or src/**; empty to clear"),
+        keys: &[("Enter", "apply"), ("Esc", "clear filter")],
+    };
+    render_input_dialog(frame, state, dialog, &state.filter_dialog_input);
+}
@@ -0,0 +1,105 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::git::patch::format_patch_for_hunk;
+use crate::state::AppState;
+
+pub fn render_hunk_patch_confirm(frame: &mut Frame, state: &AppState) {
+    let Some(req) = &state.hunk_patch_confirm else {
+        return;
+    };
+    let theme = &state.theme;
+    let area = frame.area();
+    let dialog_width = 56.min(area.width.saturating_sub(4));
+    let dialog_height = 7.min(area.height.saturating_sub(4));
+
+    let x = (area.width.saturating_sub(dialog_width)) / 2;
+    let y = (area.height.saturating_sub(dialog_height)) / 2;
+
+    let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+    frame.render_widget(Clear, dialog_area);
+
+    let (title, warning, border_color) = if req.reverse {
+        (
+            " Confirm Revert Hunk ",
+            " This will discard this hunk's changes from:",
+            theme.error,
+        )
+    } else {
+        (
+            " Confirm Apply Hunk ",
+            " This will apply this hunk to the index for:",
+            theme.accent,
+        )
+    };
+
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color));
+
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // warning text
+            Constraint::Length(1), // file path + hunk header
+            Constraint::Length(1), // blank
+            Constraint::Length(1), // key hints
+        ])
+        .split(inner);
+
+    let warning = Line::from(vec![Span::styled(
+        warning,
+        Style::default().fg(theme.warning),
+    )]);
+    frame.render_widget(Paragraph::new(warning), rows[0]);
+
+    let delta = state.diff.deltas.get(req.file_index);
+    let file_name = delta
+        .map(|d| format!(" {}", d.path.display()))
+        .unwrap_or_default();
+    // The recomputed header of the block's own patch, not the full-file hunk
+    let header = delta
+        .and_then(|d| format_patch_for_hunk(d, req.hunk_index, req.lines.clone()))
+        .and_then(|p| {
+            p.lines()
+                .find(|l| l.starts_with("@@"))
+                .map(|h| format!("  {h}"))
+        })
+        .unwrap_or_default();
+    let path_line = Line::from(vec![
+        Span::styled(
+            file_name,
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(header, Style::default().fg(theme.diff_hunk_header_fg)),
+    ]);
+    frame.render_widget(Paragraph::new(path_line), rows[1]);
+
+    let hints = Line::from(vec![
+        Span::styled(
+            " [Enter/y]",
+            Style::default()
+                .fg(border_color)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("confirm  ", Style::default().fg(theme.text_muted)),
+        Span::styled(
+            "[Esc/n]",
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("cancel", Style::default().fg(theme.text_muted)),
+    ]);
+    frame.render_widget(Paragraph::new(hints), rows[3]);
+}
@@ -0,0 +1,18 @@
+use ratatui::Frame;
+
+use super::filter_dialog::{render_input_dialog, InputDialog};
+use crate::state::AppState;
+
+pub fn render_jump_hunk_dialog(frame: &mut Frame, state: &AppState, total_hunks: usize) {
+    let title = format!(" Jump to Hunk (1-{total_hunks}) ");
+    let dialog = InputDialog {
+        title: &title,
+        width: 36,
+        border: state.theme.accent,
+        above: Vec::new(),
+        below: Vec::new(),
+        hint: None,
+        keys: &[("Enter", "jump"), ("Esc", "cancel")],
+    };
+    render_input_dialog(frame, state, dialog, &state.jump_hunk_input);
+}
@@ -6,17 +6,31 @@ pub mod checklist_panel;
 pub mod comment_editor;
 pub mod commit_dialog;
 pub mod context_bar;
+pub mod context_menu;
 pub mod diff_view;
 pub mod feedback_summary;
+pub mod file_log;
+pub mod filter_dialog;
 pub mod global_search_bar;
+pub mod hunk_patch_confirm;
+pub mod jump_hunk_dialog;
 pub mod navigator;
+pub mod patch_summary;
 pub mod prompt_preview;
+pub mod push_dialog;
+pub mod rebase_dialog;
+pub mod replace_dialog;
 pub mod restore_confirm;
+pub mod revert_confirm;
+pub mod review_summary;
+pub mod session_dialog;
 pub mod settings_modal;
 pub mod target_dialog;
 pub mod text_input;
 pub mod which_key;
 pub mod worktree_browser;
+pub mod worktree_dialog;
+pub mod worktree_remove_confirm;
 
 use ratatui::{layout::Rect, Frame};
 
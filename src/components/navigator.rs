@@ -1,3 +1,5 @@
+use std::cell::Cell;
+
 use ratatui::{
     layout::Rect,
     style::{Modifier, Style},
@@ -11,7 +13,78 @@ use crate::state::{app_state::FocusPanel, AppState};
 
 use super::Component;
 
-pub struct Navigator;
+#[derive(Default)]
+pub struct Navigator {
+    /// Screen rect of each rendered file row and the delta it shows, refreshed
+    /// on every render so mouse clicks resolve against what is on screen.
+    hit_map: Cell<Vec<(Rect, usize)>>,
+}
+
+impl Navigator {
+    /// Delta index of the file row at the given screen position, if any.
+    pub fn hit_test(&self, col: u16, row: u16) -> Option<usize> {
+        let map = self.hit_map.take();
+        let hit = map
+            .iter()
+            .find(|(rect, _)| {
+                rect.x <= col
+                    && col < rect.x + rect.width
+                    && rect.y <= row
+                    && row < rect.y + rect.height
+            })
+            .map(|&(_, delta_index)| delta_index);
+        self.hit_map.set(map);
+        hit
+    }
+}
+
+/// Rows above the file list reserved for the change summary.
+pub(crate) const SUMMARY_ROWS: u16 = 1;
+
+/// Width of the add/delete ratio bar in the summary row.
+const SUMMARY_BAR_WIDTH: usize = 10;
+
+/// One-line summary: file count, total additions/deletions, and a ratio bar.
+fn summary_line<'a>(state: &AppState, width: usize) -> Line<'a> {
+    let theme = &state.theme;
+    if state.diff.loading {
+        return Line::from(Span::styled(
+            " Loading\u{2026}",
+            Style::default().fg(theme.text_muted),
+        ));
+    }
+
+    let files = state.diff.deltas.len();
+    let additions: usize = state.diff.deltas.iter().map(|d| d.additions).sum();
+    let deletions: usize = state.diff.deltas.iter().map(|d| d.deletions).sum();
+    let noun = if files == 1 { "file" } else { "files" };
+
+    let files_text = format!(" {files} {noun} ");
+    let add_text = format!("+{additions}");
+    let del_text = format!(" -{deletions}");
+    let mut spans = vec![
+        Span::styled(files_text.clone(), Style::default().fg(theme.text)),
+        Span::styled(add_text.clone(), Style::default().fg(theme.success)),
+        Span::styled(del_text.clone(), Style::default().fg(theme.error)),
+    ];
+
+    let used = files_text.chars().count() + add_text.len() + del_text.len();
+    let total = additions + deletions;
+    if total > 0 && used + 1 + SUMMARY_BAR_WIDTH <= width {
+        let add_cells = ((additions * SUMMARY_BAR_WIDTH) as f64 / total as f64).round() as usize;
+        spans.push(Span::raw(" "));
+        spans.push(Span::styled(
+            "\u{2588}".repeat(add_cells),
+            Style::default().fg(theme.success),
+        ));
+        spans.push(Span::styled(
+            "\u{2591}".repeat(SUMMARY_BAR_WIDTH - add_cells),
+            Style::default().fg(theme.error),
+        ));
+    }
+
+    Line::from(spans)
+}
 
 impl Component for Navigator {
     fn render(&self, frame: &mut Frame, area: Rect, state: &AppState) {
@@ -32,7 +105,10 @@ impl Component for Navigator {
             let ci = state.navigator.search_query.cursor_char_index();
             let before: String = q.chars().take(ci).collect();
             let after: String = q.chars().skip(ci).collect();
-            format!(" /{}\u{2588}{} ", before, after)
+            match state.navigator.search_terms().len() {
+                n @ 2.. => format!(" /{}\u{2588}{} ({n} terms) ", before, after),
+                _ => format!(" /{}\u{2588}{} ", before, after),
+            }
         } else {
             format!(" Files ({total}) ")
         };
@@ -42,23 +118,36 @@ impl Component for Navigator {
             .borders(Borders::ALL)
             .border_style(border_style);
 
+        // Available width for the display text after prefix ("▶ ") and review icon ("✓ ")
+        let inner_width = area.width.saturating_sub(2) as usize; // block borders
+        let summary = summary_line(state, inner_width);
+
         if visible.is_empty() {
-            let msg = if state.navigator.search_active {
-                " No matches"
+            let (msg, color) = if state.navigator.search_active {
+                (" No matches", theme.text_muted)
+            } else if state.diff.loading {
+                ("", theme.text_muted)
+            } else if state.diff.load_error.is_some() {
+                (" (Error loading diff)", theme.error)
             } else {
-                " No changes"
+                (" No changes", theme.text_muted)
             };
-            let paragraph = Paragraph::new(msg)
-                .style(Style::default().fg(theme.text_muted))
-                .block(block);
+            let lines = vec![
+                summary,
+                Line::from(Span::styled(msg, Style::default().fg(color))),
+            ];
+            let paragraph = Paragraph::new(lines).block(block);
             frame.render_widget(paragraph, area);
+            self.hit_map.set(Vec::new());
             return;
         }
 
-        let inner_height = area.height.saturating_sub(2) as usize;
-        // Available width for the display text after prefix ("▶ ") and review icon ("✓ ")
-        let inner_width = area.width.saturating_sub(2) as usize; // block borders
-        let prefix_width = 5; // "▶ " (3) + "✓ " (2, icon is 1 char + space)
+        let inner_height = area.height.saturating_sub(2 + SUMMARY_ROWS) as usize;
+        let show_marks = !state.navigator.marked.is_empty();
+        let mut prefix_width = 5; // "▶ " (3) + "✓ " (2, icon is 1 char + space)
+        if show_marks {
+            prefix_width += 2; // mark column "✓ "
+        }
         let max_display_width = inner_width.saturating_sub(prefix_width);
         let selected = state.navigator.selected;
 
@@ -68,12 +157,12 @@ impl Component for Navigator {
             0
         };
 
-        let lines: Vec<Line> = visible
+        let mut lines: Vec<Line> = visible
             .iter()
             .enumerate()
             .skip(scroll)
             .take(inner_height)
-            .map(|(vis_idx, (_entry_idx, entry))| {
+            .map(|(vis_idx, (entry_idx, entry))| {
                 let is_selected = vis_idx == selected;
                 let is_active = state.diff.selected_file == Some(entry.delta_index);
 
@@ -86,11 +175,26 @@ impl Component for Navigator {
                     Style::default()
                         .fg(theme.text)
                         .bg(theme.selection_inactive_bg)
+                } else if state.navigator.collapsed.contains(&entry.delta_index) {
+                    Style::default().fg(theme.text_muted)
                 } else {
-                    Style::default().fg(theme.text)
+                    let heat = state.diff.deltas.get(entry.delta_index).and_then(|d| {
+                        theme.heat_bg(d.additions, d.deletions, state.diff.max_changes)
+                    });
+                    match heat {
+                        Some(bg) => Style::default().fg(theme.text).bg(bg),
+                        None => Style::default().fg(theme.text),
+                    }
                 };
 
-                let prefix = if is_selected { "\u{25b6}" } else { " " };
+                let collapsed = state.navigator.collapsed.contains(&entry.delta_index);
+                let prefix = if is_selected {
+                    "\u{25b6}"
+                } else if collapsed {
+                    "\u{25b8}"
+                } else {
+                    " "
+                };
 
                 // Review status icon
                 let review_status = state.review.status(&entry.path);
@@ -98,18 +202,81 @@ impl Component for Navigator {
                     FileReviewStatus::Reviewed => ("\u{2713}", theme.success), // ✓
                     FileReviewStatus::Unreviewed => ("\u{25cb}", theme.text_muted), // ○
                     FileReviewStatus::ChangedSinceReview => ("\u{25cf}", theme.warning), // ●
-                    FileReviewStatus::New => ("\u{2605}", theme.accent),       // ★
+                    FileReviewStatus::PartiallyChanged { .. } => ("~", theme.warning),
+                    FileReviewStatus::New => ("\u{2605}", theme.accent), // ★
                 };
 
-                let display = middle_ellipsis(&entry.display, max_display_width);
+                let conflicted = state
+                    .diff
+                    .deltas
+                    .get(entry.delta_index)
+                    .is_some_and(|d| d.has_conflicts);
+                // ⚡ is double width
+                let display_width = if conflicted {
+                    max_display_width.saturating_sub(2)
+                } else {
+                    max_display_width
+                };
+                let display = middle_ellipsis(&entry.display, display_width);
 
-                Line::from(vec![
-                    Span::styled(format!("{prefix} "), style),
-                    Span::styled(format!("{review_icon} "), Style::default().fg(review_color)),
-                    Span::styled(display, style),
-                ])
+                let mut spans = vec![Span::styled(format!("{prefix} "), style)];
+                if show_marks {
+                    let mark = if state.navigator.marked.contains(&entry.delta_index) {
+                        "\u{2713} "
+                    } else {
+                        "  "
+                    };
+                    spans.push(Span::styled(
+                        mark,
+                        Style::default()
+                            .fg(theme.secondary)
+                            .add_modifier(Modifier::BOLD),
+                    ));
+                }
+                spans.push(Span::styled(
+                    format!("{review_icon} "),
+                    Style::default().fg(review_color),
+                ));
+                if conflicted {
+                    spans.push(Span::styled("\u{26a1}", Style::default().fg(theme.warning)));
+                }
+                match state.navigator.match_positions.get(entry_idx) {
+                    Some(positions) => {
+                        let len = entry.display.chars().count();
+                        let matched: Vec<usize> = positions
+                            .iter()
+                            .filter_map(|&p| ellipsis_position(p, len, display_width))
+                            .collect();
+                        let mut match_style = style.fg(theme.accent).add_modifier(Modifier::BOLD);
+                        if is_selected {
+                            match_style = match_style.add_modifier(Modifier::UNDERLINED);
+                        }
+                        spans.extend(highlight_chars(&display, &matched, style, match_style));
+                    }
+                    None => spans.push(Span::styled(display, style)),
+                }
+                Line::from(spans)
             })
             .collect();
+        lines.insert(0, summary);
+
+        let list_top = area.y + 1 + SUMMARY_ROWS;
+        let hit_map = visible
+            .iter()
+            .skip(scroll)
+            .take(inner_height)
+            .enumerate()
+            .map(|(row, (_, entry))| {
+                let rect = Rect::new(
+                    area.x + 1,
+                    list_top + row as u16,
+                    area.width.saturating_sub(2),
+                    1,
+                );
+                (rect, entry.delta_index)
+            })
+            .collect();
+        self.hit_map.set(hit_map);
 
         let scroll_info = if total > inner_height {
             format!(" {}/{} ", selected + 1, total)
@@ -123,6 +290,53 @@ impl Component for Navigator {
     }
 }
 
+/// Where char `pos` of a `len`-char string lands after [`middle_ellipsis`]
+/// to `max_chars`, or `None` if it was cut.
+fn ellipsis_position(pos: usize, len: usize, max_chars: usize) -> Option<usize> {
+    if len <= max_chars {
+        return Some(pos);
+    }
+    if max_chars < 3 {
+        return None;
+    }
+    let keep = max_chars - 1;
+    let head = keep / 2;
+    let tail = keep - head;
+    if pos < head {
+        Some(pos)
+    } else if pos >= len - tail {
+        Some(pos - (len - tail) + head + 1)
+    } else {
+        None
+    }
+}
+
+/// Split `text` into spans, using `matched_style` for the chars at `matched`.
+fn highlight_chars<'a>(
+    text: &str,
+    matched: &[usize],
+    style: Style,
+    matched_style: Style,
+) -> Vec<Span<'a>> {
+    let mut spans = Vec::new();
+    let mut run = String::new();
+    let mut run_matched = false;
+    for (i, c) in text.chars().enumerate() {
+        let is_match = matched.contains(&i);
+        if is_match != run_matched && !run.is_empty() {
+            let run_style = if run_matched { matched_style } else { style };
+            spans.push(Span::styled(std::mem::take(&mut run), run_style));
+        }
+        run_matched = is_match;
+        run.push(c);
+    }
+    if !run.is_empty() {
+        let run_style = if run_matched { matched_style } else { style };
+        spans.push(Span::styled(run, run_style));
+    }
+    spans
+}
+
 fn middle_ellipsis(s: &str, max_chars: usize) -> String {
     let len = s.chars().count();
     if len <= max_chars {
@@ -148,7 +362,23 @@ fn middle_ellipsis(s: &str, max_chars: usize) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::middle_ellipsis;
+    use super::{ellipsis_position, middle_ellipsis, Navigator};
+    use ratatui::layout::Rect;
+
+    #[test]
+    fn hit_test_resolves_rendered_rows() {
+        let nav = Navigator::default();
+        nav.hit_map.set(vec![
+            (Rect::new(1, 3, 20, 1), 7),
+            (Rect::new(1, 4, 20, 1), 2),
+        ]);
+        assert_eq!(nav.hit_test(5, 4), Some(2));
+        assert_eq!(nav.hit_test(1, 3), Some(7));
+        assert_eq!(nav.hit_test(21, 3), None);
+        assert_eq!(nav.hit_test(5, 5), None);
+        // Lookups leave the map in place
+        assert_eq!(nav.hit_test(5, 4), Some(2));
+    }
 
     #[test]
     fn returns_original_when_short_enough() {
@@ -164,6 +394,17 @@ mod tests {
         assert_eq!(middle_ellipsis("abcdef", 3), "a…f");
     }
 
+    #[test]
+    fn ellipsis_position_tracks_kept_chars() {
+        let text = "abcdefghij";
+        let out: Vec<char> = middle_ellipsis(text, 6).chars().collect();
+        assert_eq!(out, ['a', 'b', '…', 'h', 'i', 'j']);
+        assert_eq!(ellipsis_position(1, 10, 6), Some(1));
+        assert_eq!(ellipsis_position(4, 10, 6), None);
+        assert_eq!(ellipsis_position(7, 10, 6), Some(3));
+        assert_eq!(ellipsis_position(9, 10, 6), Some(5));
+    }
+
     #[test]
     fn truncates_with_middle_ellipsis_and_tail_bias() {
         let out = middle_ellipsis("src/components/navigator.rs [M] +12 -4", 20);
@@ -0,0 +1,71 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::state::app_state::PatchSummary;
+use crate::state::AppState;
+
+pub fn render_patch_summary(frame: &mut Frame, state: &AppState, summary: &PatchSummary) {
+    let theme = &state.theme;
+    let area = frame.area();
+    let dialog_width = 56.min(area.width.saturating_sub(4));
+    let dialog_height = 6.min(area.height.saturating_sub(4));
+
+    let x = (area.width.saturating_sub(dialog_width)) / 2;
+    let y = (area.height.saturating_sub(dialog_height)) / 2;
+
+    let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .title(" Staged vs Unstaged ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent));
+
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // file path
+            Constraint::Length(1), // counts
+            Constraint::Length(1), // blank
+            Constraint::Length(1), // key hint
+        ])
+        .split(inner);
+
+    let path_line = Line::from(vec![Span::styled(
+        format!(" {}", summary.path),
+        Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+    )]);
+    frame.render_widget(Paragraph::new(path_line), rows[0]);
+
+    let muted = Style::default().fg(theme.text_muted);
+    let counts = Line::from(vec![
+        Span::styled(" Modified lines: ", muted),
+        Span::styled(
+            (summary.staged + summary.unstaged).to_string(),
+            Style::default().fg(theme.text),
+        ),
+        Span::styled("  |  Staged: ", muted),
+        Span::styled(
+            summary.staged.to_string(),
+            Style::default().fg(theme.success),
+        ),
+        Span::styled("  |  Unstaged: ", muted),
+        Span::styled(
+            summary.unstaged.to_string(),
+            Style::default().fg(theme.warning),
+        ),
+    ]);
+    frame.render_widget(Paragraph::new(counts), rows[1]);
+
+    let hint = Line::from(vec![Span::styled(" Press any key to close", muted)]);
+    frame.render_widget(Paragraph::new(hint), rows[3]);
+}
@@ -1,21 +1,26 @@
 use ratatui::{
     layout::Rect,
-    style::Style,
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Wrap},
     Frame,
 };
 
-use crate::state::AppState;
+use crate::state::{app_state::FocusPanel, AppState};
 
 /// Render the prompt preview pane showing the rendered template.
 pub fn render_prompt_preview(frame: &mut Frame, area: Rect, state: &AppState) {
     let theme = &state.theme;
+    let border_color = if state.focus == FocusPanel::PromptPreview {
+        theme.accent
+    } else {
+        theme.secondary
+    };
 
     let block = Block::default()
-        .title(" Prompt Preview ")
+        .title(preview_title(state))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(theme.secondary));
+        .border_style(Style::default().fg(border_color));
 
     if state.prompt_preview_text.is_empty() {
         let msg = Paragraph::new(" Select lines and press [y] to generate a prompt")
@@ -25,11 +30,16 @@ pub fn render_prompt_preview(frame: &mut Frame, area: Rect, state: &AppState) {
         return;
     }
 
+    let current_match = state
+        .preview_search_match_index
+        .and_then(|idx| state.preview_search_matches.get(idx));
     let lines: Vec<Line> = state
         .prompt_preview_text
         .lines()
-        .map(|l| {
-            let style = if l.starts_with('+') {
+        .enumerate()
+        .skip(state.preview_scroll_offset)
+        .map(|(idx, l)| {
+            let mut style = if l.starts_with('+') {
                 Style::default().fg(theme.diff_add_fg)
             } else if l.starts_with('-') {
                 Style::default().fg(theme.diff_del_fg)
@@ -38,6 +48,12 @@ pub fn render_prompt_preview(frame: &mut Frame, area: Rect, state: &AppState) {
             } else {
                 Style::default().fg(theme.text)
             };
+            if state.preview_search_matches.binary_search(&idx).is_ok() {
+                style = style.bg(theme.search_match_bg);
+                if current_match == Some(&idx) {
+                    style = style.add_modifier(Modifier::BOLD);
+                }
+            }
             Line::from(Span::styled(format!(" {l}"), style))
         })
         .collect();
@@ -47,3 +63,30 @@ pub fn render_prompt_preview(frame: &mut Frame, area: Rect, state: &AppState) {
         .wrap(Wrap { trim: false });
     frame.render_widget(paragraph, area);
 }
+
+/// Pane title, with the search query and match position while searching.
+fn preview_title(state: &AppState) -> String {
+    let query = &state.preview_search_query;
+    if !state.preview_search_active && query.is_empty() {
+        return " Prompt Preview ".to_string();
+    }
+    let match_info = if state.preview_search_matches.is_empty() {
+        if query.is_empty() {
+            String::new()
+        } else {
+            " (no matches)".to_string()
+        }
+    } else {
+        let idx = state.preview_search_match_index.map_or(0, |i| i + 1);
+        format!(" ({}/{})", idx, state.preview_search_matches.len())
+    };
+    let cursor = if state.preview_search_active {
+        "\u{2588}"
+    } else {
+        ""
+    };
+    let ci = query.cursor_char_index();
+    let before: String = query.text().chars().take(ci).collect();
+    let after: String = query.text().chars().skip(ci).collect();
+    format!(" Prompt Preview /{before}{cursor}{after}{match_info} ")
+}
@@ -0,0 +1,17 @@
+use ratatui::Frame;
+
+use super::filter_dialog::{render_input_dialog, InputDialog};
+use crate::state::AppState;
+
+pub fn render_push_dialog(frame: &mut Frame, state: &AppState) {
+    let dialog = InputDialog {
+        title: " Push: Set Upstream ",
+        width: 50,
+        border: state.theme.accent,
+        above: Vec::new(),
+        below: Vec::new(),
+        hint: Some("<remote> <branch>, pushed with git push -u"),
+        keys: &[("Enter", "push"), ("Esc", "cancel")],
+    };
+    render_input_dialog(frame, state, dialog, &state.push_dialog_input);
+}
@@ -0,0 +1,17 @@
+use ratatui::Frame;
+
+use super::filter_dialog::{render_input_dialog, InputDialog};
+use crate::state::AppState;
+
+pub fn render_rebase_dialog(frame: &mut Frame, state: &AppState) {
+    let dialog = InputDialog {
+        title: " Rebase -i HEAD~N: number of commits ",
+        width: 44,
+        border: state.theme.accent,
+        above: Vec::new(),
+        below: Vec::new(),
+        hint: None,
+        keys: &[("Enter", "rebase"), ("Esc", "cancel")],
+    };
+    render_input_dialog(frame, state, dialog, &state.rebase_dialog_input);
+}
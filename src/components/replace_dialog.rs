@@ -0,0 +1,132 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::filter_dialog::{render_input_dialog, InputDialog};
+use crate::state::{AppState, TextBuffer};
+
+fn centered(frame: &Frame, width: u16, height: u16) -> Rect {
+    let area = frame.area();
+    let width = width.min(area.width.saturating_sub(4));
+    let height = height.min(area.height.saturating_sub(4));
+    let x = (area.width.saturating_sub(width)) / 2;
+    let y = (area.height.saturating_sub(height)) / 2;
+    Rect::new(x, y, width, height)
+}
+
+pub fn render_replace_dialog(frame: &mut Frame, state: &AppState) {
+    let theme = &state.theme;
+    let label = |text, focused: bool| {
+        let style = if focused {
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text_muted)
+        };
+        Line::from(Span::styled(text, style))
+    };
+    let value = |buf: &TextBuffer| {
+        Line::from(Span::styled(
+            format!(" {}", buf.text()),
+            Style::default().fg(theme.text),
+        ))
+    };
+    let search_label = label(" search:", !state.replace_editing_with);
+    let with_label = label(" replace with:", state.replace_editing_with);
+    // The field being edited is the dialog's input; the other is shown as text
+    let (above, below, input) = if state.replace_editing_with {
+        (
+            vec![search_label, value(&state.replace_search), with_label],
+            Vec::new(),
+            &state.replace_with,
+        )
+    } else {
+        (
+            vec![search_label],
+            vec![with_label, value(&state.replace_with)],
+            &state.replace_search,
+        )
+    };
+    let dialog = InputDialog {
+        title: " Replace in Diff ",
+        width: 60,
+        border: theme.warning,
+        above,
+        below,
+        hint: Some("applies to added lines in every file of the diff"),
+        keys: &[
+            ("Enter", "preview"),
+            ("Tab", "switch field"),
+            ("Esc", "cancel"),
+        ],
+    };
+    render_input_dialog(frame, state, dialog, input);
+}
+
+pub fn render_replace_confirm(frame: &mut Frame, state: &AppState) {
+    let Some(plan) = &state.replace_confirm else {
+        return;
+    };
+    let theme = &state.theme;
+    let dialog_area = centered(frame, 56, 7);
+
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .title(" Confirm Replace ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.warning));
+
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // summary
+            Constraint::Length(1), // search -> replace
+            Constraint::Length(1), // blank
+            Constraint::Length(1), // key hints
+        ])
+        .split(inner);
+
+    let summary = Line::from(vec![Span::styled(
+        format!(
+            " Replace {} occurrence(s) in {} file(s) in the working tree:",
+            plan.substitutions, plan.files
+        ),
+        Style::default().fg(theme.warning),
+    )]);
+    frame.render_widget(Paragraph::new(summary), rows[0]);
+
+    let bold = Style::default().fg(theme.text).add_modifier(Modifier::BOLD);
+    let change = Line::from(vec![
+        Span::styled(format!(" {}", plan.search), bold),
+        Span::styled(" → ", Style::default().fg(theme.text_muted)),
+        Span::styled(plan.replace.clone(), bold),
+    ]);
+    frame.render_widget(Paragraph::new(change), rows[1]);
+
+    let hints = Line::from(vec![
+        Span::styled(
+            " [Enter/y]",
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("apply  ", Style::default().fg(theme.text_muted)),
+        Span::styled(
+            "[Esc/n]",
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("cancel", Style::default().fg(theme.text_muted)),
+    ]);
+    frame.render_widget(Paragraph::new(hints), rows[3]);
+}
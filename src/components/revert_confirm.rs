@@ -0,0 +1,78 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::git::types::CommitSummary;
+use crate::state::AppState;
+
+pub fn render_revert_confirm(frame: &mut Frame, state: &AppState, commit: &CommitSummary) {
+    let theme = &state.theme;
+    let area = frame.area();
+    let dialog_width = 60.min(area.width.saturating_sub(4));
+    let dialog_height = 7.min(area.height.saturating_sub(4));
+
+    let x = (area.width.saturating_sub(dialog_width)) / 2;
+    let y = (area.height.saturating_sub(dialog_height)) / 2;
+
+    let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .title(" Confirm Revert ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.warning));
+
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // warning text
+            Constraint::Length(1), // commit
+            Constraint::Length(1), // blank
+            Constraint::Length(1), // key hints
+        ])
+        .split(inner);
+
+    let warning = Line::from(vec![Span::styled(
+        " Revert into the working tree (git revert --no-commit):",
+        Style::default().fg(theme.warning),
+    )]);
+    frame.render_widget(Paragraph::new(warning), rows[0]);
+
+    let commit_line = Line::from(vec![
+        Span::styled(
+            format!(" {:.7} ", commit.oid),
+            Style::default().fg(theme.accent),
+        ),
+        Span::styled(
+            commit.subject.as_str(),
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+        ),
+    ]);
+    frame.render_widget(Paragraph::new(commit_line), rows[1]);
+
+    let hints = Line::from(vec![
+        Span::styled(
+            " [Enter/y]",
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("revert  ", Style::default().fg(theme.text_muted)),
+        Span::styled(
+            "[Esc/n]",
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("cancel", Style::default().fg(theme.text_muted)),
+    ]);
+    frame.render_widget(Paragraph::new(hints), rows[3]);
+}
@@ -0,0 +1,219 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::state::annotation_state::Annotation;
+use crate::state::{AnnotationState, AppState};
+
+use super::Component;
+
+/// Maximum length of the comment preview shown for each annotation.
+const PREVIEW_LEN: usize = 80;
+
+/// One selectable row of the review summary: a file header or an annotation under it.
+pub enum ReviewSummaryRow<'a> {
+    File { path: &'a str, count: usize },
+    Annotation(&'a Annotation),
+}
+
+impl ReviewSummaryRow<'_> {
+    pub fn file_path(&self) -> &str {
+        match self {
+            Self::File { path, .. } => path,
+            Self::Annotation(ann) => &ann.anchor.file_path,
+        }
+    }
+}
+
+/// Rows for every annotated file, in path order, each followed by its
+/// annotations sorted by line.
+pub fn review_summary_rows(annotations: &AnnotationState) -> Vec<ReviewSummaryRow<'_>> {
+    let mut rows = Vec::new();
+    for (path, anns) in &annotations.annotations {
+        if anns.is_empty() {
+            continue;
+        }
+        rows.push(ReviewSummaryRow::File {
+            path,
+            count: anns.len(),
+        });
+        let mut sorted: Vec<&Annotation> = anns.iter().collect();
+        sorted.sort_by_key(|a| a.anchor.sort_line());
+        rows.extend(sorted.into_iter().map(ReviewSummaryRow::Annotation));
+    }
+    rows
+}
+
+/// First line of the comment, truncated to `PREVIEW_LEN` characters.
+fn comment_preview(comment: &str) -> String {
+    let first = comment.lines().next().unwrap_or("").trim();
+    let more = comment.lines().nth(1).is_some();
+    if first.chars().count() > PREVIEW_LEN {
+        let cut: String = first.chars().take(PREVIEW_LEN - 1).collect();
+        format!("{cut}…")
+    } else if more {
+        format!("{first} …")
+    } else {
+        first.to_string()
+    }
+}
+
+fn line_label(ann: &Annotation) -> String {
+    if ann.unanchored {
+        return "unanchored".to_string();
+    }
+    match (ann.anchor.old_range, ann.anchor.new_range) {
+        (_, Some((s, e))) if s == e => format!("L{s}"),
+        (_, Some((s, e))) => format!("L{s}-{e}"),
+        (Some((s, e)), None) if s == e => format!("L{s} (old)"),
+        (Some((s, e)), None) => format!("L{s}-{e} (old)"),
+        (None, None) => "L?".to_string(),
+    }
+}
+
+pub struct ReviewSummary;
+
+impl Component for ReviewSummary {
+    fn render(&self, frame: &mut Frame, area: Rect, state: &AppState) {
+        let theme = &state.theme;
+        let key_style = Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD);
+        let block = Block::default()
+            .title(format!(
+                " Review Summary: {} annotations in {} files ",
+                state.annotations.count(),
+                state.annotations.files_with_annotations()
+            ))
+            .title_bottom(Line::from(vec![
+                Span::styled(" [Enter]", key_style),
+                Span::styled("jump  ", Style::default().fg(theme.text_muted)),
+                Span::styled("[d]", key_style),
+                Span::styled(
+                    "delete file's annotations  ",
+                    Style::default().fg(theme.text_muted),
+                ),
+                Span::styled("[e]", key_style),
+                Span::styled(
+                    "copy review checklist  ",
+                    Style::default().fg(theme.text_muted),
+                ),
+                Span::styled("[Esc/A]", key_style),
+                Span::styled("close ", Style::default().fg(theme.text_muted)),
+            ]))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent));
+
+        let rows = review_summary_rows(&state.annotations);
+        if rows.is_empty() {
+            let empty = Paragraph::new(vec![
+                Line::from(""),
+                Line::from(Span::styled(
+                    "  No annotations yet",
+                    Style::default()
+                        .fg(theme.text_muted)
+                        .add_modifier(Modifier::ITALIC),
+                )),
+            ])
+            .block(block);
+            frame.render_widget(empty, area);
+            return;
+        }
+
+        let items: Vec<ListItem> = rows
+            .iter()
+            .map(|row| match row {
+                ReviewSummaryRow::File { path, count } => ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!(
+                            " {}",
+                            if path.is_empty() {
+                                "(pull request)"
+                            } else {
+                                path
+                            }
+                        ),
+                        Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!("  ({count})"),
+                        Style::default().fg(theme.text_muted),
+                    ),
+                ])),
+                ReviewSummaryRow::Annotation(ann) => ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("   {:<12}", line_label(ann)),
+                        Style::default().fg(theme.secondary),
+                    ),
+                    Span::styled(
+                        comment_preview(&ann.comment),
+                        Style::default().fg(theme.text_muted),
+                    ),
+                ])),
+            })
+            .collect();
+
+        let list = List::new(items).block(block).highlight_style(
+            Style::default()
+                .bg(theme.selection_bg)
+                .add_modifier(Modifier::BOLD),
+        );
+
+        let mut list_state = ListState::default();
+        list_state.select(Some(state.review_summary_selected.min(rows.len() - 1)));
+        frame.render_stateful_widget(list, area, &mut list_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{comment_preview, review_summary_rows, ReviewSummaryRow};
+    use crate::state::annotation_state::{Annotation, LineAnchor};
+    use crate::state::AnnotationState;
+
+    fn annotation(path: &str, line: u32, comment: &str) -> Annotation {
+        Annotation {
+            anchor: LineAnchor::new(path.to_string(), None, Some((line, line))),
+            comment: comment.to_string(),
+            created_at: String::new(),
+            tags: Vec::new(),
+            unanchored: false,
+        }
+    }
+
+    #[test]
+    fn groups_annotations_under_file_headers() {
+        let mut state = AnnotationState::default();
+        state.add(annotation("src/b.rs", 9, "later"));
+        state.add(annotation("src/b.rs", 2, "earlier"));
+        state.add(annotation("src/a.rs", 5, "only"));
+
+        let rows = review_summary_rows(&state);
+        let shape: Vec<String> = rows
+            .iter()
+            .map(|row| match row {
+                ReviewSummaryRow::File { path, count } => format!("{path}:{count}"),
+                ReviewSummaryRow::Annotation(ann) => ann.comment.clone(),
+            })
+            .collect();
+        assert_eq!(
+            shape,
+            ["src/a.rs:1", "only", "src/b.rs:2", "earlier", "later"]
+        );
+        assert_eq!(rows[3].file_path(), "src/b.rs");
+    }
+
+    #[test]
+    fn preview_truncates_long_and_multiline_comments() {
+        assert_eq!(comment_preview("short"), "short");
+        assert_eq!(comment_preview("first\nsecond"), "first …");
+        let long = "x".repeat(100);
+        let preview = comment_preview(&long);
+        assert_eq!(preview.chars().count(), 80);
+        assert!(preview.ends_with('…'));
+    }
+}
@@ -0,0 +1,101 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::filter_dialog::{render_input_dialog, InputDialog};
+use crate::state::app_state::SessionDialogMode;
+use crate::state::AppState;
+
+fn centered(frame: &Frame, width: u16, height: u16) -> Rect {
+    let area = frame.area();
+    let width = width.min(area.width.saturating_sub(4));
+    let height = height.min(area.height.saturating_sub(4));
+    let x = (area.width.saturating_sub(width)) / 2;
+    let y = (area.height.saturating_sub(height)) / 2;
+    Rect::new(x, y, width, height)
+}
+
+/// Path prompt for exporting or importing a `.mdiffsession` file.
+pub fn render_session_dialog(frame: &mut Frame, state: &AppState, mode: SessionDialogMode) {
+    let (title, action) = match mode {
+        SessionDialogMode::Export => (" Export Session ", "export"),
+        SessionDialogMode::Import => (" Import Session ", "import"),
+    };
+    let dialog = InputDialog {
+        title,
+        width: 60,
+        border: state.theme.accent,
+        above: Vec::new(),
+        below: Vec::new(),
+        hint: Some("file path; relative paths start at the repository"),
+        keys: &[("Enter", action), ("Esc", "cancel")],
+    };
+    render_input_dialog(frame, state, dialog, &state.session_dialog_input);
+}
+
+/// Asks how to merge imported annotations that overlap existing ones.
+pub fn render_session_import_confirm(frame: &mut Frame, state: &AppState) {
+    let Some(pending) = &state.session_import else {
+        return;
+    };
+    let theme = &state.theme;
+    let dialog_area = centered(frame, 60, 7);
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .title(" Import Conflicts ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.warning));
+
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // summary
+            Constraint::Length(1), // question
+            Constraint::Length(1), // blank
+            Constraint::Length(1), // key hints
+        ])
+        .split(inner);
+
+    let noun = if pending.conflicts == 1 {
+        "annotation is"
+    } else {
+        "annotations are"
+    };
+    let summary = Line::from(Span::styled(
+        format!(
+            " {} imported {noun} on lines you already annotated.",
+            pending.conflicts
+        ),
+        Style::default().fg(theme.warning),
+    ));
+    frame.render_widget(Paragraph::new(summary), rows[0]);
+    let question = Line::from(Span::styled(
+        " Which should be kept?",
+        Style::default().fg(theme.text),
+    ));
+    frame.render_widget(Paragraph::new(question), rows[1]);
+
+    let key_style = Style::default()
+        .fg(theme.accent)
+        .add_modifier(Modifier::BOLD);
+    let muted = Style::default().fg(theme.text_muted);
+    let hints = Line::from(vec![
+        Span::styled(" [m]", key_style),
+        Span::styled("mine  ", muted),
+        Span::styled("[t]", key_style),
+        Span::styled("theirs  ", muted),
+        Span::styled("[b/Enter]", key_style),
+        Span::styled("both  ", muted),
+        Span::styled("[Esc]", key_style),
+        Span::styled("cancel", muted),
+    ]);
+    frame.render_widget(Paragraph::new(hints), rows[3]);
+}
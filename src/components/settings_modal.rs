@@ -89,6 +89,46 @@ pub fn render_settings_modal(frame: &mut Frame, state: &AppState) {
         theme,
     );
 
+    // Row 4: Lines quoted around annotations in prompts
+    let padding_value = format!("< {} >", state.context_padding);
+    render_setting_row(
+        frame,
+        rows[4],
+        "Prompt Padding",
+        &padding_value,
+        selected == 4,
+        theme,
+    );
+
+    // Row 5: Export Format
+    let export_value = format!("< {} >", state.export_format.label());
+    render_setting_row(
+        frame,
+        rows[5],
+        "Export Format",
+        &export_value,
+        selected == 5,
+        theme,
+    );
+
+    // Rows 6-7: Session file export/import
+    render_setting_row(
+        frame,
+        rows[6],
+        "Export Session",
+        "[Enter]",
+        selected == 6,
+        theme,
+    );
+    render_setting_row(
+        frame,
+        rows[7],
+        "Import Session",
+        "[Enter]",
+        selected == 7,
+        theme,
+    );
+
     // Hints
     let hints = Line::from(vec![
         Span::styled(
@@ -105,6 +145,13 @@ pub fn render_settings_modal(frame: &mut Frame, state: &AppState) {
                 .add_modifier(Modifier::BOLD),
         ),
         Span::styled("change ", Style::default().fg(theme.text_muted)),
+        Span::styled(
+            "[Enter]",
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("run ", Style::default().fg(theme.text_muted)),
         Span::styled(
             "[Esc]",
             Style::default()
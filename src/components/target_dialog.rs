@@ -1,48 +1,37 @@
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph},
     Frame,
 };
 
-use super::text_input::render_text_input;
+use super::filter_dialog::{render_input_dialog, InputDialog};
 use crate::state::AppState;
 
-pub fn render_target_dialog(frame: &mut Frame, state: &AppState) {
-    let theme = &state.theme;
-    let area = frame.area();
-    let dialog_width = 60.min(area.width.saturating_sub(4));
-    let dialog_height = 9.min(area.height.saturating_sub(4));
-
-    let x = (area.width.saturating_sub(dialog_width)) / 2;
-    let y = (area.height.saturating_sub(dialog_height)) / 2;
-
-    let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
-
-    frame.render_widget(Clear, dialog_area);
-
-    let block = Block::default()
-        .title(" Compare Against ")
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(theme.success));
+/// Most completions listed under the input.
+const MAX_COMPLETIONS: usize = 5;
 
-    let inner = block.inner(dialog_area);
-    frame.render_widget(block, dialog_area);
+/// Branches starting with what's being typed: the whole input, or the part
+/// after `..` when typing the end of a range.
+pub fn branch_completions<'a>(branches: &'a [String], input: &str) -> Vec<&'a str> {
+    let prefix = input.rsplit("..").next().unwrap_or(input).trim_start();
+    if prefix.is_empty() {
+        return Vec::new();
+    }
+    branches
+        .iter()
+        .map(String::as_str)
+        .filter(|b| b.starts_with(prefix) && *b != prefix)
+        .take(MAX_COMPLETIONS)
+        .collect()
+}
 
-    let rows = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(1), // current target
-            Constraint::Length(1), // blank
-            Constraint::Min(1),    // input line (wraps if needed)
-            Constraint::Length(1), // blank
-            Constraint::Length(1), // hint text
-            Constraint::Length(1), // key hints
-        ])
-        .split(inner);
+pub fn render_target_dialog(frame: &mut Frame, state: &AppState) {
+    let theme = &state.theme;
+    let completions = branch_completions(
+        &state.target_dialog_branches,
+        state.target_dialog_input.text(),
+    );
 
-    // Current target display
     let current = Line::from(vec![
         Span::styled(" current: ", Style::default().fg(theme.text_muted)),
         Span::styled(
@@ -52,40 +41,57 @@ pub fn render_target_dialog(frame: &mut Frame, state: &AppState) {
                 .add_modifier(Modifier::BOLD),
         ),
     ]);
-    frame.render_widget(Paragraph::new(current), rows[0]);
+    let completion_lines: Vec<Line> = completions
+        .iter()
+        .enumerate()
+        .map(|(i, branch)| {
+            let (marker, color) = if i == 0 {
+                ("\u{21e5} ", theme.accent)
+            } else {
+                ("  ", theme.text_muted)
+            };
+            Line::from(vec![
+                Span::styled(format!(" {marker}"), Style::default().fg(theme.text_muted)),
+                Span::styled(*branch, Style::default().fg(color)),
+            ])
+        })
+        .collect();
 
-    // Input with wrapping
-    render_text_input(
-        frame,
-        rows[2],
-        state.target_dialog_input.text(),
-        state.target_dialog_input.cursor_char_index(),
-        Style::default().fg(theme.text),
-    );
+    let dialog = InputDialog {
+        title: if state.target_dialog_new_tab {
+            " New Tab: Compare Against "
+        } else {
+            " Compare Against "
+        },
+        width: 60,
+        border: theme.success,
+        above: vec![current, Line::default()],
+        below: completion_lines,
+        hint: Some("branch, tag, commit, A..B range, or empty for HEAD"),
+        keys: &[("Enter", "confirm"), ("Tab", "complete"), ("Esc", "cancel")],
+    };
+    render_input_dialog(frame, state, dialog, &state.target_dialog_input);
+}
 
-    // Hint
-    let hint = Paragraph::new(Line::from(vec![Span::styled(
-        " branch, tag, commit, or empty for HEAD",
-        Style::default().fg(theme.text_muted),
-    )]));
-    frame.render_widget(hint, rows[4]);
+#[cfg(test)]
+mod tests {
+    use super::branch_completions;
 
-    // Key hints
-    let hints = Line::from(vec![
-        Span::styled(
-            " [Enter]",
-            Style::default()
-                .fg(theme.accent)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Span::styled("confirm  ", Style::default().fg(theme.text_muted)),
-        Span::styled(
-            "[Esc]",
-            Style::default()
-                .fg(theme.accent)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Span::styled("cancel", Style::default().fg(theme.text_muted)),
-    ]);
-    frame.render_widget(Paragraph::new(hints), rows[5]);
+    #[test]
+    fn completes_branch_prefixes_and_range_ends() {
+        let branches: Vec<String> = ["main", "feature/login", "feature/logout", "fix"]
+            .iter()
+            .map(|b| b.to_string())
+            .collect();
+        assert_eq!(
+            branch_completions(&branches, "feature/log"),
+            ["feature/login", "feature/logout"]
+        );
+        assert_eq!(
+            branch_completions(&branches, "main..f"),
+            ["feature/login", "feature/logout", "fix"]
+        );
+        assert!(branch_completions(&branches, "main").is_empty());
+        assert!(branch_completions(&branches, "").is_empty());
+    }
 }
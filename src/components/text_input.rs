@@ -1,13 +1,13 @@
 use ratatui::{
     layout::Rect,
-    style::Style,
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::Paragraph,
     Frame,
 };
 
 /// Render a text input that wraps and scrolls within the given area.
-/// Supports embedded newlines. The cursor (block character) is shown at `cursor_char_index`.
+/// Supports embedded newlines. The cursor (a blinking block) is shown at `cursor_char_index`.
 pub fn render_text_input(
     frame: &mut Frame,
     area: Rect,
@@ -101,7 +101,11 @@ pub fn render_text_input(
                 // Insert block cursor at the right column
                 let before: String = line_text.chars().take(cursor_col).collect();
                 let after: String = line_text.chars().skip(cursor_col).collect();
-                Line::from(Span::styled(format!(" {}\u{2588}{}", before, after), style))
+                Line::from(vec![
+                    Span::styled(format!(" {before}"), style),
+                    Span::styled("\u{2588}", style.add_modifier(Modifier::SLOW_BLINK)),
+                    Span::styled(after, style),
+                ])
             } else {
                 Line::from(Span::styled(format!(" {}", line_text), style))
             }
@@ -124,9 +124,11 @@ fn get_context_title(state: &AppState) -> &'static str {
         ActiveView::WorktreeBrowser => "Worktree Browser",
         ActiveView::AgentOutputs => "Agent Outputs",
         ActiveView::FeedbackSummary => "Feedback Summary",
+        ActiveView::ReviewSummary => "Review Summary",
         ActiveView::DiffExplorer => match state.focus {
             FocusPanel::Navigator => "Navigator",
             FocusPanel::DiffView => "Diff View",
+            FocusPanel::PromptPreview => "Prompt Preview",
         },
     }
 }
@@ -179,6 +181,26 @@ fn get_context_entries(state: &AppState) -> Vec<KeyEntry> {
                 key: "f",
                 description: "Freeze",
             },
+            KeyEntry {
+                key: "l/Ctrl+L",
+                description: "Lock/unlock",
+            },
+            KeyEntry {
+                key: "F",
+                description: "Fetch",
+            },
+            KeyEntry {
+                key: "P",
+                description: "Pull (rebase)",
+            },
+            KeyEntry {
+                key: "N",
+                description: "New worktree",
+            },
+            KeyEntry {
+                key: "D",
+                description: "Remove worktree",
+            },
             KeyEntry {
                 key: "Esc",
                 description: "Back",
@@ -189,10 +211,18 @@ fn get_context_entries(state: &AppState) -> Vec<KeyEntry> {
                 key: "j/k",
                 description: "Navigate",
             },
+            KeyEntry {
+                key: "PgUp/PgDn",
+                description: "Page runs",
+            },
             KeyEntry {
                 key: "y",
                 description: "Copy prompt",
             },
+            KeyEntry {
+                key: "r",
+                description: "Re-run agent",
+            },
             KeyEntry {
                 key: "w",
                 description: "Switch worktree",
@@ -228,6 +258,24 @@ fn get_context_entries(state: &AppState) -> Vec<KeyEntry> {
                 description: "Back to diff",
             },
         ],
+        ActiveView::ReviewSummary => vec![
+            KeyEntry {
+                key: "j/k",
+                description: "Navigate",
+            },
+            KeyEntry {
+                key: "Enter",
+                description: "Jump to comment",
+            },
+            KeyEntry {
+                key: "d",
+                description: "Delete file's comments",
+            },
+            KeyEntry {
+                key: "Esc/A",
+                description: "Back to diff",
+            },
+        ],
         ActiveView::DiffExplorer => match state.focus {
             FocusPanel::Navigator => vec![
                 KeyEntry {
@@ -262,6 +310,26 @@ fn get_context_entries(state: &AppState) -> Vec<KeyEntry> {
                     key: "u",
                     description: "Unstage file",
                 },
+                KeyEntry {
+                    key: "Space",
+                    description: "Mark file",
+                },
+                KeyEntry {
+                    key: "x",
+                    description: "Collapse/expand file",
+                },
+                KeyEntry {
+                    key: "S/U",
+                    description: "Stage/unstage marked",
+                },
+                KeyEntry {
+                    key: "P",
+                    description: "Push",
+                },
+                KeyEntry {
+                    key: "i",
+                    description: "Staged vs unstaged",
+                },
                 KeyEntry {
                     key: "r",
                     description: "Restore file",
@@ -274,6 +342,26 @@ fn get_context_entries(state: &AppState) -> Vec<KeyEntry> {
                     key: "t",
                     description: "Change target",
                 },
+                KeyEntry {
+                    key: "Ctrl+T",
+                    description: "New tab",
+                },
+                KeyEntry {
+                    key: "Alt+←/→",
+                    description: "Prev/next tab",
+                },
+                KeyEntry {
+                    key: "Alt+W",
+                    description: "Close tab",
+                },
+                KeyEntry {
+                    key: "f",
+                    description: "Filter files (glob)",
+                },
+                KeyEntry {
+                    key: "T",
+                    description: "Filter by status",
+                },
                 KeyEntry {
                     key: "o",
                     description: "Agent outputs",
@@ -286,6 +374,10 @@ fn get_context_entries(state: &AppState) -> Vec<KeyEntry> {
                     key: "Ctrl+A",
                     description: "Agent selector",
                 },
+                KeyEntry {
+                    key: "Ctrl+R",
+                    description: "Interactive rebase",
+                },
                 KeyEntry {
                     key: "Tab",
                     description: "Split/unified",
@@ -320,6 +412,10 @@ fn get_context_entries(state: &AppState) -> Vec<KeyEntry> {
                     key: "h",
                     description: "Focus navigator",
                 },
+                KeyEntry {
+                    key: "Ctrl+N/P",
+                    description: "Next/prev file",
+                },
                 KeyEntry {
                     key: "PgUp/Dn",
                     description: "Page scroll",
@@ -328,6 +424,38 @@ fn get_context_entries(state: &AppState) -> Vec<KeyEntry> {
                     key: "Space",
                     description: "Expand context",
                 },
+                KeyEntry {
+                    key: "z",
+                    description: "Toggle line wrap",
+                },
+                KeyEntry {
+                    key: "zc/zo",
+                    description: "Fold/unfold all context",
+                },
+                KeyEntry {
+                    key: "gd",
+                    description: "Go to definition",
+                },
+                KeyEntry {
+                    key: "Alt+a",
+                    description: "Show/hide annotations",
+                },
+                KeyEntry {
+                    key: "Alt+i",
+                    description: "Inline comments",
+                },
+                KeyEntry {
+                    key: "</>",
+                    description: "Scroll left/right",
+                },
+                KeyEntry {
+                    key: "e",
+                    description: "Open in $EDITOR",
+                },
+                KeyEntry {
+                    key: "Ctrl+O",
+                    description: "Show in file manager",
+                },
                 KeyEntry {
                     key: "/",
                     description: "Search in diff",
@@ -356,14 +484,90 @@ fn get_context_entries(state: &AppState) -> Vec<KeyEntry> {
                     key: "[",
                     description: "Prev annotation",
                 },
+                KeyEntry {
+                    key: "m",
+                    description: "Toggle bookmark",
+                },
+                KeyEntry {
+                    key: "'n/'p",
+                    description: "Next/prev bookmark",
+                },
+                KeyEntry {
+                    key: "} / {",
+                    description: "Next/prev conflict marker",
+                },
+                KeyEntry {
+                    key: "H",
+                    description: "Jump to hunk N",
+                },
+                KeyEntry {
+                    key: "X",
+                    description: "Split/unsplit hunk here",
+                },
+                KeyEntry {
+                    key: "+/-",
+                    description: "More/less context",
+                },
+                KeyEntry {
+                    key: "Ctrl+Z",
+                    description: "Undo annotation (+Shift: redo)",
+                },
                 KeyEntry {
                     key: "p",
                     description: "Prompt preview",
                 },
+                KeyEntry {
+                    key: "Alt+f",
+                    description: "Focus mode",
+                },
+                KeyEntry {
+                    key: "J",
+                    description: "Focus prompt preview",
+                },
+                KeyEntry {
+                    key: "Alt+↑/↓",
+                    description: "Resize prompt preview",
+                },
                 KeyEntry {
                     key: "y",
                     description: "Copy prompt",
                 },
+                KeyEntry {
+                    key: "Y",
+                    description: "Copy file diff",
+                },
+                KeyEntry {
+                    key: "yy",
+                    description: "Copy cursor line",
+                },
+                KeyEntry {
+                    key: "Alt+j",
+                    description: "Copy diff as JSON",
+                },
+                KeyEntry {
+                    key: "Alt+Y",
+                    description: "Copy hunk as patch",
+                },
+                KeyEntry {
+                    key: "Ctrl+Y",
+                    description: "Copy changed file list",
+                },
+                KeyEntry {
+                    key: "Ctrl+H",
+                    description: "Replace in added lines",
+                },
+                KeyEntry {
+                    key: "E",
+                    description: "Export annotations",
+                },
+                KeyEntry {
+                    key: "I",
+                    description: "Import GitHub review comments",
+                },
+                KeyEntry {
+                    key: "L",
+                    description: "File log",
+                },
                 KeyEntry {
                     key: "1-5",
                     description: "Quick score",
@@ -380,6 +584,18 @@ fn get_context_entries(state: &AppState) -> Vec<KeyEntry> {
                     key: "u",
                     description: "Unstage file",
                 },
+                KeyEntry {
+                    key: "Ctrl+S/U",
+                    description: "Stage/unstage hunk",
+                },
+                KeyEntry {
+                    key: "P",
+                    description: "Apply hunk to index",
+                },
+                KeyEntry {
+                    key: "Alt+P",
+                    description: "Revert hunk",
+                },
                 KeyEntry {
                     key: "w",
                     description: "Toggle whitespace",
@@ -397,6 +613,36 @@ fn get_context_entries(state: &AppState) -> Vec<KeyEntry> {
                     description: "Quit",
                 },
             ],
+            FocusPanel::PromptPreview => vec![
+                KeyEntry {
+                    key: "j/k",
+                    description: "Scroll",
+                },
+                KeyEntry {
+                    key: "g/G",
+                    description: "Top/bottom",
+                },
+                KeyEntry {
+                    key: "PgUp/Dn",
+                    description: "Page scroll",
+                },
+                KeyEntry {
+                    key: "/",
+                    description: "Search in prompt",
+                },
+                KeyEntry {
+                    key: "n/N",
+                    description: "Next/prev match",
+                },
+                KeyEntry {
+                    key: "p",
+                    description: "Close preview",
+                },
+                KeyEntry {
+                    key: "K/Esc",
+                    description: "Focus diff view",
+                },
+            ],
         },
     }
 }
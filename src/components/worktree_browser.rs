@@ -78,6 +78,11 @@ impl Component for WorktreeBrowser {
                     row_style
                 };
                 let name_span = Span::styled(format!("{:<16}", wt.name), name_style);
+                let lock_span = if wt.locked {
+                    Span::styled("\u{1f512} ", Style::default().fg(theme.warning))
+                } else {
+                    Span::raw("   ")
+                };
 
                 // Path (abbreviated)
                 let path_str = abbreviate_path(&wt.path);
@@ -108,6 +113,7 @@ impl Component for WorktreeBrowser {
                 Line::from(vec![
                     Span::styled(format!("{prefix} "), row_style),
                     status_span,
+                    lock_span,
                     name_span,
                     path_span,
                     branch_span,
@@ -138,6 +144,27 @@ impl Component for WorktreeBrowser {
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled("freeze  ", Style::default().fg(theme.text_muted)),
+            Span::styled(
+                "[l/C-l]",
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("lock/unlock  ", Style::default().fg(theme.text_muted)),
+            Span::styled(
+                "[D]",
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("remove  ", Style::default().fg(theme.text_muted)),
+            Span::styled(
+                "[F/P]",
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("fetch/pull  ", Style::default().fg(theme.text_muted)),
             Span::styled(
                 "[r]",
                 Style::default()
@@ -161,14 +188,10 @@ impl Component for WorktreeBrowser {
 }
 
 fn abbreviate_path(path: &std::path::Path) -> String {
-    if let Some(home) = dirs_next_home() {
+    if let Some(home) = crate::paths::home_dir() {
         if let Ok(stripped) = path.strip_prefix(&home) {
             return format!("~/{}", stripped.display());
         }
     }
     path.display().to_string()
 }
-
-fn dirs_next_home() -> Option<std::path::PathBuf> {
-    std::env::var_os("HOME").map(std::path::PathBuf::from)
-}
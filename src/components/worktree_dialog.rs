@@ -0,0 +1,26 @@
+use ratatui::{
+    style::Style,
+    text::{Line, Span},
+    Frame,
+};
+
+use super::filter_dialog::{render_input_dialog, InputDialog};
+use crate::state::AppState;
+
+pub fn render_worktree_dialog(frame: &mut Frame, state: &AppState, default_path: &str) {
+    let theme = &state.theme;
+    let path = Line::from(vec![
+        Span::styled(" path: ", Style::default().fg(theme.text_muted)),
+        Span::styled(default_path, Style::default().fg(theme.success)),
+    ]);
+    let dialog = InputDialog {
+        title: " New Worktree ",
+        width: 60,
+        border: theme.success,
+        above: vec![path, Line::default()],
+        below: Vec::new(),
+        hint: Some("new or existing branch name"),
+        keys: &[("Enter", "create"), ("Esc", "cancel")],
+    };
+    render_input_dialog(frame, state, dialog, &state.worktree_dialog_input);
+}
@@ -0,0 +1,81 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::git::worktree::WorktreeInfo;
+use crate::state::AppState;
+
+pub fn render_worktree_remove_confirm(frame: &mut Frame, state: &AppState, wt: &WorktreeInfo) {
+    let theme = &state.theme;
+    let area = frame.area();
+    let dialog_width = 60.min(area.width.saturating_sub(4));
+    let dialog_height = 8.min(area.height.saturating_sub(4));
+
+    let x = (area.width.saturating_sub(dialog_width)) / 2;
+    let y = (area.height.saturating_sub(dialog_height)) / 2;
+
+    let dialog_area = Rect::new(x, y, dialog_width, dialog_height);
+
+    frame.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .title(" Remove Worktree ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.error));
+
+    let inner = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // prompt
+            Constraint::Length(1), // worktree path
+            Constraint::Length(1), // dirty warning
+            Constraint::Length(1), // blank
+            Constraint::Length(1), // key hints
+        ])
+        .split(inner);
+
+    let prompt = Line::from(vec![Span::styled(
+        format!(" Run git worktree remove --force on {}?", wt.name),
+        Style::default().fg(theme.text),
+    )]);
+    frame.render_widget(Paragraph::new(prompt), rows[0]);
+
+    let path_line = Line::from(vec![Span::styled(
+        format!(" {}", wt.path.display()),
+        Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+    )]);
+    frame.render_widget(Paragraph::new(path_line), rows[1]);
+
+    if wt.is_dirty {
+        let warning = Line::from(vec![Span::styled(
+            " It has uncommitted or untracked changes, which will be lost.",
+            Style::default().fg(theme.warning),
+        )]);
+        frame.render_widget(Paragraph::new(warning), rows[2]);
+    }
+
+    let hints = Line::from(vec![
+        Span::styled(
+            " [Enter/y]",
+            Style::default()
+                .fg(theme.error)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("remove  ", Style::default().fg(theme.text_muted)),
+        Span::styled(
+            "[Esc/n]",
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("cancel", Style::default().fg(theme.text_muted)),
+    ]);
+    frame.render_widget(Paragraph::new(hints), rows[4]);
+}
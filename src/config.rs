@@ -1,20 +1,39 @@
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
-use crate::theme::{apply_overrides, Theme, ThemeOverrides};
+use crate::action::Action;
+use crate::keybindings::{parse_keybindings, KeySpec};
+use crate::paths;
+use crate::theme::{
+    apply_overrides, parse_hex_color, ColorSupport, Theme, ThemeOverrides, THEME_NAMES,
+};
 
 #[derive(Debug, Clone, Deserialize)]
 #[allow(dead_code)]
 pub struct AgentProviderConfig {
     pub name: String,
+    #[serde(default)]
     pub command: String,
+    /// Script run with `sh` instead of `command`, given the model and prompt in
+    /// `MDIFF_MODEL` / `MDIFF_PROMPT` rather than by substitution.
+    #[serde(default)]
+    pub command_file: Option<PathBuf>,
     #[serde(default)]
     pub models: Vec<String>,
     #[serde(default)]
     pub default_model: String,
     #[serde(default)]
     pub description: String,
+    /// Overrides the global `prompt_template` when this agent is launched.
+    #[serde(default)]
+    pub prompt_template: Option<String>,
+    /// Price per 1000 prompt tokens, used for the cost estimate in the runs list.
+    #[serde(default)]
+    pub cost_per_1k_tokens: Option<f64>,
+    /// Context window size in tokens; runs whose prompt exceeds it are flagged.
+    #[serde(default)]
+    pub context_window: Option<usize>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -33,6 +52,38 @@ fn default_mouse_enabled() -> bool {
     true
 }
 
+/// File format used by the annotation export (`E`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Markdown,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "markdown",
+            ExportFormat::Json => "json",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Json => "json",
+        }
+    }
+
+    pub fn toggle(self) -> Self {
+        match self {
+            ExportFormat::Markdown => ExportFormat::Json,
+            ExportFormat::Json => ExportFormat::Markdown,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct ChecklistItemConfig {
     pub label: String,
@@ -44,20 +95,74 @@ pub struct ChecklistConfig {
     pub items: Vec<ChecklistItemConfig>,
 }
 
+const DEFAULT_LOG_LIMIT: usize = 10;
+const DEFAULT_HUNK_STAT_BAR_WIDTH: usize = 8;
+/// Accepted range for context lines around each change, however they're set.
+pub const MIN_CONTEXT: usize = 1;
+pub const MAX_CONTEXT: usize = 50;
+const DEFAULT_CONTEXT_PADDING: u32 = 5;
+pub const MAX_CONTEXT_PADDING: u32 = 20;
+pub const DEFAULT_PROMPT_PREVIEW_RATIO: u16 = 60;
+pub const PROMPT_PREVIEW_RATIO_MIN: u16 = 20;
+pub const PROMPT_PREVIEW_RATIO_MAX: u16 = 80;
+
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub struct MdiffConfig {
     pub agents: Vec<AgentProviderConfig>,
     pub agents_by_name: HashMap<String, usize>,
     pub theme: Theme,
+    /// The `[colors]` table, already applied to `theme`; kept for themes picked
+    /// on the command line.
+    pub color_overrides: Option<ThemeOverrides>,
     pub unified: Option<bool>,
     pub ignore_whitespace: Option<bool>,
     pub context_lines: Option<usize>,
+    /// Lines of code quoted above and below each annotation in agent prompts.
+    pub context_padding: u32,
     /// Last-used model per agent name (e.g. "claude" -> "claude-opus-4-6").
     pub agent_models: HashMap<String, String>,
+    /// Agents starred with `f` in the agent selector, listed before the others.
+    pub agent_favorites: HashSet<String>,
     pub mouse: MouseConfig,
     /// Checklist configuration for review templates
     pub checklist: Option<ChecklistConfig>,
+    /// Instruction preamble placed at the top of agent prompts. `None` uses the built-in text.
+    pub prompt_template: Option<String>,
+    /// Format written by the annotation export.
+    pub export_format: ExportFormat,
+    /// Editor argument template with `{file}` and `{line}`, e.g. `"+{line} {file}"`.
+    /// `None` picks a template from the editor's name.
+    pub editor_open_args: Option<String>,
+    /// Number of commits shown in the file log panel.
+    pub log_limit: usize,
+    /// Width of the additions/deletions bar on hunk headers; 0 hides it.
+    pub hunk_stat_bar_width: usize,
+    /// Mark added and removed lines with `▶` / `◀` as well as by color.
+    pub accessible: bool,
+    /// Added and context lines longer than this many characters get a warning
+    /// background past the limit.
+    pub max_line_length: Option<usize>,
+    /// Share of the diff area, in percent, kept by the diff when the prompt preview is open.
+    pub prompt_preview_ratio: u16,
+    /// Extra `git diff` arguments; when set, diffs come from the git CLI instead of libgit2.
+    pub diff_tool: Option<String>,
+    /// Command printing a ctags-format index for `gd`; `None` runs `ctags -R`.
+    pub tags_command: Option<String>,
+    /// File the review summary's checklist export is also written to, relative
+    /// to the repository root.
+    pub review_checklist_path: Option<String>,
+    /// Preview stage, unstage and restore in the status bar and ask for a
+    /// second key press before running them.
+    pub safe_mode: bool,
+    /// User overrides from `[keybindings]`, checked before the built-in keys.
+    pub keybindings: HashMap<Action, KeySpec>,
+    /// Globs from `exclude_paths`; matching files are dropped from the diff.
+    pub exclude_paths: Vec<IgnorePattern>,
+    /// Problems found while parsing `[keybindings]` and `exclude_paths`, shown on startup.
+    pub config_warnings: Vec<String>,
+    /// Terminal color capability; set from CLI flags and the environment at startup.
+    pub color_support: ColorSupport,
 }
 
 impl Default for MdiffConfig {
@@ -72,12 +177,31 @@ impl Default for MdiffConfig {
             agents,
             agents_by_name,
             theme: Theme::from_name("one-dark"),
+            color_overrides: None,
             unified: None,
             ignore_whitespace: None,
             context_lines: None,
+            context_padding: DEFAULT_CONTEXT_PADDING,
             agent_models: HashMap::new(),
+            agent_favorites: HashSet::new(),
             mouse: MouseConfig::default(),
             checklist: None,
+            prompt_template: None,
+            export_format: ExportFormat::default(),
+            editor_open_args: None,
+            log_limit: DEFAULT_LOG_LIMIT,
+            hunk_stat_bar_width: DEFAULT_HUNK_STAT_BAR_WIDTH,
+            accessible: false,
+            max_line_length: None,
+            prompt_preview_ratio: DEFAULT_PROMPT_PREVIEW_RATIO,
+            diff_tool: None,
+            tags_command: None,
+            review_checklist_path: None,
+            safe_mode: false,
+            keybindings: HashMap::new(),
+            exclude_paths: Vec::new(),
+            config_warnings: Vec::new(),
+            color_support: ColorSupport::TrueColor,
         }
     }
 }
@@ -106,6 +230,10 @@ fn known_agents() -> Vec<AgentProviderConfig> {
             ],
             default_model: "claude-sonnet-4-6".to_string(),
             description: "Anthropic Claude Code".to_string(),
+            prompt_template: None,
+            cost_per_1k_tokens: None,
+            context_window: None,
+            command_file: None,
         },
         AgentProviderConfig {
             name: "codex".to_string(),
@@ -115,6 +243,10 @@ fn known_agents() -> Vec<AgentProviderConfig> {
             models: vec![],
             default_model: String::new(),
             description: "OpenAI Codex CLI".to_string(),
+            prompt_template: None,
+            cost_per_1k_tokens: None,
+            context_window: None,
+            command_file: None,
         },
         AgentProviderConfig {
             name: "opencode".to_string(),
@@ -126,6 +258,10 @@ fn known_agents() -> Vec<AgentProviderConfig> {
             ],
             default_model: "anthropic/claude-sonnet-4-6".to_string(),
             description: "OpenCode CLI".to_string(),
+            prompt_template: None,
+            cost_per_1k_tokens: None,
+            context_window: None,
+            command_file: None,
         },
         AgentProviderConfig {
             name: "gemini".to_string(),
@@ -138,6 +274,10 @@ fn known_agents() -> Vec<AgentProviderConfig> {
             ],
             default_model: "gemini-3-flash-preview".to_string(),
             description: "Google Gemini CLI".to_string(),
+            prompt_template: None,
+            cost_per_1k_tokens: None,
+            context_window: None,
+            command_file: None,
         },
     ]
 }
@@ -165,22 +305,371 @@ struct ConfigFile {
     #[serde(default)]
     context_lines: Option<usize>,
     #[serde(default)]
+    context_padding: Option<u32>,
+    #[serde(default)]
     agent_models: HashMap<String, String>,
     #[serde(default)]
+    agent_favorites: HashSet<String>,
+    #[serde(default)]
     mouse: MouseConfig,
     checklist: Option<ChecklistConfig>,
+    #[serde(default)]
+    prompt_template: Option<String>,
+    #[serde(default)]
+    export_format: Option<ExportFormat>,
+    #[serde(default)]
+    editor_open_args: Option<String>,
+    #[serde(default)]
+    log_limit: Option<usize>,
+    #[serde(default)]
+    hunk_stat_bar_width: Option<usize>,
+    #[serde(default)]
+    accessible: Option<bool>,
+    #[serde(default)]
+    max_line_length: Option<usize>,
+    #[serde(default)]
+    keybindings: HashMap<String, String>,
+    #[serde(default)]
+    prompt_preview_ratio: Option<u16>,
+    #[serde(default)]
+    diff_tool: Option<String>,
+    #[serde(default)]
+    tags_command: Option<String>,
+    #[serde(default)]
+    review_checklist_path: Option<String>,
+    #[serde(default)]
+    safe_mode: bool,
+    #[serde(default)]
+    exclude_paths: Vec<String>,
+}
+
+/// Keys `ConfigFile` understands, for spotting typos in `mdiff config validate`.
+const CONFIG_KEYS: &[&str] = &[
+    "agents",
+    "theme",
+    "colors",
+    "unified",
+    "ignore_whitespace",
+    "context_lines",
+    "context_padding",
+    "agent_models",
+    "agent_favorites",
+    "mouse",
+    "checklist",
+    "prompt_template",
+    "export_format",
+    "editor_open_args",
+    "log_limit",
+    "hunk_stat_bar_width",
+    "accessible",
+    "max_line_length",
+    "keybindings",
+    "prompt_preview_ratio",
+    "diff_tool",
+    "tags_command",
+    "review_checklist_path",
+    "safe_mode",
+    "exclude_paths",
+];
+
+const AGENT_KEYS: &[&str] = &[
+    "name",
+    "command",
+    "command_file",
+    "models",
+    "default_model",
+    "description",
+    "prompt_template",
+    "cost_per_1k_tokens",
+    "context_window",
+];
+
+const COLOR_KEYS: &[&str] = &[
+    "accent",
+    "secondary",
+    "text",
+    "text_muted",
+    "surface",
+    "selection_bg",
+    "selection_inactive_bg",
+    "diff_add_bg",
+    "diff_del_bg",
+    "diff_add_char_bg",
+    "diff_del_char_bg",
+    "diff_add_fg",
+    "diff_del_fg",
+    "diff_context_fg",
+    "diff_hunk_header_fg",
+    "visual_select_bg",
+    "cursor_line_fg",
+    "cursor_line_bg",
+    "collapsed_bg",
+    "search_match_bg",
+    "success",
+    "error",
+    "warning",
+];
+
+const SYNTAX_COLOR_KEYS: &[&str] = &[
+    "comment",
+    "keyword",
+    "string",
+    "number",
+    "function",
+    "type_name",
+    "variable",
+    "operator",
+    "property",
+    "tag",
+    "punctuation",
+    "default_fg",
+];
+
+/// Report everything wrong with a config file: TOML and type errors, unknown
+/// keys, bad colors and theme names, references to agents that aren't
+/// configured, and agent commands `has_command` can't find. Empty when valid.
+pub fn validate_config(contents: &str, has_command: impl Fn(&str) -> bool) -> Vec<String> {
+    let value: toml::Table = match toml::from_str(contents) {
+        Ok(v) => v,
+        Err(e) => return vec![format!("invalid TOML: {}", e.message())],
+    };
+    let file: ConfigFile = match toml::from_str(contents) {
+        Ok(f) => f,
+        Err(e) => return vec![e.message().to_string()],
+    };
+
+    let mut problems = Vec::new();
+    let mut unknown_keys = |table: Option<&toml::Table>, known: &[&str], prefix: &str| {
+        for key in table.into_iter().flat_map(|t| t.keys()) {
+            if !known.contains(&key.as_str()) {
+                problems.push(format!("unknown key `{prefix}{key}`"));
+            }
+        }
+    };
+    unknown_keys(Some(&value), CONFIG_KEYS, "");
+    let colors = value.get("colors").and_then(|v| v.as_table());
+    let mut color_keys = COLOR_KEYS.to_vec();
+    color_keys.push("syntax");
+    unknown_keys(colors, &color_keys, "colors.");
+    let syntax = colors
+        .and_then(|c| c.get("syntax"))
+        .and_then(|v| v.as_table());
+    unknown_keys(syntax, SYNTAX_COLOR_KEYS, "colors.syntax.");
+    let mouse = value.get("mouse").and_then(|v| v.as_table());
+    unknown_keys(mouse, &["enabled"], "mouse.");
+    let agent_tables = value.get("agents").and_then(|v| v.as_array());
+    for agent in agent_tables.into_iter().flatten() {
+        unknown_keys(agent.as_table(), AGENT_KEYS, "agents.");
+    }
+
+    if let Some(theme) = &file.theme {
+        if !THEME_NAMES.contains(&theme.to_ascii_lowercase().as_str()) {
+            problems.push(format!(
+                "unknown theme `{theme}` (expected one of: {})",
+                THEME_NAMES.join(", ")
+            ));
+        }
+    }
+
+    let mut check_colors = |table: Option<&toml::Table>, prefix: &str| {
+        for (key, v) in table.into_iter().flatten() {
+            if let Some(hex) = v.as_str() {
+                if parse_hex_color(hex).is_none() {
+                    problems.push(format!(
+                        "`{prefix}{key}`: `{hex}` is not a #rrggbb hex color"
+                    ));
+                }
+            }
+        }
+    };
+    check_colors(colors, "colors.");
+    check_colors(syntax, "colors.syntax.");
+
+    let agents = if file.agents.is_empty() {
+        known_agents()
+    } else {
+        file.agents
+    };
+    for name in file.agent_models.keys() {
+        if !agents.iter().any(|a| &a.name == name) {
+            problems.push(format!("agent_models: unknown agent `{name}`"));
+        }
+    }
+    let mut favorites: Vec<&String> = file.agent_favorites.iter().collect();
+    favorites.sort();
+    for name in favorites {
+        if !agents.iter().any(|a| &a.name == name) {
+            problems.push(format!("agent_favorites: unknown agent `{name}`"));
+        }
+    }
+    if file
+        .checklist
+        .as_ref()
+        .is_some_and(|c| c.items.iter().any(|item| item.key.chars().count() != 1))
+    {
+        problems.push("checklist: every item key must be a single character".to_string());
+    }
+    if agent_tables.is_some() {
+        for agent in &agents {
+            if let Some(file) = &agent.command_file {
+                let file = expand_home(file);
+                if !file.is_file() {
+                    problems.push(format!(
+                        "agent `{}`: command_file {} does not exist",
+                        agent.name,
+                        file.display()
+                    ));
+                } else if !is_executable(&file) {
+                    problems.push(format!(
+                        "agent `{}`: command_file {} is not executable",
+                        agent.name,
+                        file.display()
+                    ));
+                }
+                continue;
+            }
+            match agent.command.split_whitespace().next() {
+                None => problems.push(format!("agent `{}` has an empty command", agent.name)),
+                Some(bin) if !has_command(bin) => problems.push(format!(
+                    "agent `{}`: `{bin}` was not found on PATH",
+                    agent.name
+                )),
+                Some(_) => {}
+            }
+        }
+    }
+
+    problems.extend(parse_keybindings(&file.keybindings).1);
+    for glob in &file.exclude_paths {
+        if let Err(e) = IgnorePattern::new(glob) {
+            problems.push(format!("exclude_paths `{glob}`: {e}"));
+        }
+    }
+    problems
+}
+
+/// `mdiff config validate`: print a report for the config file and return the
+/// process exit code.
+pub fn run_validate() -> i32 {
+    let path = config_path();
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            println!("{}: not found, using defaults", path.display());
+            return 0;
+        }
+        Err(e) => {
+            eprintln!("{}: {e}", path.display());
+            return 1;
+        }
+    };
+    let problems = validate_config(&contents, has_command);
+    if problems.is_empty() {
+        println!("{}: OK", path.display());
+        return 0;
+    }
+    eprintln!("{}: {} problem(s)", path.display(), problems.len());
+    for problem in &problems {
+        eprintln!("  - {problem}");
+    }
+    1
 }
 
 fn config_path() -> PathBuf {
-    let mut path = dirs_home().unwrap_or_else(|| PathBuf::from("."));
-    path.push(".config");
-    path.push("mdiff");
-    path.push("config.toml");
-    path
+    paths::config_dir().join("config.toml")
+}
+
+/// Resolve a leading `~/` against `$HOME`.
+pub fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), paths::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Whether `path` is a file the current user may execute.
+pub fn is_executable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::metadata(path).is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+    }
+    #[cfg(not(unix))]
+    {
+        path.is_file()
+    }
+}
+
+/// One `.mdiffignore` or `exclude_paths` glob. As in `.gitignore`, a pattern
+/// with a `/` at its start or in the middle is anchored at the repository
+/// root; others match in any directory.
+#[derive(Debug, Clone)]
+pub struct IgnorePattern {
+    glob: glob::Pattern,
+    anchored: bool,
 }
 
-fn dirs_home() -> Option<PathBuf> {
-    std::env::var_os("HOME").map(PathBuf::from)
+impl IgnorePattern {
+    /// Compile `line`; a leading `/` anchors it at the repository root and a
+    /// trailing `/` matches everything under a directory.
+    pub fn new(line: &str) -> Result<Self, glob::PatternError> {
+        let anchored = line.trim_end_matches('/').contains('/');
+        let mut glob = line.trim_start_matches('/').to_string();
+        if glob.ends_with('/') {
+            glob.push_str("**");
+        }
+        Ok(IgnorePattern {
+            glob: glob::Pattern::new(&glob)?,
+            anchored,
+        })
+    }
+
+    fn matches(&self, path: &Path) -> bool {
+        if self.anchored {
+            return self.glob.matches_path(path);
+        }
+        // Try the path from each directory down, so `vendor/**` matches `a/vendor/b.rs`
+        let mut components = path.components();
+        loop {
+            if self.glob.matches_path(components.as_path()) {
+                return true;
+            }
+            if components.next().is_none() {
+                return false;
+            }
+        }
+    }
+}
+
+/// Whether `path` matches one of `patterns`.
+pub fn is_ignored(patterns: &[IgnorePattern], path: &Path) -> bool {
+    patterns.iter().any(|p| p.matches(path))
+}
+
+/// Parse `.mdiffignore` contents: one [`IgnorePattern`] per line, `#`
+/// comments and blank lines skipped.
+pub fn parse_ignore_file(contents: &str) -> (Vec<IgnorePattern>, Vec<String>) {
+    let mut patterns = Vec::new();
+    let mut warnings = Vec::new();
+    for (n, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match IgnorePattern::new(line) {
+            Ok(pattern) => patterns.push(pattern),
+            Err(e) => warnings.push(format!(".mdiffignore line {}: {e}", n + 1)),
+        }
+    }
+    (patterns, warnings)
+}
+
+/// Load `.mdiffignore` from the repository root; a missing file ignores nothing.
+pub fn load_ignore_file(repo_path: &Path) -> (Vec<IgnorePattern>, Vec<String>) {
+    match std::fs::read_to_string(repo_path.join(".mdiffignore")) {
+        Ok(contents) => parse_ignore_file(&contents),
+        Err(_) => (Vec::new(), Vec::new()),
+    }
 }
 
 /// Build the agents_by_name index from an agents list.
@@ -192,19 +681,32 @@ fn build_agents_index(agents: &[AgentProviderConfig]) -> HashMap<String, usize>
         .collect()
 }
 
-/// Load config from `~/.config/mdiff/config.toml`, falling back to defaults.
+/// Load config from `config.toml` in the config directory (`~/.config/mdiff`
+/// unless `$XDG_CONFIG_HOME` is set), falling back to defaults.
 /// If no agents are configured, auto-detects known CLIs on PATH.
 pub fn load_config() -> MdiffConfig {
+    load_config_with_colors(ColorSupport::detect(false, false))
+}
+
+/// [`load_config`] for a terminal with `color_support`. Without colors the
+/// theme starts from [`Theme::no_color`], and `[colors]` overrides still apply.
+pub fn load_config_with_colors(color_support: ColorSupport) -> MdiffConfig {
     let path = config_path();
+    let defaults = || {
+        let mut config = MdiffConfig::default();
+        config.theme = color_support.adapt_theme(config.theme);
+        config.color_support = color_support;
+        config
+    };
 
     let contents = match std::fs::read_to_string(&path) {
         Ok(c) => c,
-        Err(_) => return MdiffConfig::default(),
+        Err(_) => return defaults(),
     };
 
     let file: ConfigFile = match toml::from_str(&contents) {
         Ok(f) => f,
-        Err(_) => return MdiffConfig::default(),
+        Err(_) => return defaults(),
     };
 
     // Use configured agents, or fall back to auto-detection
@@ -215,10 +717,18 @@ pub fn load_config() -> MdiffConfig {
     };
 
     let agents_by_name = build_agents_index(&agents);
+    let (keybindings, mut config_warnings) = parse_keybindings(&file.keybindings);
+    let mut exclude_paths = Vec::new();
+    for glob in &file.exclude_paths {
+        match IgnorePattern::new(glob) {
+            Ok(pattern) => exclude_paths.push(pattern),
+            Err(e) => config_warnings.push(format!("exclude_paths `{glob}`: {e}")),
+        }
+    }
 
     // Load theme by name, apply color overrides
     let theme_name = file.theme.as_deref().unwrap_or("one-dark");
-    let mut theme = Theme::from_name(theme_name);
+    let mut theme = color_support.adapt_theme(Theme::from_name(theme_name));
     if let Some(ref overrides) = file.colors {
         apply_overrides(&mut theme, overrides);
     }
@@ -227,12 +737,41 @@ pub fn load_config() -> MdiffConfig {
         agents,
         agents_by_name,
         theme,
+        color_overrides: file.colors,
         unified: file.unified,
         ignore_whitespace: file.ignore_whitespace,
-        context_lines: file.context_lines,
+        context_lines: file
+            .context_lines
+            .map(|n| n.clamp(MIN_CONTEXT, MAX_CONTEXT)),
+        context_padding: file
+            .context_padding
+            .unwrap_or(DEFAULT_CONTEXT_PADDING)
+            .min(MAX_CONTEXT_PADDING),
         agent_models: file.agent_models,
+        agent_favorites: file.agent_favorites,
         mouse: file.mouse,
         checklist: file.checklist,
+        prompt_template: file.prompt_template,
+        export_format: file.export_format.unwrap_or_default(),
+        editor_open_args: file.editor_open_args,
+        log_limit: file.log_limit.unwrap_or(DEFAULT_LOG_LIMIT),
+        hunk_stat_bar_width: file
+            .hunk_stat_bar_width
+            .unwrap_or(DEFAULT_HUNK_STAT_BAR_WIDTH),
+        accessible: file.accessible.unwrap_or(false),
+        max_line_length: file.max_line_length,
+        prompt_preview_ratio: file
+            .prompt_preview_ratio
+            .unwrap_or(DEFAULT_PROMPT_PREVIEW_RATIO)
+            .clamp(PROMPT_PREVIEW_RATIO_MIN, PROMPT_PREVIEW_RATIO_MAX),
+        diff_tool: file.diff_tool,
+        tags_command: file.tags_command,
+        review_checklist_path: file.review_checklist_path,
+        safe_mode: file.safe_mode,
+        keybindings,
+        exclude_paths,
+        config_warnings,
+        color_support,
     }
 }
 
@@ -242,9 +781,12 @@ pub struct PersistentSettings {
     pub unified: bool,
     pub ignore_whitespace: bool,
     pub context_lines: usize,
+    pub context_padding: u32,
+    pub export_format: ExportFormat,
+    pub prompt_preview_ratio: u16,
 }
 
-/// Save persistent settings to `~/.config/mdiff/config.toml`.
+/// Save persistent settings to `config.toml` in the config directory.
 /// Reads the existing file (if any), updates only the settings fields, and writes back.
 /// Preserves other config values (agents, prompt_template, color overrides).
 pub fn save_settings(settings: &PersistentSettings) {
@@ -275,6 +817,18 @@ pub fn save_settings(settings: &PersistentSettings) {
         "context_lines".to_string(),
         toml::Value::Integer(settings.context_lines as i64),
     );
+    table.insert(
+        "context_padding".to_string(),
+        toml::Value::Integer(settings.context_padding.into()),
+    );
+    table.insert(
+        "export_format".to_string(),
+        toml::Value::String(settings.export_format.label().to_string()),
+    );
+    table.insert(
+        "prompt_preview_ratio".to_string(),
+        toml::Value::Integer(settings.prompt_preview_ratio.into()),
+    );
 
     // Ensure directory exists
     if let Some(parent) = path.parent() {
@@ -351,3 +905,139 @@ pub fn save_agent_model(agent_name: &str, model: &str) {
     let toml_string = toml::to_string_pretty(&table).unwrap_or_default();
     let _ = std::fs::write(&path, toml_string);
 }
+
+/// Save the favorite agents to config.toml as a sorted `agent_favorites` array.
+pub fn save_agent_favorites(favorites: &HashSet<String>) {
+    let path = config_path();
+
+    let mut table = if let Ok(contents) = std::fs::read_to_string(&path) {
+        contents
+            .parse::<toml::Table>()
+            .unwrap_or_else(|_| toml::Table::new())
+    } else {
+        toml::Table::new()
+    };
+
+    let mut names: Vec<&String> = favorites.iter().collect();
+    names.sort();
+    table.insert(
+        "agent_favorites".to_string(),
+        toml::Value::Array(
+            names
+                .into_iter()
+                .map(|n| toml::Value::String(n.clone()))
+                .collect(),
+        ),
+    );
+
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+
+    let toml_string = toml::to_string_pretty(&table).unwrap_or_default();
+    let _ = std::fs::write(&path, toml_string);
+}
+
+/// Save the prompt preview split to config.toml, leaving other settings as they are.
+pub fn save_prompt_preview_ratio(ratio: u16) {
+    let path = config_path();
+
+    let mut table = if let Ok(contents) = std::fs::read_to_string(&path) {
+        contents
+            .parse::<toml::Table>()
+            .unwrap_or_else(|_| toml::Table::new())
+    } else {
+        toml::Table::new()
+    };
+
+    table.insert(
+        "prompt_preview_ratio".to_string(),
+        toml::Value::Integer(ratio.into()),
+    );
+
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+
+    let toml_string = toml::to_string_pretty(&table).unwrap_or_default();
+    let _ = std::fs::write(&path, toml_string);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_ignored, parse_ignore_file, validate_config};
+    use std::path::Path;
+
+    #[test]
+    fn parses_mdiffignore_like_gitignore() {
+        let (patterns, warnings) =
+            parse_ignore_file("# generated\n\nCargo.lock\n/vendor/\ngen/*.pb.go\n[oops\n");
+        assert_eq!(patterns.len(), 3);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with(".mdiffignore line 6"));
+
+        assert!(is_ignored(&patterns, Path::new("sub/Cargo.lock")));
+        assert!(is_ignored(&patterns, Path::new("vendor/a/b.rs")));
+        assert!(!is_ignored(&patterns, Path::new("sub/vendor/b.rs")));
+        assert!(is_ignored(&patterns, Path::new("gen/api.pb.go")));
+        assert!(!is_ignored(&patterns, Path::new("sub/gen/api.pb.go")));
+        assert!(!is_ignored(&patterns, Path::new("src/main.rs")));
+    }
+
+    #[test]
+    fn anchored_ignore_patterns_match_only_at_the_root() {
+        let (patterns, _) = parse_ignore_file("/Cargo.lock\n");
+        assert!(is_ignored(&patterns, Path::new("Cargo.lock")));
+        assert!(!is_ignored(&patterns, Path::new("sub/Cargo.lock")));
+    }
+
+    #[test]
+    fn directory_ignore_patterns_match_at_any_depth() {
+        let (patterns, _) = parse_ignore_file("vendor/\n");
+        assert!(is_ignored(&patterns, Path::new("vendor/a.rs")));
+        assert!(is_ignored(&patterns, Path::new("crates/x/vendor/b/c.rs")));
+        assert!(!is_ignored(&patterns, Path::new("src/vendored.rs")));
+    }
+
+    #[test]
+    fn validate_reports_each_problem() {
+        let config = r##"
+theme = "vaporwave"
+unifed = true
+exclude_paths = ["[oops"]
+agent_favorites = ["ghost"]
+
+[colors]
+accent = "#12345"
+text = "#abcdef"
+
+[agent_models]
+nope = "m"
+
+[[agents]]
+name = "local"
+command = "missing-bin --flag"
+"##;
+        let problems = validate_config(config, |bin| bin != "missing-bin");
+        let expect = [
+            "unknown key `unifed`",
+            "unknown theme `vaporwave`",
+            "`colors.accent`",
+            "unknown agent `nope`",
+            "agent_favorites: unknown agent `ghost`",
+            "`missing-bin` was not found on PATH",
+            "exclude_paths `[oops`",
+        ];
+        for needle in expect {
+            assert!(
+                problems.iter().any(|p| p.contains(needle)),
+                "missing {needle:?} in {problems:?}"
+            );
+        }
+        assert_eq!(problems.len(), expect.len());
+
+        assert!(validate_config("theme = \"dracula\"\n", |_| true).is_empty());
+        assert!(validate_config("unified = \"yes\"", |_| true).len() == 1);
+        assert!(validate_config("[[agents", |_| true)[0].starts_with("invalid TOML"));
+    }
+}
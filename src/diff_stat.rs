@@ -0,0 +1,152 @@
+use crossterm::style::{Color, Stylize};
+
+use crate::git::types::FileDelta;
+
+/// Colors for the `+` and `-` parts of the bar; `None` prints plain text.
+pub type StatColors = Option<(Color, Color)>;
+
+/// `git diff --stat`-style summary of `deltas` for a terminal `width` columns
+/// wide: one ` path | N +++--` line per file and a totals line.
+pub fn format_diff_stat(deltas: &[FileDelta], width: usize, colors: StatColors) -> String {
+    let names: Vec<String> = deltas.iter().map(display_name).collect();
+    let name_width = names.iter().map(|n| n.chars().count()).max().unwrap_or(0);
+    let max_change = deltas
+        .iter()
+        .map(|d| d.additions + d.deletions)
+        .max()
+        .unwrap_or(0);
+    let count_width = max_change.max(1).to_string().len();
+    // " name | count " plus at least a little room for the bar
+    let bar_width = width
+        .saturating_sub(name_width + count_width + 5)
+        .clamp(10, 100);
+
+    let mut out = String::new();
+    for (delta, name) in deltas.iter().zip(&names) {
+        let pad = name_width - name.chars().count();
+        out.push_str(&format!(" {name}{:pad$} | ", ""));
+        if delta.binary {
+            out.push_str("Bin\n");
+            continue;
+        }
+        let total = delta.additions + delta.deletions;
+        let (plus, minus) = if max_change <= bar_width {
+            (delta.additions, delta.deletions)
+        } else {
+            let scale = |n: usize| {
+                if n == 0 {
+                    0
+                } else {
+                    (n * bar_width / max_change).max(1)
+                }
+            };
+            (scale(delta.additions), scale(delta.deletions))
+        };
+        out.push_str(&format!("{total:>count_width$} "));
+        let (plus, minus) = ("+".repeat(plus), "-".repeat(minus));
+        match colors {
+            Some((add, del)) => {
+                out.push_str(&format!("{}{}", plus.with(add), minus.with(del)));
+            }
+            None => {
+                out.push_str(&plus);
+                out.push_str(&minus);
+            }
+        }
+        out.push('\n');
+    }
+    out.push_str(&totals_line(deltas));
+    out.push('\n');
+    out
+}
+
+fn display_name(delta: &FileDelta) -> String {
+    let path = delta.path.to_string_lossy();
+    match &delta.old_path {
+        Some(old) if *old != delta.path => format!("{} => {path}", old.to_string_lossy()),
+        _ => path.into_owned(),
+    }
+}
+
+fn totals_line(deltas: &[FileDelta]) -> String {
+    let plural = |n: usize, word: &str| {
+        if n == 1 {
+            word.to_string()
+        } else {
+            format!("{word}s")
+        }
+    };
+    let files = deltas.len();
+    let additions: usize = deltas.iter().map(|d| d.additions).sum();
+    let deletions: usize = deltas.iter().map(|d| d.deletions).sum();
+    let mut line = format!(" {files} {} changed", plural(files, "file"));
+    if additions > 0 || deletions == 0 {
+        line.push_str(&format!(
+            ", {additions} {}(+)",
+            plural(additions, "insertion")
+        ));
+    }
+    if deletions > 0 {
+        line.push_str(&format!(
+            ", {deletions} {}(-)",
+            plural(deletions, "deletion")
+        ));
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_diff_stat;
+    use crate::git::types::{FileDelta, FileStatus};
+    use std::path::PathBuf;
+
+    fn delta(path: &str, additions: usize, deletions: usize) -> FileDelta {
+        FileDelta {
+            path: PathBuf::from(path),
+            old_path: None,
+            status: FileStatus::Modified,
+            hunks: Vec::new(),
+            additions,
+            deletions,
+            binary: false,
+            has_conflicts: false,
+            mode: 0o100644,
+        }
+    }
+
+    #[test]
+    fn formats_files_and_totals() {
+        let mut renamed = delta("src/new.rs", 1, 0);
+        renamed.old_path = Some(PathBuf::from("src/old.rs"));
+        let mut image = delta("logo.png", 0, 0);
+        image.binary = true;
+
+        let out = format_diff_stat(&[delta("src/app.rs", 3, 2), renamed, image], 80, None);
+        assert_eq!(
+            out,
+            concat!(
+                " src/app.rs               | 5 +++--\n",
+                " src/old.rs => src/new.rs | 1 +\n",
+                " logo.png                 | Bin\n",
+                " 3 files changed, 4 insertions(+), 2 deletions(-)\n",
+            )
+        );
+    }
+
+    #[test]
+    fn scales_bars_to_the_width() {
+        let out = format_diff_stat(&[delta("a", 300, 100), delta("b", 1, 0)], 40, None);
+        let lines: Vec<&str> = out.lines().collect();
+        // 40 columns leave 31 for the bar after " a | 400 "
+        assert_eq!(
+            lines[0],
+            format!(" a | 400 {}{}", "+".repeat(23), "-".repeat(7))
+        );
+        assert_eq!(lines[1], " b |   1 +");
+        assert_eq!(
+            lines[2],
+            " 2 files changed, 301 insertions(+), 100 deletions(-)"
+        );
+    }
+}
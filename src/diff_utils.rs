@@ -0,0 +1,185 @@
+/// Paired lines whose character edit distance is below this get
+/// character-level highlighting; the rest are compared word by word.
+const CHAR_DIFF_LIMIT: usize = 30;
+
+/// Past this many changed words the emphasis would cover most of both lines,
+/// so none is shown at all.
+const WORD_DIFF_LIMIT: usize = 60;
+
+/// A byte range `(start, end)` into a line.
+pub type ByteRange = (usize, usize);
+
+/// Byte ranges of the characters removed from `old` and inserted into `new`,
+/// from a Myers diff over chars, or `None` if that takes more than
+/// `max_edits` insertions and deletions. Adjacent changed characters are merged.
+pub fn diff_chars(
+    old: &str,
+    new: &str,
+    max_edits: usize,
+) -> Option<(Vec<ByteRange>, Vec<ByteRange>)> {
+    changed_ranges(&char_tokens(old), &char_tokens(new), max_edits)
+}
+
+/// Like [`diff_chars`], but over words, whitespace runs and punctuation.
+pub fn diff_words(
+    old: &str,
+    new: &str,
+    max_edits: usize,
+) -> Option<(Vec<ByteRange>, Vec<ByteRange>)> {
+    changed_ranges(&word_tokens(old), &word_tokens(new), max_edits)
+}
+
+/// Ranges to emphasise in a paired deletion and addition: per character when
+/// the lines differ by fewer than `CHAR_DIFF_LIMIT` characters, per word
+/// otherwise, or `None` when the lines have too little in common.
+pub fn intraline_diff(old: &str, new: &str) -> Option<(Vec<ByteRange>, Vec<ByteRange>)> {
+    diff_chars(old, new, CHAR_DIFF_LIMIT - 1).or_else(|| diff_words(old, new, WORD_DIFF_LIMIT))
+}
+
+/// Each char of `s` with its byte offset.
+fn char_tokens(s: &str) -> Vec<(usize, &str)> {
+    s.char_indices()
+        .map(|(i, c)| (i, &s[i..i + c.len_utf8()]))
+        .collect()
+}
+
+/// Runs of word characters, runs of whitespace, and single punctuation chars.
+fn word_tokens(s: &str) -> Vec<(usize, &str)> {
+    fn class(c: char) -> u8 {
+        if c.is_alphanumeric() || c == '_' {
+            0
+        } else if c.is_whitespace() {
+            1
+        } else {
+            2
+        }
+    }
+    let mut tokens: Vec<(usize, &str)> = Vec::new();
+    let mut start = 0;
+    let mut prev: Option<u8> = None;
+    for (i, c) in s.char_indices() {
+        let cls = class(c);
+        if prev.is_some_and(|p| p != cls || p == 2) {
+            tokens.push((start, &s[start..i]));
+            start = i;
+        }
+        prev = Some(cls);
+    }
+    if start < s.len() {
+        tokens.push((start, &s[start..]));
+    }
+    tokens
+}
+
+fn changed_ranges(
+    a: &[(usize, &str)],
+    b: &[(usize, &str)],
+    max_d: usize,
+) -> Option<(Vec<ByteRange>, Vec<ByteRange>)> {
+    let a_text: Vec<&str> = a.iter().map(|t| t.1).collect();
+    let b_text: Vec<&str> = b.iter().map(|t| t.1).collect();
+    let (old_changed, new_changed) = myers(&a_text, &b_text, max_d)?;
+    Some((to_ranges(a, &old_changed), to_ranges(b, &new_changed)))
+}
+
+/// Merge the changed tokens into contiguous byte ranges.
+fn to_ranges(tokens: &[(usize, &str)], changed: &[bool]) -> Vec<ByteRange> {
+    let mut ranges: Vec<ByteRange> = Vec::new();
+    for (&(start, text), _) in tokens.iter().zip(changed).filter(|(_, &c)| c) {
+        let end = start + text.len();
+        match ranges.last_mut() {
+            Some(last) if last.1 == start => last.1 = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+    ranges
+}
+
+/// Myers' O(ND) diff. Returns which tokens of `a` were deleted and which of
+/// `b` were inserted, or `None` if the edit script is longer than `max_d`.
+fn myers(a: &[&str], b: &[&str], max_d: usize) -> Option<(Vec<bool>, Vec<bool>)> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let max_d = max_d.min(a.len() + b.len()) as isize;
+    let offset = max_d + 1;
+    let mut v = vec![0isize; 2 * max_d as usize + 3];
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    for d in 0..=max_d {
+        trace.push(v.clone());
+        for k in (-d..=d).step_by(2) {
+            let i = (k + offset) as usize;
+            let mut x = if k == -d || (k != d && v[i - 1] < v[i + 1]) {
+                v[i + 1]
+            } else {
+                v[i - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[i] = x;
+            if x >= n && y >= m {
+                return Some(backtrack(&trace, n, m, offset));
+            }
+        }
+    }
+    None
+}
+
+/// Walk the saved frontiers back from the end, marking the token each
+/// non-diagonal step consumed.
+fn backtrack(trace: &[Vec<isize>], n: isize, m: isize, offset: isize) -> (Vec<bool>, Vec<bool>) {
+    let mut old_changed = vec![false; n as usize];
+    let mut new_changed = vec![false; m as usize];
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().skip(1).rev() {
+        let d = d as isize;
+        let k = x - y;
+        let at = |k: isize| v[(k + offset) as usize];
+        let prev_k = if k == -d || (k != d && at(k - 1) < at(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = at(prev_k);
+        let prev_y = prev_x - prev_k;
+        if prev_k == k + 1 {
+            new_changed[prev_y as usize] = true;
+        } else {
+            old_changed[prev_x as usize] = true;
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+    (old_changed, new_changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff_chars, intraline_diff};
+
+    #[test]
+    fn diffs_chars_and_falls_back_to_words() {
+        assert_eq!(
+            diff_chars("let x = 1;", "let y = 12;", 10),
+            Some((vec![(4, 5)], vec![(4, 5), (9, 10)]))
+        );
+        assert_eq!(diff_chars("let x = 1;", "let y = 12;", 2), None);
+        assert_eq!(diff_chars("same", "same", 0), Some((vec![], vec![])));
+        // Ranges are byte offsets, so multi-byte chars stay intact
+        assert_eq!(
+            diff_chars("a→b", "a←b", 10),
+            Some((vec![(1, 4)], vec![(1, 4)]))
+        );
+
+        let old = "fn render(frame: &mut Frame, area: Rect) -> Result<()> {";
+        let new = "fn render_all(frames: &mut Vec<Frame>, region: Rect, theme: &Theme) {";
+        let (del, add) = intraline_diff(old, new).unwrap();
+        // Word level: `render` → `render_all` is a whole changed token
+        assert_eq!(&old[del[0].0..del[0].1], "render");
+        assert_eq!(&new[add[0].0..add[0].1], "render_all");
+
+        assert_eq!(intraline_diff(&"a ".repeat(80), &"b ".repeat(80)), None);
+    }
+}
@@ -1,6 +1,7 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use crate::git::types::{DiffLine, DiffLineOrigin, FileDelta};
+use crate::git::types::{DiffLine, DiffLineOrigin, FileDelta, Hunk};
+use crate::state::review_state::change_blocks;
 use crate::state::DiffViewMode;
 
 /// Direction a collapsed indicator expands toward when activated.
@@ -211,6 +212,49 @@ pub struct DisplayRowInfo {
     pub hidden_count: usize,
     /// Expand direction for collapsed indicators.
     pub expand_direction: Option<ExpandDirection>,
+    /// For a header inserted by a user split (`X`), the index of the hunk line it
+    /// sits above. Split headers are display-only and never map to a git hunk.
+    pub split_line: Option<usize>,
+}
+
+/// Header row for a user split of hunk `hunk_index` above line `line_index`.
+fn split_header_row(hunk_index: usize, line_index: usize) -> DisplayRowInfo {
+    DisplayRowInfo {
+        hunk_index,
+        line_index: None,
+        old_lineno: None,
+        new_lineno: None,
+        origin: None,
+        is_header: true,
+        is_collapsed_indicator: false,
+        gap_id: None,
+        hidden_count: 0,
+        expand_direction: None,
+        split_line: Some(line_index),
+    }
+}
+
+/// Header text for a user split above `line_index`: an empty `@@ -N,0 +M,0 @@`
+/// range at the line numbers the split falls on.
+pub fn split_header_text(hunk: &Hunk, line_index: usize) -> String {
+    let position = |lineno: fn(&DiffLine) -> Option<u32>| {
+        hunk.lines[line_index..]
+            .iter()
+            .find_map(lineno)
+            .or_else(|| {
+                hunk.lines[..line_index]
+                    .iter()
+                    .rev()
+                    .find_map(lineno)
+                    .map(|n| n + 1)
+            })
+            .unwrap_or(0)
+    };
+    format!(
+        "@@ -{},0 +{},0 @@",
+        position(|l| l.old_lineno),
+        position(|l| l.new_lineno)
+    )
 }
 
 /// Build a display map for the split view.
@@ -218,6 +262,7 @@ pub fn build_split_display_map(
     delta: &FileDelta,
     display_context: usize,
     gap_expansions: &HashMap<usize, usize>,
+    hunk_splits: &[(usize, usize)],
 ) -> Vec<DisplayRowInfo> {
     let mut rows = Vec::new();
     let mut gap_id_offset = 0;
@@ -235,11 +280,13 @@ pub fn build_split_display_map(
             gap_id: None,
             hidden_count: 0,
             expand_direction: None,
+            split_line: None,
         });
 
         let (items, next_offset) =
             filter_hunk_lines(&hunk.lines, display_context, gap_expansions, gap_id_offset);
         gap_id_offset = next_offset;
+        let split_at = |line_index: usize| hunk_splits.contains(&(hunk_idx, line_index));
 
         let mut i = 0;
         while i < items.len() {
@@ -260,6 +307,7 @@ pub fn build_split_display_map(
                         gap_id: Some(*gap_id),
                         hidden_count: *hidden_count,
                         expand_direction: Some(*direction),
+                        split_line: None,
                     });
                     i += 1;
                 }
@@ -268,6 +316,9 @@ pub fn build_split_display_map(
                     hunk_line_index,
                 } => match line.origin {
                     DiffLineOrigin::Context => {
+                        if split_at(*hunk_line_index) {
+                            rows.push(split_header_row(hunk_idx, *hunk_line_index));
+                        }
                         rows.push(DisplayRowInfo {
                             hunk_index: hunk_idx,
                             line_index: Some(*hunk_line_index),
@@ -279,6 +330,7 @@ pub fn build_split_display_map(
                             gap_id: None,
                             hidden_count: 0,
                             expand_direction: None,
+                            split_line: None,
                         });
                         i += 1;
                     }
@@ -337,6 +389,14 @@ pub fn build_split_display_map(
                         let max = dels.len().max(adds.len());
 
                         for j in 0..max {
+                            let split = [dels.get(j), adds.get(j)]
+                                .into_iter()
+                                .flatten()
+                                .map(|&(_, idx)| idx)
+                                .find(|&idx| split_at(idx));
+                            if let Some(idx) = split {
+                                rows.push(split_header_row(hunk_idx, idx));
+                            }
                             let (old_lineno, new_lineno, origin, line_idx) =
                                 if j < dels.len() && j < adds.len() {
                                     (
@@ -372,10 +432,14 @@ pub fn build_split_display_map(
                                 gap_id: None,
                                 hidden_count: 0,
                                 expand_direction: None,
+                                split_line: None,
                             });
                         }
                     }
                     DiffLineOrigin::Addition => {
+                        if split_at(*hunk_line_index) {
+                            rows.push(split_header_row(hunk_idx, *hunk_line_index));
+                        }
                         rows.push(DisplayRowInfo {
                             hunk_index: hunk_idx,
                             line_index: Some(*hunk_line_index),
@@ -387,6 +451,7 @@ pub fn build_split_display_map(
                             gap_id: None,
                             hidden_count: 0,
                             expand_direction: None,
+                            split_line: None,
                         });
                         i += 1;
                     }
@@ -403,6 +468,7 @@ pub fn build_unified_display_map(
     delta: &FileDelta,
     display_context: usize,
     gap_expansions: &HashMap<usize, usize>,
+    hunk_splits: &[(usize, usize)],
 ) -> Vec<DisplayRowInfo> {
     let mut rows = Vec::new();
     let mut gap_id_offset = 0;
@@ -420,11 +486,13 @@ pub fn build_unified_display_map(
             gap_id: None,
             hidden_count: 0,
             expand_direction: None,
+            split_line: None,
         });
 
         let (items, next_offset) =
             filter_hunk_lines(&hunk.lines, display_context, gap_expansions, gap_id_offset);
         gap_id_offset = next_offset;
+        let split_at = |line_index: usize| hunk_splits.contains(&(hunk_idx, line_index));
 
         for item in &items {
             match item {
@@ -444,12 +512,16 @@ pub fn build_unified_display_map(
                         gap_id: Some(*gap_id),
                         hidden_count: *hidden_count,
                         expand_direction: Some(*direction),
+                        split_line: None,
                     });
                 }
                 FilteredItem::Line {
                     line,
                     hunk_line_index,
                 } => {
+                    if split_at(*hunk_line_index) {
+                        rows.push(split_header_row(hunk_idx, *hunk_line_index));
+                    }
                     rows.push(DisplayRowInfo {
                         hunk_index: hunk_idx,
                         line_index: Some(*hunk_line_index),
@@ -461,6 +533,7 @@ pub fn build_unified_display_map(
                         gap_id: None,
                         hidden_count: 0,
                         expand_direction: None,
+                        split_line: None,
                     });
                 }
             }
@@ -470,15 +543,103 @@ pub fn build_unified_display_map(
     rows
 }
 
+/// Display rows showing a merge conflict marker on either side.
+pub fn conflict_rows(delta: &FileDelta, display_map: &[DisplayRowInfo]) -> Vec<usize> {
+    let mut old_markers = HashSet::new();
+    let mut new_markers = HashSet::new();
+    for line in delta.hunks.iter().flat_map(|h| &h.lines) {
+        if line.is_conflict_marker() {
+            old_markers.extend(line.old_lineno);
+            new_markers.extend(line.new_lineno);
+        }
+    }
+    display_map
+        .iter()
+        .enumerate()
+        .filter(|(_, info)| {
+            info.old_lineno.is_some_and(|n| old_markers.contains(&n))
+                || info.new_lineno.is_some_and(|n| new_markers.contains(&n))
+        })
+        .map(|(row, _)| row)
+        .collect()
+}
+
+/// First display row of each change block, in order. Used to address hunks by
+/// number since each file's diff is a single full-context hunk.
+pub fn change_block_rows(delta: &FileDelta, display_map: &[DisplayRowInfo]) -> Vec<usize> {
+    change_blocks(delta)
+        .iter()
+        .filter_map(|block| {
+            display_map.iter().position(|info| {
+                info.hunk_index == block.hunk_index
+                    && info.line_index.is_some_and(|l| block.lines.contains(&l))
+            })
+        })
+        .collect()
+}
+
 /// Build the appropriate display map based on the current view mode.
+/// `hunk_splits` holds `(hunk_index, line_index)` positions of user splits.
 pub fn build_display_map(
     delta: &FileDelta,
     mode: DiffViewMode,
     display_context: usize,
     gap_expansions: &HashMap<usize, usize>,
+    hunk_splits: &[(usize, usize)],
 ) -> Vec<DisplayRowInfo> {
     match mode {
-        DiffViewMode::Split => build_split_display_map(delta, display_context, gap_expansions),
-        DiffViewMode::Unified => build_unified_display_map(delta, display_context, gap_expansions),
+        DiffViewMode::Split => {
+            build_split_display_map(delta, display_context, gap_expansions, hunk_splits)
+        }
+        DiffViewMode::Unified => {
+            build_unified_display_map(delta, display_context, gap_expansions, hunk_splits)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_unified_display_map, split_header_text};
+    use crate::git::types::{DiffLine, DiffLineOrigin, FileDelta, FileStatus, Hunk};
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    #[test]
+    fn user_splits_add_display_only_headers() {
+        let line = |origin, old, new| DiffLine {
+            origin,
+            old_lineno: old,
+            new_lineno: new,
+            content: "x\n".to_string(),
+        };
+        let hunk = Hunk {
+            header: "@@ -1,2 +1,3 @@".to_string(),
+            lines: vec![
+                line(DiffLineOrigin::Context, Some(1), Some(1)),
+                line(DiffLineOrigin::Addition, None, Some(2)),
+                line(DiffLineOrigin::Context, Some(2), Some(3)),
+            ],
+            additions: 1,
+            deletions: 0,
+        };
+        assert_eq!(split_header_text(&hunk, 1), "@@ -2,0 +2,0 @@");
+        let delta = FileDelta {
+            path: PathBuf::from("a.rs"),
+            old_path: None,
+            status: FileStatus::Modified,
+            hunks: vec![hunk],
+            additions: 1,
+            deletions: 0,
+            binary: false,
+            has_conflicts: false,
+            mode: 0o100644,
+        };
+
+        let rows = build_unified_display_map(&delta, 3, &HashMap::new(), &[(0, 1)]);
+        assert_eq!(rows.len(), 5);
+        assert!(rows[0].is_header && rows[0].split_line.is_none());
+        assert!(rows[2].is_header);
+        assert_eq!(rows[2].split_line, Some(1));
+        assert_eq!(rows[3].new_lineno, Some(2));
     }
 }
@@ -0,0 +1,89 @@
+use std::path::Path;
+use std::process::Command;
+
+/// The user's editor command line: `$VISUAL`, then `$EDITOR`, then `vi`.
+pub fn detect_editor() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|v| !v.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string())
+}
+
+/// Argument template for jumping to a line, chosen by editor binary name.
+/// `{file}` and `{line}` are substituted when building the command.
+fn default_open_args(program: &str) -> &'static str {
+    let name = Path::new(program)
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    match name.as_str() {
+        "vi" | "vim" | "nvim" | "nano" | "emacs" | "emacsclient" | "kak" | "micro" => {
+            "+{line} {file}"
+        }
+        "code" | "code-insiders" | "cursor" | "codium" => "-g {file}:{line}",
+        "hx" | "helix" | "subl" | "zed" => "{file}:{line}",
+        _ => "{file}",
+    }
+}
+
+/// Build the editor invocation for `file` at `line`. `editor` may include its own
+/// arguments (e.g. `code --wait`); `open_args` overrides the per-editor template.
+pub fn editor_command(editor: &str, open_args: Option<&str>, file: &Path, line: u32) -> Command {
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+    let mut cmd = Command::new(program);
+    cmd.args(parts);
+
+    let template = open_args.unwrap_or_else(|| default_open_args(program));
+    let file = file.to_string_lossy();
+    let line = line.to_string();
+    for arg in template.split_whitespace() {
+        cmd.arg(arg.replace("{file}", &file).replace("{line}", &line));
+    }
+    cmd
+}
+
+#[cfg(test)]
+mod tests {
+    use super::editor_command;
+    use std::path::Path;
+
+    fn argv(cmd: &std::process::Command) -> Vec<String> {
+        std::iter::once(cmd.get_program())
+            .chain(cmd.get_args())
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn picks_template_by_editor_name() {
+        let file = Path::new("/repo/src/main.rs");
+        assert_eq!(
+            argv(&editor_command("/usr/bin/nvim", None, file, 12)),
+            ["/usr/bin/nvim", "+12", "/repo/src/main.rs"]
+        );
+        assert_eq!(
+            argv(&editor_command("code --wait", None, file, 3)),
+            ["code", "--wait", "-g", "/repo/src/main.rs:3"]
+        );
+        assert_eq!(
+            argv(&editor_command("hx", None, file, 7)),
+            ["hx", "/repo/src/main.rs:7"]
+        );
+    }
+
+    #[test]
+    fn configured_template_overrides_default() {
+        let file = Path::new("a.rs");
+        assert_eq!(
+            argv(&editor_command(
+                "myedit",
+                Some("--line={line} {file}"),
+                file,
+                5
+            )),
+            ["myedit", "--line=5", "a.rs"]
+        );
+    }
+}
@@ -4,13 +4,15 @@ use crossterm::event::{
 };
 use futures::StreamExt;
 use ratatui::layout::Rect;
+use std::collections::HashMap;
 use std::time::Duration;
 use tokio::sync::mpsc;
 
 use crate::action::Action;
 use crate::action::QuitCombo;
+use crate::keybindings::KeySpec;
+use crate::state::annotation_state::MergeResolution;
 use crate::state::app_state::{ActiveView, FocusPanel};
-use crate::state::navigator_state::NavigatorEntry;
 
 #[derive(Debug)]
 pub enum Event {
@@ -23,6 +25,7 @@ pub enum Event {
 
 pub struct EventReader {
     rx: mpsc::UnboundedReceiver<Event>,
+    tasks: Vec<tokio::task::JoinHandle<()>>,
 }
 
 impl EventReader {
@@ -30,7 +33,7 @@ impl EventReader {
         let (tx, rx) = mpsc::unbounded_channel();
 
         let event_tx = tx.clone();
-        tokio::spawn(async move {
+        let input_task = tokio::spawn(async move {
             let mut reader = EventStream::new();
             loop {
                 match reader.next().await {
@@ -61,7 +64,7 @@ impl EventReader {
         });
 
         let tick_tx = tx;
-        tokio::spawn(async move {
+        let tick_task = tokio::spawn(async move {
             let mut interval = tokio::time::interval(tick_rate);
             loop {
                 interval.tick().await;
@@ -71,7 +74,10 @@ impl EventReader {
             }
         });
 
-        Self { rx }
+        Self {
+            rx,
+            tasks: vec![input_task, tick_task],
+        }
     }
 
     pub async fn next(&mut self) -> Option<Event> {
@@ -84,32 +90,65 @@ impl EventReader {
     }
 }
 
+impl Drop for EventReader {
+    /// Stop reading the terminal so a suspended TUI doesn't steal input from a child process.
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
 /// All context needed to map a key event to an action.
-pub struct KeyContext {
+pub struct KeyContext<'a> {
     pub focus: FocusPanel,
     pub search_active: bool,
     pub diff_search_active: bool,
+    pub preview_search_active: bool,
     pub global_search_active: bool,
     pub commit_dialog_open: bool,
     pub target_dialog_open: bool,
+    pub worktree_dialog_open: bool,
+    pub filter_dialog_open: bool,
+    pub jump_hunk_dialog_open: bool,
+    pub rebase_dialog_open: bool,
+    pub push_dialog_open: bool,
+    pub replace_dialog_open: bool,
+    pub file_log_open: bool,
+    pub replace_confirm_open: bool,
     pub comment_editor_open: bool,
     pub agent_selector_open: bool,
     pub annotation_menu_open: bool,
+    pub context_menu_open: bool,
+    /// First key of a chord pressed in the diff view, waiting for the second.
+    pub chord_prefix: Option<char>,
     pub restore_confirm_open: bool,
+    pub worktree_remove_confirm_open: bool,
+    pub revert_confirm_open: bool,
+    pub hunk_patch_confirm_open: bool,
+    pub patch_summary_open: bool,
+    pub session_dialog_open: bool,
+    pub session_import_open: bool,
     pub settings_open: bool,
     pub visual_mode_active: bool,
     pub active_view: ActiveView,
     pub pty_focus: bool,
     pub checklist_panel_open: bool,
+    /// Whether the selected file is collapsed, so `x` expands rather than collapses.
+    pub file_collapsed: bool,
+    /// Whether the navigator is hidden, so `Esc` in the diff view brings it back.
+    pub focus_mode: bool,
+    pub keybindings: &'a HashMap<Action, KeySpec>,
 }
 
 /// Context for mouse event mapping.
-pub struct MouseContext<'a> {
+pub struct MouseContext {
     pub navigator_rect: Rect,
     pub diff_view_rect: Rect,
-    pub navigator_scroll_offset: usize,
-    pub navigator_item_count: usize,
-    pub navigator_visible_entries: &'a [(usize, &'a NavigatorEntry)],
+    /// Delta index of the navigator row under the pointer, from the last render.
+    pub navigator_hit: Option<usize>,
+    /// Screen area of the right-click menu while it's open.
+    pub context_menu_rect: Option<Rect>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -118,7 +157,7 @@ enum Panel {
     DiffView,
 }
 
-impl MouseContext<'_> {
+impl MouseContext {
     /// Determine which panel a screen coordinate falls in.
     fn panel_at(&self, col: u16, row: u16) -> Option<Panel> {
         if self.navigator_rect.x <= col
@@ -137,21 +176,10 @@ impl MouseContext<'_> {
             None
         }
     }
-
-    /// Convert a mouse row in the navigator area to a visible entry index.
-    fn navigator_row_to_visible_index(&self, row: u16) -> Option<usize> {
-        let relative_row = row.saturating_sub(self.navigator_rect.y + 1); // +1 for border
-        let visible_index = self.navigator_scroll_offset + relative_row as usize;
-        if visible_index < self.navigator_item_count {
-            Some(visible_index)
-        } else {
-            None
-        }
-    }
 }
 
 /// Map a key event to an action based on current app context.
-pub fn map_key_to_action(key: KeyEvent, ctx: &KeyContext) -> Option<Action> {
+pub fn map_key_to_action(key: KeyEvent, ctx: &KeyContext<'_>) -> Option<Action> {
     // Priority 0: PTY focus mode - forward ALL keys except Esc to the PTY.
     // This must come first so Ctrl+C/D go to the agent, not quit mdiff.
     if ctx.pty_focus {
@@ -175,6 +203,11 @@ pub fn map_key_to_action(key: KeyEvent, ctx: &KeyContext) -> Option<Action> {
         }
     }
 
+    // Priority 0.74: Staged/unstaged summary popup closes on any key
+    if ctx.patch_summary_open {
+        return Some(Action::ClosePatchSummary);
+    }
+
     // Priority 0.75: Restore confirm dialog
     if ctx.restore_confirm_open {
         return match key.code {
@@ -184,6 +217,55 @@ pub fn map_key_to_action(key: KeyEvent, ctx: &KeyContext) -> Option<Action> {
         };
     }
 
+    // Priority 0.755: Revert commit confirm dialog
+    if ctx.revert_confirm_open {
+        return match key.code {
+            KeyCode::Enter | KeyCode::Char('y') => Some(Action::ConfirmRevertCommit),
+            KeyCode::Esc | KeyCode::Char('n') => Some(Action::CancelRevertCommit),
+            _ => None,
+        };
+    }
+
+    // Priority 0.76: Worktree removal confirm dialog
+    if ctx.worktree_remove_confirm_open {
+        return match key.code {
+            KeyCode::Enter | KeyCode::Char('y') => Some(Action::ConfirmDeleteWorktree),
+            KeyCode::Esc | KeyCode::Char('n') => Some(Action::CancelDeleteWorktree),
+            _ => None,
+        };
+    }
+
+    // Priority 0.77: Conflicting annotations in an imported session
+    if ctx.session_import_open {
+        return match key.code {
+            KeyCode::Char('m') => Some(Action::ResolveSessionImport(MergeResolution::KeepMine)),
+            KeyCode::Char('t') => Some(Action::ResolveSessionImport(MergeResolution::TakeTheirs)),
+            KeyCode::Char('b') | KeyCode::Enter => {
+                Some(Action::ResolveSessionImport(MergeResolution::KeepBoth))
+            }
+            KeyCode::Esc => Some(Action::CancelSessionImport),
+            _ => None,
+        };
+    }
+
+    // Priority 0.78: Hunk apply/revert confirmation
+    if ctx.hunk_patch_confirm_open {
+        return match key.code {
+            KeyCode::Enter | KeyCode::Char('y') => Some(Action::ConfirmHunkPatch),
+            KeyCode::Esc | KeyCode::Char('n') => Some(Action::CancelHunkPatch),
+            _ => None,
+        };
+    }
+
+    // Priority 0.79: Search-and-replace confirmation
+    if ctx.replace_confirm_open {
+        return match key.code {
+            KeyCode::Enter | KeyCode::Char('y') => Some(Action::ApplyReplace),
+            KeyCode::Esc | KeyCode::Char('n') => Some(Action::CancelApplyReplace),
+            _ => None,
+        };
+    }
+
     // Priority 1: Commit dialog mode
     if ctx.commit_dialog_open {
         if key.modifiers.contains(KeyModifiers::CONTROL) {
@@ -200,6 +282,7 @@ pub fn map_key_to_action(key: KeyEvent, ctx: &KeyContext) -> Option<Action> {
                 Some(Action::CommitNewline)
             }
             KeyCode::Enter => Some(Action::ConfirmCommit),
+            KeyCode::Tab => Some(Action::ToggleCommitAmend),
             KeyCode::Backspace => Some(Action::CommitBackspace),
             KeyCode::Left => Some(Action::TextCursorLeft),
             KeyCode::Right => Some(Action::TextCursorRight),
@@ -228,11 +311,150 @@ pub fn map_key_to_action(key: KeyEvent, ctx: &KeyContext) -> Option<Action> {
             KeyCode::Right => Some(Action::TextCursorRight),
             KeyCode::Home => Some(Action::TextCursorHome),
             KeyCode::End => Some(Action::TextCursorEnd),
+            KeyCode::Tab => Some(Action::TargetComplete),
             KeyCode::Char(c) => Some(Action::TargetChar(c)),
             _ => None,
         };
     }
 
+    // Priority 1.55: File filter dialog
+    if ctx.filter_dialog_open {
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return match key.code {
+                KeyCode::Char('a') => Some(Action::TextCursorHome),
+                KeyCode::Char('e') => Some(Action::TextCursorEnd),
+                KeyCode::Char('w') => Some(Action::TextDeleteWord),
+                _ => None,
+            };
+        }
+        return match key.code {
+            KeyCode::Esc => Some(Action::CancelFilter),
+            KeyCode::Enter => Some(Action::ConfirmFilter),
+            KeyCode::Backspace => Some(Action::FilterBackspace),
+            KeyCode::Left => Some(Action::TextCursorLeft),
+            KeyCode::Right => Some(Action::TextCursorRight),
+            KeyCode::Home => Some(Action::TextCursorHome),
+            KeyCode::End => Some(Action::TextCursorEnd),
+            KeyCode::Char(c) => Some(Action::FilterChar(c)),
+            _ => None,
+        };
+    }
+
+    // Priority 1.555: Session file path dialog
+    if ctx.session_dialog_open {
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return match key.code {
+                KeyCode::Char('a') => Some(Action::TextCursorHome),
+                KeyCode::Char('e') => Some(Action::TextCursorEnd),
+                KeyCode::Char('w') => Some(Action::TextDeleteWord),
+                _ => None,
+            };
+        }
+        return match key.code {
+            KeyCode::Esc => Some(Action::CancelSessionDialog),
+            KeyCode::Enter => Some(Action::ConfirmSessionDialog),
+            KeyCode::Backspace => Some(Action::SessionDialogBackspace),
+            KeyCode::Left => Some(Action::TextCursorLeft),
+            KeyCode::Right => Some(Action::TextCursorRight),
+            KeyCode::Home => Some(Action::TextCursorHome),
+            KeyCode::End => Some(Action::TextCursorEnd),
+            KeyCode::Char(c) => Some(Action::SessionDialogChar(c)),
+            _ => None,
+        };
+    }
+
+    // Priority 1.56: Jump-to-hunk dialog
+    if ctx.jump_hunk_dialog_open {
+        return match key.code {
+            KeyCode::Esc => Some(Action::CancelJumpToHunk),
+            KeyCode::Enter => Some(Action::JumpToHunk),
+            KeyCode::Backspace => Some(Action::JumpToHunkBackspace),
+            KeyCode::Char(c) if c.is_ascii_digit() => Some(Action::JumpToHunkChar(c)),
+            _ => None,
+        };
+    }
+
+    // Priority 1.562: Interactive rebase commit count
+    if ctx.rebase_dialog_open {
+        return match key.code {
+            KeyCode::Esc => Some(Action::CancelRebaseInteractive),
+            KeyCode::Enter => Some(Action::ConfirmRebaseInteractive),
+            KeyCode::Backspace => Some(Action::RebaseDialogBackspace),
+            KeyCode::Char(c) if c.is_ascii_digit() => Some(Action::RebaseDialogChar(c)),
+            _ => None,
+        };
+    }
+
+    // Priority 1.565: Push upstream dialog
+    if ctx.push_dialog_open {
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return match key.code {
+                KeyCode::Char('a') => Some(Action::TextCursorHome),
+                KeyCode::Char('e') => Some(Action::TextCursorEnd),
+                KeyCode::Char('w') => Some(Action::TextDeleteWord),
+                _ => None,
+            };
+        }
+        return match key.code {
+            KeyCode::Esc => Some(Action::CancelPushUpstream),
+            KeyCode::Enter => Some(Action::ConfirmPushUpstream),
+            KeyCode::Backspace => Some(Action::PushDialogBackspace),
+            KeyCode::Left => Some(Action::TextCursorLeft),
+            KeyCode::Right => Some(Action::TextCursorRight),
+            KeyCode::Home => Some(Action::TextCursorHome),
+            KeyCode::End => Some(Action::TextCursorEnd),
+            KeyCode::Char(c) => Some(Action::PushDialogChar(c)),
+            _ => None,
+        };
+    }
+
+    // Priority 1.57: Search-and-replace dialog
+    if ctx.replace_dialog_open {
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return match key.code {
+                KeyCode::Char('a') => Some(Action::TextCursorHome),
+                KeyCode::Char('e') => Some(Action::TextCursorEnd),
+                KeyCode::Char('w') => Some(Action::TextDeleteWord),
+                _ => None,
+            };
+        }
+        return match key.code {
+            KeyCode::Esc => Some(Action::CancelReplace),
+            KeyCode::Enter => Some(Action::ConfirmReplace),
+            KeyCode::Tab | KeyCode::BackTab => Some(Action::ReplaceSwitchField),
+            KeyCode::Backspace => Some(Action::ReplaceBackspace),
+            KeyCode::Left => Some(Action::TextCursorLeft),
+            KeyCode::Right => Some(Action::TextCursorRight),
+            KeyCode::Home => Some(Action::TextCursorHome),
+            KeyCode::End => Some(Action::TextCursorEnd),
+            KeyCode::Char(c) => Some(Action::ReplaceChar(c)),
+            _ => None,
+        };
+    }
+
+    // Priority 1.6: New worktree dialog
+    if ctx.worktree_dialog_open {
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return match key.code {
+                KeyCode::Char('a') => Some(Action::TextCursorHome),
+                KeyCode::Char('e') => Some(Action::TextCursorEnd),
+                KeyCode::Char('w') => Some(Action::TextDeleteWord),
+                _ => None,
+            };
+        }
+        return match key.code {
+            KeyCode::Esc => Some(Action::CancelWorktreeDialog),
+            KeyCode::Enter => Some(Action::ConfirmWorktreeDialog),
+            KeyCode::Backspace => Some(Action::WorktreeDialogBackspace),
+            KeyCode::Left => Some(Action::TextCursorLeft),
+            KeyCode::Right => Some(Action::TextCursorRight),
+            KeyCode::Home => Some(Action::TextCursorHome),
+            KeyCode::End => Some(Action::TextCursorEnd),
+            KeyCode::Char(c) => Some(Action::WorktreeDialogChar(c)),
+            _ => None,
+        };
+    }
+
     // Priority 2: Comment editor mode
     if ctx.comment_editor_open {
         if key.modifiers.contains(KeyModifiers::CONTROL) {
@@ -266,6 +488,7 @@ pub fn map_key_to_action(key: KeyEvent, ctx: &KeyContext) -> Option<Action> {
             KeyCode::Char('k') | KeyCode::Up => Some(Action::SettingsUp),
             KeyCode::Char('h') | KeyCode::Left => Some(Action::SettingsLeft),
             KeyCode::Char('l') | KeyCode::Right => Some(Action::SettingsRight),
+            KeyCode::Enter => Some(Action::SettingsActivate),
             KeyCode::Esc | KeyCode::Char(':') => Some(Action::CloseSettings),
             _ => None,
         };
@@ -299,6 +522,7 @@ pub fn map_key_to_action(key: KeyEvent, ctx: &KeyContext) -> Option<Action> {
             KeyCode::Up | KeyCode::Char('k') => Some(Action::AgentSelectorUp),
             KeyCode::Down | KeyCode::Char('j') => Some(Action::AgentSelectorDown),
             KeyCode::Tab => Some(Action::AgentSelectorCycleModel),
+            KeyCode::Char('f') => Some(Action::FavoriteAgent),
             KeyCode::Backspace => Some(Action::AgentSelectorBackspace),
             KeyCode::Left => Some(Action::TextCursorLeft),
             KeyCode::Right => Some(Action::TextCursorRight),
@@ -321,6 +545,17 @@ pub fn map_key_to_action(key: KeyEvent, ctx: &KeyContext) -> Option<Action> {
         };
     }
 
+    // Priority 2.76: Right-click menu
+    if ctx.context_menu_open {
+        return match key.code {
+            KeyCode::Esc => Some(Action::CloseContextMenu),
+            KeyCode::Up | KeyCode::Char('k') => Some(Action::ContextMenuUp),
+            KeyCode::Down | KeyCode::Char('j') => Some(Action::ContextMenuDown),
+            KeyCode::Enter => Some(Action::ContextMenuSelect),
+            _ => None,
+        };
+    }
+
     // Priority 2.8: Global diff search mode
     if ctx.global_search_active {
         if key.modifiers.contains(KeyModifiers::CONTROL) {
@@ -345,6 +580,28 @@ pub fn map_key_to_action(key: KeyEvent, ctx: &KeyContext) -> Option<Action> {
         };
     }
 
+    // Priority 2.9: Prompt preview search mode
+    if ctx.preview_search_active {
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return match key.code {
+                KeyCode::Char('a') => Some(Action::TextCursorHome),
+                KeyCode::Char('e') => Some(Action::TextCursorEnd),
+                KeyCode::Char('w') => Some(Action::TextDeleteWord),
+                _ => None,
+            };
+        }
+        return match key.code {
+            KeyCode::Esc | KeyCode::Enter => Some(Action::EndPreviewSearch),
+            KeyCode::Backspace => Some(Action::PreviewSearchBackspace),
+            KeyCode::Left => Some(Action::TextCursorLeft),
+            KeyCode::Right => Some(Action::TextCursorRight),
+            KeyCode::Home => Some(Action::TextCursorHome),
+            KeyCode::End => Some(Action::TextCursorEnd),
+            KeyCode::Char(c) => Some(Action::PreviewSearchChar(c)),
+            _ => None,
+        };
+    }
+
     // Priority 3: Diff text search mode
     if ctx.diff_search_active {
         if key.modifiers.contains(KeyModifiers::CONTROL) {
@@ -392,6 +649,28 @@ pub fn map_key_to_action(key: KeyEvent, ctx: &KeyContext) -> Option<Action> {
         };
     }
 
+    // Priority 3.8: Second key of a `zc` / `zo` / `gd` / `yy` / `'n` / `'p` / `]h` / `[h` chord
+    if let (Some(prefix), KeyCode::Char(c)) = (ctx.chord_prefix, key.code) {
+        match (prefix, c) {
+            ('z', 'c') => return Some(Action::FoldAllHunks),
+            ('z', 'o') => return Some(Action::UnfoldAllHunks),
+            ('g', 'd') => return Some(Action::GoToDefinition),
+            ('y', 'y') => return Some(Action::CopyCursorLineContent),
+            ('\'', 'n') => return Some(Action::NextBookmark),
+            ('\'', 'p') => return Some(Action::PrevBookmark),
+            // `]` / `[` already jumped when pressed
+            (']', 'h') | ('[', 'h') => return Some(Action::Noop),
+            _ => {}
+        }
+    }
+
+    // Priority 3.9: User keybindings from config, in the diff explorer
+    if ctx.active_view == ActiveView::DiffExplorer && !ctx.visual_mode_active {
+        if let Some((action, _)) = ctx.keybindings.iter().find(|(_, spec)| spec.matches(&key)) {
+            return Some(action.clone());
+        }
+    }
+
     // Priority 4: Global bindings (always active)
     match key.code {
         KeyCode::Char('q') if !ctx.visual_mode_active => return Some(Action::Quit),
@@ -404,6 +683,9 @@ pub fn map_key_to_action(key: KeyEvent, ctx: &KeyContext) -> Option<Action> {
         KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             return Some(Action::StartGlobalSearch)
         }
+        KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            return Some(Action::RebaseInteractive)
+        }
         _ => {}
     }
 
@@ -413,12 +695,41 @@ pub fn map_key_to_action(key: KeyEvent, ctx: &KeyContext) -> Option<Action> {
             match key.code {
                 KeyCode::Char(']') => return Some(Action::NextAnnotation),
                 KeyCode::Char('[') => return Some(Action::PrevAnnotation),
+                KeyCode::Char('n') => return Some(Action::NextFile),
+                KeyCode::Char('p') => return Some(Action::PrevFile),
+                KeyCode::Char('h') => return Some(Action::ReplaceInDiff),
+                KeyCode::Char('t') => return Some(Action::NewTab),
+                KeyCode::Char('o') => return Some(Action::OpenFileInExplorer),
+                KeyCode::Char('s') => return Some(Action::StageHunk),
+                KeyCode::Char('u') => return Some(Action::UnstageHunk),
+                KeyCode::Char('y') => return Some(Action::CopyFileList),
+                KeyCode::Tab => return Some(Action::NextTab),
+                KeyCode::BackTab => return Some(Action::PrevTab),
+                KeyCode::Char('z') if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                    return Some(Action::RedoAnnotation)
+                }
+                KeyCode::Char('Z') => return Some(Action::RedoAnnotation),
+                KeyCode::Char('z') => return Some(Action::UndoAnnotation),
+                _ => {}
+            }
+        }
+        // Terminals without keyboard enhancements can't send Ctrl+Tab
+        if key.modifiers.contains(KeyModifiers::ALT) {
+            match key.code {
+                KeyCode::Char('w') => return Some(Action::CloseTab),
+                KeyCode::Char('f') => return Some(Action::ToggleFocusMode),
+                KeyCode::Right => return Some(Action::NextTab),
+                KeyCode::Left => return Some(Action::PrevTab),
+                KeyCode::Up => return Some(Action::PromptPreviewGrow),
+                KeyCode::Down => return Some(Action::PromptPreviewShrink),
                 _ => {}
             }
         }
         match key.code {
-            KeyCode::Char(']') => return Some(Action::JumpNextHunk),
-            KeyCode::Char('[') => return Some(Action::JumpPrevHunk),
+            KeyCode::Char(']') => return Some(Action::StartChord(']')),
+            KeyCode::Char('[') => return Some(Action::StartChord('[')),
+            KeyCode::Char('}') => return Some(Action::NextConflict),
+            KeyCode::Char('{') => return Some(Action::PrevConflict),
             _ => {}
         }
     }
@@ -431,6 +742,14 @@ pub fn map_key_to_action(key: KeyEvent, ctx: &KeyContext) -> Option<Action> {
             KeyCode::Enter => Some(Action::WorktreeSelect),
             KeyCode::Char('r') => Some(Action::WorktreeRefresh),
             KeyCode::Char('f') => Some(Action::WorktreeFreeze),
+            KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(Action::UnlockWorktree)
+            }
+            KeyCode::Char('l') => Some(Action::LockWorktree),
+            KeyCode::Char('F') => Some(Action::WorktreeFetch),
+            KeyCode::Char('P') => Some(Action::WorktreePull),
+            KeyCode::Char('N') => Some(Action::OpenWorktreeDialog),
+            KeyCode::Char('D') => Some(Action::DeleteWorktree),
             KeyCode::Esc => Some(Action::WorktreeBack),
             _ => None,
         };
@@ -445,7 +764,10 @@ pub fn map_key_to_action(key: KeyEvent, ctx: &KeyContext) -> Option<Action> {
         return match key.code {
             KeyCode::Up | KeyCode::Char('k') => Some(Action::AgentOutputsUp),
             KeyCode::Down | KeyCode::Char('j') => Some(Action::AgentOutputsDown),
+            KeyCode::PageUp => Some(Action::AgentOutputsListScrollUp),
+            KeyCode::PageDown => Some(Action::AgentOutputsListScrollDown),
             KeyCode::Char('y') => Some(Action::AgentOutputsCopyPrompt),
+            KeyCode::Char('r') => Some(Action::RerunAgent),
             KeyCode::Char('w') => Some(Action::AgentOutputsSwitchWorktree),
             KeyCode::Enter => Some(Action::EnterPtyFocus),
             KeyCode::Esc => Some(Action::SwitchToAgentOutputs), // toggle back
@@ -465,6 +787,31 @@ pub fn map_key_to_action(key: KeyEvent, ctx: &KeyContext) -> Option<Action> {
         };
     }
 
+    // Priority 5.7: Review summary view
+    if ctx.active_view == ActiveView::ReviewSummary {
+        return match key.code {
+            KeyCode::Up | KeyCode::Char('k') => Some(Action::ReviewSummaryUp),
+            KeyCode::Down | KeyCode::Char('j') => Some(Action::ReviewSummaryDown),
+            KeyCode::Enter => Some(Action::ReviewSummarySelect),
+            KeyCode::Char('d') => Some(Action::ReviewSummaryDeleteFile),
+            KeyCode::Char('e') => Some(Action::ExportChecklist),
+            KeyCode::Esc | KeyCode::Char('A') => Some(Action::ToggleReviewSummary),
+            _ => None,
+        };
+    }
+
+    // Priority 5.8: File log panel (other keys fall through)
+    if ctx.file_log_open {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => return Some(Action::FileLogUp),
+            KeyCode::Down | KeyCode::Char('j') => return Some(Action::FileLogDown),
+            KeyCode::Enter => return Some(Action::FileLogSelect),
+            KeyCode::Char('R') => return Some(Action::RevertCommit),
+            KeyCode::Esc => return Some(Action::ToggleFileLog),
+            _ => {}
+        }
+    }
+
     // Priority 6: Diff explorer global bindings
     match key.code {
         KeyCode::Tab => return Some(Action::ToggleViewMode),
@@ -474,22 +821,43 @@ pub fn map_key_to_action(key: KeyEvent, ctx: &KeyContext) -> Option<Action> {
             return match ctx.focus {
                 FocusPanel::Navigator => Some(Action::StartSearch),
                 FocusPanel::DiffView => Some(Action::StartDiffSearch),
+                FocusPanel::PromptPreview => Some(Action::StartPreviewSearch),
             }
         }
         KeyCode::Char('s') if !ctx.visual_mode_active => return Some(Action::StageFile),
         KeyCode::Char('u') if !ctx.visual_mode_active => return Some(Action::UnstageFile),
+        KeyCode::Char('S') if !ctx.visual_mode_active => return Some(Action::BulkStage),
+        KeyCode::Char('U') if !ctx.visual_mode_active => return Some(Action::BulkUnstage),
         KeyCode::Char('r') if !ctx.visual_mode_active => return Some(Action::RestoreFile),
         KeyCode::Char('c') if !ctx.visual_mode_active => return Some(Action::OpenCommitDialog),
         KeyCode::Char('o') if !ctx.visual_mode_active => return Some(Action::SwitchToAgentOutputs),
         KeyCode::Char('F') => return Some(Action::ToggleFeedbackSummary),
+        KeyCode::Char('A') if !ctx.visual_mode_active => return Some(Action::ToggleReviewSummary),
+        KeyCode::Char('L') if !ctx.visual_mode_active => return Some(Action::ToggleFileLog),
+        KeyCode::Char('E') if !ctx.visual_mode_active => return Some(Action::ExportAnnotations),
+        KeyCode::Char('I') if !ctx.visual_mode_active => return Some(Action::ImportGithubReviews),
         KeyCode::Char('R') => return Some(Action::RefreshDiff),
         KeyCode::Char('n') if !ctx.visual_mode_active => {
             return match ctx.focus {
                 FocusPanel::DiffView => Some(Action::DiffSearchNext),
                 FocusPanel::Navigator => Some(Action::NextUnreviewed),
+                FocusPanel::PromptPreview => Some(Action::PreviewSearchNext),
             }
         }
         KeyCode::Char('t') if !ctx.visual_mode_active => return Some(Action::OpenTargetDialog),
+        KeyCode::Char('f') if !ctx.visual_mode_active => return Some(Action::FilterFiles),
+        KeyCode::Char('T') if !ctx.visual_mode_active => return Some(Action::ToggleFileStatus),
+        KeyCode::Char('H') if !ctx.visual_mode_active => return Some(Action::OpenJumpToHunk),
+        KeyCode::Char('X') if !ctx.visual_mode_active => return Some(Action::SplitHunk),
+        KeyCode::Char('x') if !ctx.visual_mode_active => {
+            return Some(if ctx.file_collapsed {
+                Action::ExpandFile
+            } else {
+                Action::CollapseFile
+            })
+        }
+        KeyCode::Char('+') if !ctx.visual_mode_active => return Some(Action::IncreaseContext),
+        KeyCode::Char('-') if !ctx.visual_mode_active => return Some(Action::DecreaseContext),
         KeyCode::Char('C') if !ctx.visual_mode_active => return Some(Action::ToggleChecklist),
         KeyCode::Char('?') => return Some(Action::ToggleWhichKey),
         KeyCode::Char(':') if !ctx.visual_mode_active => return Some(Action::OpenSettings),
@@ -517,31 +885,92 @@ pub fn map_key_to_action(key: KeyEvent, ctx: &KeyContext) -> Option<Action> {
             KeyCode::Char('g') => Some(Action::NavigatorTop),
             KeyCode::Char('G') => Some(Action::NavigatorBottom),
             KeyCode::Char('m') => Some(Action::ToggleFileReviewed),
+            KeyCode::Char(' ') => Some(Action::ToggleFileMark),
+            KeyCode::Char('P') => Some(Action::Push),
+            KeyCode::Char('i') => Some(Action::DiffCurrentFilePatch),
             KeyCode::Right | KeyCode::Char('l') | KeyCode::Enter => Some(Action::FocusDiffView),
             _ => None,
         },
         FocusPanel::DiffView => match key.code {
+            KeyCode::Esc if ctx.focus_mode => Some(Action::ToggleFocusMode),
+            KeyCode::Char('j') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Some(Action::DiffFormatJson)
+            }
             KeyCode::Up | KeyCode::Char('k') => Some(Action::ScrollUp),
             KeyCode::Down | KeyCode::Char('j') => Some(Action::ScrollDown),
-            KeyCode::Char('g') => Some(Action::ScrollToTop),
+            KeyCode::Char('g') => Some(Action::StartChord('g')),
             KeyCode::Char('G') => Some(Action::ScrollToBottom),
+            KeyCode::Left if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                Some(Action::ScrollLeft)
+            }
+            KeyCode::Right if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                Some(Action::ScrollRight)
+            }
+            KeyCode::Char('<') => Some(Action::ScrollLeft),
+            KeyCode::Char('>') => Some(Action::ScrollRight),
+            KeyCode::Char('z') => Some(Action::StartChord('z')),
             KeyCode::Left | KeyCode::Char('h') => Some(Action::FocusNavigator),
             KeyCode::PageUp => Some(Action::ScrollPageUp),
             KeyCode::PageDown => Some(Action::ScrollPageDown),
             KeyCode::Char(' ') => Some(Action::ExpandContext),
             KeyCode::Char('v') | KeyCode::Char('V') => Some(Action::EnterVisualMode),
+            KeyCode::Char('i') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Some(Action::ToggleInlineAnnotations)
+            }
             KeyCode::Char('i') => Some(Action::OpenCommentEditor),
             KeyCode::Char('p') => Some(Action::TogglePromptPreview),
-            KeyCode::Char('y') => Some(Action::CopyPromptToClipboard),
+            KeyCode::Char('J') => Some(Action::FocusPromptPreview),
+            KeyCode::Char('Y') | KeyCode::Char('y')
+                if key.modifiers.contains(KeyModifiers::ALT) =>
+            {
+                Some(Action::CopyHunkPatch)
+            }
+            KeyCode::Char('y') => Some(Action::StartChord('y')),
+            KeyCode::Char('m') => Some(Action::ToggleBookmark),
+            KeyCode::Char('\'') => Some(Action::StartChord('\'')),
+            KeyCode::Char('Y') => Some(Action::CopyFileDiff),
+            KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Some(Action::ToggleAnnotationsVisible)
+            }
             KeyCode::Char('a') => Some(Action::OpenAnnotationMenu),
             KeyCode::Char('N') => Some(Action::DiffSearchPrev),
+            KeyCode::Char('e') => Some(Action::OpenInEditor),
+            KeyCode::Char('P') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Some(Action::RevertHunk)
+            }
+            KeyCode::Char('P') => Some(Action::ApplyHunk),
+            _ => None,
+        },
+        FocusPanel::PromptPreview => match key.code {
+            KeyCode::Up | KeyCode::Char('k') => Some(Action::PromptPreviewScrollUp),
+            KeyCode::Down | KeyCode::Char('j') => Some(Action::PromptPreviewScrollDown),
+            KeyCode::PageUp => Some(Action::PromptPreviewPageUp),
+            KeyCode::PageDown => Some(Action::PromptPreviewPageDown),
+            KeyCode::Char('g') => Some(Action::PromptPreviewTop),
+            KeyCode::Char('G') => Some(Action::PromptPreviewBottom),
+            KeyCode::Char('N') => Some(Action::PreviewSearchPrev),
+            KeyCode::Char('p') => Some(Action::TogglePromptPreview),
+            KeyCode::Char('K') | KeyCode::Esc => Some(Action::FocusDiffView),
             _ => None,
         },
     }
 }
 
 /// Map a mouse event to an action based on current app context.
-pub fn map_mouse_to_action(mouse: MouseEvent, ctx: &MouseContext<'_>) -> Option<Action> {
+pub fn map_mouse_to_action(mouse: MouseEvent, ctx: &MouseContext) -> Option<Action> {
+    // An open menu takes every click: on an entry picks it, anywhere else closes it
+    if let Some(rect) = ctx.context_menu_rect {
+        let MouseEventKind::Down(_) = mouse.kind else {
+            return None;
+        };
+        let inner = rect.inner(ratatui::layout::Margin::new(1, 1));
+        return if inner.contains(ratatui::layout::Position::new(mouse.column, mouse.row)) {
+            Some(Action::ContextMenuClick((mouse.row - inner.y) as usize))
+        } else {
+            Some(Action::CloseContextMenu)
+        };
+    }
+
     match mouse.kind {
         // Scroll wheel
         MouseEventKind::ScrollUp => match ctx.panel_at(mouse.column, mouse.row) {
@@ -557,12 +986,7 @@ pub fn map_mouse_to_action(mouse: MouseEvent, ctx: &MouseContext<'_>) -> Option<
         // Left click
         MouseEventKind::Down(MouseButton::Left) => {
             match ctx.panel_at(mouse.column, mouse.row) {
-                Some(Panel::Navigator) => {
-                    let visible_index = ctx.navigator_row_to_visible_index(mouse.row);
-                    visible_index
-                        .and_then(|idx| ctx.navigator_visible_entries.get(idx))
-                        .map(|(_, entry)| Action::SelectFile(entry.delta_index))
-                }
+                Some(Panel::Navigator) => ctx.navigator_hit.map(Action::SelectFile),
                 Some(Panel::DiffView) => {
                     // Click to focus diff view + position cursor
                     Some(Action::FocusDiffView)
@@ -570,6 +994,10 @@ pub fn map_mouse_to_action(mouse: MouseEvent, ctx: &MouseContext<'_>) -> Option<
                 _ => None,
             }
         }
+        MouseEventKind::Down(MouseButton::Right) => match ctx.panel_at(mouse.column, mouse.row) {
+            Some(Panel::DiffView) => Some(Action::OpenContextMenu(mouse.column, mouse.row)),
+            _ => None,
+        },
         _ => None,
     }
 }
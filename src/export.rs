@@ -0,0 +1,401 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::config::ExportFormat;
+use crate::git::types::{DiffLineOrigin, FileDelta};
+use crate::paths;
+use crate::state::annotation_state::Annotation;
+use crate::state::review_state::{FileReviewStatus, ReviewState};
+use crate::state::AnnotationState;
+
+fn export_file(repo_path: &Path, target_label: &str, format: ExportFormat) -> PathBuf {
+    let repo_name = repo_path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "repo".to_string());
+    let sanitized = format!("{repo_name}-{target_label}").replace(['/', '\\', ':', ' '], "_");
+    paths::data_dir().join(format!("{sanitized}-annotations.{}", format.extension()))
+}
+
+/// Render annotations as Markdown, grouped under a `# tag` heading by first tag
+/// (untagged ones last), with one `## path:Lstart-Lend` section per comment.
+pub fn render_markdown(annotations: &AnnotationState) -> String {
+    let mut groups: BTreeMap<Option<&str>, Vec<&Annotation>> = BTreeMap::new();
+    for ann in annotations.all_sorted() {
+        groups
+            .entry(ann.tags.first().map(String::as_str))
+            .or_default()
+            .push(ann);
+    }
+    let has_tags = groups.keys().any(Option::is_some);
+
+    let mut out = String::new();
+    let tagged = groups.iter().filter(|(tag, _)| tag.is_some());
+    let untagged = groups.iter().filter(|(tag, _)| tag.is_none());
+    for (tag, anns) in tagged.chain(untagged) {
+        if has_tags {
+            out.push_str(&format!("# {}\n\n", tag.unwrap_or("untagged")));
+        }
+        for ann in anns {
+            render_markdown_entry(&mut out, ann);
+        }
+    }
+    out
+}
+
+fn render_markdown_entry(out: &mut String, ann: &Annotation) {
+    let range = match (ann.anchor.old_range, ann.anchor.new_range) {
+        (_, Some((s, e))) if s == e => format!("L{s}"),
+        (_, Some((s, e))) => format!("L{s}-L{e}"),
+        (Some((s, e)), None) if s == e => format!("L{s} (old)"),
+        (Some((s, e)), None) => format!("L{s}-L{e} (old)"),
+        (None, None) => "L?".to_string(),
+    };
+    out.push_str(&format!("## {}:{range}\n", ann.anchor.file_path));
+    for line in ann.comment.lines() {
+        out.push_str(&format!("> {line}\n"));
+    }
+    out.push('\n');
+}
+
+/// Write all annotations to the export directory and return the written path.
+pub fn export_annotations(
+    repo_path: &Path,
+    target_label: &str,
+    annotations: &AnnotationState,
+    format: ExportFormat,
+) -> Result<PathBuf> {
+    let contents = match format {
+        ExportFormat::Markdown => render_markdown(annotations),
+        ExportFormat::Json => serde_json::to_string_pretty(&annotations.annotations)
+            .context("Failed to serialize annotations")?,
+    };
+
+    let path = export_file(repo_path, target_label, format);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(&path, contents).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// The diff's files one per line with their status label, e.g. `M src/lib.rs`.
+pub fn render_file_list(deltas: &[FileDelta]) -> String {
+    deltas
+        .iter()
+        .map(|d| format!("{} {}\n", d.status.label(), d.path.to_string_lossy()))
+        .collect()
+}
+
+/// A GitHub task list of the diff's files, ticked when fully reviewed, with
+/// each file's annotations nested under it as `Line 42: comment`.
+pub fn render_review_checklist(
+    deltas: &[FileDelta],
+    review: &ReviewState,
+    annotations: &AnnotationState,
+) -> String {
+    let mut out = String::new();
+    for delta in deltas {
+        let path = delta.path.to_string_lossy();
+        let done = review.status(&path) == FileReviewStatus::Reviewed;
+        out.push_str(&format!("- [{}] {path}\n", if done { 'x' } else { ' ' }));
+
+        let mut anns: Vec<&Annotation> = annotations
+            .annotations
+            .get(path.as_ref())
+            .into_iter()
+            .flatten()
+            .collect();
+        anns.sort_by_key(|a| a.anchor.sort_line());
+        for ann in anns {
+            let mut lines = ann.comment.lines();
+            out.push_str(&format!(
+                "  - {}: {}\n",
+                checklist_range(ann),
+                lines.next().unwrap_or("").trim()
+            ));
+            for line in lines {
+                out.push_str(&format!("    {line}\n"));
+            }
+        }
+    }
+    out
+}
+
+fn checklist_range(ann: &Annotation) -> String {
+    if ann.unanchored {
+        return "Unanchored".to_string();
+    }
+    match (ann.anchor.old_range, ann.anchor.new_range) {
+        (_, Some((s, e))) if s == e => format!("Line {s}"),
+        (_, Some((s, e))) => format!("Lines {s}-{e}"),
+        (Some((s, e)), None) if s == e => format!("Removed line {s}"),
+        (Some((s, e)), None) => format!("Removed lines {s}-{e}"),
+        (None, None) => "File".to_string(),
+    }
+}
+
+#[derive(Serialize)]
+struct JsonDiff<'a> {
+    files: Vec<JsonFile<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonFile<'a> {
+    path: String,
+    hunks: Vec<JsonHunk<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonHunk<'a> {
+    header: &'a str,
+    lines: Vec<JsonLine<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonLine<'a> {
+    op: &'static str,
+    old: Option<u32>,
+    new: Option<u32>,
+    text: &'a str,
+}
+
+/// The diff as `{"files": [{"path", "hunks": [{"header", "lines": [{"op",
+/// "old", "new", "text"}]}]}]}`, for handing to an LLM as tool input. `op` is
+/// `+`, `-` or a space, and `old`/`new` are `null` on the side a line isn't on.
+pub fn serialize_deltas_to_json(deltas: &[FileDelta]) -> String {
+    let files = deltas
+        .iter()
+        .map(|delta| JsonFile {
+            path: delta.path.to_string_lossy().into_owned(),
+            hunks: delta
+                .hunks
+                .iter()
+                .map(|hunk| JsonHunk {
+                    header: hunk.header.trim_end(),
+                    lines: hunk
+                        .lines
+                        .iter()
+                        .map(|line| JsonLine {
+                            op: match line.origin {
+                                DiffLineOrigin::Addition => "+",
+                                DiffLineOrigin::Deletion => "-",
+                                DiffLineOrigin::Context => " ",
+                            },
+                            old: line.old_lineno,
+                            new: line.new_lineno,
+                            text: line.content.trim_end_matches(['\n', '\r']),
+                        })
+                        .collect(),
+                })
+                .collect(),
+        })
+        .collect();
+    // Plain structs of strings and numbers always serialize
+    serde_json::to_string_pretty(&JsonDiff { files }).unwrap_or_default()
+}
+
+#[derive(Serialize)]
+struct DiffMetadata<'a> {
+    target: &'a str,
+    files: &'a [FileDelta],
+}
+
+/// The whole diff as `{"target", "files": [...]}` for `--json`, with each file
+/// serialized as-is (status, line counts, hunks and their lines).
+pub fn render_diff_json(target: &str, deltas: &[FileDelta]) -> Result<String> {
+    let metadata = DiffMetadata {
+        target,
+        files: deltas,
+    };
+    serde_json::to_string_pretty(&metadata).context("Failed to serialize the diff")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        render_diff_json, render_file_list, render_markdown, render_review_checklist,
+        serialize_deltas_to_json,
+    };
+    use crate::git::types::{DiffLine, DiffLineOrigin, FileDelta, FileStatus, Hunk};
+    use crate::state::annotation_state::{Annotation, LineAnchor};
+    use crate::state::review_state::{compute_diff_hashes, ReviewState};
+    use crate::state::AnnotationState;
+
+    fn annotation(path: &str, old: Option<(u32, u32)>, new: Option<(u32, u32)>) -> Annotation {
+        Annotation {
+            anchor: LineAnchor::new(path.to_string(), old, new),
+            comment: "first line\nsecond line".to_string(),
+            created_at: String::new(),
+            tags: Vec::new(),
+            unanchored: false,
+        }
+    }
+
+    #[test]
+    fn markdown_lists_each_annotation_with_range() {
+        let mut state = AnnotationState::default();
+        state.add(annotation("src/b.rs", Some((4, 4)), None));
+        state.add(annotation("src/a.rs", None, Some((10, 12))));
+
+        let md = render_markdown(&state);
+        assert_eq!(
+            md,
+            "## src/a.rs:L10-L12\n> first line\n> second line\n\n\
+             ## src/b.rs:L4 (old)\n> first line\n> second line\n\n"
+        );
+    }
+
+    #[test]
+    fn markdown_groups_by_first_tag() {
+        let mut state = AnnotationState::default();
+        let mut bug = annotation("src/b.rs", None, Some((1, 1)));
+        bug.tags = vec!["bug".to_string()];
+        state.add(bug);
+        state.add(annotation("src/a.rs", None, Some((2, 2))));
+
+        let md = render_markdown(&state);
+        let bug_at = md.find("# bug\n").unwrap();
+        let untagged_at = md.find("# untagged\n").unwrap();
+        assert!(bug_at < md.find("src/b.rs").unwrap());
+        assert!(untagged_at > md.find("src/b.rs").unwrap());
+        assert!(untagged_at < md.find("src/a.rs").unwrap());
+    }
+
+    #[test]
+    fn checklist_ticks_reviewed_files_and_nests_annotations() {
+        let file = |path: &str| FileDelta {
+            path: path.into(),
+            old_path: None,
+            status: FileStatus::Modified,
+            hunks: vec![Hunk {
+                header: "@@ -1 +1 @@".to_string(),
+                lines: vec![DiffLine {
+                    origin: DiffLineOrigin::Addition,
+                    old_lineno: None,
+                    new_lineno: Some(1),
+                    content: format!("{path}\n"),
+                }],
+                additions: 1,
+                deletions: 0,
+            }],
+            additions: 1,
+            deletions: 0,
+            binary: false,
+            has_conflicts: false,
+            mode: 0o100644,
+        };
+        let deltas = [file("src/lib.rs"), file("src/main.rs")];
+        let mut review = ReviewState::default();
+        review.on_diff_refresh(compute_diff_hashes(&deltas));
+        review.mark_reviewed("src/lib.rs");
+        let mut annotations = AnnotationState::default();
+        annotations.add(annotation("src/main.rs", None, Some((42, 42))));
+
+        assert_eq!(
+            render_review_checklist(&deltas, &review, &annotations),
+            "- [x] src/lib.rs\n\
+             - [ ] src/main.rs\n  - Line 42: first line\n    second line\n"
+        );
+    }
+
+    #[test]
+    fn file_list_has_one_status_and_path_per_line() {
+        let file = |path: &str, status| FileDelta {
+            path: path.into(),
+            old_path: None,
+            status,
+            hunks: Vec::new(),
+            additions: 0,
+            deletions: 0,
+            binary: false,
+            has_conflicts: false,
+            mode: 0o100644,
+        };
+        let deltas = [
+            file("src/lib.rs", FileStatus::Modified),
+            file("src/main.rs", FileStatus::Added),
+        ];
+        assert_eq!(render_file_list(&deltas), "M src/lib.rs\nA src/main.rs\n");
+    }
+
+    #[test]
+    fn json_lists_hunk_lines_with_ops_and_numbers() {
+        let line = |origin, old, new, content: &str| DiffLine {
+            origin,
+            old_lineno: old,
+            new_lineno: new,
+            content: content.to_string(),
+        };
+        let delta = FileDelta {
+            path: "src/a.rs".into(),
+            old_path: None,
+            status: FileStatus::Modified,
+            hunks: vec![Hunk {
+                header: "@@ -1,2 +1,2 @@\n".to_string(),
+                lines: vec![
+                    line(DiffLineOrigin::Context, Some(1), Some(1), "fn a() {\n"),
+                    line(DiffLineOrigin::Deletion, Some(2), None, "    old();\n"),
+                    line(DiffLineOrigin::Addition, None, Some(2), "    new();\n"),
+                ],
+                additions: 1,
+                deletions: 1,
+            }],
+            additions: 1,
+            deletions: 1,
+            binary: false,
+            has_conflicts: false,
+            mode: 0o100644,
+        };
+
+        let json: serde_json::Value =
+            serde_json::from_str(&serialize_deltas_to_json(std::slice::from_ref(&delta))).unwrap();
+        let hunk = &json["files"][0]["hunks"][0];
+        assert_eq!(json["files"][0]["path"], "src/a.rs");
+        assert_eq!(hunk["header"], "@@ -1,2 +1,2 @@");
+        assert_eq!(
+            hunk["lines"][1],
+            serde_json::json!({"op": "-", "old": 2, "new": null, "text": "    old();"})
+        );
+        assert_eq!(hunk["lines"][2]["op"], "+");
+        assert_eq!(hunk["lines"][0]["op"], " ");
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render_diff_json("HEAD", &[delta]).unwrap()).unwrap();
+        let file = &json["files"][0];
+        assert_eq!(json["target"], "HEAD");
+        assert_eq!(file["status"], "modified");
+        assert_eq!(file["additions"], 1);
+        assert_eq!(file["binary"], false);
+        assert_eq!(file["hunks"][0]["lines"][2]["origin"], "addition");
+        assert_eq!(file["hunks"][0]["lines"][2]["new_lineno"], 2);
+        assert_eq!(file["path"], "src/a.rs");
+        assert!(file["old_path"].is_null());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn diff_json_keeps_non_utf8_paths() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let delta = FileDelta {
+            path: std::ffi::OsStr::from_bytes(b"bad\xff.bin").into(),
+            old_path: None,
+            status: FileStatus::Added,
+            hunks: Vec::new(),
+            additions: 0,
+            deletions: 0,
+            binary: true,
+            has_conflicts: false,
+            mode: 0o100644,
+        };
+        let json: serde_json::Value =
+            serde_json::from_str(&render_diff_json("HEAD", &[delta]).unwrap()).unwrap();
+        assert_eq!(json["files"][0]["path"], "bad\u{fffd}.bin");
+    }
+}
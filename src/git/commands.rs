@@ -1,6 +1,9 @@
 use anyhow::{Context, Result};
+use std::io::Write;
 use std::path::Path;
-use std::process::Command;
+use std::process::{Command, Stdio};
+
+use super::types::CommitSummary;
 
 pub struct GitCli {
     workdir: std::path::PathBuf,
@@ -58,6 +61,51 @@ impl GitCli {
         Ok(())
     }
 
+    /// Apply a patch to the index (`cached`) or the working tree, optionally in reverse.
+    pub fn apply_patch(&self, patch: &str, cached: bool, reverse: bool) -> Result<()> {
+        let mut cmd = Command::new("git");
+        cmd.arg("apply");
+        if cached {
+            cmd.arg("--cached");
+        }
+        if reverse {
+            cmd.arg("-R");
+        }
+        let mut child = cmd
+            .arg("-")
+            .current_dir(&self.workdir)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("Failed to run git apply")?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(patch.as_bytes())
+                .context("Failed to write patch to git apply")?;
+        }
+
+        let output = child
+            .wait_with_output()
+            .context("Failed to run git apply")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("git apply failed: {stderr}");
+        }
+        Ok(())
+    }
+
+    /// `git apply --cached`: add the changes in `patch` to the index.
+    pub fn stage_patch(&self, patch: &str) -> Result<()> {
+        self.apply_patch(patch, true, false)
+    }
+
+    /// `git apply --cached -R`: take the changes in `patch` back out of the index.
+    pub fn unstage_patch(&self, patch: &str) -> Result<()> {
+        self.apply_patch(patch, true, true)
+    }
+
     pub fn commit(&self, message: &str) -> Result<()> {
         let output = Command::new("git")
             .args(["commit", "-m", message])
@@ -72,6 +120,289 @@ impl GitCli {
         Ok(())
     }
 
+    /// `git commit --amend`, replacing the last commit's message with `message`.
+    pub fn commit_amend(&self, message: &str) -> Result<()> {
+        let output = Command::new("git")
+            .args(["commit", "--amend", "-m", message])
+            .current_dir(&self.workdir)
+            .output()
+            .context("Failed to run git commit --amend")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("git commit --amend failed: {stderr}");
+        }
+        Ok(())
+    }
+
+    /// Full message of the `HEAD` commit, without the trailing newline.
+    pub fn last_commit_message(&self) -> Result<String> {
+        let output = Command::new("git")
+            .args(["log", "-1", "--format=%B"])
+            .current_dir(&self.workdir)
+            .output()
+            .context("Failed to run git log")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("git log failed: {stderr}");
+        }
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .trim_end()
+            .to_string())
+    }
+
+    /// Remove the linked worktree at `path`, discarding any changes left in it.
+    pub fn remove_worktree(&self, path: &Path) -> Result<()> {
+        let output = Command::new("git")
+            .args(["worktree", "remove", "--force"])
+            .arg(path)
+            .current_dir(&self.workdir)
+            .output()
+            .context("Failed to run git worktree remove")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("git worktree remove failed: {stderr}");
+        }
+        Ok(())
+    }
+
+    /// `git worktree lock [--reason <reason>] <path>`.
+    pub fn lock_worktree(&self, path: &Path, reason: Option<&str>) -> Result<()> {
+        let mut cmd = Command::new("git");
+        cmd.args(["worktree", "lock"]);
+        if let Some(reason) = reason {
+            cmd.args(["--reason", reason]);
+        }
+        let output = cmd
+            .arg(path)
+            .current_dir(&self.workdir)
+            .output()
+            .context("Failed to run git worktree lock")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("git worktree lock failed: {}", stderr.trim());
+        }
+        Ok(())
+    }
+
+    pub fn unlock_worktree(&self, path: &Path) -> Result<()> {
+        let output = Command::new("git")
+            .args(["worktree", "unlock"])
+            .arg(path)
+            .current_dir(&self.workdir)
+            .output()
+            .context("Failed to run git worktree unlock")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("git worktree unlock failed: {}", stderr.trim());
+        }
+        Ok(())
+    }
+
+    /// Add a worktree at `path` on a new branch, or on `branch` if it already exists.
+    pub fn create_worktree(&self, branch: &str, path: &Path) -> Result<()> {
+        let branch_exists = Command::new("git")
+            .args(["show-ref", "--verify", "--quiet"])
+            .arg(format!("refs/heads/{branch}"))
+            .current_dir(&self.workdir)
+            .status()
+            .context("Failed to run git show-ref")?
+            .success();
+
+        let mut cmd = Command::new("git");
+        cmd.args(["worktree", "add"]).arg(path);
+        if branch_exists {
+            cmd.arg(branch);
+        } else {
+            cmd.args(["-b", branch]);
+        }
+        let output = cmd
+            .current_dir(&self.workdir)
+            .output()
+            .context("Failed to run git worktree add")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("git worktree add failed: {stderr}");
+        }
+        Ok(())
+    }
+
+    /// `git revert --no-commit <oid>`: undo a commit's changes in the working
+    /// tree and index without committing. Fails when the revert conflicts.
+    pub fn revert_no_commit(&self, oid: &str) -> Result<()> {
+        let output = Command::new("git")
+            .args(["revert", "--no-commit", oid])
+            .current_dir(&self.workdir)
+            .output()
+            .context("Failed to run git revert")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("git revert failed: {}", stderr.trim());
+        }
+        Ok(())
+    }
+
+    /// Paths with unresolved merge conflicts in the index.
+    pub fn conflicted_files(&self) -> Result<Vec<std::path::PathBuf>> {
+        let output = Command::new("git")
+            .args(["diff", "--name-only", "--diff-filter=U"])
+            .current_dir(&self.workdir)
+            .output()
+            .context("Failed to run git diff")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("git diff failed: {stderr}");
+        }
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(std::path::PathBuf::from)
+            .collect())
+    }
+
+    /// The last `limit` commits that touched `path`, newest first.
+    pub fn file_log(&self, path: &Path, limit: usize) -> Result<Vec<CommitSummary>> {
+        let output = Command::new("git")
+            .args(["log", "--date=short", "--format=%H%x1f%ad%x1f%an%x1f%s"])
+            .arg(format!("-n{limit}"))
+            .arg("--")
+            .arg(path)
+            .current_dir(&self.workdir)
+            .output()
+            .context("Failed to run git log")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("git log failed: {stderr}");
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        Ok(stdout
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(4, '\x1f');
+                Some(CommitSummary {
+                    oid: git2::Oid::from_str(fields.next()?).ok()?,
+                    date: fields.next()?.to_string(),
+                    author: fields.next()?.to_string(),
+                    subject: fields.next().unwrap_or_default().to_string(),
+                })
+            })
+            .collect())
+    }
+
+    pub async fn fetch(&self) -> Result<()> {
+        self.run_async(&["fetch", "--all"]).await
+    }
+
+    pub async fn pull_rebase(&self) -> Result<()> {
+        self.run_async(&["pull", "--rebase"]).await
+    }
+
+    pub async fn push(&self) -> Result<()> {
+        self.run_async(&["push"]).await
+    }
+
+    /// `git push -u -- <remote> <branch>`, for a branch with no upstream yet. The
+    /// `--` keeps a remote typed into the push dialog from being read as an option.
+    pub async fn push_upstream(&self, remote: &str, branch: &str) -> Result<()> {
+        self.run_async(&["push", "-u", "--", remote, branch]).await
+    }
+
+    /// Local and remote-tracking branch names, e.g. `main` and `origin/main`.
+    pub fn list_branches(&self) -> Result<Vec<String>> {
+        let output = Command::new("git")
+            .args(["branch", "-a", "--format=%(refname:short)"])
+            .current_dir(&self.workdir)
+            .output()
+            .context("Failed to run git branch")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("git branch failed: {stderr}");
+        }
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            // skip the "(HEAD detached at ...)" entry
+            .filter(|b| !b.is_empty() && !b.starts_with('('))
+            .map(String::from)
+            .collect())
+    }
+
+    /// Name of the checked-out branch (`HEAD` when detached).
+    pub fn current_branch(&self) -> Result<String> {
+        let output = Command::new("git")
+            .args(["rev-parse", "--abbrev-ref", "HEAD"])
+            .current_dir(&self.workdir)
+            .output()
+            .context("Failed to run git rev-parse")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("git rev-parse failed: {stderr}");
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Contents of `path` at `rev`, as printed by `git show <rev>:<path>`.
+    pub fn show_blob(&self, rev: &str, path: &Path) -> Result<Vec<u8>> {
+        let spec = format!("{rev}:{}", path.to_string_lossy());
+        let output = Command::new("git")
+            .args(["show", &spec])
+            .current_dir(&self.workdir)
+            .output()
+            .context("Failed to run git show")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("git show failed: {stderr}");
+        }
+        Ok(output.stdout)
+    }
+
+    /// Merge base of HEAD and `rev`, or `rev` itself when there is none.
+    pub fn merge_base(&self, rev: &str) -> Result<String> {
+        let output = Command::new("git")
+            .args(["merge-base", "HEAD", rev])
+            .current_dir(&self.workdir)
+            .output()
+            .context("Failed to run git merge-base")?;
+
+        if !output.status.success() {
+            return Ok(rev.to_string());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Run a network git command in the background. Credential and SSH
+    /// prompts would hang on the TUI's terminal, so they fail instead.
+    async fn run_async(&self, args: &[&str]) -> Result<()> {
+        let mut cmd = tokio::process::Command::new("git");
+        cmd.args(args)
+            .current_dir(&self.workdir)
+            .stdin(Stdio::null())
+            .env("GIT_TERMINAL_PROMPT", "0");
+        // Leave a user's own SSH command alone
+        if std::env::var_os("GIT_SSH_COMMAND").is_none() && std::env::var_os("GIT_SSH").is_none() {
+            cmd.env("GIT_SSH_COMMAND", "ssh -o BatchMode=yes");
+        }
+        let output = cmd
+            .output()
+            .await
+            .with_context(|| format!("Failed to run git {}", args.join(" ")))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("git {} failed: {}", args.join(" "), stderr.trim());
+        }
+        Ok(())
+    }
+
     pub fn stage_all(&self) -> Result<()> {
         let output = Command::new("git")
             .args(["add", "-A"])
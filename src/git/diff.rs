@@ -1,9 +1,42 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
 use anyhow::{Context, Result};
-use git2::{Delta, Diff, DiffFormat, DiffOptions, Repository};
+use git2::{Delta, Diff, DiffFormat, DiffOptions, FileMode, Repository, Status, StatusOptions};
 
 use super::types::*;
 use crate::state::diff_state::DiffOptions as AppDiffOptions;
 
+/// Which implementation produces the diff.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum DiffBackend {
+    /// libgit2, in-process.
+    #[default]
+    Libgit2,
+    /// `git diff` with extra arguments such as `--histogram`, for options
+    /// libgit2 does not expose.
+    GitCli { args: Vec<String> },
+}
+
+/// Restrict a diff to files touched by commits made within a date window.
+/// Bounds are Unix seconds and inclusive.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiffFilter {
+    pub since: Option<i64>,
+    pub until: Option<i64>,
+}
+
+impl DiffFilter {
+    pub fn is_active(&self) -> bool {
+        self.since.is_some() || self.until.is_some()
+    }
+
+    fn contains(&self, time: i64) -> bool {
+        self.since.is_none_or(|since| time >= since) && self.until.is_none_or(|until| time <= until)
+    }
+}
+
 pub struct DiffEngine;
 
 impl DiffEngine {
@@ -11,6 +44,116 @@ impl DiffEngine {
         repo: &Repository,
         target: &ComparisonTarget,
         options: &AppDiffOptions,
+    ) -> Result<Vec<FileDelta>> {
+        let mut deltas = match &options.backend {
+            DiffBackend::Libgit2 => Self::compute_diff_via_libgit2(repo, target, options)?,
+            DiffBackend::GitCli { args } => {
+                Self::compute_diff_via_cli(repo, target, options, args)?
+            }
+        };
+        if options.filter.is_active() {
+            let touched = Self::paths_touched_in_window(repo, target, &options.filter)?;
+            deltas.retain(|d| {
+                touched.contains(&d.path)
+                    || d.old_path.as_ref().is_some_and(|p| touched.contains(p))
+            });
+        }
+        Ok(deltas)
+    }
+
+    /// Changed lines (added plus removed) in `path` that are staged, from `HEAD`
+    /// to the index, and unstaged, from the index to the working tree.
+    pub fn staged_unstaged_lines(repo: &Repository, path: &Path) -> Result<(usize, usize)> {
+        let opts = || {
+            let mut opts = DiffOptions::new();
+            opts.pathspec(path)
+                .disable_pathspec_match(true)
+                .include_untracked(true)
+                .show_untracked_content(true);
+            opts
+        };
+        let changed = |diff: Diff| -> Result<usize> {
+            let stats = diff.stats()?;
+            Ok(stats.insertions() + stats.deletions())
+        };
+        let head_tree = repo.head().ok().and_then(|h| h.peel_to_tree().ok());
+        let staged = repo.diff_tree_to_index(head_tree.as_ref(), None, Some(&mut opts()))?;
+        let unstaged = repo.diff_index_to_workdir(None, Some(&mut opts()))?;
+        Ok((changed(staged)?, changed(unstaged)?))
+    }
+
+    /// Paths changed by the target's commits whose commit time falls inside
+    /// `filter`. Each commit is compared against its first parent. The walk goes
+    /// newest first and stops at the first commit older than `since`.
+    fn paths_touched_in_window(
+        repo: &Repository,
+        target: &ComparisonTarget,
+        filter: &DiffFilter,
+    ) -> Result<HashSet<PathBuf>> {
+        let resolve = |name: &str| -> Result<git2::Oid> {
+            Ok(repo
+                .revparse_single(name)
+                .with_context(|| format!("Could not resolve: {name}"))?
+                .peel_to_commit()
+                .with_context(|| format!("{name} does not point to a commit"))?
+                .id())
+        };
+        let head = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+
+        let mut walk = repo.revwalk()?;
+        walk.set_sorting(git2::Sort::TIME)?;
+        match target {
+            ComparisonTarget::HeadVsWorkdir => {
+                if let Some(head) = &head {
+                    walk.push(head.id())?;
+                }
+            }
+            ComparisonTarget::Branch(_) | ComparisonTarget::Commit(_) => {
+                let target_oid = match target {
+                    ComparisonTarget::Branch(name) => resolve(name)?,
+                    ComparisonTarget::Commit(oid) => *oid,
+                    _ => unreachable!(),
+                };
+                if let Some(head) = &head {
+                    walk.push(head.id())?;
+                    if let Ok(base) = repo.merge_base(head.id(), target_oid) {
+                        walk.hide(base)?;
+                    }
+                }
+            }
+            ComparisonTarget::Range { from, to } => {
+                walk.push(*to)?;
+                walk.hide(*from)?;
+            }
+        }
+
+        let mut touched = HashSet::new();
+        for oid in walk {
+            let commit = repo.find_commit(oid?)?;
+            let time = commit.time().seconds();
+            if filter.since.is_some_and(|since| time < since) {
+                break;
+            }
+            if !filter.contains(time) {
+                continue;
+            }
+            let parent_tree = match commit.parent(0) {
+                Ok(parent) => Some(parent.tree()?),
+                Err(_) => None,
+            };
+            let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+            for delta in diff.deltas() {
+                touched.extend(delta.old_file().path().map(PathBuf::from));
+                touched.extend(delta.new_file().path().map(PathBuf::from));
+            }
+        }
+        Ok(touched)
+    }
+
+    fn compute_diff_via_libgit2(
+        repo: &Repository,
+        target: &ComparisonTarget,
+        options: &AppDiffOptions,
     ) -> Result<Vec<FileDelta>> {
         let mut diff_opts = DiffOptions::new();
         diff_opts.ignore_whitespace(options.ignore_whitespace);
@@ -45,11 +188,150 @@ impl DiffEngine {
                 let base_tree = Self::merge_base_tree(repo, *oid)?;
                 repo.diff_tree_to_workdir_with_index(Some(&base_tree), Some(&mut diff_opts))?
             }
+            ComparisonTarget::Range { from, to } => {
+                let from_tree = repo
+                    .find_commit(*from)
+                    .with_context(|| format!("Could not find commit {from}"))?
+                    .tree()?;
+                let to_tree = repo
+                    .find_commit(*to)
+                    .with_context(|| format!("Could not find commit {to}"))?
+                    .tree()?;
+                repo.diff_tree_to_tree(Some(&from_tree), Some(&to_tree), Some(&mut diff_opts))?
+            }
         };
 
         Self::parse_diff(&diff)
     }
 
+    /// Run `git diff` against the same base the libgit2 path would use and
+    /// parse its patch output. Untracked files are added from `git status`,
+    /// since `git diff` never lists them.
+    fn compute_diff_via_cli(
+        repo: &Repository,
+        target: &ComparisonTarget,
+        options: &AppDiffOptions,
+        args: &[String],
+    ) -> Result<Vec<FileDelta>> {
+        let workdir = repo
+            .workdir()
+            .context("Repository has no working directory")?;
+
+        let (from, to) = match target {
+            ComparisonTarget::HeadVsWorkdir => {
+                let tree = match repo.head() {
+                    Ok(head) => head.peel_to_commit()?.tree()?.id(),
+                    // No commits yet: diff against the empty tree
+                    Err(_) => repo.treebuilder(None)?.write()?,
+                };
+                (tree.to_string(), None)
+            }
+            ComparisonTarget::Branch(name) => {
+                let target_commit = repo
+                    .revparse_single(name)
+                    .with_context(|| format!("Could not resolve: {name}"))?
+                    .peel_to_commit()
+                    .with_context(|| format!("{name} does not point to a commit"))?;
+                let base_tree = Self::merge_base_tree(repo, target_commit.id())?;
+                (base_tree.id().to_string(), None)
+            }
+            ComparisonTarget::Commit(oid) => {
+                let base_tree = Self::merge_base_tree(repo, *oid)?;
+                (base_tree.id().to_string(), None)
+            }
+            ComparisonTarget::Range { from, to } => (from.to_string(), Some(to.to_string())),
+        };
+
+        let mut cmd = Command::new("git");
+        cmd.current_dir(workdir).args([
+            "-c",
+            "core.quotepath=false",
+            "diff",
+            "--no-color",
+            "--no-ext-diff",
+            "--src-prefix=a/",
+            "--dst-prefix=b/",
+            "-U999999",
+        ]);
+        if options.ignore_whitespace {
+            cmd.arg("-w");
+        }
+        cmd.args(args).arg(&from).args(&to).arg("--");
+
+        let output = cmd.output().context("Failed to run git diff")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("git diff failed: {}", stderr.trim());
+        }
+
+        let mut deltas = parse_unified_diff(&String::from_utf8_lossy(&output.stdout));
+        if to.is_none() {
+            deltas.extend(Self::untracked_deltas(repo)?);
+        }
+        deltas.sort_by(|a, b| a.path.cmp(&b.path));
+        deltas.iter_mut().for_each(FileDelta::detect_conflicts);
+        Ok(deltas)
+    }
+
+    /// Untracked, non-ignored files as all-addition deltas.
+    fn untracked_deltas(repo: &Repository) -> Result<Vec<FileDelta>> {
+        let workdir = repo
+            .workdir()
+            .context("Repository has no working directory")?;
+        let mut status_opts = StatusOptions::new();
+        status_opts.include_untracked(true);
+        status_opts.recurse_untracked_dirs(true);
+
+        let mut deltas = Vec::new();
+        for entry in repo.statuses(Some(&mut status_opts))?.iter() {
+            if !entry.status().contains(Status::WT_NEW) {
+                continue;
+            }
+            let Some(path) = entry.path().map(PathBuf::from) else {
+                continue;
+            };
+            let bytes = std::fs::read(workdir.join(&path)).unwrap_or_default();
+            let mode = worktree_mode(&workdir.join(&path));
+            let binary = bytes.contains(&0);
+
+            let mut hunks = Vec::new();
+            let mut additions = 0;
+            if !binary && !bytes.is_empty() {
+                let text = String::from_utf8_lossy(&bytes);
+                let lines: Vec<DiffLine> = text
+                    .split_inclusive('\n')
+                    .enumerate()
+                    .map(|(i, content)| DiffLine {
+                        origin: DiffLineOrigin::Addition,
+                        old_lineno: None,
+                        new_lineno: Some(i as u32 + 1),
+                        content: content.to_string(),
+                    })
+                    .collect();
+                additions = lines.len();
+                hunks.push(Hunk {
+                    header: format!("@@ -0,0 +1,{additions} @@"),
+                    lines,
+                    additions,
+                    deletions: 0,
+                });
+            }
+
+            deltas.push(FileDelta {
+                path,
+                old_path: None,
+                status: FileStatus::Untracked,
+                hunks,
+                additions,
+                deletions: 0,
+                binary,
+                has_conflicts: false,
+                mode,
+            });
+        }
+        Ok(deltas)
+    }
+
     /// Find the merge-base between HEAD and the given commit, returning the
     /// merge-base's tree. This implements 3-dot diff semantics: showing only
     /// the changes on the current branch since it diverged from the target.
@@ -96,7 +378,10 @@ impl DiffEngine {
                 None
             };
 
+            let is_submodule = delta.new_file().mode() == FileMode::Commit
+                || delta.old_file().mode() == FileMode::Commit;
             let status = match delta.status() {
+                _ if is_submodule => FileStatus::Submodule,
                 Delta::Added => FileStatus::Added,
                 Delta::Deleted => FileStatus::Deleted,
                 Delta::Modified => FileStatus::Modified,
@@ -106,6 +391,11 @@ impl DiffEngine {
             };
 
             let binary = delta.flags().is_binary();
+            let mode = if status == FileStatus::Deleted {
+                delta.old_file().mode()
+            } else {
+                delta.new_file().mode()
+            };
 
             deltas.push(FileDelta {
                 path,
@@ -115,6 +405,8 @@ impl DiffEngine {
                 additions: 0,
                 deletions: 0,
                 binary,
+                has_conflicts: false,
+                mode: mode.into(),
             });
         }
 
@@ -168,6 +460,8 @@ impl DiffEngine {
                     current_hunk = Some(Hunk {
                         header,
                         lines: Vec::new(),
+                        additions: 0,
+                        deletions: 0,
                     });
                 }
                 '+' => {
@@ -180,6 +474,7 @@ impl DiffEngine {
                     };
                     deltas[idx].additions += 1;
                     if let Some(h) = current_hunk.as_mut() {
+                        h.additions += 1;
                         h.lines.push(diff_line);
                     }
                 }
@@ -193,6 +488,7 @@ impl DiffEngine {
                     };
                     deltas[idx].deletions += 1;
                     if let Some(h) = current_hunk.as_mut() {
+                        h.deletions += 1;
                         h.lines.push(diff_line);
                     }
                 }
@@ -221,6 +517,352 @@ impl DiffEngine {
             }
         }
 
+        // Submodules without printed content (e.g. ignored by the diff
+        // options) still get their commit change from the delta's ids
+        for (i, d) in deltas.iter_mut().enumerate() {
+            if d.status != FileStatus::Submodule || !d.hunks.is_empty() {
+                continue;
+            }
+            let Some(delta) = diff.get_delta(i) else {
+                continue;
+            };
+            let oid = |f: git2::DiffFile<'_>| (!f.id().is_zero()).then(|| f.id().to_string());
+            let (old, new) = (oid(delta.old_file()), oid(delta.new_file()));
+            if old == new {
+                continue;
+            }
+            let hunk = submodule_hunk(old.as_deref(), new.as_deref());
+            d.additions = hunk.additions;
+            d.deletions = hunk.deletions;
+            d.hunks.push(hunk);
+        }
+
+        deltas.iter_mut().for_each(FileDelta::detect_conflicts);
         Ok(deltas)
     }
 }
+
+/// Mode git would record for an untracked working-tree file.
+fn worktree_mode(path: &std::path::Path) -> u32 {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if std::fs::metadata(path).is_ok_and(|m| m.permissions().mode() & 0o111 != 0) {
+            return 0o100755;
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    0o100644
+}
+
+/// Path from a `diff --git a/X b/Y` header. Renames and copies are corrected
+/// later by the `rename to` / `+++` lines, so this only has to handle X == Y.
+fn git_header_path(rest: &str) -> PathBuf {
+    let n = rest.len().saturating_sub(5) / 2;
+    if rest.len() == 2 * n + 5 && rest.starts_with("a/") && rest.get(n + 2..n + 5) == Some(" b/") {
+        return PathBuf::from(&rest[2..n + 2]);
+    }
+    match rest.rfind(" b/") {
+        Some(i) => PathBuf::from(&rest[i + 3..]),
+        None => PathBuf::from(rest),
+    }
+}
+
+/// Parse `@@ -a,b +c,d @@` into its start lines and normalise the header to
+/// the form the libgit2 path produces (explicit counts, no section text).
+fn parse_hunk_header(line: &str) -> Option<(String, u32, u32)> {
+    let inner = line.strip_prefix("@@ ")?;
+    let inner = &inner[..inner.find(" @@")?];
+    let (old, new) = inner.split_once(' ')?;
+    let range = |r: &str| -> Option<(u32, u32)> {
+        match r.split_once(',') {
+            Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+            None => Some((r.parse().ok()?, 1)),
+        }
+    };
+    let (old_start, old_lines) = range(old.strip_prefix('-')?)?;
+    let (new_start, new_lines) = range(new.strip_prefix('+')?)?;
+    Some((
+        format!("@@ -{old_start},{old_lines} +{new_start},{new_lines} @@"),
+        old_start,
+        new_start,
+    ))
+}
+
+/// Parse `git diff` patch output into file deltas.
+fn parse_unified_diff(text: &str) -> Vec<FileDelta> {
+    let mut deltas: Vec<FileDelta> = Vec::new();
+    let mut in_hunk = false;
+    let mut old_lineno = 0;
+    let mut new_lineno = 0;
+
+    for line in text.split_inclusive('\n') {
+        let bare = line.strip_suffix('\n').unwrap_or(line);
+        if let Some(rest) = bare.strip_prefix("diff --git ") {
+            deltas.push(FileDelta {
+                path: git_header_path(rest),
+                old_path: None,
+                status: FileStatus::Modified,
+                hunks: Vec::new(),
+                additions: 0,
+                deletions: 0,
+                binary: false,
+                has_conflicts: false,
+                mode: 0o100644,
+            });
+            in_hunk = false;
+            continue;
+        }
+        let Some(delta) = deltas.last_mut() else {
+            continue;
+        };
+
+        if bare.starts_with("@@ ") {
+            if let Some((header, old_start, new_start)) = parse_hunk_header(bare) {
+                delta.hunks.push(Hunk {
+                    header,
+                    lines: Vec::new(),
+                    additions: 0,
+                    deletions: 0,
+                });
+                old_lineno = old_start;
+                new_lineno = new_start;
+                in_hunk = true;
+            }
+            continue;
+        }
+
+        if !in_hunk {
+            // Gitlinks are recorded with mode 160000
+            if bare.starts_with("index ") && bare.ends_with(" 160000")
+                || bare.ends_with("file mode 160000")
+            {
+                delta.status = FileStatus::Submodule;
+            } else if let Some(mode) = bare.strip_prefix("new file mode ") {
+                delta.status = FileStatus::Added;
+                delta.mode = u32::from_str_radix(mode, 8).unwrap_or(delta.mode);
+            } else if let Some(mode) = bare.strip_prefix("deleted file mode ") {
+                delta.status = FileStatus::Deleted;
+                delta.mode = u32::from_str_radix(mode, 8).unwrap_or(delta.mode);
+            } else if let Some(mode) = bare.strip_prefix("new mode ") {
+                delta.mode = u32::from_str_radix(mode, 8).unwrap_or(delta.mode);
+            } else if let Some(from) = bare.strip_prefix("rename from ") {
+                delta.status = FileStatus::Renamed;
+                delta.old_path = Some(PathBuf::from(from));
+            } else if let Some(to) = bare.strip_prefix("rename to ") {
+                delta.path = PathBuf::from(to);
+            } else if let Some(to) = bare.strip_prefix("+++ b/") {
+                delta.path = PathBuf::from(to);
+            } else if bare.starts_with("Binary files ") {
+                delta.binary = true;
+            }
+            continue;
+        }
+
+        let Some(hunk) = delta.hunks.last_mut() else {
+            continue;
+        };
+        let content = line.get(1..).unwrap_or_default().to_string();
+        match line.as_bytes().first() {
+            Some(b' ') => {
+                hunk.lines.push(DiffLine {
+                    origin: DiffLineOrigin::Context,
+                    old_lineno: Some(old_lineno),
+                    new_lineno: Some(new_lineno),
+                    content,
+                });
+                old_lineno += 1;
+                new_lineno += 1;
+            }
+            Some(b'+') => {
+                hunk.lines.push(DiffLine {
+                    origin: DiffLineOrigin::Addition,
+                    old_lineno: None,
+                    new_lineno: Some(new_lineno),
+                    content,
+                });
+                hunk.additions += 1;
+                delta.additions += 1;
+                new_lineno += 1;
+            }
+            Some(b'-') => {
+                hunk.lines.push(DiffLine {
+                    origin: DiffLineOrigin::Deletion,
+                    old_lineno: Some(old_lineno),
+                    new_lineno: None,
+                    content,
+                });
+                hunk.deletions += 1;
+                delta.deletions += 1;
+                old_lineno += 1;
+            }
+            // "\ No newline at end of file" applies to the line before it
+            Some(b'\\') => {
+                if let Some(last) = hunk.lines.last_mut() {
+                    if last.content.ends_with('\n') {
+                        last.content.pop();
+                    }
+                }
+            }
+            _ => in_hunk = false,
+        }
+    }
+
+    deltas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_unified_diff, DiffEngine, DiffFilter};
+    use crate::git::types::ComparisonTarget;
+    use crate::git::types::{submodule_hunk, DiffLineOrigin, FileDelta, FileStatus};
+    use std::path::PathBuf;
+
+    #[test]
+    fn window_keeps_paths_of_commits_inside_it() {
+        let dir = std::env::temp_dir().join(format!("mdiff-window-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let repo = git2::Repository::init(&dir).unwrap();
+        let mut parent: Option<git2::Oid> = None;
+        for (name, time) in [("a", 100), ("b", 200), ("c", 300)] {
+            std::fs::write(dir.join(name), name).unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(std::path::Path::new(name)).unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let sig = git2::Signature::new("t", "t@t", &git2::Time::new(time, 0)).unwrap();
+            let parents: Vec<git2::Commit> = parent
+                .iter()
+                .map(|&p| repo.find_commit(p).unwrap())
+                .collect();
+            let parents: Vec<&git2::Commit> = parents.iter().collect();
+            parent = Some(
+                repo.commit(Some("HEAD"), &sig, &sig, name, &tree, &parents)
+                    .unwrap(),
+            );
+        }
+
+        let touched = |since, until| {
+            let filter = DiffFilter { since, until };
+            let mut paths: Vec<PathBuf> = DiffEngine::paths_touched_in_window(
+                &repo,
+                &ComparisonTarget::HeadVsWorkdir,
+                &filter,
+            )
+            .unwrap()
+            .into_iter()
+            .collect();
+            paths.sort();
+            paths
+        };
+        let paths = |names: &[&str]| names.iter().map(PathBuf::from).collect::<Vec<_>>();
+        assert_eq!(touched(Some(150), None), paths(&["b", "c"]));
+        assert_eq!(touched(Some(150), Some(250)), paths(&["b"]));
+        assert_eq!(touched(None, Some(150)), paths(&["a"]));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn parses_git_diff_output() {
+        let text = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,2 +1,2 @@ fn main() {
+ keep
+-old
++new
+\\ No newline at end of file
+diff --git a/new file.txt b/new file.txt
+new file mode 100755
+index 0000000..3333333
+--- /dev/null
++++ b/new file.txt
+@@ -0,0 +1 @@
++hello
+diff --git a/old.rs b/renamed.rs
+similarity index 100%
+rename from old.rs
+rename to renamed.rs
+diff --git a/logo.png b/logo.png
+index 4444444..5555555 100644
+Binary files a/logo.png and b/logo.png differ
+";
+        let deltas = parse_unified_diff(text);
+        assert_eq!(deltas.len(), 4);
+
+        let lib = &deltas[0];
+        assert_eq!(lib.path, PathBuf::from("src/lib.rs"));
+        assert_eq!((lib.additions, lib.deletions), (1, 1));
+        assert_eq!(lib.hunks[0].header, "@@ -1,2 +1,2 @@");
+        let lines = &lib.hunks[0].lines;
+        assert_eq!(lines[1].origin, DiffLineOrigin::Deletion);
+        assert_eq!(lines[1].old_lineno, Some(2));
+        assert_eq!(lines[2].new_lineno, Some(2));
+        assert_eq!(lines[2].content, "new");
+
+        assert_eq!(deltas[1].path, PathBuf::from("new file.txt"));
+        assert_eq!(deltas[1].status, FileStatus::Added);
+        assert_eq!(deltas[1].mode, 0o100755);
+        assert_eq!(deltas[1].hunks[0].header, "@@ -0,0 +1,1 @@");
+
+        assert_eq!(deltas[2].status, FileStatus::Renamed);
+        assert_eq!(deltas[2].path, PathBuf::from("renamed.rs"));
+        assert_eq!(deltas[2].old_path, Some(PathBuf::from("old.rs")));
+
+        assert!(deltas[3].binary);
+        assert!(deltas[3].hunks.is_empty());
+    }
+
+    #[test]
+    fn detects_submodule_commit_changes() {
+        let text = "\
+diff --git a/vendor/lib b/vendor/lib
+index 2ed3512..a740aed 160000
+--- a/vendor/lib
++++ b/vendor/lib
+@@ -1 +1 @@
+-Subproject commit 2ed351255785fa90745c6c110234bd1560ed59aa
++Subproject commit a740aed43abb365f344c1ca16383647a2e3f0382
+";
+        let deltas = parse_unified_diff(text);
+        assert_eq!(deltas[0].status, FileStatus::Submodule);
+        assert_eq!(
+            deltas[0].submodule_commits(),
+            (
+                Some("2ed351255785fa90745c6c110234bd1560ed59aa"),
+                Some("a740aed43abb365f344c1ca16383647a2e3f0382")
+            )
+        );
+
+        let added = submodule_hunk(None, Some("a740aed"));
+        assert_eq!(added.header, "@@ -0,0 +1,1 @@");
+        assert_eq!(added.lines[0].content, "Subproject commit a740aed\n");
+    }
+
+    #[test]
+    fn detects_conflict_markers_on_the_new_side() {
+        let text = "\
+diff --git a/a.rs b/a.rs
+--- a/a.rs
++++ b/a.rs
+@@ -1,1 +1,5 @@
++<<<<<<< HEAD
+ shared
++=======
++theirs
++>>>>>>> feature
+diff --git a/b.rs b/b.rs
+--- a/b.rs
++++ b/b.rs
+@@ -1,2 +1,1 @@
+-<<<<<<< HEAD
+ =======x
+";
+        let mut deltas = parse_unified_diff(text);
+        deltas.iter_mut().for_each(FileDelta::detect_conflicts);
+        assert!(deltas[0].has_conflicts);
+        assert!(!deltas[1].has_conflicts);
+    }
+}
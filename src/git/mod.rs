@@ -1,8 +1,9 @@
 pub mod commands;
 pub mod diff;
+pub mod patch;
 pub mod repository;
 pub mod types;
 pub mod worktree;
 
-pub use diff::DiffEngine;
+pub use diff::{DiffBackend, DiffEngine, DiffFilter};
 pub use repository::RepoCache;
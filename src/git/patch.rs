@@ -0,0 +1,385 @@
+use std::ops::Range;
+
+use super::types::{DiffLine, DiffLineOrigin, FileDelta, FileStatus, Hunk};
+
+/// Context lines kept around a change block by [`format_patch_for_hunk`].
+const PATCH_CONTEXT: usize = 3;
+
+/// Build the full unified diff of `delta`, as `git diff` prints it for one file.
+/// Returns `None` for binary files.
+pub fn file_patch(delta: &FileDelta) -> Option<String> {
+    if delta.binary {
+        return None;
+    }
+    let mut patch = file_header(delta);
+    for hunk in &delta.hunks {
+        push_hunk(&mut patch, hunk);
+    }
+    Some(patch)
+}
+
+/// Build a minimal patch for the changed lines `lines` of hunk `hunk_idx`, with
+/// up to three lines of context and a recomputed `@@` header, suitable for
+/// `git apply` elsewhere. Returns `None` for binary files or an empty range.
+pub fn format_patch_for_hunk(
+    delta: &FileDelta,
+    hunk_idx: usize,
+    lines: Range<usize>,
+) -> Option<String> {
+    if delta.binary {
+        return None;
+    }
+    let hunk = delta.hunks.get(hunk_idx)?;
+    if lines.is_empty() || lines.end > hunk.lines.len() {
+        return None;
+    }
+    let start = lines.start.saturating_sub(PATCH_CONTEXT);
+    let end = (lines.end + PATCH_CONTEXT).min(hunk.lines.len());
+    let slice = &hunk.lines[start..end];
+
+    // A side with no lines in the slice starts at the line before it, as git does
+    let side_start = |lineno: fn(&DiffLine) -> Option<u32>| {
+        slice.iter().find_map(lineno).unwrap_or_else(|| {
+            hunk.lines[..start]
+                .iter()
+                .rev()
+                .find_map(lineno)
+                .unwrap_or(0)
+        })
+    };
+    let old_start = side_start(|l| l.old_lineno);
+    let new_start = side_start(|l| l.new_lineno);
+    let old_count = slice
+        .iter()
+        .filter(|l| l.origin != DiffLineOrigin::Addition)
+        .count();
+    let new_count = slice
+        .iter()
+        .filter(|l| l.origin != DiffLineOrigin::Deletion)
+        .count();
+
+    let mut patch = file_header(delta);
+    push_hunk(
+        &mut patch,
+        &Hunk {
+            header: format!("@@ -{old_start},{old_count} +{new_start},{new_count} @@"),
+            lines: slice.to_vec(),
+            additions: 0,
+            deletions: 0,
+        },
+    );
+    Some(patch)
+}
+
+/// Build a working-tree patch replacing `search` with `replace` on the lines
+/// `delta` adds. Returns the patch and the number of substitutions, or `None`
+/// when nothing matches (or the file is binary or deleted).
+pub fn replace_patch(delta: &FileDelta, search: &str, replace: &str) -> Option<(String, usize)> {
+    if delta.binary || delta.status == FileStatus::Deleted || search.is_empty() {
+        return None;
+    }
+
+    // With full-file context the new side of the diff is the whole working-tree file
+    let new_lines: Vec<&DiffLine> = delta
+        .hunks
+        .iter()
+        .flat_map(|h| &h.lines)
+        .filter(|l| l.origin != DiffLineOrigin::Deletion)
+        .collect();
+
+    let mut body = String::new();
+    let mut substitutions = 0;
+    for line in &new_lines {
+        let count = if line.origin == DiffLineOrigin::Addition {
+            line.content.matches(search).count()
+        } else {
+            0
+        };
+        if count == 0 {
+            push_patch_line(&mut body, ' ', &line.content);
+        } else {
+            substitutions += count;
+            push_patch_line(&mut body, '-', &line.content);
+            push_patch_line(&mut body, '+', &line.content.replace(search, replace));
+        }
+    }
+    if substitutions == 0 {
+        return None;
+    }
+
+    let path = delta.path.to_string_lossy();
+    let n = new_lines.len();
+    let patch = format!(
+        "diff --git a/{path} b/{path}\n--- a/{path}\n+++ b/{path}\n@@ -1,{n} +1,{n} @@\n{body}"
+    );
+    Some((patch, substitutions))
+}
+
+fn push_patch_line(patch: &mut String, prefix: char, content: &str) {
+    patch.push(prefix);
+    patch.push_str(content);
+    if !content.ends_with('\n') {
+        patch.push_str("\n\\ No newline at end of file\n");
+    }
+}
+
+/// `diff --git` line plus mode and `---`/`+++` headers for `delta`.
+fn file_header(delta: &FileDelta) -> String {
+    let new_path = delta.path.to_string_lossy();
+    let old_path = delta
+        .old_path
+        .as_ref()
+        .map(|p| p.to_string_lossy())
+        .unwrap_or_else(|| new_path.clone());
+
+    let mut patch = format!("diff --git a/{old_path} b/{new_path}\n");
+    match delta.status {
+        FileStatus::Added | FileStatus::Untracked => {
+            patch.push_str(&format!("new file mode {:o}\n", delta.mode));
+            patch.push_str("--- /dev/null\n");
+            patch.push_str(&format!("+++ b/{new_path}\n"));
+        }
+        FileStatus::Deleted => {
+            patch.push_str(&format!("deleted file mode {:o}\n", delta.mode));
+            patch.push_str(&format!("--- a/{old_path}\n"));
+            patch.push_str("+++ /dev/null\n");
+        }
+        FileStatus::Modified | FileStatus::Renamed | FileStatus::Submodule => {
+            patch.push_str(&format!("--- a/{old_path}\n"));
+            patch.push_str(&format!("+++ b/{new_path}\n"));
+        }
+    }
+    patch
+}
+
+/// Append `hunk`'s header and prefixed lines to `patch`.
+fn push_hunk(patch: &mut String, hunk: &Hunk) {
+    patch.push_str(hunk.header.trim_end());
+    patch.push('\n');
+    for line in &hunk.lines {
+        let prefix = match line.origin {
+            DiffLineOrigin::Context => ' ',
+            DiffLineOrigin::Addition => '+',
+            DiffLineOrigin::Deletion => '-',
+        };
+        push_patch_line(patch, prefix, &line.content);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{file_patch, format_patch_for_hunk, replace_patch};
+    use crate::git::types::{DiffLine, DiffLineOrigin, FileDelta, FileStatus, Hunk};
+    use std::path::PathBuf;
+
+    fn line(origin: DiffLineOrigin, old: Option<u32>, new: Option<u32>, text: &str) -> DiffLine {
+        DiffLine {
+            origin,
+            old_lineno: old,
+            new_lineno: new,
+            content: text.to_string(),
+        }
+    }
+
+    fn delta(status: FileStatus, lines: Vec<DiffLine>) -> FileDelta {
+        FileDelta {
+            path: PathBuf::from("src/lib.rs"),
+            old_path: None,
+            status,
+            hunks: vec![Hunk {
+                header: "@@ -1,2 +1,2 @@".to_string(),
+                lines,
+                additions: 0,
+                deletions: 0,
+            }],
+            additions: 1,
+            deletions: 1,
+            binary: false,
+            has_conflicts: false,
+            mode: 0o100644,
+        }
+    }
+
+    #[test]
+    fn modified_hunk_patch() {
+        let d = delta(
+            FileStatus::Modified,
+            vec![
+                line(DiffLineOrigin::Context, Some(1), Some(1), "fn a() {}\n"),
+                line(DiffLineOrigin::Deletion, Some(2), None, "old\n"),
+                line(DiffLineOrigin::Addition, None, Some(2), "new\n"),
+            ],
+        );
+        assert_eq!(
+            file_patch(&d).unwrap(),
+            "diff --git a/src/lib.rs b/src/lib.rs\n\
+             --- a/src/lib.rs\n\
+             +++ b/src/lib.rs\n\
+             @@ -1,2 +1,2 @@\n \
+             fn a() {}\n\
+             -old\n\
+             +new\n"
+        );
+    }
+
+    #[test]
+    fn missing_trailing_newline_is_marked() {
+        let mut d = delta(
+            FileStatus::Added,
+            vec![line(DiffLineOrigin::Addition, None, Some(1), "tail")],
+        );
+        d.mode = 0o100755;
+        let patch = file_patch(&d).unwrap();
+        assert!(patch.contains("new file mode 100755\n--- /dev/null\n+++ b/src/lib.rs\n"));
+        assert!(patch.ends_with("+tail\n\\ No newline at end of file\n"));
+    }
+
+    #[test]
+    fn replace_patch_rewrites_only_added_lines() {
+        let d = delta(
+            FileStatus::Modified,
+            vec![
+                line(
+                    DiffLineOrigin::Context,
+                    Some(1),
+                    Some(1),
+                    "let colour = 1;\n",
+                ),
+                line(DiffLineOrigin::Deletion, Some(2), None, "old colour\n"),
+                line(DiffLineOrigin::Addition, None, Some(2), "colour + colour"),
+            ],
+        );
+        let (patch, count) = replace_patch(&d, "colour", "color").unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(
+            patch,
+            "diff --git a/src/lib.rs b/src/lib.rs\n\
+             --- a/src/lib.rs\n\
+             +++ b/src/lib.rs\n\
+             @@ -1,2 +1,2 @@\n \
+             let colour = 1;\n\
+             -colour + colour\n\\ No newline at end of file\n\
+             +color + color\n\\ No newline at end of file\n"
+        );
+        assert!(replace_patch(&d, "missing", "x").is_none());
+    }
+
+    #[test]
+    fn block_patch_keeps_three_context_lines() {
+        let ctx = |n: u32| {
+            line(
+                DiffLineOrigin::Context,
+                Some(n),
+                Some(n + 1),
+                &format!("c{n}\n"),
+            )
+        };
+        let lines = vec![
+            line(DiffLineOrigin::Addition, None, Some(1), "top\n"),
+            ctx(1),
+            ctx(2),
+            ctx(3),
+            ctx(4),
+            line(DiffLineOrigin::Deletion, Some(5), None, "gone\n"),
+            ctx(6),
+        ];
+        let d = delta(FileStatus::Modified, lines);
+
+        let patch = format_patch_for_hunk(&d, 0, 5..6).unwrap();
+        assert!(patch.ends_with("@@ -2,5 +3,4 @@\n c2\n c3\n c4\n-gone\n c6\n"));
+
+        let patch = format_patch_for_hunk(&d, 0, 0..1).unwrap();
+        assert!(patch.ends_with("@@ -1,3 +1,4 @@\n+top\n c1\n c2\n c3\n"));
+        assert!(format_patch_for_hunk(&d, 0, 3..3).is_none());
+    }
+
+    /// Run git in `dir`, returning stdout.
+    fn git(dir: &std::path::Path, args: &[&str]) -> String {
+        let out = std::process::Command::new("git")
+            .args(["-c", "user.name=t", "-c", "user.email=t@t"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(out.status.success(), "{args:?}: {out:?}");
+        String::from_utf8(out.stdout).unwrap()
+    }
+
+    /// A repo whose `src/lib.rs` has lines 2 and 9 changed in the working
+    /// tree, far enough apart to be two change blocks, and the matching delta.
+    fn two_block_repo(name: &str) -> (PathBuf, FileDelta) {
+        let dir = std::env::temp_dir().join(format!("mdiff-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        let old: Vec<String> = (1..=10).map(|n| format!("l{n}\n")).collect();
+        std::fs::write(dir.join("src/lib.rs"), old.concat()).unwrap();
+        git(&dir, &["init", "-q"]);
+        git(&dir, &["add", "."]);
+        git(&dir, &["commit", "-q", "-m", "init"]);
+
+        let mut lines = Vec::new();
+        for (i, text) in old.iter().enumerate() {
+            let n = i as u32 + 1;
+            if n == 2 || n == 9 {
+                lines.push(line(DiffLineOrigin::Deletion, Some(n), None, text));
+                lines.push(line(
+                    DiffLineOrigin::Addition,
+                    None,
+                    Some(n),
+                    &format!("x{n}\n"),
+                ));
+            } else {
+                lines.push(line(DiffLineOrigin::Context, Some(n), Some(n), text));
+            }
+        }
+        let mut new = old.clone();
+        new[1] = "x2\n".to_string();
+        new[8] = "x9\n".to_string();
+        std::fs::write(dir.join("src/lib.rs"), new.concat()).unwrap();
+        (dir, delta(FileStatus::Modified, lines))
+    }
+
+    #[test]
+    fn staging_one_block_leaves_the_other_unstaged() {
+        use crate::git::commands::GitCli;
+        use crate::state::review_state::change_blocks;
+
+        let (dir, d) = two_block_repo("stage-block");
+        let blocks = change_blocks(&d);
+        assert_eq!(blocks.len(), 2);
+        let patch = format_patch_for_hunk(&d, blocks[0].hunk_index, blocks[0].lines.clone());
+        GitCli::new(&dir).stage_patch(&patch.unwrap()).unwrap();
+
+        let staged = git(&dir, &["diff", "--cached"]);
+        let unstaged = git(&dir, &["diff"]);
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(
+            staged.contains("+x2") && !staged.contains("+x9"),
+            "{staged}"
+        );
+        assert!(
+            unstaged.contains("+x9") && !unstaged.contains("+x2"),
+            "{unstaged}"
+        );
+    }
+
+    #[test]
+    fn reverting_one_block_keeps_the_other() {
+        use crate::git::commands::GitCli;
+        use crate::state::review_state::change_blocks;
+
+        let (dir, d) = two_block_repo("revert-block");
+        let blocks = change_blocks(&d);
+        let patch = format_patch_for_hunk(&d, blocks[1].hunk_index, blocks[1].lines.clone());
+        GitCli::new(&dir)
+            .apply_patch(&patch.unwrap(), false, true)
+            .unwrap();
+
+        let content = std::fs::read_to_string(dir.join("src/lib.rs")).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(
+            content.contains("x2\n") && content.contains("l9\n") && !content.contains("x9"),
+            "{content}"
+        );
+    }
+}
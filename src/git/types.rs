@@ -1,12 +1,32 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+use serde::{Serialize, Serializer};
+
+/// Serialize a path as a string, replacing invalid UTF-8 rather than failing.
+fn serialize_path<S: Serializer>(path: &Path, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&path.to_string_lossy())
+}
+
+fn serialize_opt_path<S: Serializer>(
+    path: &Option<PathBuf>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match path {
+        Some(path) => serialize_path(path, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum FileStatus {
     Added,
     Deleted,
     Modified,
     Renamed,
     Untracked,
+    /// A submodule whose recorded commit changed.
+    Submodule,
 }
 
 impl FileStatus {
@@ -17,18 +37,20 @@ impl FileStatus {
             FileStatus::Modified => "M",
             FileStatus::Renamed => "R",
             FileStatus::Untracked => "?",
+            FileStatus::Submodule => "S",
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum DiffLineOrigin {
     Context,
     Addition,
     Deletion,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct DiffLine {
     pub origin: DiffLineOrigin,
     pub old_lineno: Option<u32>,
@@ -36,21 +58,93 @@ pub struct DiffLine {
     pub content: String,
 }
 
-#[derive(Debug, Clone)]
+impl DiffLine {
+    /// Whether the line is a git merge conflict marker (`<<<<<<<`, `|||||||`,
+    /// `=======` or `>>>>>>>`).
+    pub fn is_conflict_marker(&self) -> bool {
+        let text = self.content.trim_end_matches(['\n', '\r']);
+        text == "======="
+            || ["<<<<<<<", "|||||||", ">>>>>>>"].iter().any(|marker| {
+                text.strip_prefix(marker)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with(' '))
+            })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct Hunk {
     pub header: String,
     pub lines: Vec<DiffLine>,
+    pub additions: usize,
+    pub deletions: usize,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct FileDelta {
+    #[serde(serialize_with = "serialize_path")]
     pub path: PathBuf,
+    #[serde(serialize_with = "serialize_opt_path")]
     pub old_path: Option<PathBuf>,
     pub status: FileStatus,
     pub hunks: Vec<Hunk>,
     pub additions: usize,
     pub deletions: usize,
     pub binary: bool,
+    /// The new side still contains merge conflict markers.
+    pub has_conflicts: bool,
+    /// Git file mode of the new side, or of the old side for deletions.
+    #[serde(skip)]
+    pub mode: u32,
+}
+
+impl FileDelta {
+    /// Set `has_conflicts` from the new-side lines.
+    pub fn detect_conflicts(&mut self) {
+        self.has_conflicts = self
+            .hunks
+            .iter()
+            .flat_map(|h| &h.lines)
+            .any(|l| l.origin != DiffLineOrigin::Deletion && l.is_conflict_marker());
+    }
+
+    /// Old and new commits of a submodule from its `Subproject commit` lines.
+    pub fn submodule_commits(&self) -> (Option<&str>, Option<&str>) {
+        let mut commits = (None, None);
+        for line in self.hunks.iter().flat_map(|h| &h.lines) {
+            let Some(oid) = line.content.trim_end().strip_prefix("Subproject commit ") else {
+                continue;
+            };
+            match line.origin {
+                DiffLineOrigin::Deletion => commits.0 = Some(oid),
+                DiffLineOrigin::Addition => commits.1 = Some(oid),
+                DiffLineOrigin::Context => {}
+            }
+        }
+        commits
+    }
+}
+
+/// The one-hunk diff git shows for a submodule moving from `old` to `new`;
+/// `None` on either side for an added or removed submodule.
+pub fn submodule_hunk(old: Option<&str>, new: Option<&str>) -> Hunk {
+    let line = |origin, oid: &str| DiffLine {
+        old_lineno: (origin == DiffLineOrigin::Deletion).then_some(1),
+        new_lineno: (origin == DiffLineOrigin::Addition).then_some(1),
+        origin,
+        content: format!("Subproject commit {oid}\n"),
+    };
+    let lines: Vec<DiffLine> = old
+        .map(|oid| line(DiffLineOrigin::Deletion, oid))
+        .into_iter()
+        .chain(new.map(|oid| line(DiffLineOrigin::Addition, oid)))
+        .collect();
+    let (deletions, additions) = (old.is_some() as usize, new.is_some() as usize);
+    Hunk {
+        header: format!("@@ -{deletions},{deletions} +{additions},{additions} @@"),
+        lines,
+        additions,
+        deletions,
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -58,4 +152,31 @@ pub enum ComparisonTarget {
     HeadVsWorkdir,
     Branch(String),
     Commit(git2::Oid),
+    /// Two-dot diff between two commits (`A..B`), independent of the working tree.
+    Range {
+        from: git2::Oid,
+        to: git2::Oid,
+    },
+}
+
+impl ComparisonTarget {
+    /// Short label used in the context bar and for per-target session files.
+    pub fn label(&self) -> String {
+        match self {
+            ComparisonTarget::HeadVsWorkdir => "HEAD".to_string(),
+            ComparisonTarget::Branch(name) => name.clone(),
+            ComparisonTarget::Commit(oid) => format!("{:.7}", oid),
+            ComparisonTarget::Range { from, to } => format!("{:.7}..{:.7}", from, to),
+        }
+    }
+}
+
+/// One `git log` entry for the file log panel.
+#[derive(Debug, Clone)]
+pub struct CommitSummary {
+    pub oid: git2::Oid,
+    /// Committer date as `YYYY-MM-DD`.
+    pub date: String,
+    pub author: String,
+    pub subject: String,
 }
@@ -9,6 +9,8 @@ pub struct WorktreeInfo {
     pub head_ref: Option<String>,
     pub is_main: bool,
     pub is_dirty: bool,
+    /// Locked with `git worktree lock`, so `git worktree prune` leaves it alone.
+    pub locked: bool,
     pub agent: Option<AgentInfo>,
     /// HEAD commit timestamp (seconds since epoch), used for sorting.
     pub head_time: i64,
@@ -40,6 +42,19 @@ impl AgentType {
     }
 }
 
+/// Default location for a new worktree: a sibling of the repo named `<repo>-<branch>`.
+pub fn default_worktree_path(repo_root: &Path, branch: &str) -> PathBuf {
+    let repo_name = repo_root
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "repo".to_string());
+    let dir_name = format!("{repo_name}-{}", branch.replace('/', "-"));
+    match repo_root.parent() {
+        Some(parent) => parent.join(dir_name),
+        None => PathBuf::from(dir_name),
+    }
+}
+
 /// List all worktrees for the repository at the given path.
 pub fn list_worktrees(repo_path: &Path) -> Result<Vec<WorktreeInfo>> {
     let repo = Repository::discover(repo_path).context("Not a git repository")?;
@@ -65,6 +80,7 @@ pub fn list_worktrees(repo_path: &Path) -> Result<Vec<WorktreeInfo>> {
             head_ref,
             is_main: true,
             is_dirty,
+            locked: false,
             agent: None,
             head_time,
         };
@@ -81,6 +97,7 @@ pub fn list_worktrees(repo_path: &Path) -> Result<Vec<WorktreeInfo>> {
         };
 
         let wt_path = wt.path().to_path_buf();
+        let locked = matches!(wt.is_locked(), Ok(git2::WorktreeLockStatus::Locked(_)));
 
         // Open the worktree's repo to get head info
         let (head_ref, is_dirty, head_time) = match Repository::open(&wt_path) {
@@ -105,6 +122,7 @@ pub fn list_worktrees(repo_path: &Path) -> Result<Vec<WorktreeInfo>> {
             head_ref,
             is_main: false,
             is_dirty,
+            locked,
             agent: None,
             head_time,
         };
@@ -182,3 +200,17 @@ fn detect_agent(path: &Path) -> Option<AgentInfo> {
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::default_worktree_path;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn default_path_is_sibling_named_after_branch() {
+        assert_eq!(
+            default_worktree_path(Path::new("/src/mdiff"), "feature/login"),
+            PathBuf::from("/src/mdiff-feature-login")
+        );
+    }
+}
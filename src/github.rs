@@ -0,0 +1,253 @@
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::git::types::FileDelta;
+use crate::state::annotation_state::{Annotation, LineAnchor};
+
+/// A pull request comment fetched through `gh`: either an inline review
+/// comment or the body of a submitted review.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewComment {
+    pub author: String,
+    pub body: String,
+    /// `None` for review bodies, which aren't attached to a file.
+    pub path: Option<String>,
+    /// Commented line range; `None` when GitHub marks the comment outdated.
+    pub lines: Option<(u32, u32)>,
+    /// Line the comment was originally written against.
+    pub original_line: Option<u32>,
+    /// Whether `lines` refers to the old side of the diff (`"side": "LEFT"`).
+    pub old_side: bool,
+}
+
+#[derive(Deserialize)]
+struct GhUser {
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct GhReview {
+    author: Option<GhUser>,
+    #[serde(default)]
+    body: String,
+}
+
+#[derive(Deserialize)]
+struct GhPullRequest {
+    number: u64,
+    #[serde(default)]
+    reviews: Vec<GhReview>,
+}
+
+#[derive(Deserialize)]
+struct GhComment {
+    user: Option<GhUser>,
+    body: String,
+    path: String,
+    line: Option<u32>,
+    start_line: Option<u32>,
+    original_line: Option<u32>,
+    side: Option<String>,
+}
+
+/// Whether the GitHub CLI is installed.
+pub fn gh_available() -> bool {
+    Command::new("gh")
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+fn run_gh(workdir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("gh")
+        .args(args)
+        .current_dir(workdir)
+        .stdin(Stdio::null())
+        .output()
+        .with_context(|| format!("Failed to run gh {}", args.join(" ")))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("gh {} failed: {}", args[..2].join(" "), stderr.trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Review bodies and inline comments of the pull request for the current branch.
+pub fn fetch_review_comments(workdir: &Path) -> Result<Vec<ReviewComment>> {
+    let pr = run_gh(workdir, &["pr", "view", "--json", "number,reviews"])?;
+    let pr: GhPullRequest =
+        serde_json::from_str(&pr).context("Unexpected output from gh pr view")?;
+
+    let endpoint = format!("repos/{{owner}}/{{repo}}/pulls/{}/comments", pr.number);
+    // `--jq '.[]'` prints one comment per line, which also flattens pagination
+    let comments = run_gh(workdir, &["api", "--paginate", &endpoint, "--jq", ".[]"])?;
+
+    let mut out: Vec<ReviewComment> = pr
+        .reviews
+        .into_iter()
+        .filter(|r| !r.body.trim().is_empty())
+        .map(|r| ReviewComment {
+            author: login(r.author),
+            body: r.body,
+            path: None,
+            lines: None,
+            original_line: None,
+            old_side: false,
+        })
+        .collect();
+    out.extend(parse_review_comments(&comments)?);
+    Ok(out)
+}
+
+fn login(user: Option<GhUser>) -> String {
+    user.map(|u| u.login).unwrap_or_else(|| "ghost".to_string())
+}
+
+/// Parse newline-delimited comment objects from the pull request comments API.
+fn parse_review_comments(ndjson: &str) -> Result<Vec<ReviewComment>> {
+    ndjson
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|line| {
+            let c: GhComment =
+                serde_json::from_str(line).context("Unexpected review comment from gh api")?;
+            Ok(ReviewComment {
+                author: login(c.user),
+                body: c.body,
+                path: Some(c.path),
+                lines: c
+                    .line
+                    .map(|end| (c.start_line.unwrap_or(end).min(end), end)),
+                original_line: c.original_line,
+                old_side: c.side.as_deref() == Some("LEFT"),
+            })
+        })
+        .collect()
+}
+
+/// Turn review comments into annotations, anchoring each one whose line is in
+/// the local diff. The rest are kept with `unanchored` set so they still show
+/// up in the review summary.
+pub fn to_annotations(comments: Vec<ReviewComment>, deltas: &[FileDelta]) -> Vec<Annotation> {
+    let now = chrono::Utc::now().to_rfc3339();
+    comments
+        .into_iter()
+        .map(|c| {
+            let path = c.path.clone().unwrap_or_default();
+            let delta = deltas.iter().find(|d| d.path.to_string_lossy() == path);
+            let in_diff = |lineno: u32| {
+                delta.is_some_and(|d| {
+                    d.hunks.iter().flat_map(|h| &h.lines).any(|l| {
+                        if c.old_side {
+                            l.old_lineno == Some(lineno)
+                        } else {
+                            l.new_lineno == Some(lineno)
+                        }
+                    })
+                })
+            };
+            let anchored = c
+                .lines
+                .filter(|&(start, end)| in_diff(start) && in_diff(end));
+
+            let mut comment = format!("@{}: {}", c.author, c.body.trim());
+            let anchor = match anchored {
+                Some(range) if c.old_side => LineAnchor::new(path, Some(range), None),
+                Some(range) => LineAnchor::new(path, None, Some(range)),
+                None => {
+                    if let Some(line) = c.lines.map(|(_, end)| end).or(c.original_line) {
+                        comment.push_str(&format!(" (GitHub line {line})"));
+                    }
+                    LineAnchor::new(path, None, None)
+                }
+            };
+            Annotation {
+                anchor,
+                comment,
+                created_at: now.clone(),
+                tags: Vec::new(),
+                unanchored: anchored.is_none(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_review_comments, to_annotations, ReviewComment};
+    use crate::git::types::{DiffLine, DiffLineOrigin, FileDelta, FileStatus, Hunk};
+    use std::path::PathBuf;
+
+    #[test]
+    fn parses_inline_comments() {
+        let ndjson = concat!(
+            r#"{"user":{"login":"octo"},"body":"nit","path":"src/a.rs","line":12,"start_line":10,"original_line":12,"side":"RIGHT"}"#,
+            "\n",
+            r#"{"user":null,"body":"stale","path":"src/b.rs","line":null,"start_line":null,"original_line":3,"side":"LEFT"}"#,
+            "\n",
+        );
+        let comments = parse_review_comments(ndjson).unwrap();
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments[0].author, "octo");
+        assert_eq!(comments[0].lines, Some((10, 12)));
+        assert!(!comments[0].old_side);
+        assert_eq!(comments[1].author, "ghost");
+        assert_eq!(comments[1].lines, None);
+        assert!(comments[1].old_side);
+    }
+
+    #[test]
+    fn anchors_comments_on_diff_lines_only() {
+        let line = |n: u32| DiffLine {
+            origin: DiffLineOrigin::Addition,
+            old_lineno: None,
+            new_lineno: Some(n),
+            content: "x\n".to_string(),
+        };
+        let delta = FileDelta {
+            path: PathBuf::from("src/a.rs"),
+            old_path: None,
+            status: FileStatus::Added,
+            hunks: vec![Hunk {
+                header: "@@ -0,0 +1,2 @@".to_string(),
+                lines: vec![line(1), line(2)],
+                additions: 2,
+                deletions: 0,
+            }],
+            additions: 2,
+            deletions: 0,
+            binary: false,
+            has_conflicts: false,
+            mode: 0o100644,
+        };
+        let comment = |path: Option<&str>, lines: Option<(u32, u32)>| ReviewComment {
+            author: "octo".to_string(),
+            body: "please fix".to_string(),
+            path: path.map(String::from),
+            lines,
+            original_line: None,
+            old_side: false,
+        };
+
+        let anns = to_annotations(
+            vec![
+                comment(Some("src/a.rs"), Some((2, 2))),
+                comment(Some("src/a.rs"), Some((40, 40))),
+                comment(None, None),
+            ],
+            &[delta],
+        );
+        assert!(!anns[0].unanchored);
+        assert_eq!(anns[0].anchor.new_range, Some((2, 2)));
+        assert_eq!(anns[0].comment, "@octo: please fix");
+        assert!(anns[1].unanchored);
+        assert_eq!(anns[1].comment, "@octo: please fix (GitHub line 40)");
+        assert!(anns[2].unanchored);
+        assert_eq!(anns[2].anchor.file_path, "");
+    }
+}
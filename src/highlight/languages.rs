@@ -49,10 +49,16 @@ pub fn language_entries() -> Vec<LanguageEntry> {
         ),
         lang!(
             "typescript",
-            &["ts", "tsx"],
+            &["ts", "mts", "cts"],
             tree_sitter_typescript::LANGUAGE_TYPESCRIPT,
             tree_sitter_typescript::HIGHLIGHTS_QUERY
         ),
+        lang!(
+            "tsx",
+            &["tsx"],
+            tree_sitter_typescript::LANGUAGE_TSX,
+            tree_sitter_typescript::HIGHLIGHTS_QUERY
+        ),
         lang!(
             "python",
             &["py", "pyi"],
@@ -61,7 +67,7 @@ pub fn language_entries() -> Vec<LanguageEntry> {
         ),
         lang!(
             "json",
-            &["json", "jsonc"],
+            &["json", "jsonc", "json5", "geojson", "webmanifest"],
             tree_sitter_json::LANGUAGE,
             tree_sitter_json::HIGHLIGHTS_QUERY
         ),
@@ -79,7 +85,7 @@ pub fn language_entries() -> Vec<LanguageEntry> {
         ),
         lang!(
             "html",
-            &["html", "htm"],
+            &["html", "htm", "xhtml"],
             tree_sitter_html::LANGUAGE,
             tree_sitter_html::HIGHLIGHTS_QUERY
         ),
@@ -91,13 +97,13 @@ pub fn language_entries() -> Vec<LanguageEntry> {
         ),
         lang!(
             "ruby",
-            &["rb", "rake", "gemspec"],
+            &["rb", "rake", "gemspec", "ru"],
             tree_sitter_ruby::LANGUAGE,
             tree_sitter_ruby::HIGHLIGHTS_QUERY
         ),
         lang!(
             "bash",
-            &["sh", "bash", "zsh"],
+            &["sh", "bash", "zsh", "ksh"],
             tree_sitter_bash::LANGUAGE,
             tree_sitter_bash::HIGHLIGHT_QUERY
         ),
@@ -111,19 +117,57 @@ pub fn language_entries() -> Vec<LanguageEntry> {
 }
 
 pub fn detect_language(path: &std::path::Path) -> Option<&'static str> {
-    let ext = path.extension()?.to_str()?;
     // Check against known extensions
-    for entry in language_entries() {
-        if entry.extensions.contains(&ext) {
-            return Some(entry.name);
+    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+        for entry in language_entries() {
+            if entry.extensions.contains(&ext) {
+                return Some(entry.name);
+            }
         }
     }
-    // Filename-based detection
+    // Filename-based detection, mostly for files without an extension. Only
+    // known TOML lockfiles: `.lock` alone also covers yarn.lock and Gemfile.lock
     let filename = path.file_name()?.to_str()?;
     match filename {
         "Makefile" | "makefile" | "GNUmakefile" => Some("bash"),
-        "Dockerfile" => Some("bash"),
-        "Gemfile" | "Rakefile" => Some("ruby"),
+        "Dockerfile" | "Containerfile" => Some("bash"),
+        ".bashrc" | ".bash_profile" | ".zshrc" | ".zprofile" | ".profile" | ".envrc" => {
+            Some("bash")
+        }
+        "Gemfile" | "Rakefile" | "Podfile" | "Vagrantfile" | "Brewfile" => Some("ruby"),
+        "Pipfile" | "Cargo.lock" | "poetry.lock" | "uv.lock" => Some("toml"),
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{detect_language, language_entries};
+    use std::path::Path;
+
+    #[test]
+    fn detects_by_extension_then_filename() {
+        assert_eq!(detect_language(Path::new("src/App.tsx")), Some("tsx"));
+        assert_eq!(
+            detect_language(Path::new("lib/index.mts")),
+            Some("typescript")
+        );
+        assert_eq!(detect_language(Path::new("Cargo.lock")), Some("toml"));
+        assert_eq!(detect_language(Path::new("py/uv.lock")), Some("toml"));
+        assert_eq!(detect_language(Path::new("yarn.lock")), None);
+        assert_eq!(detect_language(Path::new("Gemfile.lock")), None);
+        assert_eq!(detect_language(Path::new("Makefile")), Some("bash"));
+        assert_eq!(detect_language(Path::new("home/.zshrc")), Some("bash"));
+        assert_eq!(detect_language(Path::new("Vagrantfile")), Some("ruby"));
+        assert_eq!(detect_language(Path::new("main.zig")), None);
+        assert_eq!(detect_language(Path::new("LICENSE")), None);
+    }
+
+    #[test]
+    fn every_highlight_query_compiles() {
+        let names = crate::highlight::theme::highlight_names_vec();
+        for entry in language_entries() {
+            entry.config(&names);
+        }
+    }
+}
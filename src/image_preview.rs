@@ -0,0 +1,230 @@
+use std::path::{Path, PathBuf};
+
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+
+/// Extensions of binary files drawn as images instead of "Binary file differs".
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp"];
+
+/// Blobs larger than this aren't decoded.
+#[cfg_attr(not(feature = "image"), allow(dead_code))]
+const MAX_IMAGE_BYTES: usize = 4 * 1024 * 1024;
+/// Nor are images with more pixels than this, whatever their file size.
+#[cfg_attr(not(feature = "image"), allow(dead_code))]
+const MAX_IMAGE_PIXELS: u64 = 2048 * 2048;
+
+/// Whether `path` has one of the image extensions we try to preview.
+pub fn is_image_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| IMAGE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+/// Decoded pixels, row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RgbaImage {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<[u8; 4]>,
+}
+
+/// One side of a binary image diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImageSide {
+    /// The file doesn't exist on this side (added or deleted).
+    Missing,
+    Decoded {
+        image: RgbaImage,
+        bytes: usize,
+    },
+    /// Too large, in a format we can't decode, or built without the `image` feature.
+    Undecoded {
+        bytes: usize,
+    },
+}
+
+impl ImageSide {
+    pub fn from_bytes(bytes: Option<Vec<u8>>) -> Self {
+        let Some(bytes) = bytes else {
+            return Self::Missing;
+        };
+        match decode(&bytes) {
+            Some(image) => Self::Decoded {
+                image,
+                bytes: bytes.len(),
+            },
+            None => Self::Undecoded { bytes: bytes.len() },
+        }
+    }
+
+    pub fn bytes(&self) -> Option<usize> {
+        match self {
+            Self::Missing => None,
+            Self::Decoded { bytes, .. } | Self::Undecoded { bytes } => Some(*bytes),
+        }
+    }
+}
+
+/// Old and new versions of the selected binary image.
+#[derive(Debug, Clone)]
+pub struct ImagePreview {
+    pub path: PathBuf,
+    pub old: ImageSide,
+    pub new: ImageSide,
+}
+
+impl ImagePreview {
+    /// Whether either side can be drawn; otherwise the diff shows just the size.
+    pub fn any_decoded(&self) -> bool {
+        [&self.old, &self.new]
+            .iter()
+            .any(|side| matches!(side, ImageSide::Decoded { .. }))
+    }
+}
+
+#[cfg(feature = "image")]
+fn decode(bytes: &[u8]) -> Option<RgbaImage> {
+    if bytes.len() > MAX_IMAGE_BYTES {
+        return None;
+    }
+    let reader = || {
+        image::ImageReader::new(std::io::Cursor::new(bytes))
+            .with_guessed_format()
+            .ok()
+    };
+    let (width, height) = reader()?.into_dimensions().ok()?;
+    if u64::from(width) * u64::from(height) > MAX_IMAGE_PIXELS {
+        return None;
+    }
+    let rgba = reader()?.decode().ok()?.into_rgba8();
+    Some(RgbaImage {
+        width,
+        height,
+        pixels: rgba.pixels().map(|p| p.0).collect(),
+    })
+}
+
+#[cfg(not(feature = "image"))]
+fn decode(_bytes: &[u8]) -> Option<RgbaImage> {
+    None
+}
+
+/// Pixel size to draw a `width`×`height` image at in `cols` columns and `rows`
+/// rows of half blocks (two pixels per cell), keeping the aspect ratio. Small
+/// images are drawn at their own size rather than scaled up.
+fn fit(width: u32, height: u32, cols: u16, rows: u16) -> (u32, u32) {
+    let (max_w, max_h) = (u32::from(cols), u32::from(rows) * 2);
+    if width <= max_w && height <= max_h {
+        return (width, height);
+    }
+    let scale = f64::min(
+        f64::from(max_w) / f64::from(width),
+        f64::from(max_h) / f64::from(height),
+    );
+    let scaled = |n: u32| ((f64::from(n) * scale) as u32).max(1);
+    (scaled(width), scaled(height))
+}
+
+/// Draw `image` with `▀` / `▄` / `█` half blocks, the upper pixel of each cell
+/// in the foreground and the lower one in the background. Mostly transparent
+/// pixels are left blank.
+pub fn render_half_blocks(image: &RgbaImage, cols: u16, rows: u16) -> Vec<Line<'static>> {
+    if image.width == 0 || image.height == 0 || cols == 0 || rows == 0 {
+        return Vec::new();
+    }
+    let (width, height) = fit(image.width, image.height, cols, rows);
+    // Nearest-neighbour sampling is enough at terminal resolution
+    let pixel = |x: u32, y: u32| -> Option<Color> {
+        if y >= height {
+            return None;
+        }
+        let sx = x * image.width / width;
+        let sy = y * image.height / height;
+        let [r, g, b, a] = image.pixels[(sy * image.width + sx) as usize];
+        (a >= 128).then_some(Color::Rgb(r, g, b))
+    };
+
+    (0..height.div_ceil(2))
+        .map(|row| {
+            let spans: Vec<Span<'static>> = (0..width)
+                .map(|x| match (pixel(x, row * 2), pixel(x, row * 2 + 1)) {
+                    (Some(top), Some(bottom)) if top == bottom => {
+                        Span::styled("\u{2588}", Style::default().fg(top))
+                    }
+                    (Some(top), Some(bottom)) => {
+                        Span::styled("\u{2580}", Style::default().fg(top).bg(bottom))
+                    }
+                    (Some(top), None) => Span::styled("\u{2580}", Style::default().fg(top)),
+                    (None, Some(bottom)) => Span::styled("\u{2584}", Style::default().fg(bottom)),
+                    (None, None) => Span::raw(" "),
+                })
+                .collect();
+            Line::from(spans)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fit, is_image_path, render_half_blocks, RgbaImage};
+    use ratatui::style::Color;
+    use std::path::Path;
+
+    #[test]
+    fn fits_large_images_and_keeps_small_ones() {
+        assert!(is_image_path(Path::new("assets/Logo.PNG")));
+        assert!(!is_image_path(Path::new("assets/logo.svg")));
+        assert!(is_image_path(Path::new("assets/photo.jpg")));
+
+        assert_eq!(fit(16, 16, 80, 24), (16, 16));
+        // 400×200 into 80 columns by 40 pixel rows: width-bound
+        assert_eq!(fit(400, 200, 80, 20), (80, 40));
+        assert_eq!(fit(100, 1000, 80, 10), (2, 20));
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn decodes_png_and_keeps_size_of_the_rest() {
+        use super::ImageSide;
+
+        let mut png = Vec::new();
+        image::RgbaImage::from_pixel(3, 2, image::Rgba([1, 2, 3, 255]))
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+        match ImageSide::from_bytes(Some(png.clone())) {
+            ImageSide::Decoded { image, bytes } => {
+                assert_eq!((image.width, image.height, bytes), (3, 2, png.len()));
+                assert_eq!(image.pixels[5], [1, 2, 3, 255]);
+            }
+            other => panic!("not decoded: {other:?}"),
+        }
+        assert_eq!(
+            ImageSide::from_bytes(Some(b"not an image".to_vec())),
+            ImageSide::Undecoded { bytes: 12 }
+        );
+        assert_eq!(ImageSide::from_bytes(None), ImageSide::Missing);
+    }
+
+    #[test]
+    fn draws_two_pixels_per_cell() {
+        let red = [255, 0, 0, 255];
+        let blue = [0, 0, 255, 255];
+        let clear = [0, 0, 0, 0];
+        // 3×3: the last row ends up alone in the bottom cell row
+        let image = RgbaImage {
+            width: 3,
+            height: 3,
+            pixels: vec![red, red, clear, red, blue, red, blue, clear, clear],
+        };
+        let lines = render_half_blocks(&image, 10, 10);
+        assert_eq!(lines.len(), 2);
+
+        let text =
+            |i: usize| -> String { lines[i].spans.iter().map(|s| s.content.as_ref()).collect() };
+        assert_eq!(text(0), "\u{2588}\u{2580}\u{2584}");
+        assert_eq!(text(1), "\u{2580}  ");
+        assert_eq!(lines[0].spans[1].style.fg, Some(Color::Rgb(255, 0, 0)));
+        assert_eq!(lines[0].spans[1].style.bg, Some(Color::Rgb(0, 0, 255)));
+        assert_eq!(lines[0].spans[2].style.fg, Some(Color::Rgb(255, 0, 0)));
+    }
+}
@@ -0,0 +1,287 @@
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::action::Action;
+
+/// A key plus modifiers, parsed from strings like `S`, `Ctrl+Space` or `Alt+p`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeySpec {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeySpec {
+    pub fn parse(spec: &str) -> Result<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut parts: Vec<&str> = spec.split('+').map(str::trim).collect();
+        // "Ctrl++" binds the plus key itself
+        if spec.ends_with("++") {
+            parts.truncate(parts.len() - 2);
+            parts.push("+");
+        }
+        let Some(key) = parts.pop().filter(|k| !k.is_empty()) else {
+            bail!("empty key in `{spec}`");
+        };
+        for part in parts {
+            match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => modifiers |= KeyModifiers::CONTROL,
+                "alt" | "meta" => modifiers |= KeyModifiers::ALT,
+                "shift" => modifiers |= KeyModifiers::SHIFT,
+                other => bail!("unknown modifier `{other}` in `{spec}`"),
+            }
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            // Shift is folded into the character: `Shift+s` is `S`
+            (Some(c), None) if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_ascii_lowercase().as_str() {
+                "space" => KeyCode::Char(' '),
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=12) => KeyCode::F(n),
+                    _ => bail!("unknown key `{key}` in `{spec}`"),
+                },
+            },
+        };
+        Ok(Self { code, modifiers })
+    }
+
+    /// Whether a key event is this key. Shift is ignored for characters since the
+    /// terminal already reports `S` rather than `Shift+s`.
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        let ignore = match self.code {
+            KeyCode::Char(_) => KeyModifiers::SHIFT,
+            _ => KeyModifiers::NONE,
+        };
+        key.code == self.code
+            && key.modifiers.difference(ignore) == self.modifiers.difference(ignore)
+    }
+}
+
+/// Formats the key the way `parse` reads it, e.g. `Ctrl+Space`.
+impl std::fmt::Display for KeySpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "Ctrl"),
+            (KeyModifiers::ALT, "Alt"),
+            (KeyModifiers::SHIFT, "Shift"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{name}+")?;
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Delete"),
+            KeyCode::Up => write!(f, "Up"),
+            KeyCode::Down => write!(f, "Down"),
+            KeyCode::Left => write!(f, "Left"),
+            KeyCode::Right => write!(f, "Right"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::PageUp => write!(f, "PageUp"),
+            KeyCode::PageDown => write!(f, "PageDown"),
+            KeyCode::F(n) => write!(f, "F{n}"),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
+/// Config names for the actions that can be rebound.
+fn action_by_name(name: &str) -> Option<Action> {
+    Some(match name {
+        "quit" => Action::Quit,
+        "toggle_view" => Action::ToggleViewMode,
+        "toggle_whitespace" => Action::ToggleWhitespace,
+        "stage" => Action::StageFile,
+        "unstage" => Action::UnstageFile,
+        "patch_summary" => Action::DiffCurrentFilePatch,
+        "stage_hunk" => Action::StageHunk,
+        "unstage_hunk" => Action::UnstageHunk,
+        "bulk_stage" => Action::BulkStage,
+        "bulk_unstage" => Action::BulkUnstage,
+        "restore" => Action::RestoreFile,
+        "commit" => Action::OpenCommitDialog,
+        "push" => Action::Push,
+        "rebase_interactive" => Action::RebaseInteractive,
+        "agent_outputs" => Action::SwitchToAgentOutputs,
+        "agent_selector" => Action::OpenAgentSelector,
+        "worktree_browser" => Action::ToggleWorktreeBrowser,
+        "feedback_summary" => Action::ToggleFeedbackSummary,
+        "review_summary" => Action::ToggleReviewSummary,
+        "file_log" => Action::ToggleFileLog,
+        "export_annotations" => Action::ExportAnnotations,
+        "import_github_reviews" => Action::ImportGithubReviews,
+        "refresh" => Action::RefreshDiff,
+        "target" => Action::OpenTargetDialog,
+        "new_tab" => Action::NewTab,
+        "close_tab" => Action::CloseTab,
+        "next_tab" => Action::NextTab,
+        "prev_tab" => Action::PrevTab,
+        "filter" => Action::FilterFiles,
+        "status_filter" => Action::ToggleFileStatus,
+        "replace" => Action::ReplaceInDiff,
+        "global_search" => Action::StartGlobalSearch,
+        "checklist" => Action::ToggleChecklist,
+        "settings" => Action::OpenSettings,
+        "which_key" => Action::ToggleWhichKey,
+        "next_file" => Action::NextFile,
+        "prev_file" => Action::PrevFile,
+        "next_hunk" => Action::JumpNextHunk,
+        "prev_hunk" => Action::JumpPrevHunk,
+        "jump_to_hunk" => Action::OpenJumpToHunk,
+        "split_hunk" => Action::SplitHunk,
+        "next_annotation" => Action::NextAnnotation,
+        "prev_annotation" => Action::PrevAnnotation,
+        "toggle_bookmark" => Action::ToggleBookmark,
+        "next_bookmark" => Action::NextBookmark,
+        "prev_bookmark" => Action::PrevBookmark,
+        "next_unreviewed" => Action::NextUnreviewed,
+        "toggle_reviewed" => Action::ToggleFileReviewed,
+        "collapse_file" => Action::CollapseFile,
+        "expand_file" => Action::ExpandFile,
+        "undo_annotation" => Action::UndoAnnotation,
+        "redo_annotation" => Action::RedoAnnotation,
+        "line_wrap" => Action::ToggleLineWrap,
+        "toggle_annotations" => Action::ToggleAnnotationsVisible,
+        "inline_annotations" => Action::ToggleInlineAnnotations,
+        "increase_context" => Action::IncreaseContext,
+        "decrease_context" => Action::DecreaseContext,
+        "prompt_preview" => Action::TogglePromptPreview,
+        "focus_mode" => Action::ToggleFocusMode,
+        "copy_prompt" => Action::CopyPromptToClipboard,
+        "copy_line" => Action::CopyCursorLineContent,
+        "copy_file_diff" => Action::CopyFileDiff,
+        "copy_hunk_patch" => Action::CopyHunkPatch,
+        "copy_file_list" => Action::CopyFileList,
+        "copy_diff_json" => Action::DiffFormatJson,
+        "open_in_editor" => Action::OpenInEditor,
+        "go_to_definition" => Action::GoToDefinition,
+        "open_in_file_manager" => Action::OpenFileInExplorer,
+        "apply_hunk" => Action::ApplyHunk,
+        "revert_hunk" => Action::RevertHunk,
+        _ => return None,
+    })
+}
+
+/// Keys the dialogs and confirm prompts handle themselves; a binding on one of
+/// these never fires while a dialog is open (or at all, for the quit keys).
+fn is_modal_key(spec: &KeySpec) -> bool {
+    let ctrl = spec.modifiers.contains(KeyModifiers::CONTROL);
+    match spec.code {
+        KeyCode::Enter | KeyCode::Esc | KeyCode::Tab | KeyCode::Backspace => true,
+        KeyCode::Char('c' | 'd' | 'a' | 'e' | 'w') => ctrl,
+        _ => false,
+    }
+}
+
+/// Parse the `[keybindings]` config table. Unknown actions, bad key strings and
+/// conflicts are skipped or kept with a warning for the status bar.
+pub fn parse_keybindings(
+    table: &HashMap<String, String>,
+) -> (HashMap<Action, KeySpec>, Vec<String>) {
+    let mut bindings: HashMap<Action, KeySpec> = HashMap::new();
+    let mut warnings = Vec::new();
+
+    let mut names: Vec<&String> = table.keys().collect();
+    names.sort();
+    for name in names {
+        let key = &table[name];
+        let Some(action) = action_by_name(name) else {
+            warnings.push(format!("unknown keybinding action `{name}`"));
+            continue;
+        };
+        let spec = match KeySpec::parse(key) {
+            Ok(spec) => spec,
+            Err(e) => {
+                warnings.push(format!("keybinding `{name}`: {e}"));
+                continue;
+            }
+        };
+        if bindings.values().any(|bound| *bound == spec) {
+            warnings.push(format!("keybinding `{name} = \"{key}\"` is already bound"));
+            continue;
+        }
+        if is_modal_key(&spec) {
+            warnings.push(format!(
+                "keybinding `{name} = \"{key}\"` conflicts with a dialog key"
+            ));
+        }
+        bindings.insert(action, spec);
+    }
+    (bindings, warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_keybindings, KeySpec};
+    use crate::action::Action;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use std::collections::HashMap;
+
+    #[test]
+    fn parses_and_matches_key_specs() {
+        let ctrl_space = KeySpec::parse("Ctrl+Space").unwrap();
+        assert_eq!(ctrl_space.code, KeyCode::Char(' '));
+        assert!(ctrl_space.matches(&KeyEvent::new(KeyCode::Char(' '), KeyModifiers::CONTROL)));
+        assert!(!ctrl_space.matches(&KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE)));
+
+        let upper = KeySpec::parse("S").unwrap();
+        assert!(upper.matches(&KeyEvent::new(KeyCode::Char('S'), KeyModifiers::SHIFT)));
+        assert_eq!(KeySpec::parse("shift+s").unwrap(), upper);
+
+        assert_eq!(KeySpec::parse("F5").unwrap().code, KeyCode::F(5));
+        assert!(KeySpec::parse("Hyper+x").is_err());
+        assert!(KeySpec::parse("Ctrl+").is_err());
+
+        for spec in ["Ctrl+Space", "Alt+p", "S", "Ctrl+Alt+F5", "PageDown"] {
+            assert_eq!(KeySpec::parse(spec).unwrap().to_string(), spec);
+        }
+        assert_eq!(KeySpec::parse("shift+s").unwrap().to_string(), "S");
+    }
+
+    #[test]
+    fn reports_unknown_duplicate_and_modal_bindings() {
+        let table: HashMap<String, String> = [
+            ("stage", "S"),
+            ("unstage", "S"),
+            ("refresh", "Enter"),
+            ("frobnicate", "x"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        let (bindings, warnings) = parse_keybindings(&table);
+        assert_eq!(bindings.len(), 2);
+        assert!(bindings.contains_key(&Action::RefreshDiff));
+        assert_eq!(warnings.len(), 3);
+        assert!(warnings.iter().any(|w| w.contains("frobnicate")));
+        assert!(warnings.iter().any(|w| w.contains("dialog key")));
+        assert!(warnings.iter().any(|w| w.contains("already bound")));
+    }
+}
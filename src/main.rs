@@ -6,25 +6,37 @@ mod async_diff;
 mod cli;
 mod components;
 mod config;
+mod diff_stat;
+mod diff_utils;
 mod display_map;
+mod editor;
 mod event;
+mod export;
 mod git;
+mod github;
 mod highlight;
+mod image_preview;
+mod keybindings;
+mod paths;
+mod platform;
 mod pty_runner;
 mod session;
 mod state;
+mod tags;
 mod theme;
 mod tui;
 
 use anyhow::Result;
 use clap::Parser;
 use std::env;
+use std::io::IsTerminal;
 
-use crate::app::{parse_target, App};
-use crate::cli::Cli;
-use crate::git::RepoCache;
+use crate::app::{parse_ref_pair, parse_target, App};
+use crate::cli::{Cli, Command, ConfigCommand};
+use crate::config::{MAX_CONTEXT, MIN_CONTEXT};
+use crate::git::{DiffBackend, DiffFilter, RepoCache};
 use crate::state::DiffOptions;
-use crate::theme::Theme;
+use crate::theme::{apply_overrides, ColorSupport, Theme, THEME_NAMES};
 
 fn install_panic_hook() {
     let default_hook = std::panic::take_hook();
@@ -35,6 +47,43 @@ fn install_panic_hook() {
     }));
 }
 
+/// A built-in theme with the config's `[colors]` overrides on top.
+fn theme_with_overrides(name: &str, config: &config::MdiffConfig) -> Theme {
+    let mut theme = config.color_support.adapt_theme(Theme::from_name(name));
+    if let Some(ref overrides) = config.color_overrides {
+        apply_overrides(&mut theme, overrides);
+    }
+    theme
+}
+
+/// `--list-themes`: print every built-in theme, or just the resolved `--theme`.
+fn list_themes(cli: &Cli) -> i32 {
+    let themes = match cli.theme.as_deref() {
+        Some(name) if !THEME_NAMES.contains(&name.to_ascii_lowercase().as_str()) => {
+            eprintln!(
+                "mdiff: unknown theme `{name}` (expected one of: {})",
+                THEME_NAMES.join(", ")
+            );
+            return 1;
+        }
+        Some(name) => vec![theme_with_overrides(
+            name,
+            &config::load_config_with_colors(ColorSupport::TrueColor),
+        )],
+        None => THEME_NAMES
+            .iter()
+            .map(|name| Theme::from_name(name))
+            .collect(),
+    };
+    let color = if cli.color || std::io::stdout().is_terminal() {
+        ColorSupport::detect(cli.color, cli.no_color)
+    } else {
+        ColorSupport::None
+    };
+    print!("{}", theme::format_theme_list(&themes, color));
+    0
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     color_eyre::install().ok();
@@ -42,6 +91,17 @@ async fn main() -> Result<()> {
 
     let cli = Cli::parse();
 
+    if let Some(Command::Config {
+        action: ConfigCommand::Validate,
+    }) = cli.command
+    {
+        std::process::exit(config::run_validate());
+    }
+
+    if cli.list_themes {
+        std::process::exit(list_themes(&cli));
+    }
+
     let cwd = env::current_dir()?;
 
     // Validate we're in a git repo before launching TUI
@@ -58,29 +118,114 @@ async fn main() -> Result<()> {
     let repo_path = repo.workdir().to_path_buf();
     drop(repo);
 
-    let target = parse_target(cli.target.as_deref());
+    let target = match (cli.target.as_deref(), cli.target2) {
+        (Some(from), Some(to)) => parse_ref_pair(&repo_path, from, &to),
+        (target, None) => parse_target(&repo_path, target).map(|t| {
+            let label = t.label();
+            (t, label)
+        }),
+        (None, Some(_)) => unreachable!("clap fills positionals in order"),
+    };
+    let (target, target_label) = match target {
+        Ok(t) => t,
+        Err(e) => {
+            eprintln!("mdiff: invalid target: {e}");
+            std::process::exit(1);
+        }
+    };
 
     // Load config, apply CLI overrides
-    let mut config = config::load_config();
+    let color_support = ColorSupport::detect(cli.color, cli.no_color);
+    let mut config = config::load_config_with_colors(color_support);
     if let Some(ref theme_name) = cli.theme {
-        config.theme = Theme::from_name(theme_name);
+        config.theme = theme_with_overrides(theme_name, &config);
+    }
+    if let Some(padding) = cli.padding {
+        config.context_padding = padding;
     }
+    config.accessible |= cli.accessible;
 
     // Merge CLI flags with config-file settings (CLI wins)
     let unified = cli.unified || config.unified.unwrap_or(false);
     let ignore_ws = cli.ignore_whitespace || config.ignore_whitespace.unwrap_or(false);
-    let context_lines = config.context_lines;
+    let context_lines = match cli.context {
+        Some(n) if !(MIN_CONTEXT..=MAX_CONTEXT).contains(&n) => {
+            let clamped = n.clamp(MIN_CONTEXT, MAX_CONTEXT);
+            eprintln!(
+                "warning: --context {n} is outside {MIN_CONTEXT}-{MAX_CONTEXT}, using {clamped}"
+            );
+            Some(clamped)
+        }
+        Some(n) => Some(n),
+        None => config.context_lines,
+    };
 
-    let diff_options = DiffOptions::new(ignore_ws, unified);
+    let mut diff_options = DiffOptions::new(ignore_ws, unified);
+    if let Some(args) = cli.diff_tool.as_ref().or(config.diff_tool.as_ref()) {
+        diff_options.backend = DiffBackend::GitCli {
+            args: args.split_whitespace().map(String::from).collect(),
+        };
+    }
+    diff_options.filter = DiffFilter {
+        since: cli.since,
+        until: cli.until,
+    };
     let mut app = App::new(
         diff_options,
         cli.worktree_browser,
         target,
+        target_label,
         repo_path,
         config,
         context_lines,
     );
 
+    if cli.stat {
+        // Like git, only color the bars on a terminal unless --color asks for it
+        let color = if cli.color || std::io::stdout().is_terminal() {
+            color_support
+        } else {
+            ColorSupport::None
+        };
+        match app.run_stat(color).await {
+            Ok(()) => std::process::exit(0),
+            Err(e) => {
+                eprintln!("mdiff: {e:#}");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if cli.json {
+        match app.run_json().await {
+            Ok(()) => std::process::exit(0),
+            Err(e) => {
+                eprintln!("mdiff: {e:#}");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if cli.check {
+        match app.run_check().await {
+            Ok(code) => std::process::exit(code),
+            Err(e) => {
+                eprintln!("mdiff: {e:#}");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if let (true, Some(agent)) = (cli.headless, cli.agent.as_deref()) {
+        match app.run_headless(agent, cli.model).await {
+            Ok(code) => std::process::exit(code),
+            Err(e) => {
+                eprintln!("mdiff: {e:#}");
+                std::process::exit(1);
+            }
+        }
+    }
+
     let mut terminal = tui::init()?;
     let result = app.run(&mut terminal).await;
     tui::restore()?;
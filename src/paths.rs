@@ -0,0 +1,63 @@
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+/// The user's home directory from `$HOME`.
+pub fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(PathBuf::from)
+}
+
+/// Directory holding `config.toml`: `$XDG_CONFIG_HOME/mdiff`, by default
+/// `~/.config/mdiff`.
+pub fn config_dir() -> PathBuf {
+    xdg_dir(std::env::var_os("XDG_CONFIG_HOME"), home_dir(), ".config")
+}
+
+/// Directory for mdiff's own data (exports, the session database):
+/// `$XDG_DATA_HOME/mdiff`, by default `~/.local/share/mdiff`.
+pub fn data_dir() -> PathBuf {
+    xdg_dir(
+        std::env::var_os("XDG_DATA_HOME"),
+        home_dir(),
+        ".local/share",
+    )
+}
+
+/// `<base>/mdiff` for an XDG base directory variable, or `<home>/<fallback>/mdiff`
+/// when it's unset, empty or relative (which the spec says to ignore).
+fn xdg_dir(var: Option<OsString>, home: Option<PathBuf>, fallback: &str) -> PathBuf {
+    let base = var
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .unwrap_or_else(|| {
+            home.unwrap_or_else(|| PathBuf::from("."))
+                .join(Path::new(fallback))
+        });
+    base.join("mdiff")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::xdg_dir;
+    use std::path::PathBuf;
+
+    #[test]
+    fn prefers_absolute_xdg_dirs_over_home() {
+        let home = || Some(PathBuf::from("/home/me"));
+        assert_eq!(
+            xdg_dir(Some("/xdg/config".into()), home(), ".config"),
+            PathBuf::from("/xdg/config/mdiff")
+        );
+        assert_eq!(
+            xdg_dir(None, home(), ".local/share"),
+            PathBuf::from("/home/me/.local/share/mdiff")
+        );
+        assert_eq!(
+            xdg_dir(Some("".into()), home(), ".config"),
+            PathBuf::from("/home/me/.config/mdiff")
+        );
+        assert_eq!(
+            xdg_dir(Some("relative".into()), None, ".config"),
+            PathBuf::from("./.config/mdiff")
+        );
+    }
+}
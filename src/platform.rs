@@ -0,0 +1,38 @@
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use anyhow::{bail, Result};
+
+/// Show `path` in the system file manager: revealed in Finder on macOS,
+/// its containing directory opened with `xdg-open` elsewhere.
+pub fn open_in_explorer(path: &Path) -> Result<()> {
+    let mut cmd = if cfg!(target_os = "macos") {
+        let mut cmd = Command::new("open");
+        cmd.arg("-R").arg(path);
+        cmd
+    } else if cfg!(windows) {
+        let mut cmd = Command::new("explorer");
+        cmd.arg(format!("/select,{}", path.display()));
+        cmd
+    } else {
+        let dir = path.parent().unwrap_or(path);
+        let mut cmd = Command::new("xdg-open");
+        cmd.arg(dir);
+        cmd
+    };
+    let program = cmd.get_program().to_string_lossy().into_owned();
+
+    // Detached and silenced so the file manager's output can't draw over the TUI
+    match cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        Ok(_) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            bail!("`{program}` is not available to open a file manager")
+        }
+        Err(e) => bail!("Failed to run {program}: {e}"),
+    }
+}
@@ -22,7 +22,14 @@ pub struct PtyRunner {
 impl PtyRunner {
     /// Spawn an agent subprocess in a PTY. Returns a PtyRunner that can be
     /// polled for output and written to for interactive input.
-    pub fn spawn(run_id: usize, command: &str, rows: u16, cols: u16, cwd: &Path) -> Self {
+    pub fn spawn(
+        run_id: usize,
+        command: &str,
+        env: &[(&str, String)],
+        rows: u16,
+        cols: u16,
+        cwd: &Path,
+    ) -> Self {
         let pty_system = native_pty_system();
         let pair = pty_system
             .openpty(PtySize {
@@ -36,6 +43,9 @@ impl PtyRunner {
         let mut cmd = CommandBuilder::new("sh");
         cmd.arg("-c");
         cmd.arg(command);
+        for (key, value) in env {
+            cmd.env(key, value);
+        }
         cmd.cwd(cwd);
 
         let child = pair.slave.spawn_command(cmd).expect("failed to spawn");
@@ -108,11 +118,7 @@ impl PtyRunner {
     /// Check if the child has exited. Returns Some(exit_code) if done.
     pub fn try_wait(&mut self) -> Option<i32> {
         match self.child.try_wait() {
-            Ok(Some(status)) => {
-                // portable-pty ExitStatus doesn't directly expose code on all
-                // platforms, but success() is reliable.
-                Some(if status.success() { 0 } else { 1 })
-            }
+            Ok(Some(status)) => Some(status.exit_code() as i32),
             _ => None,
         }
     }
@@ -1,28 +1,399 @@
+use std::collections::HashMap;
 use std::fs;
-use std::io::Write;
 use std::path::{Path, PathBuf};
 
+use anyhow::{bail, Context};
+use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 
-use crate::state::annotation_state::Annotation;
-use crate::state::{AnnotationState, ChecklistState};
+use crate::paths;
+use crate::state::annotation_state::{Annotation, LineAnchor};
+use crate::state::review_state::ReviewState;
+use crate::state::{AnnotationState, ChecklistItem, ChecklistState};
 
-#[derive(Serialize, Deserialize)]
-struct SessionFile {
+/// Everything persisted for one (repo, target) pair.
+#[derive(Default)]
+pub struct SessionData {
+    pub annotations: AnnotationState,
+    pub checklist: Option<ChecklistState>,
+    /// Reviewed hunk hashes per file, with when each was reviewed.
+    pub reviewed: HashMap<String, HashMap<u64, String>>,
+}
+
+/// Format version written to `.mdiffsession` files. Version 1 files hold hunk
+/// hashes from an unstable hasher, so their reviewed hunks are dropped on import.
+const SESSION_FILE_VERSION: u32 = 2;
+
+/// A review exported to a `.mdiffsession` file, for handing off to a teammate
+/// or archiving.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionFile {
+    pub version: u32,
+    /// Comparison target the review was made against.
+    pub target: String,
+    pub exported_at: String,
+    pub annotations: Vec<Annotation>,
+    /// Reviewed hunk hashes per file, with when each was reviewed.
+    #[serde(default)]
+    pub reviewed: HashMap<String, HashMap<u64, String>>,
+}
+
+/// Default export path for a review: in the data directory rather than the
+/// worktree, so the file doesn't show up as an untracked change.
+pub fn default_session_file(repo_path: &Path, target_label: &str) -> PathBuf {
+    let repo_name = repo_path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "repo".to_string());
+    let sanitized = format!("{repo_name}-{target_label}").replace(['/', '\\', ':', ' '], "_");
+    paths::data_dir().join(format!("{sanitized}.mdiffsession"))
+}
+
+/// Write the annotations and reviewed hunks to `path` as JSON, returning the
+/// number of annotations written.
+pub fn export_session_file(
+    path: &Path,
+    target_label: &str,
+    annotations: &AnnotationState,
+    review: &ReviewState,
+) -> anyhow::Result<usize> {
+    let file = SessionFile {
+        version: SESSION_FILE_VERSION,
+        target: target_label.to_string(),
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        annotations: annotations.all_sorted().into_iter().cloned().collect(),
+        reviewed: review.reviewed_hunks().clone(),
+    };
+    let json = serde_json::to_string_pretty(&file)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(path, json).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(file.annotations.len())
+}
+
+/// Read a `.mdiffsession` file written by [`export_session_file`].
+pub fn read_session_file(path: &Path) -> anyhow::Result<SessionFile> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut file: SessionFile = serde_json::from_str(&contents)
+        .with_context(|| format!("{} is not an mdiff session file", path.display()))?;
+    if file.version > SESSION_FILE_VERSION {
+        bail!(
+            "{} was written by a newer mdiff (format {})",
+            path.display(),
+            file.version
+        );
+    }
+    if file.version < 2 {
+        file.reviewed.clear();
+    }
+    Ok(file)
+}
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS sessions (
+        repo_path TEXT NOT NULL,
+        target_label TEXT NOT NULL,
+        saved_at TEXT NOT NULL,
+        PRIMARY KEY (repo_path, target_label)
+    );
+    CREATE TABLE IF NOT EXISTS annotations (
+        id INTEGER PRIMARY KEY,
+        repo_path TEXT NOT NULL,
+        target_label TEXT NOT NULL,
+        file_path TEXT NOT NULL,
+        old_start INTEGER,
+        old_end INTEGER,
+        new_start INTEGER,
+        new_end INTEGER,
+        comment TEXT NOT NULL,
+        created_at TEXT NOT NULL,
+        context_before TEXT NOT NULL DEFAULT '[]',
+        context_after TEXT NOT NULL DEFAULT '[]',
+        tags TEXT NOT NULL DEFAULT '[]'
+    );
+    CREATE INDEX IF NOT EXISTS annotations_by_target
+        ON annotations (repo_path, target_label);
+    CREATE TABLE IF NOT EXISTS review_state (
+        repo_path TEXT NOT NULL,
+        target_label TEXT NOT NULL,
+        file_path TEXT NOT NULL,
+        hash INTEGER NOT NULL,
+        reviewed_at TEXT NOT NULL,
+        PRIMARY KEY (repo_path, target_label, file_path, hash)
+    );
+    CREATE TABLE IF NOT EXISTS checklist (
+        repo_path TEXT NOT NULL,
+        target_label TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        label TEXT NOT NULL,
+        key TEXT NOT NULL,
+        checked INTEGER NOT NULL,
+        note TEXT,
+        PRIMARY KEY (repo_path, target_label, position)
+    );
+";
+
+fn db_path() -> PathBuf {
+    paths::data_dir().join("sessions.db")
+}
+
+/// Schema changes made after `SCHEMA` was first released, applied in order and
+/// tracked with `PRAGMA user_version`.
+const MIGRATIONS: &[&str] = &[
+    "ALTER TABLE annotations ADD COLUMN unanchored INTEGER NOT NULL DEFAULT 0",
+    // Hunk hashes were `DefaultHasher` output, which differs between builds;
+    // they can't be converted to the FNV-1a hashes used now
+    "DELETE FROM review_state",
+];
+
+fn init_db(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(SCHEMA)?;
+    let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    for migration in MIGRATIONS.iter().skip(version as usize) {
+        conn.execute_batch(migration)?;
+    }
+    conn.pragma_update(None, "user_version", MIGRATIONS.len() as i64)
+}
+
+fn open_db() -> rusqlite::Result<Connection> {
+    let path = db_path();
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let conn = Connection::open(path)?;
+    init_db(&conn)?;
+    Ok(conn)
+}
+
+fn repo_key(repo_path: &Path) -> String {
+    repo_path.to_string_lossy().into_owned()
+}
+
+/// Load annotations, checklist and review state for `target_label`. Sessions
+/// saved by older versions as `.mdiff/session_*.json` in the repo are read
+/// until the database has its own copy.
+pub fn load_session_data(repo_path: &Path, target_label: &str) -> SessionData {
+    let from_db = open_db()
+        .and_then(|conn| read_session(&conn, &repo_key(repo_path), target_label))
+        .ok()
+        .flatten();
+    from_db.unwrap_or_else(|| load_legacy_session(repo_path, target_label))
+}
+
+/// Save annotations, checklist and reviewed hunks for `target_label`.
+pub fn save_session_data(
+    repo_path: &Path,
+    target_label: &str,
+    annotations: &AnnotationState,
+    checklist: Option<&ChecklistState>,
+    review: &ReviewState,
+) {
+    if let Ok(mut conn) = open_db() {
+        let _ = write_session(
+            &mut conn,
+            &repo_key(repo_path),
+            target_label,
+            annotations,
+            checklist,
+            review.reviewed_hunks(),
+        );
+    }
+}
+
+fn read_session(
+    conn: &Connection,
+    repo: &str,
+    target_label: &str,
+) -> rusqlite::Result<Option<SessionData>> {
+    let saved: Option<String> = conn
+        .query_row(
+            "SELECT saved_at FROM sessions WHERE repo_path = ?1 AND target_label = ?2",
+            params![repo, target_label],
+            |row| row.get(0),
+        )
+        .optional()?;
+    if saved.is_none() {
+        return Ok(None);
+    }
+
+    let mut data = SessionData::default();
+
+    let mut stmt = conn.prepare(
+        "SELECT file_path, old_start, old_end, new_start, new_end, comment, created_at,
+                context_before, context_after, tags, unanchored
+         FROM annotations WHERE repo_path = ?1 AND target_label = ?2 ORDER BY id",
+    )?;
+    let rows = stmt.query_map(params![repo, target_label], |row| {
+        let old_start: Option<u32> = row.get(1)?;
+        let old_end: Option<u32> = row.get(2)?;
+        let new_start: Option<u32> = row.get(3)?;
+        let new_end: Option<u32> = row.get(4)?;
+        let json_list = |idx: usize| -> rusqlite::Result<Vec<String>> {
+            let text: String = row.get(idx)?;
+            Ok(serde_json::from_str(&text).unwrap_or_default())
+        };
+        Ok(Annotation {
+            anchor: LineAnchor {
+                context_before: json_list(7)?,
+                context_after: json_list(8)?,
+                ..LineAnchor::new(row.get(0)?, old_start.zip(old_end), new_start.zip(new_end))
+            },
+            comment: row.get(5)?,
+            created_at: row.get(6)?,
+            tags: json_list(9)?,
+            unanchored: row.get(10)?,
+        })
+    })?;
+    for ann in rows {
+        data.annotations.add(ann?);
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT label, key, checked, note FROM checklist
+         WHERE repo_path = ?1 AND target_label = ?2 ORDER BY position",
+    )?;
+    let items = stmt
+        .query_map(params![repo, target_label], |row| {
+            let key: String = row.get(1)?;
+            Ok(ChecklistItem {
+                label: row.get(0)?,
+                key: key.chars().next().unwrap_or(' '),
+                checked: row.get(2)?,
+                note: row.get(3)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    if !items.is_empty() {
+        data.checklist = Some(ChecklistState {
+            items,
+            selected: 0,
+            panel_open: false,
+        });
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT file_path, hash, reviewed_at FROM review_state
+         WHERE repo_path = ?1 AND target_label = ?2",
+    )?;
+    let rows = stmt.query_map(params![repo, target_label], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, i64>(1)?,
+            row.get::<_, String>(2)?,
+        ))
+    })?;
+    for row in rows {
+        let (file_path, hash, reviewed_at) = row?;
+        data.reviewed
+            .entry(file_path)
+            .or_default()
+            .insert(hash as u64, reviewed_at);
+    }
+
+    Ok(Some(data))
+}
+
+fn write_session(
+    conn: &mut Connection,
+    repo: &str,
+    target_label: &str,
+    annotations: &AnnotationState,
+    checklist: Option<&ChecklistState>,
+    reviewed: &HashMap<String, HashMap<u64, String>>,
+) -> rusqlite::Result<()> {
+    let tx = conn.transaction()?;
+    for table in ["annotations", "checklist", "review_state"] {
+        tx.execute(
+            &format!("DELETE FROM {table} WHERE repo_path = ?1 AND target_label = ?2"),
+            params![repo, target_label],
+        )?;
+    }
+    tx.execute(
+        "INSERT OR REPLACE INTO sessions (repo_path, target_label, saved_at)
+         VALUES (?1, ?2, ?3)",
+        params![repo, target_label, chrono::Utc::now().to_rfc3339()],
+    )?;
+
+    {
+        let mut stmt = tx.prepare(
+            "INSERT INTO annotations (repo_path, target_label, file_path, old_start, old_end,
+                 new_start, new_end, comment, created_at, context_before, context_after, tags,
+                 unanchored)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+        )?;
+        let to_json = |list: &Vec<String>| serde_json::to_string(list).unwrap_or_default();
+        for a in annotations.all_sorted() {
+            stmt.execute(params![
+                repo,
+                target_label,
+                a.anchor.file_path,
+                a.anchor.old_range.map(|(s, _)| s),
+                a.anchor.old_range.map(|(_, e)| e),
+                a.anchor.new_range.map(|(s, _)| s),
+                a.anchor.new_range.map(|(_, e)| e),
+                a.comment,
+                a.created_at,
+                to_json(&a.anchor.context_before),
+                to_json(&a.anchor.context_after),
+                to_json(&a.tags),
+                a.unanchored,
+            ])?;
+        }
+
+        let mut stmt = tx.prepare(
+            "INSERT INTO checklist (repo_path, target_label, position, label, key, checked, note)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        )?;
+        for (position, item) in checklist.iter().flat_map(|c| c.items.iter()).enumerate() {
+            stmt.execute(params![
+                repo,
+                target_label,
+                position as i64,
+                item.label,
+                item.key.to_string(),
+                item.checked,
+                item.note,
+            ])?;
+        }
+
+        let mut stmt = tx.prepare(
+            "INSERT INTO review_state (repo_path, target_label, file_path, hash, reviewed_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+        )?;
+        for (file_path, hunks) in reviewed {
+            for (&hash, reviewed_at) in hunks {
+                stmt.execute(params![
+                    repo,
+                    target_label,
+                    file_path,
+                    hash as i64,
+                    reviewed_at
+                ])?;
+            }
+        }
+    }
+    tx.commit()
+}
+
+#[derive(Deserialize)]
+struct LegacySessionFile {
     version: u32,
     target_label: String,
-    annotations: Vec<AnnotationEntry>,
+    annotations: Vec<LegacyAnnotationEntry>,
     #[serde(default)]
-    checklist: Option<ChecklistSessionData>,
+    checklist: Option<LegacyChecklist>,
 }
 
-#[derive(Serialize, Deserialize)]
-struct ChecklistSessionData {
-    items: Vec<ChecklistItemSession>,
+#[derive(Deserialize)]
+struct LegacyChecklist {
+    items: Vec<LegacyChecklistItem>,
 }
 
-#[derive(Serialize, Deserialize)]
-struct ChecklistItemSession {
+#[derive(Deserialize)]
+struct LegacyChecklistItem {
     label: String,
     key: char,
     checked: bool,
@@ -30,8 +401,8 @@ struct ChecklistItemSession {
 }
 
 /// V2 annotation entry with separate old/new ranges.
-#[derive(Serialize, Deserialize)]
-struct AnnotationEntry {
+#[derive(Deserialize)]
+struct LegacyAnnotationEntry {
     file_path: String,
     #[serde(default)]
     old_start: Option<u32>,
@@ -46,62 +417,35 @@ struct AnnotationEntry {
     line_start: Option<u32>,
     #[serde(default)]
     line_end: Option<u32>,
+    #[serde(default)]
+    context_before: Vec<String>,
+    #[serde(default)]
+    context_after: Vec<String>,
     comment: String,
     created_at: String,
+    #[serde(default)]
+    tags: Vec<String>,
 }
 
-fn session_dir(repo_path: &Path) -> PathBuf {
-    repo_path.join(".mdiff")
-}
-
-fn session_file(repo_path: &Path, target_label: &str) -> PathBuf {
+fn legacy_session_file(repo_path: &Path, target_label: &str) -> PathBuf {
     let sanitized = target_label.replace(['/', '\\', ':', ' '], "_");
-    session_dir(repo_path).join(format!("session_{sanitized}.json"))
+    repo_path
+        .join(".mdiff")
+        .join(format!("session_{sanitized}.json"))
 }
 
-/// Ensure `.mdiff/` is listed in `.gitignore`.
-fn ensure_gitignore(repo_path: &Path) {
-    let gitignore_path = repo_path.join(".gitignore");
-    let entry = ".mdiff/";
+/// Read a session written by versions that stored JSON files in `.mdiff/`.
+fn load_legacy_session(repo_path: &Path, target_label: &str) -> SessionData {
+    let mut data = SessionData::default();
 
-    if let Ok(contents) = fs::read_to_string(&gitignore_path) {
-        if contents.lines().any(|line| line.trim() == entry) {
-            return;
-        }
-        // Append to existing .gitignore
-        if let Ok(mut f) = fs::OpenOptions::new().append(true).open(&gitignore_path) {
-            // Add newline if file doesn't end with one
-            if !contents.ends_with('\n') {
-                let _ = writeln!(f);
-            }
-            let _ = writeln!(f, "{entry}");
-        }
-    } else {
-        // No .gitignore yet — create one
-        let _ = fs::write(&gitignore_path, format!("{entry}\n"));
-    }
-}
-
-/// Load both annotations and checklist state from the session file.
-pub fn load_session_data(
-    repo_path: &Path,
-    target_label: &str,
-) -> (AnnotationState, Option<ChecklistState>) {
-    let path = session_file(repo_path, target_label);
-    let mut annotations_state = AnnotationState::default();
-
-    let Ok(contents) = fs::read_to_string(&path) else {
-        return (annotations_state, None);
+    let Ok(contents) = fs::read_to_string(legacy_session_file(repo_path, target_label)) else {
+        return data;
     };
-
-    let Ok(session) = serde_json::from_str::<SessionFile>(&contents) else {
-        return (annotations_state, None);
+    let Ok(session) = serde_json::from_str::<LegacySessionFile>(&contents) else {
+        return data;
     };
-
-    if !(session.version == 1 || session.version == 2 || session.version == 3)
-        || session.target_label != target_label
-    {
-        return (annotations_state, None);
+    if !(1..=3).contains(&session.version) || session.target_label != target_label {
+        return data;
     }
 
     for entry in session.annotations {
@@ -111,98 +455,120 @@ pub fn load_session_data(
             let le = entry.line_end.unwrap_or(ls);
             (None, Some((ls, le)))
         } else {
-            // V2: use explicit old/new ranges
-            let old_range = entry.old_start.zip(entry.old_end);
-            let new_range = entry.new_start.zip(entry.new_end);
-            (old_range, new_range)
+            (
+                entry.old_start.zip(entry.old_end),
+                entry.new_start.zip(entry.new_end),
+            )
         };
 
-        annotations_state.add(Annotation {
-            anchor: crate::state::annotation_state::LineAnchor {
-                file_path: entry.file_path,
-                old_range,
-                new_range,
+        data.annotations.add(Annotation {
+            anchor: LineAnchor {
+                context_before: entry.context_before,
+                context_after: entry.context_after,
+                ..LineAnchor::new(entry.file_path, old_range, new_range)
             },
             comment: entry.comment,
             created_at: entry.created_at,
+            tags: entry.tags,
+            unanchored: false,
         });
     }
 
-    // Load checklist state if present
-    let checklist_state = session.checklist.map(|checklist_data| {
-        let items = checklist_data
+    data.checklist = session.checklist.map(|checklist| ChecklistState {
+        items: checklist
             .items
             .into_iter()
-            .map(|item| crate::state::ChecklistItem {
+            .map(|item| ChecklistItem {
                 label: item.label,
                 key: item.key,
                 checked: item.checked,
                 note: item.note,
             })
-            .collect();
-
-        ChecklistState {
-            items,
-            selected: 0,
-            panel_open: false,
-        }
+            .collect(),
+        selected: 0,
+        panel_open: false,
     });
 
-    (annotations_state, checklist_state)
+    data
 }
 
-/// Save both annotations and checklist state to the session file (v3 format).
-pub fn save_session_data(
-    repo_path: &Path,
-    target_label: &str,
-    annotations: &AnnotationState,
-    checklist: Option<&ChecklistState>,
-) {
-    let dir = session_dir(repo_path);
-    if fs::create_dir_all(&dir).is_err() {
-        return;
-    }
-
-    ensure_gitignore(repo_path);
-
-    let entries: Vec<AnnotationEntry> = annotations
-        .all_sorted()
-        .into_iter()
-        .map(|a| AnnotationEntry {
-            file_path: a.anchor.file_path.clone(),
-            old_start: a.anchor.old_range.map(|(s, _)| s),
-            old_end: a.anchor.old_range.map(|(_, e)| e),
-            new_start: a.anchor.new_range.map(|(s, _)| s),
-            new_end: a.anchor.new_range.map(|(_, e)| e),
-            line_start: None,
-            line_end: None,
-            comment: a.comment.clone(),
-            created_at: a.created_at.clone(),
-        })
-        .collect();
+#[cfg(test)]
+mod tests {
+    use super::{init_db, read_session, write_session, MIGRATIONS, SCHEMA};
+    use crate::state::annotation_state::{Annotation, LineAnchor};
+    use crate::state::{AnnotationState, ChecklistItem, ChecklistState};
+    use rusqlite::Connection;
+    use std::collections::HashMap;
 
-    // Serialize checklist state if present
-    let checklist_data = checklist.map(|cl| ChecklistSessionData {
-        items: cl
-            .items
-            .iter()
-            .map(|item| ChecklistItemSession {
-                label: item.label.clone(),
-                key: item.key,
-                checked: item.checked,
-                note: item.note.clone(),
-            })
-            .collect(),
-    });
+    #[test]
+    fn session_round_trips_through_sqlite() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        init_db(&conn).unwrap();
+        assert!(read_session(&conn, "/repo", "main").unwrap().is_none());
 
-    let session = SessionFile {
-        version: if checklist_data.is_some() { 3 } else { 2 },
-        target_label: target_label.to_string(),
-        annotations: entries,
-        checklist: checklist_data,
-    };
+        let mut annotations = AnnotationState::default();
+        annotations.add(Annotation {
+            anchor: LineAnchor {
+                context_before: vec!["fn a() {".to_string()],
+                ..LineAnchor::new("src/a.rs".to_string(), None, Some((3, 5)))
+            },
+            comment: "rename this".to_string(),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            tags: vec!["nit".to_string()],
+            unanchored: false,
+        });
+        let checklist = ChecklistState {
+            items: vec![ChecklistItem {
+                label: "Tests".to_string(),
+                key: 't',
+                checked: true,
+                note: None,
+            }],
+            selected: 0,
+            panel_open: false,
+        };
+        let reviewed = HashMap::from([(
+            "src/a.rs".to_string(),
+            HashMap::from([(u64::MAX, "2026-01-02T00:00:00Z".to_string())]),
+        )]);
+
+        write_session(
+            &mut conn,
+            "/repo",
+            "main",
+            &annotations,
+            Some(&checklist),
+            &reviewed,
+        )
+        .unwrap();
+        let loaded = read_session(&conn, "/repo", "main").unwrap().unwrap();
+
+        let anns = loaded.annotations.all_sorted();
+        assert_eq!(anns.len(), 1);
+        assert_eq!(anns[0].anchor.new_range, Some((3, 5)));
+        assert_eq!(anns[0].anchor.context_before, ["fn a() {"]);
+        assert_eq!(anns[0].tags, ["nit"]);
+        assert_eq!(loaded.checklist.unwrap().items[0].key, 't');
+        assert_eq!(loaded.reviewed, reviewed);
+        assert!(read_session(&conn, "/repo", "other").unwrap().is_none());
+    }
+
+    #[test]
+    fn migration_drops_unstable_review_hashes() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(SCHEMA).unwrap();
+        conn.execute_batch(MIGRATIONS[0]).unwrap();
+        conn.pragma_update(None, "user_version", 1).unwrap();
+        conn.execute(
+            "INSERT INTO review_state VALUES ('/repo', 'main', 'a.rs', 42, 'then')",
+            [],
+        )
+        .unwrap();
 
-    if let Ok(json) = serde_json::to_string_pretty(&session) {
-        let _ = fs::write(session_file(repo_path, target_label), json);
+        init_db(&conn).unwrap();
+        let rows: i64 = conn
+            .query_row("SELECT COUNT(*) FROM review_state", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(rows, 0);
     }
 }
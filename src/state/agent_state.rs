@@ -1,6 +1,7 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 use crate::config::AgentProviderConfig;
 
@@ -25,8 +26,70 @@ pub struct AgentRun {
     pub terminal: vt100::Parser,
     pub status: AgentRunStatus,
     pub started_at: String,
+    pub started_at_instant: Instant,
+    /// Set when the process exits; `None` while the run is going.
+    pub finished_at: Option<Instant>,
     pub worktree_name: String,
     pub worktree_path: PathBuf,
+    /// Token count reported by the agent itself, parsed from its output on exit.
+    pub token_count: Option<usize>,
+    pub cost_per_1k_tokens: Option<f64>,
+    pub context_window: Option<usize>,
+}
+
+impl AgentRun {
+    /// Wall-clock time from start to exit, or to now for a running agent.
+    pub fn elapsed(&self) -> Duration {
+        self.finished_at
+            .unwrap_or_else(Instant::now)
+            .duration_since(self.started_at_instant)
+    }
+
+    /// Rough prompt size in tokens (four bytes per token).
+    pub fn estimated_tokens(&self) -> usize {
+        self.rendered_prompt.len() / 4
+    }
+
+    /// The reported token count if the agent printed one, else the estimate.
+    pub fn tokens(&self) -> usize {
+        self.token_count.unwrap_or_else(|| self.estimated_tokens())
+    }
+
+    pub fn estimated_cost(&self) -> Option<f64> {
+        self.cost_per_1k_tokens
+            .map(|rate| self.tokens() as f64 / 1000.0 * rate)
+    }
+
+    pub fn exceeds_context_window(&self) -> bool {
+        self.context_window
+            .is_some_and(|window| self.tokens() > window)
+    }
+}
+
+/// `45s`, or `1m 23s` from a minute up.
+pub fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs < 60 {
+        format!("{secs}s")
+    } else {
+        format!("{}m {}s", secs / 60, secs % 60)
+    }
+}
+
+/// Find the last `Tokens used: N` line in agent output. Thousands separators
+/// are accepted (`Tokens used: 12,345`).
+pub fn parse_tokens_used(output: &str) -> Option<usize> {
+    output.lines().rev().find_map(|line| {
+        let lower = line.to_ascii_lowercase();
+        let rest = &line[lower.find("tokens used:")? + "tokens used:".len()..];
+        let digits: String = rest
+            .trim_start()
+            .chars()
+            .take_while(|c| c.is_ascii_digit() || *c == ',')
+            .filter(char::is_ascii_digit)
+            .collect();
+        digits.parse().ok()
+    })
 }
 
 impl fmt::Debug for AgentRun {
@@ -40,6 +103,7 @@ impl fmt::Debug for AgentRun {
             .field("started_at", &self.started_at)
             .field("worktree_name", &self.worktree_name)
             .field("worktree_path", &self.worktree_path)
+            .field("token_count", &self.token_count)
             .finish_non_exhaustive()
     }
 }
@@ -50,12 +114,15 @@ pub struct AgentOutputsState {
     pub runs: Vec<AgentRun>,
     pub selected_run: usize,
     pub next_id: usize,
+    /// Index of the first run shown in the run list.
+    pub run_list_scroll: usize,
 }
 
 impl AgentOutputsState {
     pub fn add_run(&mut self, run: AgentRun) {
         self.runs.insert(0, run);
         self.selected_run = 0;
+        self.run_list_scroll = 0;
         self.next_id += 1;
     }
 
@@ -72,6 +139,20 @@ impl AgentOutputsState {
             self.selected_run = (self.selected_run + 1).min(self.runs.len() - 1);
         }
     }
+
+    /// Scroll the run list up by `step` runs, moving the selection along with it.
+    pub fn scroll_list_up(&mut self, step: usize) {
+        self.run_list_scroll = self.run_list_scroll.saturating_sub(step);
+        self.selected_run = self.selected_run.saturating_sub(step);
+    }
+
+    /// Scroll the run list down by `step` runs, moving the selection along with it.
+    pub fn scroll_list_down(&mut self, step: usize) {
+        if let Some(last) = self.runs.len().checked_sub(1) {
+            self.run_list_scroll = (self.run_list_scroll + step).min(last);
+            self.selected_run = (self.selected_run + step).min(last);
+        }
+    }
 }
 
 /// State for the agent selector modal.
@@ -86,6 +167,8 @@ pub struct AgentSelectorState {
     pub rerun_prompt: Option<String>,
     /// Last-used model per agent name, loaded from config.
     pub last_models: HashMap<String, String>,
+    /// Favorite agent names, loaded from config; listed first.
+    pub favorites: HashSet<String>,
 }
 
 impl AgentSelectorState {
@@ -98,6 +181,8 @@ impl AgentSelectorState {
         self.restore_model_for_selected();
     }
 
+    /// Filter agents by name, with favorites first (sorted by name) and the rest
+    /// in config order.
     pub fn refilter(&mut self) {
         if self.filter.is_empty() {
             self.filtered_indices = (0..self.agents.len()).collect();
@@ -111,6 +196,15 @@ impl AgentSelectorState {
                 .map(|(i, _)| i)
                 .collect();
         }
+        let agents = &self.agents;
+        let favorites = &self.favorites;
+        self.filtered_indices.sort_by(|&a, &b| {
+            let (a, b) = (&agents[a].name, &agents[b].name);
+            match (favorites.contains(a), favorites.contains(b)) {
+                (true, true) => a.cmp(b),
+                (fa, fb) => fb.cmp(&fa),
+            }
+        });
         if !self.filtered_indices.is_empty() {
             self.selected_agent = self.selected_agent.min(self.filtered_indices.len() - 1);
         } else {
@@ -118,6 +212,26 @@ impl AgentSelectorState {
         }
     }
 
+    pub fn is_favorite(&self, agent: &AgentProviderConfig) -> bool {
+        self.favorites.contains(&agent.name)
+    }
+
+    /// Star or unstar the selected agent, keeping it selected after the list is
+    /// re-sorted. Returns the agent's name and whether it is now a favorite.
+    pub fn toggle_favorite(&mut self) -> Option<(String, bool)> {
+        let agent_idx = *self.filtered_indices.get(self.selected_agent)?;
+        let name = self.agents[agent_idx].name.clone();
+        let now_favorite = !self.favorites.remove(&name);
+        if now_favorite {
+            self.favorites.insert(name.clone());
+        }
+        self.refilter();
+        if let Some(pos) = self.filtered_indices.iter().position(|&i| i == agent_idx) {
+            self.selected_agent = pos;
+        }
+        Some((name, now_favorite))
+    }
+
     /// Get the currently selected agent config, if any.
     pub fn selected_agent_config(&self) -> Option<&AgentProviderConfig> {
         self.filtered_indices
@@ -181,3 +295,62 @@ impl AgentSelectorState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{format_elapsed, parse_tokens_used, AgentSelectorState};
+    use crate::config::AgentProviderConfig;
+    use std::time::Duration;
+
+    #[test]
+    fn lists_favorite_agents_first() {
+        let agent = |name: &str| -> AgentProviderConfig {
+            toml::from_str(&format!("name = \"{name}\"")).unwrap()
+        };
+        let mut selector = AgentSelectorState {
+            favorites: ["opencode", "claude"].map(String::from).into(),
+            ..Default::default()
+        };
+        selector.populate(&[
+            agent("codex"),
+            agent("opencode"),
+            agent("gemini"),
+            agent("claude"),
+        ]);
+        let names = |s: &AgentSelectorState| -> Vec<String> {
+            s.filtered_indices
+                .iter()
+                .map(|&i| s.agents[i].name.clone())
+                .collect()
+        };
+        assert_eq!(names(&selector), ["claude", "opencode", "codex", "gemini"]);
+
+        // Starring gemini moves it up and keeps it selected
+        selector.selected_agent = 3;
+        assert_eq!(
+            selector.toggle_favorite(),
+            Some(("gemini".to_string(), true))
+        );
+        assert_eq!(names(&selector), ["claude", "gemini", "opencode", "codex"]);
+        assert_eq!(selector.selected_agent, 1);
+
+        selector.filter.insert_char('c');
+        selector.refilter();
+        assert_eq!(names(&selector), ["claude", "opencode", "codex"]);
+    }
+
+    #[test]
+    fn formats_elapsed_minutes_and_seconds() {
+        assert_eq!(format_elapsed(Duration::from_millis(45_900)), "45s");
+        assert_eq!(format_elapsed(Duration::from_secs(83)), "1m 23s");
+        assert_eq!(format_elapsed(Duration::from_secs(3600)), "60m 0s");
+    }
+
+    #[test]
+    fn parses_last_tokens_used_line() {
+        let output = "working...\nTokens used: 1,200\nretrying\n  tokens used: 3456 (prompt)\n";
+        assert_eq!(parse_tokens_used(output), Some(3456));
+        assert_eq!(parse_tokens_used("Tokens used: 12,345"), Some(12345));
+        assert_eq!(parse_tokens_used("done\n"), None);
+    }
+}
@@ -2,6 +2,9 @@ use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
 
+/// Number of lines captured on each side of an anchor for re-anchoring.
+pub const ANCHOR_CONTEXT_LINES: usize = 3;
+
 /// A file path + line ranges that anchor an annotation to specific diff lines.
 /// Stores old-file and new-file ranges separately so the LLM prompt can
 /// distinguish between deleted, added, and context lines.
@@ -10,9 +13,127 @@ pub struct LineAnchor {
     pub file_path: String,
     pub old_range: Option<(u32, u32)>, // (start, end) in old file
     pub new_range: Option<(u32, u32)>, // (start, end) in new file
+    /// Lines just above the anchored range, on the side used for re-anchoring
+    /// (new file when `new_range` is set, old file otherwise).
+    #[serde(default)]
+    pub context_before: Vec<String>,
+    /// Lines just below the anchored range, on the same side as `context_before`.
+    #[serde(default)]
+    pub context_after: Vec<String>,
+    /// Set when the surrounding context could no longer be found after a re-diff.
+    #[serde(default)]
+    pub stale: bool,
 }
 
 impl LineAnchor {
+    /// Anchor without captured context; it keeps its line numbers across re-diffs.
+    pub fn new(
+        file_path: String,
+        old_range: Option<(u32, u32)>,
+        new_range: Option<(u32, u32)>,
+    ) -> Self {
+        Self {
+            file_path,
+            old_range,
+            new_range,
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            stale: false,
+        }
+    }
+
+    /// Record the lines surrounding the anchored range. `old_lines`/`new_lines`
+    /// are full file contents where index 0 is line 1.
+    pub fn capture_context(&mut self, old_lines: &[&str], new_lines: &[&str]) {
+        let Some(((start, end), lines)) = self.primary_range(old_lines, new_lines) else {
+            return;
+        };
+        let (start, end) = (start as usize, end as usize);
+        let before_start = start.saturating_sub(1 + ANCHOR_CONTEXT_LINES);
+        let before_end = start.saturating_sub(1).min(lines.len());
+        self.context_before = lines[before_start.min(before_end)..before_end]
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+        let after_start = end.min(lines.len());
+        let after_end = (end + ANCHOR_CONTEXT_LINES).min(lines.len());
+        self.context_after = lines[after_start..after_end]
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+    }
+
+    /// The range and file side that context is captured from.
+    fn primary_range<'a>(
+        &self,
+        old_lines: &'a [&'a str],
+        new_lines: &'a [&'a str],
+    ) -> Option<((u32, u32), &'a [&'a str])> {
+        match (self.new_range, self.old_range) {
+            (Some(r), _) => Some((r, new_lines)),
+            (None, Some(r)) => Some((r, old_lines)),
+            (None, None) => None,
+        }
+    }
+
+    /// Whether the captured context surrounds a range starting at `start` with
+    /// `len` extra lines. Blank context lines match anything.
+    fn context_matches_at(&self, lines: &[&str], start: usize, len: usize) -> bool {
+        let before = &self.context_before;
+        let after = &self.context_after;
+        if start < 1 + before.len() || start + len + after.len() > lines.len() {
+            return false;
+        }
+        let before_lines = &lines[start - 1 - before.len()..start - 1];
+        let after_lines = &lines[start + len..start + len + after.len()];
+        before
+            .iter()
+            .zip(before_lines)
+            .chain(after.iter().zip(after_lines))
+            .filter(|(a, _)| !a.trim().is_empty())
+            .all(|(a, b)| within_one_edit(a, b))
+    }
+
+    /// Move the anchor to the nearest position whose surrounding lines still
+    /// match the captured context, or mark it stale if none does. Anchors
+    /// whose captured context is empty or blank are left alone.
+    pub fn reanchor(&mut self, old_lines: &[&str], new_lines: &[&str]) {
+        if self
+            .context_before
+            .iter()
+            .chain(&self.context_after)
+            .all(|l| l.trim().is_empty())
+        {
+            return;
+        }
+        let on_new = self.new_range.is_some();
+        let Some(((start, end), lines)) = self.primary_range(old_lines, new_lines) else {
+            return;
+        };
+        let (start, len) = (start as usize, (end - start) as usize);
+
+        let max_start = lines.len().max(start);
+        let found = (0..max_start).find_map(|dist| {
+            [start.checked_sub(dist), Some(start + dist)]
+                .into_iter()
+                .flatten()
+                .find(|&s| s >= 1 && self.context_matches_at(lines, s, len))
+        });
+
+        match found {
+            Some(s) => {
+                let range = Some((s as u32, (s + len) as u32));
+                if on_new {
+                    self.new_range = range;
+                } else {
+                    self.old_range = range;
+                }
+                self.stale = false;
+            }
+            None => self.stale = true,
+        }
+    }
+
     /// A single representative line number for sorting/navigation.
     /// Prefers new-file start, falls back to old-file start.
     pub fn sort_line(&self) -> u32 {
@@ -69,12 +190,178 @@ impl LineAnchor {
     }
 }
 
+/// Whether two lines differ by at most one insertion, deletion, or substitution.
+fn within_one_edit(a: &str, b: &str) -> bool {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (short, long) = if a.len() <= b.len() {
+        (&a, &b)
+    } else {
+        (&b, &a)
+    };
+    if long.len() - short.len() > 1 {
+        return false;
+    }
+    let prefix = short
+        .iter()
+        .zip(long.iter())
+        .take_while(|(x, y)| x == y)
+        .count();
+    if short.len() == long.len() {
+        short[prefix..]
+            .iter()
+            .zip(&long[prefix..])
+            .skip(1)
+            .all(|(x, y)| x == y)
+    } else {
+        short[prefix..] == long[prefix + 1..]
+    }
+}
+
 /// A single annotation attached to a range of diff lines.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Annotation {
     pub anchor: LineAnchor,
     pub comment: String,
     pub created_at: String,
+    /// Lowercase categories parsed from `#tag` words in the comment editor.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Imported comment whose line isn't in the local diff; it has no line
+    /// range and only appears in the review summary.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub unanchored: bool,
+}
+
+impl Annotation {
+    /// Gutter marker for this annotation, chosen by its first recognised tag.
+    pub fn marker(&self) -> &'static str {
+        for tag in &self.tags {
+            match tag.as_str() {
+                "bug" => return "\u{25a0}",
+                "nit" => return "\u{25cb}",
+                "question" => return "?",
+                _ => {}
+            }
+        }
+        "\u{2502}"
+    }
+}
+
+/// Split comment-editor text into the comment and its `#tag` words.
+/// Tags are lowercased and deduplicated; the remaining words keep their order.
+pub fn parse_tags(text: &str) -> (String, Vec<String>) {
+    let mut tags: Vec<String> = Vec::new();
+    let lines: Vec<String> = text
+        .lines()
+        .map(|line| {
+            line.split(' ')
+                .filter(|word| {
+                    let Some(tag) = word.strip_prefix('#') else {
+                        return true;
+                    };
+                    // `#12` is more likely an issue reference than a tag
+                    let starts_alpha = tag.chars().next().is_some_and(char::is_alphabetic);
+                    if !starts_alpha || !tag.chars().all(|c| c.is_alphanumeric() || c == '-') {
+                        return true;
+                    }
+                    let tag = tag.to_lowercase();
+                    if !tags.contains(&tag) {
+                        tags.push(tag);
+                    }
+                    false
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect();
+    (lines.join("\n").trim().to_string(), tags)
+}
+
+/// Inverse of [`parse_tags`]: prefix the comment with its tags for editing.
+pub fn format_with_tags(comment: &str, tags: &[String]) -> String {
+    if tags.is_empty() {
+        return comment.to_string();
+    }
+    let prefix: Vec<String> = tags.iter().map(|t| format!("#{t}")).collect();
+    format!("{} {comment}", prefix.join(" "))
+}
+
+/// Maximum number of entries kept on each of the undo and redo stacks.
+pub const UNDO_LIMIT: usize = 50;
+
+/// Old-file and new-file ranges of an anchor, as stored on [`LineAnchor`].
+pub type AnchorRanges = (Option<(u32, u32)>, Option<(u32, u32)>);
+
+/// A reversible annotation change.
+#[derive(Debug, Clone)]
+pub enum AnnotationUndoEntry {
+    Added(Annotation),
+    Deleted(Annotation),
+    Edited {
+        file: String,
+        ranges: AnchorRanges,
+        old_comment: String,
+        new_comment: String,
+        old_tags: Vec<String>,
+        new_tags: Vec<String>,
+    },
+}
+
+/// Undo/redo stacks for annotation changes, each capped at [`UNDO_LIMIT`].
+#[derive(Debug, Default)]
+pub struct AnnotationHistory {
+    undo: Vec<AnnotationUndoEntry>,
+    redo: Vec<AnnotationUndoEntry>,
+}
+
+impl AnnotationHistory {
+    /// Record a new change. Clears the redo stack.
+    pub fn record(&mut self, entry: AnnotationUndoEntry) {
+        push_capped(&mut self.undo, entry);
+        self.redo.clear();
+    }
+
+    /// Reverse the most recent change. Returns false if there was nothing to undo.
+    pub fn undo(&mut self, annotations: &mut AnnotationState) -> bool {
+        let Some(entry) = self.undo.pop() else {
+            return false;
+        };
+        push_capped(&mut self.redo, annotations.revert(entry));
+        true
+    }
+
+    /// Re-apply the most recently undone change. Returns false if there was nothing to redo.
+    pub fn redo(&mut self, annotations: &mut AnnotationState) -> bool {
+        let Some(entry) = self.redo.pop() else {
+            return false;
+        };
+        push_capped(&mut self.undo, annotations.revert(entry));
+        true
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+fn push_capped(stack: &mut Vec<AnnotationUndoEntry>, entry: AnnotationUndoEntry) {
+    if stack.len() >= UNDO_LIMIT {
+        stack.remove(0);
+    }
+    stack.push(entry);
+}
+
+/// How to merge an imported annotation that overlaps one already on the same lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MergeResolution {
+    /// Drop the imported annotation.
+    KeepMine,
+    /// Replace ours with the imported one.
+    TakeTheirs,
+    /// Keep both.
+    KeepBoth,
 }
 
 /// State for all annotations in the current session.
@@ -92,33 +379,84 @@ impl AnnotationState {
         self.annotations.entry(key).or_default().push(annotation);
     }
 
-    /// Check if any annotation covers the given line numbers in a file.
-    pub fn has_annotation_at(
-        &self,
-        file_path: &str,
-        old_lineno: Option<u32>,
-        new_lineno: Option<u32>,
-    ) -> bool {
-        if let Some(anns) = self.annotations.get(file_path) {
-            anns.iter().any(|a| a.anchor.covers(old_lineno, new_lineno))
-        } else {
-            false
+    /// Re-anchor every annotation on `file_path` against freshly diffed file contents.
+    pub fn reanchor(&mut self, file_path: &str, old_lines: &[&str], new_lines: &[&str]) {
+        if let Some(anns) = self.annotations.get_mut(file_path) {
+            for ann in anns.iter_mut() {
+                ann.anchor.reanchor(old_lines, new_lines);
+            }
         }
     }
 
-    /// Delete all annotations overlapping the given ranges in a file.
+    /// Delete all annotations overlapping the given ranges in a file, returning them.
     pub fn delete_at(
         &mut self,
         file_path: &str,
         old_range: Option<(u32, u32)>,
         new_range: Option<(u32, u32)>,
-    ) {
+    ) -> Vec<Annotation> {
+        let mut removed = Vec::new();
         if let Some(anns) = self.annotations.get_mut(file_path) {
-            anns.retain(|a| !a.anchor.overlaps(old_range, new_range));
+            let (gone, kept) = std::mem::take(anns)
+                .into_iter()
+                .partition(|a| a.anchor.overlaps(old_range, new_range));
+            *anns = kept;
+            removed = gone;
             if anns.is_empty() {
                 self.annotations.remove(file_path);
             }
         }
+        removed
+    }
+
+    /// Undo `entry`, returning the entry that re-applies it.
+    fn revert(&mut self, entry: AnnotationUndoEntry) -> AnnotationUndoEntry {
+        match entry {
+            AnnotationUndoEntry::Added(ann) => {
+                // Match on identity rather than ranges, which re-anchoring may have moved
+                if let Some(anns) = self.annotations.get_mut(&ann.anchor.file_path) {
+                    if let Some(pos) = anns
+                        .iter()
+                        .position(|a| a.created_at == ann.created_at && a.comment == ann.comment)
+                    {
+                        anns.remove(pos);
+                    }
+                    if anns.is_empty() {
+                        self.annotations.remove(&ann.anchor.file_path);
+                    }
+                }
+                AnnotationUndoEntry::Deleted(ann)
+            }
+            AnnotationUndoEntry::Deleted(ann) => {
+                self.add(ann.clone());
+                AnnotationUndoEntry::Added(ann)
+            }
+            AnnotationUndoEntry::Edited {
+                file,
+                ranges,
+                old_comment,
+                new_comment,
+                old_tags,
+                new_tags,
+            } => {
+                self.update_comment(
+                    &file,
+                    ranges.0,
+                    ranges.1,
+                    &new_comment,
+                    &old_comment,
+                    &old_tags,
+                );
+                AnnotationUndoEntry::Edited {
+                    file,
+                    ranges,
+                    old_comment: new_comment,
+                    new_comment: old_comment,
+                    old_tags: new_tags,
+                    new_tags: old_tags,
+                }
+            }
+        }
     }
 
     /// Get all annotations as a flat, sorted list (by file then sort_line).
@@ -145,28 +483,26 @@ impl AnnotationState {
         }
     }
 
-    /// Delete a specific annotation matching by anchor ranges + comment text.
+    /// Delete a specific annotation matching by anchor ranges + comment text, returning it.
     pub fn delete_annotation(
         &mut self,
         file_path: &str,
         old_range: Option<(u32, u32)>,
         new_range: Option<(u32, u32)>,
         comment: &str,
-    ) {
-        if let Some(anns) = self.annotations.get_mut(file_path) {
-            if let Some(pos) = anns
-                .iter()
-                .position(|a| a.anchor.matches(old_range, new_range) && a.comment == comment)
-            {
-                anns.remove(pos);
-            }
-            if anns.is_empty() {
-                self.annotations.remove(file_path);
-            }
+    ) -> Option<Annotation> {
+        let anns = self.annotations.get_mut(file_path)?;
+        let removed = anns
+            .iter()
+            .position(|a| a.anchor.matches(old_range, new_range) && a.comment == comment)
+            .map(|pos| anns.remove(pos));
+        if anns.is_empty() {
+            self.annotations.remove(file_path);
         }
+        removed
     }
 
-    /// Update a specific annotation's comment text.
+    /// Update a specific annotation's comment text and tags.
     pub fn update_comment(
         &mut self,
         file_path: &str,
@@ -174,6 +510,7 @@ impl AnnotationState {
         new_range: Option<(u32, u32)>,
         old_comment: &str,
         new_comment: &str,
+        tags: &[String],
     ) {
         if let Some(anns) = self.annotations.get_mut(file_path) {
             if let Some(ann) = anns
@@ -181,6 +518,7 @@ impl AnnotationState {
                 .find(|a| a.anchor.matches(old_range, new_range) && a.comment == old_comment)
             {
                 ann.comment = new_comment.to_string();
+                ann.tags = tags.to_vec();
             }
         }
     }
@@ -239,6 +577,71 @@ impl AnnotationState {
     pub fn files_with_annotations(&self) -> usize {
         self.annotations.len()
     }
+
+    /// Whether an annotation covers any of the same lines of the same file as `anchor`.
+    fn overlaps_any(&self, anchor: &LineAnchor) -> bool {
+        self.annotations
+            .get(&anchor.file_path)
+            .into_iter()
+            .flatten()
+            .any(|a| a.anchor.overlaps(anchor.old_range, anchor.new_range))
+    }
+
+    /// Whether an annotation with the same lines and comment is already present.
+    fn contains_same(&self, ann: &Annotation) -> bool {
+        self.annotations
+            .get(&ann.anchor.file_path)
+            .into_iter()
+            .flatten()
+            .any(|a| {
+                a.comment == ann.comment
+                    && a.anchor.old_range == ann.anchor.old_range
+                    && a.anchor.new_range == ann.anchor.new_range
+            })
+    }
+
+    /// How many of `incoming` overlap one of ours, not counting exact duplicates.
+    pub fn count_conflicts(&self, incoming: &[Annotation]) -> usize {
+        incoming
+            .iter()
+            .filter(|ann| !self.contains_same(ann))
+            .filter(|ann| self.overlaps_any(&ann.anchor))
+            .count()
+    }
+
+    /// Merge annotations from another session, skipping exact duplicates and
+    /// settling overlaps with ours by `resolution`. Returns how many were added.
+    pub fn merge(&mut self, incoming: Vec<Annotation>, resolution: MergeResolution) -> usize {
+        // Decide every conflict against our annotations before any are replaced,
+        // so imported annotations never displace each other
+        let incoming: Vec<(Annotation, bool)> = incoming
+            .into_iter()
+            .filter(|ann| !self.contains_same(ann))
+            .map(|ann| {
+                let clashes = self.overlaps_any(&ann.anchor);
+                (ann, clashes)
+            })
+            .collect();
+        if resolution == MergeResolution::TakeTheirs {
+            for (ann, _) in incoming.iter().filter(|(_, clashes)| *clashes) {
+                if let Some(anns) = self.annotations.get_mut(&ann.anchor.file_path) {
+                    anns.retain(|a| {
+                        !a.anchor
+                            .overlaps(ann.anchor.old_range, ann.anchor.new_range)
+                    });
+                }
+            }
+        }
+        let mut added = 0;
+        for (ann, clashes) in incoming {
+            if clashes && resolution == MergeResolution::KeepMine {
+                continue;
+            }
+            self.add(ann);
+            added += 1;
+        }
+        added
+    }
 }
 
 /// Placeholder for line score (spec 003 - quick-reactions).
@@ -246,3 +649,169 @@ impl AnnotationState {
 pub struct LineScore {
     pub score: u8,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        format_with_tags, parse_tags, within_one_edit, Annotation, AnnotationHistory,
+        AnnotationState, AnnotationUndoEntry, LineAnchor, MergeResolution, UNDO_LIMIT,
+    };
+
+    #[test]
+    fn one_edit_distance() {
+        assert!(within_one_edit("let x = 1;", "let x = 1;"));
+        assert!(within_one_edit("let x = 1;", "let x = 2;"));
+        assert!(within_one_edit("let x = 1;", "let x = 12;"));
+        assert!(within_one_edit("let x = 1;", "let x =1;"));
+        assert!(!within_one_edit("let x = 1;", "let y = 2;"));
+        assert!(!within_one_edit("abc", "a"));
+    }
+
+    #[test]
+    fn reanchor_follows_inserted_lines_and_marks_stale() {
+        let before = ["a", "b", "c", "target", "d", "e", "f"];
+        let mut anchor = LineAnchor::new("f.rs".into(), None, Some((4, 4)));
+        anchor.capture_context(&[], &before);
+        assert_eq!(anchor.context_before, ["a", "b", "c"]);
+        assert_eq!(anchor.context_after, ["d", "e", "f"]);
+
+        let shifted = ["new", "new", "a", "b", "c", "target", "d", "e", "f"];
+        anchor.reanchor(&[], &shifted);
+        assert_eq!(anchor.new_range, Some((6, 6)));
+        assert!(!anchor.stale);
+
+        // Single-character lines are all one edit apart, so use real content here
+        let rewritten = ["xx", "yy", "zz", "target", "uu", "vv", "ww"];
+        anchor.reanchor(&[], &rewritten);
+        assert!(anchor.stale);
+        assert_eq!(anchor.new_range, Some((6, 6)));
+    }
+
+    #[test]
+    fn reanchor_ignores_blank_context_lines() {
+        let mut anchor = LineAnchor::new("f.rs".into(), None, Some((3, 3)));
+        anchor.capture_context(&[], &["", "", "target", "", ""]);
+        anchor.reanchor(&[], &["fn a() {}", "", "", "target", "", ""]);
+        assert!(!anchor.stale);
+        assert_eq!(anchor.new_range, Some((3, 3)));
+
+        let before = ["fn a() {", "", "target", "", "}"];
+        anchor.capture_context(&[], &before);
+        let shifted = ["// moved", "fn a() {", "    let x = 1;", "target", "", "}"];
+        anchor.reanchor(&[], &shifted);
+        assert_eq!(anchor.new_range, Some((4, 4)));
+    }
+
+    fn annotation(comment: &str) -> Annotation {
+        Annotation {
+            anchor: LineAnchor::new("a.rs".into(), None, Some((1, 1))),
+            comment: comment.to_string(),
+            created_at: comment.to_string(),
+            tags: Vec::new(),
+            unanchored: false,
+        }
+    }
+
+    #[test]
+    fn tags_are_parsed_out_of_comment() {
+        let (comment, tags) = parse_tags("#Bug off by one #nit and #bug again\nsee issue #12");
+        assert_eq!(comment, "off by one and again\nsee issue #12");
+        assert_eq!(tags, ["bug", "nit"]);
+        assert_eq!(parse_tags("C# is # not a tag").1, Vec::<String>::new());
+        assert_eq!(
+            format_with_tags("off by one", &["bug".to_string()]),
+            "#bug off by one"
+        );
+    }
+
+    #[test]
+    fn undo_and_redo_reverse_changes() {
+        let mut state = AnnotationState::default();
+        let mut history = AnnotationHistory::default();
+
+        let ann = annotation("first");
+        state.add(ann.clone());
+        history.record(AnnotationUndoEntry::Added(ann));
+        let tags = vec!["nit".to_string()];
+        state.update_comment("a.rs", None, Some((1, 1)), "first", "edited", &tags);
+        history.record(AnnotationUndoEntry::Edited {
+            file: "a.rs".into(),
+            ranges: (None, Some((1, 1))),
+            old_comment: "first".into(),
+            new_comment: "edited".into(),
+            old_tags: Vec::new(),
+            new_tags: tags,
+        });
+
+        assert!(history.undo(&mut state));
+        assert_eq!(state.all_sorted()[0].comment, "first");
+        assert!(state.all_sorted()[0].tags.is_empty());
+        assert!(history.undo(&mut state));
+        assert_eq!(state.count(), 0);
+        assert!(!history.undo(&mut state));
+
+        assert!(history.redo(&mut state));
+        assert!(history.redo(&mut state));
+        assert_eq!(state.all_sorted()[0].comment, "edited");
+        assert_eq!(state.all_sorted()[0].marker(), "\u{25cb}");
+        assert!(!history.redo(&mut state));
+    }
+
+    #[test]
+    fn merge_skips_duplicates_and_resolves_overlaps() {
+        let on_line = |line: u32, comment: &str| Annotation {
+            anchor: LineAnchor::new("a.rs".into(), None, Some((line, line))),
+            ..annotation(comment)
+        };
+        let ours = || {
+            let mut state = AnnotationState::default();
+            state.add(on_line(1, "mine"));
+            state.add(on_line(5, "shared"));
+            state
+        };
+        let theirs = || {
+            vec![
+                on_line(1, "theirs"),
+                on_line(5, "shared"),
+                on_line(9, "new"),
+            ]
+        };
+
+        assert_eq!(ours().count_conflicts(&theirs()), 1);
+
+        let mut state = ours();
+        assert_eq!(state.merge(theirs(), MergeResolution::KeepMine), 1);
+        let comments: Vec<&str> = state
+            .all_sorted()
+            .iter()
+            .map(|a| a.comment.as_str())
+            .collect();
+        assert_eq!(comments, ["mine", "shared", "new"]);
+
+        let mut state = ours();
+        assert_eq!(state.merge(theirs(), MergeResolution::TakeTheirs), 2);
+        let comments: Vec<&str> = state
+            .all_sorted()
+            .iter()
+            .map(|a| a.comment.as_str())
+            .collect();
+        assert_eq!(comments, ["theirs", "shared", "new"]);
+
+        let mut state = ours();
+        assert_eq!(state.merge(theirs(), MergeResolution::KeepBoth), 2);
+        assert_eq!(state.count(), 4);
+    }
+
+    #[test]
+    fn history_is_capped() {
+        let mut state = AnnotationState::default();
+        let mut history = AnnotationHistory::default();
+        for i in 0..UNDO_LIMIT + 10 {
+            let ann = annotation(&i.to_string());
+            state.add(ann.clone());
+            history.record(AnnotationUndoEntry::Added(ann));
+        }
+        while history.undo(&mut state) {}
+        assert_eq!(state.count(), 10);
+    }
+}
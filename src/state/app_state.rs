@@ -1,10 +1,19 @@
+use std::ops::Range;
+use std::path::PathBuf;
+
+use crate::config::{ExportFormat, DEFAULT_PROMPT_PREVIEW_RATIO};
+use crate::git::types::CommitSummary;
+use crate::git::worktree::WorktreeInfo;
+use crate::session::SessionFile;
 use crate::theme::Theme;
 
 use super::{
     AgentOutputsState, AgentSelectorState, AnnotationState, ChecklistState, DiffOptions, DiffState,
-    GlobalSearchState, NavigatorState, ReviewState, SelectionState, TextBuffer, WorktreeState,
+    GlobalSearchState, NavigatorState, ReviewState, SelectionState, TabState, TextBuffer,
+    WorktreeState,
 };
 
+use super::annotation_state::AnnotationHistory;
 use super::settings_state::SettingsState;
 
 /// Snapshot of an annotation for the annotation menu (owned to avoid borrow issues).
@@ -14,6 +23,7 @@ pub struct AnnotationMenuItem {
     pub old_range: Option<(u32, u32)>,
     pub new_range: Option<(u32, u32)>,
     pub comment: String,
+    pub tags: Vec<String>,
 }
 
 impl AnnotationMenuItem {
@@ -37,6 +47,23 @@ impl AnnotationMenuItem {
     }
 }
 
+/// A diff position marked with `m`, for jumping back to later. Kept for the
+/// session only, per tab, and stored by line number so it survives a
+/// re-render with a different layout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bookmark {
+    pub file_path: String,
+    pub old_lineno: Option<u32>,
+    pub new_lineno: Option<u32>,
+}
+
+impl Bookmark {
+    /// Line used to order bookmarks within a file.
+    pub fn sort_line(&self) -> u32 {
+        self.new_lineno.or(self.old_lineno).unwrap_or(0)
+    }
+}
+
 /// Context for editing an existing annotation (set when user presses `e` in annotation menu).
 #[derive(Debug, Clone)]
 pub struct EditingAnnotation {
@@ -44,6 +71,63 @@ pub struct EditingAnnotation {
     pub old_range: Option<(u32, u32)>,
     pub new_range: Option<(u32, u32)>,
     pub old_comment: String,
+    pub old_tags: Vec<String>,
+}
+
+/// A pending `git apply` of one change block, awaiting confirmation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HunkPatchRequest {
+    pub file_index: usize,
+    pub hunk_index: usize,
+    /// Position of the block among the file's change blocks, for the status line.
+    pub block_index: usize,
+    /// The block's changed lines within the hunk.
+    pub lines: Range<usize>,
+    /// `false`: apply the block to the index. `true`: reverse it out of the working tree.
+    pub reverse: bool,
+}
+
+/// Staged and unstaged line counts for one file, shown until a key is pressed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatchSummary {
+    pub path: String,
+    pub staged: usize,
+    pub unstaged: usize,
+}
+
+/// The diff view's right-click menu, opened at a screen position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContextMenuState {
+    pub x: u16,
+    pub y: u16,
+    pub selected: usize,
+}
+
+/// Whether the session file dialog exports or imports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionDialogMode {
+    Export,
+    Import,
+}
+
+/// A session file read for import, awaiting a decision on the annotations
+/// that overlap ours.
+#[derive(Debug)]
+pub struct PendingSessionImport {
+    pub path: PathBuf,
+    pub session: SessionFile,
+    pub conflicts: usize,
+}
+
+/// A search-and-replace across the diff's added lines, awaiting confirmation.
+#[derive(Debug, Clone)]
+pub struct ReplacePlan {
+    pub search: String,
+    pub replace: String,
+    /// Combined working-tree patch for every affected file.
+    pub patch: String,
+    pub substitutions: usize,
+    pub files: usize,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -52,12 +136,14 @@ pub enum ActiveView {
     WorktreeBrowser,
     AgentOutputs,
     FeedbackSummary,
+    ReviewSummary,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FocusPanel {
     Navigator,
     DiffView,
+    PromptPreview,
 }
 
 pub struct AppState {
@@ -69,10 +155,41 @@ pub struct AppState {
     pub should_quit: bool,
     pub commit_dialog_open: bool,
     pub commit_message: TextBuffer,
+    /// Commit dialog amends `HEAD` instead of creating a commit.
+    pub commit_amend: bool,
     pub target_dialog_open: bool,
     pub target_dialog_input: TextBuffer,
+    /// Whether confirming the target dialog opens the target in a new tab.
+    pub target_dialog_new_tab: bool,
+    /// Local and remote branch names for completion, loaded when the dialog opens.
+    pub target_dialog_branches: Vec<String>,
+    pub worktree_dialog_open: bool,
+    pub worktree_dialog_input: TextBuffer,
+    pub filter_dialog_open: bool,
+    pub filter_dialog_input: TextBuffer,
+    pub jump_hunk_dialog_open: bool,
+    /// `<remote> <branch>` dialog shown when a push finds no upstream.
+    pub push_dialog_open: bool,
+    pub push_dialog_input: TextBuffer,
+    /// Commit count prompt for `git rebase -i HEAD~N`.
+    pub rebase_dialog_open: bool,
+    pub rebase_dialog_input: TextBuffer,
+    pub jump_hunk_input: TextBuffer,
+    pub replace_dialog_open: bool,
+    pub replace_search: TextBuffer,
+    pub replace_with: TextBuffer,
+    /// Whether the replace dialog's second field (the replacement) has focus.
+    pub replace_editing_with: bool,
+    pub replace_confirm: Option<ReplacePlan>,
     pub status_message: Option<(String, bool)>, // (message, is_error)
+    /// In-progress background git operation, e.g. "Fetching my-feature".
+    pub remote_op: Option<String>,
+    /// Incremented every tick; drives spinner animation.
+    pub spinner_tick: usize,
     pub target_label: String,
+    /// One slot per open tab; see `TabState` for how the active one is kept.
+    pub tabs: Vec<TabState>,
+    pub active_tab: usize,
     pub hud_expanded: bool,
 
     // Visual selection
@@ -80,6 +197,7 @@ pub struct AppState {
 
     // Annotations
     pub annotations: AnnotationState,
+    pub annotation_history: AnnotationHistory,
 
     // Comment editor
     pub comment_editor_open: bool,
@@ -87,12 +205,35 @@ pub struct AppState {
 
     // Prompt preview
     pub prompt_preview_visible: bool,
+    /// Hide the navigator so the diff view takes the full width.
+    pub focus_mode: bool,
+    /// Lines quoted around each annotation in generated prompts.
+    pub context_padding: u32,
+    /// Percentage of the diff area the diff keeps while the preview is open.
+    pub prompt_preview_ratio: u16,
     pub prompt_preview_text: String,
+    pub preview_search_active: bool,
+    pub preview_search_query: TextBuffer,
+    /// Indices of the preview lines containing the search query.
+    pub preview_search_matches: Vec<usize>,
+    /// Current position within `preview_search_matches`.
+    pub preview_search_match_index: Option<usize>,
+    /// First preview line shown.
+    pub preview_scroll_offset: usize,
+
+    // File log panel
+    pub file_log_open: bool,
+    pub file_log: Vec<CommitSummary>,
+    pub file_log_selected: usize,
 
     // Annotation menu
     pub annotation_menu_open: bool,
     pub annotation_menu_items: Vec<AnnotationMenuItem>,
     pub annotation_menu_selected: usize,
+
+    pub bookmarks: Vec<Bookmark>,
+
+    pub context_menu: Option<ContextMenuState>,
     pub editing_annotation: Option<EditingAnnotation>,
 
     // Agent
@@ -108,11 +249,32 @@ pub struct AppState {
     // Restore confirm
     pub restore_confirm_open: bool,
 
+    /// Worktree waiting on confirmation before `git worktree remove`.
+    pub worktree_remove_confirm: Option<WorktreeInfo>,
+    /// Commit from the file log awaiting confirmation to be reverted.
+    pub revert_confirm: Option<CommitSummary>,
+
+    // Hunk apply/revert confirm
+    pub hunk_patch_confirm: Option<HunkPatchRequest>,
+    pub patch_summary: Option<PatchSummary>,
+
+    // Session file export/import
+    pub session_dialog: Option<SessionDialogMode>,
+    pub session_dialog_input: TextBuffer,
+    pub session_import: Option<PendingSessionImport>,
+
     // Theme
     pub theme: Theme,
 
     // Settings modal
     pub settings: SettingsState,
+    pub export_format: ExportFormat,
+    /// Width of the additions/deletions bar on hunk headers; 0 hides it.
+    pub hunk_stat_bar_width: usize,
+    /// Show `▶` / `◀` origin markers so changes don't rely on color alone.
+    pub accessible: bool,
+    /// Highlight added and context lines past this many characters.
+    pub max_line_length: Option<usize>,
 
     // Which-key overlay
     pub which_key_visible: bool,
@@ -122,6 +284,7 @@ pub struct AppState {
 
     // Feedback summary
     pub feedback_summary_scroll: usize,
+    pub review_summary_selected: usize,
 
     // Checklist
     pub checklist: ChecklistState,
@@ -138,30 +301,78 @@ impl AppState {
             should_quit: false,
             commit_dialog_open: false,
             commit_message: TextBuffer::new(),
+            commit_amend: false,
             target_dialog_open: false,
             target_dialog_input: TextBuffer::new(),
+            target_dialog_new_tab: false,
+            target_dialog_branches: Vec::new(),
+            worktree_dialog_open: false,
+            worktree_dialog_input: TextBuffer::new(),
+            filter_dialog_open: false,
+            filter_dialog_input: TextBuffer::new(),
+            jump_hunk_dialog_open: false,
+            push_dialog_open: false,
+            push_dialog_input: TextBuffer::new(),
+            rebase_dialog_open: false,
+            rebase_dialog_input: TextBuffer::new(),
+            jump_hunk_input: TextBuffer::new(),
+            replace_dialog_open: false,
+            replace_search: TextBuffer::new(),
+            replace_with: TextBuffer::new(),
+            replace_editing_with: false,
+            replace_confirm: None,
             status_message: None,
+            remote_op: None,
+            spinner_tick: 0,
             target_label: String::new(),
+            tabs: Vec::new(),
+            active_tab: 0,
             hud_expanded: false,
             selection: SelectionState::default(),
             annotations: AnnotationState::default(),
+            annotation_history: AnnotationHistory::default(),
             comment_editor_open: false,
             comment_editor_text: TextBuffer::new(),
             prompt_preview_visible: false,
+            focus_mode: false,
+            context_padding: 5,
+            prompt_preview_ratio: DEFAULT_PROMPT_PREVIEW_RATIO,
             prompt_preview_text: String::new(),
+            preview_search_active: false,
+            preview_search_query: TextBuffer::new(),
+            preview_search_matches: Vec::new(),
+            preview_search_match_index: None,
+            preview_scroll_offset: 0,
+            file_log_open: false,
+            file_log: Vec::new(),
+            file_log_selected: 0,
             annotation_menu_open: false,
             annotation_menu_items: Vec::new(),
             annotation_menu_selected: 0,
+            bookmarks: Vec::new(),
+            context_menu: None,
             editing_annotation: None,
             agent_outputs: AgentOutputsState::default(),
             agent_selector: AgentSelectorState::default(),
             pty_focus: false,
             review: ReviewState::default(),
             restore_confirm_open: false,
+            worktree_remove_confirm: None,
+            revert_confirm: None,
+            hunk_patch_confirm: None,
+            patch_summary: None,
+            session_dialog: None,
+            session_dialog_input: TextBuffer::new(),
+            session_import: None,
             theme,
             settings: SettingsState::default(),
+            export_format: ExportFormat::default(),
+            hunk_stat_bar_width: 8,
+            accessible: false,
+            max_line_length: None,
             global_search: GlobalSearchState::default(),
             feedback_summary_scroll: 0,
+            review_summary_selected: 0,
             which_key_visible: false,
             checklist: ChecklistState::new(),
         }
@@ -1,7 +1,10 @@
 use std::collections::HashMap;
+use std::time::Instant;
 
+use crate::git::diff::{DiffBackend, DiffFilter};
 use crate::git::types::FileDelta;
 use crate::highlight::HighlightSpan;
+use crate::image_preview::ImagePreview;
 
 use super::TextBuffer;
 
@@ -15,6 +18,8 @@ pub enum DiffViewMode {
 pub struct DiffOptions {
     pub ignore_whitespace: bool,
     pub view_mode: DiffViewMode,
+    pub backend: DiffBackend,
+    pub filter: DiffFilter,
 }
 
 impl DiffOptions {
@@ -26,6 +31,8 @@ impl DiffOptions {
             } else {
                 DiffViewMode::Split
             },
+            backend: DiffBackend::default(),
+            filter: DiffFilter::default(),
         }
     }
 }
@@ -39,14 +46,27 @@ pub struct DiffState {
     pub cursor_row: usize,
     pub viewport_height: usize,
     pub loading: bool,
+    /// When the diff currently loading was first requested, for the elapsed time.
+    pub load_start: Option<Instant>,
+    /// Why the last diff computation failed, shown in place of the diff.
+    pub load_error: Option<String>,
     /// Per-line highlight spans for the old side, indexed by 1-based line number.
     pub old_highlights: Vec<Vec<HighlightSpan>>,
     /// Per-line highlight spans for the new side, indexed by 1-based line number.
     pub new_highlights: Vec<Vec<HighlightSpan>>,
+    /// Both versions of the selected file when it's a binary image.
+    pub image_preview: Option<ImagePreview>,
     /// Number of context lines to show around each change (default 3).
     pub display_context: usize,
     /// Per-gap expansion state: gap_id -> extra lines revealed.
     pub gap_expansions: HashMap<usize, usize>,
+    /// User splits (`X`) of the selected file's hunks, as `(hunk_index, line_index)`
+    /// of the line each extra header sits above. Display-only.
+    pub hunk_splits: Vec<(usize, usize)>,
+    /// Largest `additions + deletions` of any file, the top of the navigator heatmap.
+    pub max_changes: usize,
+    /// Files dropped from the diff by `exclude_paths`.
+    pub excluded_count: usize,
 
     /// Visual row offsets for each logical display row.
     pub visual_row_offsets: Vec<usize>,
@@ -54,6 +74,18 @@ pub struct DiffState {
     pub visual_row_heights: Vec<usize>,
     /// Total visual rows after wrapping.
     pub visual_total_rows: usize,
+    /// Display row where each change block of the selected file starts, kept
+    /// with the visual metrics for the context bar's hunk counter.
+    pub change_block_rows: Vec<usize>,
+
+    /// Wrap long lines (true) or truncate them and scroll horizontally (false).
+    pub line_wrap: bool,
+    /// Horizontal scroll offset in columns, applied when `line_wrap` is off.
+    pub scroll_x: usize,
+    /// Show annotation markers in the gutter and minimap. Per session, not persisted.
+    pub annotations_visible: bool,
+    /// Draw each annotation's comment under the last line it covers.
+    pub show_inline_annotations: bool,
 
     // Diff text search
     pub search_active: bool,
@@ -74,13 +106,24 @@ impl DiffState {
             cursor_row: 0,
             viewport_height: 20,
             loading: false,
+            load_start: None,
+            load_error: None,
             old_highlights: Vec::new(),
             new_highlights: Vec::new(),
+            image_preview: None,
             display_context: 3,
             gap_expansions: HashMap::new(),
+            hunk_splits: Vec::new(),
+            max_changes: 0,
+            excluded_count: 0,
             visual_row_offsets: Vec::new(),
             visual_row_heights: Vec::new(),
             visual_total_rows: 0,
+            change_block_rows: Vec::new(),
+            line_wrap: true,
+            scroll_x: 0,
+            annotations_visible: true,
+            show_inline_annotations: false,
             search_active: false,
             search_query: TextBuffer::new(),
             search_matches: Vec::new(),
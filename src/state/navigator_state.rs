@@ -1,4 +1,6 @@
-use crate::git::types::FileDelta;
+use crate::git::types::{FileDelta, FileStatus};
+use nucleo::pattern::{AtomKind, CaseMatching, Normalization, Pattern};
+use nucleo::{Config, Matcher, Utf32Str};
 use std::collections::{HashMap, HashSet};
 
 use super::TextBuffer;
@@ -8,6 +10,60 @@ pub struct NavigatorEntry {
     pub display: String,
     pub path: String,
     pub delta_index: usize,
+    pub status: FileStatus,
+    pub conflicted: bool,
+}
+
+/// Which file statuses the navigator lists, cycled with `T`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NavigatorStatusFilter {
+    #[default]
+    All,
+    /// Modified files, including renames and submodule bumps.
+    Modified,
+    Added,
+    Deleted,
+    Untracked,
+    /// Files with merge conflict markers.
+    Conflicted,
+}
+
+impl NavigatorStatusFilter {
+    pub fn next(self) -> Self {
+        match self {
+            Self::All => Self::Modified,
+            Self::Modified => Self::Added,
+            Self::Added => Self::Deleted,
+            Self::Deleted => Self::Untracked,
+            Self::Untracked => Self::Conflicted,
+            Self::Conflicted => Self::All,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::Modified => "modified",
+            Self::Added => "added",
+            Self::Deleted => "deleted",
+            Self::Untracked => "untracked",
+            Self::Conflicted => "conflicts",
+        }
+    }
+
+    fn matches(self, entry: &NavigatorEntry) -> bool {
+        match self {
+            Self::All => true,
+            Self::Modified => matches!(
+                entry.status,
+                FileStatus::Modified | FileStatus::Renamed | FileStatus::Submodule
+            ),
+            Self::Added => entry.status == FileStatus::Added,
+            Self::Deleted => entry.status == FileStatus::Deleted,
+            Self::Untracked => entry.status == FileStatus::Untracked,
+            Self::Conflicted => entry.conflicted,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -19,6 +75,18 @@ pub struct NavigatorState {
     pub search_query: TextBuffer,
     /// Saved selection index before search started (for cancel/restore).
     pre_search_selected: Option<usize>,
+    /// Delta indices marked for bulk operations.
+    pub marked: HashSet<usize>,
+    /// Delta indices whose diff is folded away to skip the file during review.
+    pub collapsed: HashSet<usize>,
+    /// Glob pattern restricting which files are listed, e.g. `*.rs` or `src/**`.
+    pub filter_glob: Option<String>,
+    filter_pattern: Option<glob::Pattern>,
+    pub status_filter: NavigatorStatusFilter,
+    /// Display-string char positions matched by the search query, by entry index.
+    pub match_positions: HashMap<usize, Vec<usize>>,
+    /// Reused across searches; it allocates its scratch space up front.
+    matcher: Matcher,
 }
 
 impl NavigatorState {
@@ -30,14 +98,56 @@ impl NavigatorState {
             search_active: false,
             search_query: TextBuffer::new(),
             pre_search_selected: None,
+            marked: HashSet::new(),
+            collapsed: HashSet::new(),
+            filter_glob: None,
+            filter_pattern: None,
+            status_filter: NavigatorStatusFilter::All,
+            match_positions: HashMap::new(),
+            matcher: Matcher::new(Config::DEFAULT.match_paths()),
         }
     }
 
+    /// Restrict the list to paths matching `pattern`; `None` or an empty pattern clears the filter.
+    pub fn set_filter_glob(&mut self, pattern: Option<&str>) -> Result<(), glob::PatternError> {
+        match pattern.map(str::trim).filter(|p| !p.is_empty()) {
+            Some(p) => {
+                self.filter_pattern = Some(glob::Pattern::new(p)?);
+                self.filter_glob = Some(p.to_string());
+            }
+            None => {
+                self.filter_pattern = None;
+                self.filter_glob = None;
+            }
+        }
+        self.refilter();
+        Ok(())
+    }
+
+    /// Show only conflicted files, clearing any glob filter.
+    pub fn show_conflicts_only(&mut self) {
+        self.filter_pattern = None;
+        self.filter_glob = None;
+        self.status_filter = NavigatorStatusFilter::Conflicted;
+        self.refilter();
+    }
+
+    /// Move to the next status filter.
+    pub fn cycle_status_filter(&mut self) {
+        self.status_filter = self.status_filter.next();
+        self.refilter();
+    }
+
     pub fn update_from_deltas(&mut self, deltas: &[FileDelta]) {
         let paths: Vec<String> = deltas
             .iter()
             .map(|d| d.path.to_string_lossy().to_string())
             .collect();
+
+        // Delta indices shift between refreshes; carry marks and folds over by path.
+        self.marked = self.remap_by_path(&self.marked, &paths);
+        self.collapsed = self.remap_by_path(&self.collapsed, &paths);
+
         let informative_paths = build_informative_path_displays(&paths);
 
         self.entries = deltas
@@ -56,6 +166,8 @@ impl NavigatorState {
                     display,
                     path: path_str,
                     delta_index: i,
+                    status: d.status.clone(),
+                    conflicted: d.has_conflicts,
                 }
             })
             .collect();
@@ -63,19 +175,61 @@ impl NavigatorState {
         self.refilter();
     }
 
+    /// Whitespace-separated search terms; an entry has to match every one.
+    pub fn search_terms(&self) -> Vec<&str> {
+        self.search_query.text().split_whitespace().collect()
+    }
+
     pub fn refilter(&mut self) {
-        if self.search_query.is_empty() {
-            self.filtered_indices = (0..self.entries.len()).collect();
-        } else {
-            let query_lower = self.search_query.text().to_lowercase();
-            self.filtered_indices = self
-                .entries
-                .iter()
-                .enumerate()
-                .filter(|(_, e)| fuzzy_match(&e.path.to_lowercase(), &query_lower))
-                .map(|(i, _)| i)
-                .collect();
+        let query = self.search_query.text().to_string();
+        let terms: Vec<&str> = query.split_whitespace().collect();
+        let lower_terms: Vec<String> = terms.iter().map(|t| t.to_lowercase()).collect();
+        let pattern = Pattern::new(
+            &query,
+            CaseMatching::Ignore,
+            Normalization::Smart,
+            AtomKind::Fuzzy,
+        );
+        let mut buf = Vec::new();
+        let mut scored: Vec<(usize, u32)> = Vec::new();
+        self.match_positions.clear();
+        for (i, e) in self.entries.iter().enumerate() {
+            if !self.status_filter.matches(e) {
+                continue;
+            }
+            if !self
+                .filter_pattern
+                .as_ref()
+                .is_none_or(|pattern| pattern.matches(&e.path))
+            {
+                continue;
+            }
+            if terms.is_empty() {
+                scored.push((i, 0));
+                continue;
+            }
+            // Every term must appear as written; fuzzy matching only ranks
+            let lower_path = e.path.to_lowercase();
+            if !lower_terms.iter().all(|t| lower_path.contains(t.as_str())) {
+                continue;
+            }
+            let mut indices = Vec::new();
+            let haystack = Utf32Str::new(&e.path, &mut buf);
+            if let Some(score) = pattern.indices(haystack, &mut self.matcher, &mut indices) {
+                // Indices from each term are appended, not merged
+                indices.sort_unstable();
+                indices.dedup();
+                let positions: Vec<usize> = indices.into_iter().map(|i| i as usize).collect();
+                scored.push((i, score));
+                self.match_positions.insert(
+                    i,
+                    path_to_display_positions(&e.path, &e.display, &positions),
+                );
+            }
         }
+        // Stable, so equal scores keep diff order
+        scored.sort_by(|a, b| b.1.cmp(&a.1));
+        self.filtered_indices = scored.into_iter().map(|(i, _)| i).collect();
 
         // Clamp selection
         if !self.filtered_indices.is_empty() {
@@ -102,6 +256,37 @@ impl NavigatorState {
         }
     }
 
+    /// Translate a set of delta indices from the current entries to `paths`.
+    fn remap_by_path(&self, indices: &HashSet<usize>, paths: &[String]) -> HashSet<usize> {
+        let kept: HashSet<&str> = self
+            .entries
+            .iter()
+            .filter(|e| indices.contains(&e.delta_index))
+            .map(|e| e.path.as_str())
+            .collect();
+        paths
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| kept.contains(p.as_str()))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Whether the selected entry's diff is collapsed.
+    pub fn selected_collapsed(&self) -> bool {
+        self.selected_delta_index()
+            .is_some_and(|idx| self.collapsed.contains(&idx))
+    }
+
+    /// Toggle the bulk-operation mark on the selected entry.
+    pub fn toggle_mark(&mut self) {
+        if let Some(idx) = self.selected_delta_index() {
+            if !self.marked.remove(&idx) {
+                self.marked.insert(idx);
+            }
+        }
+    }
+
     pub fn selected_delta_index(&self) -> Option<usize> {
         self.filtered_indices
             .get(self.selected)
@@ -160,19 +345,33 @@ impl NavigatorState {
     }
 }
 
-/// Simple fuzzy match: all characters of pattern must appear in text in order.
-fn fuzzy_match(text: &str, pattern: &str) -> bool {
-    let mut text_iter = text.chars();
-    for pc in pattern.chars() {
-        loop {
-            match text_iter.next() {
-                Some(tc) if tc == pc => break,
-                Some(_) => continue,
-                None => return false,
+/// Map matched char positions in a full path onto its abbreviated display,
+/// where each directory component keeps only a prefix. Positions that were
+/// abbreviated away are dropped.
+fn path_to_display_positions(path: &str, display: &str, positions: &[usize]) -> Vec<usize> {
+    let path_parts: Vec<usize> = path.split('/').map(|p| p.chars().count()).collect();
+    let display_parts: Vec<usize> = display.split('/').map(|p| p.chars().count()).collect();
+
+    let mut out = Vec::new();
+    let (mut path_offset, mut display_offset) = (0, 0);
+    for (i, &path_len) in path_parts.iter().enumerate() {
+        let kept = display_parts.get(i).copied().unwrap_or(0);
+        let has_separator = i + 1 < path_parts.len();
+        for &p in positions {
+            let Some(o) = p.checked_sub(path_offset) else {
+                continue;
+            };
+            if o < path_len.min(kept) {
+                out.push(display_offset + o);
+            } else if o == path_len && has_separator {
+                out.push(display_offset + kept);
             }
         }
+        // Skip past this component and the `/` after it
+        path_offset += path_len + 1;
+        display_offset += kept + 1;
     }
-    true
+    out
 }
 
 fn build_informative_path_displays(paths: &[String]) -> Vec<String> {
@@ -268,6 +467,8 @@ mod tests {
             additions,
             deletions,
             binary: false,
+            has_conflicts: false,
+            mode: 0o100644,
         }
     }
 
@@ -316,4 +517,135 @@ mod tests {
         assert_eq!(state.entries[0].path, "src/components/navigator.rs");
         assert_eq!(state.entries[1].path, "src/config/navigator.rs");
     }
+
+    #[test]
+    fn marks_follow_paths_across_refresh() {
+        let mut nav = NavigatorState::new();
+        nav.update_from_deltas(&[
+            make_delta("a.rs", FileStatus::Modified, 1, 0),
+            make_delta("b.rs", FileStatus::Modified, 1, 0),
+        ]);
+        nav.selected = 1;
+        nav.toggle_mark();
+        assert!(nav.marked.contains(&1));
+
+        nav.update_from_deltas(&[
+            make_delta("0.rs", FileStatus::Added, 1, 0),
+            make_delta("a.rs", FileStatus::Modified, 1, 0),
+            make_delta("b.rs", FileStatus::Modified, 1, 0),
+        ]);
+        assert_eq!(nav.marked, HashSet::from([2]));
+    }
+
+    #[test]
+    fn glob_filter_restricts_visible_entries() {
+        let mut nav = NavigatorState::new();
+        nav.update_from_deltas(&[
+            make_delta("src/app.rs", FileStatus::Modified, 1, 0),
+            make_delta("web/index.ts", FileStatus::Modified, 1, 0),
+            make_delta("README.md", FileStatus::Modified, 1, 0),
+        ]);
+
+        nav.set_filter_glob(Some("*.rs")).unwrap();
+        assert_eq!(nav.filtered_indices, vec![0]);
+        nav.set_filter_glob(Some("web/**")).unwrap();
+        assert_eq!(nav.filtered_indices, vec![1]);
+        assert!(nav.set_filter_glob(Some("[")).is_err());
+        assert_eq!(nav.filter_glob.as_deref(), Some("web/**"));
+
+        nav.set_filter_glob(Some("  ")).unwrap();
+        assert_eq!(nav.filter_glob, None);
+        assert_eq!(nav.filtered_indices.len(), 3);
+    }
+
+    #[test]
+    fn conflicts_filter_clears_the_glob_and_survives_refresh() {
+        let mut conflicted = make_delta("src/app.rs", FileStatus::Modified, 1, 0);
+        conflicted.has_conflicts = true;
+        let deltas = [
+            make_delta("src/main.rs", FileStatus::Modified, 1, 0),
+            conflicted,
+        ];
+        let mut nav = NavigatorState::new();
+        nav.update_from_deltas(&deltas);
+        nav.set_filter_glob(Some("src/main.rs")).unwrap();
+
+        nav.show_conflicts_only();
+        assert_eq!(nav.filter_glob, None);
+        assert_eq!(nav.status_filter, NavigatorStatusFilter::Conflicted);
+        assert_eq!(nav.filtered_indices, vec![1]);
+        nav.update_from_deltas(&deltas);
+        assert_eq!(nav.filtered_indices, vec![1]);
+
+        nav.cycle_status_filter();
+        assert_eq!(nav.filtered_indices.len(), 2);
+    }
+
+    #[test]
+    fn status_filter_cycles_through_statuses() {
+        let mut nav = NavigatorState::new();
+        nav.update_from_deltas(&[
+            make_delta("src/app.rs", FileStatus::Modified, 1, 1),
+            make_delta("src/new.rs", FileStatus::Added, 1, 0),
+            make_delta("src/old.rs", FileStatus::Deleted, 0, 1),
+            make_delta("notes.txt", FileStatus::Untracked, 1, 0),
+        ]);
+
+        let mut seen = Vec::new();
+        for _ in 0..6 {
+            nav.cycle_status_filter();
+            seen.push((nav.status_filter.label(), nav.filtered_indices.clone()));
+        }
+        assert_eq!(
+            seen,
+            [
+                ("modified", vec![0]),
+                ("added", vec![1]),
+                ("deleted", vec![2]),
+                ("untracked", vec![3]),
+                ("conflicts", vec![]),
+                ("all", vec![0, 1, 2, 3]),
+            ]
+        );
+    }
+
+    #[test]
+    fn fuzzy_search_ranks_tighter_matches_first() {
+        let mut nav = NavigatorState::new();
+        nav.update_from_deltas(&[
+            make_delta("src/state/annotation_map.rs", FileStatus::Modified, 1, 0),
+            make_delta("src/main.rs", FileStatus::Modified, 1, 0),
+            make_delta("src/git/types.rs", FileStatus::Modified, 1, 0),
+        ]);
+        for c in "ma".chars() {
+            nav.search_push(c);
+        }
+        // "ma" starts a path component in main.rs but only follows `_` in
+        // annotation_map.rs, and never appears in types.rs
+        assert_eq!(nav.filtered_indices, vec![1, 0]);
+        // "s/main.rs [M] +1 -0"
+        assert_eq!(nav.match_positions[&1], vec![2, 3]);
+    }
+
+    #[test]
+    fn search_terms_must_all_match() {
+        let mut nav = NavigatorState::new();
+        nav.update_from_deltas(&[
+            make_delta("src/app.rs", FileStatus::Modified, 1, 0),
+            make_delta("src/app.ts", FileStatus::Modified, 1, 0),
+            make_delta("tests/app.rs", FileStatus::Modified, 1, 0),
+        ]);
+        for c in "src  .rs".chars() {
+            nav.search_push(c);
+        }
+        assert_eq!(nav.search_terms(), vec!["src", ".rs"]);
+        assert_eq!(nav.filtered_indices, vec![0]);
+
+        // `rs` is a subsequence of `src/app.ts` but not a substring of it
+        nav.search_query.clear();
+        for c in "SRC rs".chars() {
+            nav.search_push(c);
+        }
+        assert_eq!(nav.filtered_indices, vec![0]);
+    }
 }
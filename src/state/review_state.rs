@@ -1,147 +1,412 @@
-use std::collections::hash_map::DefaultHasher;
-use std::collections::HashMap;
-use std::hash::{Hash, Hasher};
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
 
-use crate::git::types::FileDelta;
+use crate::git::types::{DiffLineOrigin, FileDelta};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FileReviewStatus {
     /// Never viewed.
     Unreviewed,
-    /// Viewed and diff content unchanged since last review.
+    /// Every hunk viewed and unchanged since.
     Reviewed,
-    /// Viewed previously but diff content changed on refresh.
+    /// Viewed previously, but every reviewed hunk changed on refresh.
     ChangedSinceReview,
+    /// Some hunks reviewed; the rest are new, changed, or not yet viewed.
+    PartiallyChanged {
+        reviewed_hunks: usize,
+        changed_hunks: usize,
+    },
     /// File appeared after a diff refresh (not present before).
     New,
 }
 
-/// Tracks per-file review progress. In-memory only (resets on quit).
+/// Sorted, non-overlapping inclusive line ranges.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LineRanges(Vec<(u32, u32)>);
+
+impl LineRanges {
+    pub fn insert(&mut self, line: u32) {
+        // First range that ends at or just before `line`, i.e. could absorb it
+        let i = self
+            .0
+            .partition_point(|&(_, end)| end.saturating_add(1) < line);
+        match self.0.get(i).copied() {
+            Some((start, end)) if start <= line.saturating_add(1) => {
+                let end = end.max(line);
+                self.0[i] = (start.min(line), end);
+                if let Some(&(next_start, next_end)) = self.0.get(i + 1) {
+                    if next_start <= end.saturating_add(1) {
+                        self.0[i].1 = next_end.max(end);
+                        self.0.remove(i + 1);
+                    }
+                }
+            }
+            _ => self.0.insert(i, (line, line)),
+        }
+    }
+
+    pub fn contains(&self, line: u32) -> bool {
+        let i = self.0.partition_point(|&(_, end)| end < line);
+        self.0.get(i).is_some_and(|&(start, _)| start <= line)
+    }
+}
+
+/// Lines of a file's diff that have been on screen, per side.
+#[derive(Debug, Clone, Default)]
+pub struct SeenLines {
+    pub old: LineRanges,
+    pub new: LineRanges,
+}
+
+impl SeenLines {
+    pub fn contains(&self, old_lineno: Option<u32>, new_lineno: Option<u32>) -> bool {
+        old_lineno.is_some_and(|l| self.old.contains(l))
+            || new_lineno.is_some_and(|l| self.new.contains(l))
+    }
+}
+
+/// Tracks per-file review progress at hunk granularity. Reviewed hunks are
+/// persisted with the session; seen lines are in-memory only.
+///
+/// Diffs are loaded with full-file context, so a "hunk" here is a run of
+/// consecutive added/removed lines (see [`change_blocks`]).
 #[derive(Debug, Default)]
 pub struct ReviewState {
-    /// Status and the hash at time of review (if reviewed).
-    files: HashMap<String, (FileReviewStatus, Option<u64>)>,
-    /// Hashes from the most recent diff load.
-    current_hashes: HashMap<String, u64>,
+    /// Hashes of the hunks reviewed in each file, with when each was reviewed.
+    /// A present but empty map means the file was reviewed and all of those
+    /// hunks have since changed.
+    reviewed: HashMap<String, HashMap<u64, String>>,
+    /// Files that appeared on a refresh and have not been reviewed yet.
+    new_files: HashSet<String>,
+    /// Per-hunk hashes from the most recent diff load.
+    current_hashes: HashMap<String, Vec<u64>>,
+    /// Lines scrolled past in each file's current diff.
+    seen: HashMap<String, SeenLines>,
 }
 
 impl ReviewState {
-    /// Mark a file as reviewed, storing the current diff hash.
+    /// Mark every hunk of a file as reviewed.
     pub fn mark_reviewed(&mut self, path: &str) {
-        let hash = self.current_hashes.get(path).copied();
-        self.files
-            .insert(path.to_string(), (FileReviewStatus::Reviewed, hash));
+        let now = chrono::Utc::now().to_rfc3339();
+        let reviewed = self.reviewed.entry(path.to_string()).or_default();
+        for &hash in self.current_hashes.get(path).into_iter().flatten() {
+            reviewed.entry(hash).or_insert_with(|| now.clone());
+        }
+        self.new_files.remove(path);
+    }
+
+    /// Mark a single hunk of a file as reviewed.
+    pub fn mark_hunk_reviewed(&mut self, path: &str, hunk: usize) {
+        let Some(&hash) = self.current_hashes.get(path).and_then(|h| h.get(hunk)) else {
+            return;
+        };
+        self.reviewed
+            .entry(path.to_string())
+            .or_default()
+            .entry(hash)
+            .or_insert_with(|| chrono::Utc::now().to_rfc3339());
+        self.new_files.remove(path);
     }
 
     /// Toggle between Reviewed and Unreviewed.
     pub fn toggle_reviewed(&mut self, path: &str) {
-        match self.files.get(path).map(|(s, _)| *s) {
-            Some(FileReviewStatus::Reviewed) => {
-                self.files
-                    .insert(path.to_string(), (FileReviewStatus::Unreviewed, None));
-            }
-            _ => {
-                self.mark_reviewed(path);
-            }
+        if self.status(path) == FileReviewStatus::Reviewed {
+            self.reviewed.remove(path);
+            self.new_files.remove(path);
+        } else {
+            self.mark_reviewed(path);
+        }
+    }
+
+    /// Record a diff row as seen on screen.
+    pub fn mark_seen(&mut self, path: &str, old_lineno: Option<u32>, new_lineno: Option<u32>) {
+        let seen = self.seen.entry(path.to_string()).or_default();
+        if let Some(line) = old_lineno {
+            seen.old.insert(line);
         }
+        if let Some(line) = new_lineno {
+            seen.new.insert(line);
+        }
+    }
+
+    pub fn seen_lines(&self, path: &str) -> Option<&SeenLines> {
+        self.seen.get(path)
     }
 
     /// Get the review status for a file.
     pub fn status(&self, path: &str) -> FileReviewStatus {
-        self.files
+        let Some(reviewed) = self.reviewed.get(path) else {
+            return if self.new_files.contains(path) {
+                FileReviewStatus::New
+            } else {
+                FileReviewStatus::Unreviewed
+            };
+        };
+        let hashes = self
+            .current_hashes
             .get(path)
-            .map(|(s, _)| *s)
-            .unwrap_or(FileReviewStatus::Unreviewed)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let reviewed_hunks = hashes.iter().filter(|h| reviewed.contains_key(h)).count();
+        if reviewed_hunks == hashes.len() {
+            FileReviewStatus::Reviewed
+        } else if reviewed_hunks == 0 {
+            FileReviewStatus::ChangedSinceReview
+        } else {
+            FileReviewStatus::PartiallyChanged {
+                reviewed_hunks,
+                changed_hunks: hashes.len() - reviewed_hunks,
+            }
+        }
     }
 
-    /// Called after a diff refresh with new hashes. Compares against stored
-    /// reviewed hashes to detect changes.
-    pub fn on_diff_refresh(&mut self, new_hashes: HashMap<String, u64>) {
-        let first_load = self.current_hashes.is_empty();
-
-        if first_load {
-            // First diff load: mark all files as Unreviewed (not New).
+    /// Called after a diff refresh with new per-hunk hashes. Reviews of hunks
+    /// whose content changed are dropped; untouched hunks stay reviewed.
+    pub fn on_diff_refresh(&mut self, new_hashes: HashMap<String, Vec<u64>>) {
+        // On the first load nothing is "new"
+        if !self.current_hashes.is_empty() {
             for path in new_hashes.keys() {
-                self.files
-                    .entry(path.clone())
-                    .or_insert((FileReviewStatus::Unreviewed, None));
-            }
-        } else {
-            let old_paths: std::collections::HashSet<&String> =
-                self.current_hashes.keys().collect();
-
-            for (path, &new_hash) in &new_hashes {
-                if !old_paths.contains(path) {
-                    // File is new since last refresh.
-                    self.files
-                        .insert(path.clone(), (FileReviewStatus::New, None));
-                } else if let Some((
-                    FileReviewStatus::Reviewed | FileReviewStatus::ChangedSinceReview,
-                    reviewed_hash,
-                )) = self.files.get(path)
-                {
-                    // Check if diff content changed since review.
-                    if *reviewed_hash != Some(new_hash) {
-                        self.files.insert(
-                            path.clone(),
-                            (FileReviewStatus::ChangedSinceReview, *reviewed_hash),
-                        );
-                    } else {
-                        // Hash matches review - stays Reviewed.
-                        self.files
-                            .insert(path.clone(), (FileReviewStatus::Reviewed, *reviewed_hash));
-                    }
+                if !self.current_hashes.contains_key(path) {
+                    self.new_files.insert(path.clone());
                 }
             }
-
-            // Files that disappeared: remove from tracking.
-            let new_paths: std::collections::HashSet<&String> = new_hashes.keys().collect();
-            self.files.retain(|k, _| new_paths.contains(k));
         }
 
+        self.new_files.retain(|path| new_hashes.contains_key(path));
+        self.reviewed
+            .retain(|path, reviewed| match new_hashes.get(path) {
+                Some(hashes) => {
+                    reviewed.retain(|h, _| hashes.contains(h));
+                    true
+                }
+                None => false,
+            });
+
+        // Seen lines only make sense for the diff they were recorded against
+        self.seen
+            .retain(|path, _| self.current_hashes.get(path) == new_hashes.get(path));
+
         self.current_hashes = new_hashes;
     }
 
+    /// Reviewed hunk hashes per file, with review timestamps (for persisting).
+    pub fn reviewed_hunks(&self) -> &HashMap<String, HashMap<u64, String>> {
+        &self.reviewed
+    }
+
+    /// Restore reviews loaded from a saved session. Hunks that no longer exist
+    /// are dropped on the next diff refresh.
+    pub fn restore_reviewed(&mut self, reviewed: HashMap<String, HashMap<u64, String>>) {
+        self.reviewed = reviewed;
+    }
+
+    /// Add reviews from an imported session, keeping ours where both have one.
+    pub fn merge_reviewed(&mut self, reviewed: HashMap<String, HashMap<u64, String>>) {
+        for (path, hunks) in reviewed {
+            if hunks.is_empty() {
+                continue;
+            }
+            let ours = self.reviewed.entry(path).or_default();
+            for (hash, at) in hunks {
+                ours.entry(hash).or_insert(at);
+            }
+        }
+    }
+
     /// Reset all review state (e.g. on target/worktree change).
     pub fn reset(&mut self) {
-        self.files.clear();
+        self.reviewed.clear();
+        self.new_files.clear();
         self.current_hashes.clear();
+        self.seen.clear();
     }
 
-    /// Count files that have been reviewed.
+    /// Count files that have been fully reviewed.
     pub fn reviewed_count(&self) -> usize {
-        self.files
-            .values()
-            .filter(|(status, _)| matches!(status, FileReviewStatus::Reviewed))
+        self.reviewed
+            .keys()
+            .filter(|path| self.status(path) == FileReviewStatus::Reviewed)
             .count()
     }
 }
 
-/// Compute a hash fingerprint of a FileDelta's diff content.
-pub fn hash_file_diff(delta: &FileDelta) -> u64 {
-    let mut hasher = DefaultHasher::new();
-    for hunk in &delta.hunks {
-        hunk.header.hash(&mut hasher);
-        for line in &hunk.lines {
-            line.content.hash(&mut hasher);
-            match line.origin {
-                crate::git::types::DiffLineOrigin::Context => 0u8.hash(&mut hasher),
-                crate::git::types::DiffLineOrigin::Addition => 1u8.hash(&mut hasher),
-                crate::git::types::DiffLineOrigin::Deletion => 2u8.hash(&mut hasher),
+/// A run of consecutive added/removed lines within one hunk of a delta.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangeBlock {
+    pub hunk_index: usize,
+    /// Line indices within the hunk.
+    pub lines: Range<usize>,
+}
+
+/// Split a delta's hunks into runs of changed lines.
+pub fn change_blocks(delta: &FileDelta) -> Vec<ChangeBlock> {
+    let mut blocks = Vec::new();
+    for (hunk_index, hunk) in delta.hunks.iter().enumerate() {
+        let mut start = None;
+        for (i, line) in hunk.lines.iter().enumerate() {
+            match (line.origin == DiffLineOrigin::Context, start) {
+                (false, None) => start = Some(i),
+                (true, Some(s)) => {
+                    blocks.push(ChangeBlock {
+                        hunk_index,
+                        lines: s..i,
+                    });
+                    start = None;
+                }
+                _ => {}
             }
         }
+        if let Some(s) = start {
+            blocks.push(ChangeBlock {
+                hunk_index,
+                lines: s..hunk.lines.len(),
+            });
+        }
     }
-    hasher.finish()
+    blocks
 }
 
-/// Compute hashes for all deltas, keyed by file path.
-pub fn compute_diff_hashes(deltas: &[FileDelta]) -> HashMap<String, u64> {
-    deltas
+/// 64-bit FNV-1a. Hunk hashes are persisted, so unlike `DefaultHasher` the
+/// result mustn't change between Rust releases or builds.
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Hash each change block of a delta: its lines plus the context line above,
+/// so moving a block by inserting lines elsewhere keeps its hash.
+pub fn hash_hunks(delta: &FileDelta) -> Vec<u64> {
+    change_blocks(delta)
         .iter()
-        .map(|d| {
-            let path = d.path.to_string_lossy().to_string();
-            let hash = hash_file_diff(d);
-            (path, hash)
+        .map(|block| {
+            let lines = &delta.hunks[block.hunk_index].lines;
+            let from = block.lines.start.saturating_sub(1);
+            lines[from..block.lines.end]
+                .iter()
+                .fold(0xcbf2_9ce4_8422_2325, |hash, line| {
+                    let origin = match line.origin {
+                        DiffLineOrigin::Context => 0u8,
+                        DiffLineOrigin::Addition => 1,
+                        DiffLineOrigin::Deletion => 2,
+                    };
+                    // 0xff never appears in UTF-8, so it separates lines unambiguously
+                    let hash = fnv1a(hash, &[origin]);
+                    fnv1a(fnv1a(hash, line.content.as_bytes()), &[0xff])
+                })
         })
         .collect()
 }
+
+/// Compute per-hunk hashes for all deltas, keyed by file path.
+pub fn compute_diff_hashes(deltas: &[FileDelta]) -> HashMap<String, Vec<u64>> {
+    deltas
+        .iter()
+        .map(|d| (d.path.to_string_lossy().to_string(), hash_hunks(d)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        compute_diff_hashes, fnv1a, hash_hunks, FileReviewStatus, LineRanges, ReviewState,
+    };
+    use crate::git::types::{DiffLine, DiffLineOrigin, FileDelta, FileStatus, Hunk};
+    use std::path::PathBuf;
+
+    fn delta(lines: &[(DiffLineOrigin, &str)]) -> FileDelta {
+        FileDelta {
+            path: PathBuf::from("a.rs"),
+            old_path: None,
+            status: FileStatus::Modified,
+            hunks: vec![Hunk {
+                header: "@@ -1 +1 @@".to_string(),
+                lines: lines
+                    .iter()
+                    .map(|(origin, text)| DiffLine {
+                        origin: origin.clone(),
+                        old_lineno: None,
+                        new_lineno: None,
+                        content: text.to_string(),
+                    })
+                    .collect(),
+                additions: 0,
+                deletions: 0,
+            }],
+            additions: 0,
+            deletions: 0,
+            binary: false,
+            has_conflicts: false,
+            mode: 0o100644,
+        }
+    }
+
+    #[test]
+    fn editing_one_hunk_keeps_others_reviewed() {
+        use DiffLineOrigin::{Addition, Context};
+        let partial = FileReviewStatus::PartiallyChanged {
+            reviewed_hunks: 1,
+            changed_hunks: 1,
+        };
+        let mut review = ReviewState::default();
+        let before = delta(&[
+            (Context, "a"),
+            (Addition, "b"),
+            (Context, "c"),
+            (Addition, "d"),
+        ]);
+        review.on_diff_refresh(compute_diff_hashes(&[before]));
+        review.mark_hunk_reviewed("a.rs", 0);
+        assert_eq!(review.status("a.rs"), partial);
+        review.mark_reviewed("a.rs");
+        assert_eq!(review.status("a.rs"), FileReviewStatus::Reviewed);
+
+        // The second hunk changes; the first stays reviewed
+        let after = delta(&[
+            (Context, "a"),
+            (Addition, "b"),
+            (Context, "c"),
+            (Addition, "e"),
+        ]);
+        review.on_diff_refresh(compute_diff_hashes(&[after]));
+        assert_eq!(review.status("a.rs"), partial);
+
+        // Once every reviewed hunk changed, the whole file is flagged
+        let rewritten = delta(&[
+            (Context, "a"),
+            (Addition, "x"),
+            (Context, "c"),
+            (Addition, "e"),
+        ]);
+        review.on_diff_refresh(compute_diff_hashes(&[rewritten]));
+        assert_eq!(review.status("a.rs"), FileReviewStatus::ChangedSinceReview);
+    }
+
+    #[test]
+    fn hunk_hashes_are_stable() {
+        use DiffLineOrigin::{Addition, Context, Deletion};
+        // Reference values of 64-bit FNV-1a
+        assert_eq!(fnv1a(0xcbf2_9ce4_8422_2325, b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(0xcbf2_9ce4_8422_2325, b"a"), 0xaf63_dc4c_8601_ec8c);
+
+        // Pinned, so a change to the hashing shows up here rather than as
+        // reviews silently lost from saved sessions
+        let d = delta(&[(Context, "a"), (Deletion, "b"), (Addition, "c")]);
+        assert_eq!(hash_hunks(&d), [0xf94a_bcc1_0caf_a6dd]);
+    }
+
+    #[test]
+    fn line_ranges_merge_adjacent_lines() {
+        let mut ranges = LineRanges::default();
+        for line in [3, 1, 7, 2, 5] {
+            ranges.insert(line);
+        }
+        assert_eq!(ranges.0, vec![(1, 3), (5, 5), (7, 7)]);
+
+        ranges.insert(6);
+        assert_eq!(ranges.0, vec![(1, 3), (5, 7)]);
+        assert!(ranges.contains(2));
+        assert!(!ranges.contains(4));
+        assert!(!ranges.contains(8));
+    }
+}
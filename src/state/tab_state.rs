@@ -0,0 +1,52 @@
+use crate::async_diff::DiffWorker;
+use crate::git::types::ComparisonTarget;
+
+use super::annotation_state::AnnotationHistory;
+use super::app_state::Bookmark;
+use super::{
+    AnnotationState, ChecklistState, DiffOptions, DiffState, NavigatorState, ReviewState,
+    SelectionState,
+};
+
+/// Everything that belongs to one comparison target. Only background tabs are
+/// stored here; the active tab's state lives in the `App` and `AppState` fields
+/// and is swapped with its slot in `AppState::tabs` on every tab switch, so the
+/// active slot only ever holds an unused placeholder.
+pub struct TabState {
+    pub target: ComparisonTarget,
+    pub target_label: String,
+    pub worker: DiffWorker,
+    pub generation: u64,
+    pub diff: DiffState,
+    pub navigator: NavigatorState,
+    pub selection: SelectionState,
+    pub review: ReviewState,
+    pub annotations: AnnotationState,
+    pub annotation_history: AnnotationHistory,
+    pub checklist: ChecklistState,
+    pub bookmarks: Vec<Bookmark>,
+}
+
+impl TabState {
+    pub fn new(
+        target: ComparisonTarget,
+        target_label: String,
+        worker: DiffWorker,
+        options: DiffOptions,
+    ) -> Self {
+        Self {
+            target,
+            target_label,
+            worker,
+            generation: 0,
+            diff: DiffState::new(options),
+            navigator: NavigatorState::new(),
+            selection: SelectionState::default(),
+            review: ReviewState::default(),
+            annotations: AnnotationState::default(),
+            annotation_history: AnnotationHistory::default(),
+            checklist: ChecklistState::new(),
+            bookmarks: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use anyhow::{bail, Context, Result};
+
+/// Command that prints a tags file for the repository on stdout.
+const DEFAULT_TAGS_COMMAND: &str = "ctags -R --fields=+nl -f - .";
+
+/// Where a symbol is defined, from one line of ctags output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagEntry {
+    /// Path relative to the repository root.
+    pub file: PathBuf,
+    pub line: u32,
+}
+
+/// Run `command` (or ctags) in `workdir` and index its output by symbol name.
+pub fn load_tags(workdir: &Path, command: Option<&str>) -> Result<HashMap<String, Vec<TagEntry>>> {
+    let command = command.unwrap_or(DEFAULT_TAGS_COMMAND);
+    let output = Command::new("sh")
+        .args(["-c", command])
+        .current_dir(workdir)
+        .stdin(Stdio::null())
+        .output()
+        .with_context(|| format!("Failed to run `{command}`"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("`{command}` failed: {}", stderr.trim());
+    }
+    Ok(parse_tags(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse tags in the `name<TAB>file<TAB>excmd;"<TAB>fields` format. The line
+/// comes from the `line:` field, or from the ex command when it's a number;
+/// entries with only a search pattern are skipped.
+pub fn parse_tags(output: &str) -> HashMap<String, Vec<TagEntry>> {
+    let mut tags: HashMap<String, Vec<TagEntry>> = HashMap::new();
+    for line in output.lines().filter(|l| !l.starts_with("!_TAG_")) {
+        let mut cols = line.split('\t');
+        let (Some(name), Some(file), Some(excmd)) = (cols.next(), cols.next(), cols.next()) else {
+            continue;
+        };
+        let lineno = cols
+            .find_map(|field| field.strip_prefix("line:"))
+            .and_then(|n| n.parse().ok())
+            .or_else(|| excmd.trim_end_matches(";\"").parse().ok());
+        let Some(lineno) = lineno else {
+            continue;
+        };
+        tags.entry(name.to_string()).or_default().push(TagEntry {
+            file: PathBuf::from(file.strip_prefix("./").unwrap_or(file)),
+            line: lineno,
+        });
+    }
+    tags
+}
+
+/// Identifier-like words in a line of code, left to right.
+pub fn identifiers(line: &str) -> impl Iterator<Item = &str> {
+    line.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|w| {
+            w.chars()
+                .next()
+                .is_some_and(|c| c.is_alphabetic() || c == '_')
+        })
+}
+
+/// The definition for the first symbol on `line` that has a tag, skipping tags
+/// that point back at the line itself (`here`).
+pub fn find_definition<'a>(
+    tags: &'a HashMap<String, Vec<TagEntry>>,
+    line: &str,
+    here: (&Path, u32),
+) -> Option<(&'a str, &'a TagEntry)> {
+    identifiers(line).find_map(|word| {
+        let (name, entries) = tags.get_key_value(word)?;
+        entries
+            .iter()
+            .find(|e| (e.file.as_path(), e.line) != here)
+            .map(|e| (name.as_str(), e))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_definition, identifiers, parse_tags, TagEntry};
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn parses_ctags_output() {
+        let output = concat!(
+            "!_TAG_FILE_FORMAT\t2\t/extended format/\n",
+            "render\t./src/app.rs\t/^    fn render(&self) {$/;\"\tf\tline:42\tlanguage:Rust\n",
+            "render\tsrc/ui.rs\t7;\"\tf\n",
+            "Pattern\tsrc/lib.rs\t/^struct Pattern;$/;\"\ts\n",
+        );
+        let tags = parse_tags(output);
+        assert_eq!(tags.len(), 1);
+        assert_eq!(
+            tags["render"],
+            [
+                TagEntry {
+                    file: PathBuf::from("src/app.rs"),
+                    line: 42
+                },
+                TagEntry {
+                    file: PathBuf::from("src/ui.rs"),
+                    line: 7
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_first_tagged_symbol_elsewhere() {
+        let tags = parse_tags(concat!(
+            "helper\tsrc/a.rs\t3;\"\tf\n",
+            "main\tsrc/main.rs\t10;\"\tf\n",
+        ));
+        let words: Vec<&str> = identifiers("let x2 = helper(1, _y);").collect();
+        assert_eq!(words, ["let", "x2", "helper", "_y"]);
+
+        let line = "fn main() { helper(); }";
+        let (name, entry) = find_definition(&tags, line, (Path::new("src/main.rs"), 10)).unwrap();
+        assert_eq!(name, "helper");
+        assert_eq!(entry.line, 3);
+        assert!(find_definition(&tags, "fn main()", (Path::new("src/main.rs"), 10)).is_none());
+    }
+}
@@ -1,3 +1,4 @@
+use ratatui::buffer::Buffer;
 use ratatui::style::Color;
 use serde::Deserialize;
 
@@ -18,12 +19,18 @@ pub struct Theme {
     // Diff
     pub diff_add_bg: Color,
     pub diff_del_bg: Color,
+    /// Changed characters of a deletion and addition paired in split view.
+    pub diff_add_char_bg: Color,
+    pub diff_del_char_bg: Color,
     pub diff_add_fg: Color,
     pub diff_del_fg: Color,
     pub diff_context_fg: Color,
     pub diff_hunk_header_fg: Color,
     pub visual_select_bg: Color,
     pub cursor_line_fg: Color,
+    /// Gutter background of the cursor row. Defaults to `accent`; set a subtler
+    /// color for a less prominent cursor.
+    pub cursor_line_bg: Color,
     pub collapsed_bg: Color,
     pub search_match_bg: Color,
 
@@ -53,6 +60,42 @@ pub struct SyntaxColors {
     pub default_fg: Color,
 }
 
+/// How far towards the full add/delete color the hottest navigator row goes,
+/// so file names stay readable on top of it.
+const HEAT_MAX_INTENSITY: f32 = 0.35;
+
+/// RGB value of a color, using the xterm defaults for the named ANSI colors.
+fn rgb(color: Color) -> Option<(u8, u8, u8)> {
+    Some(match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Black => (0, 0, 0),
+        Color::Red => (205, 0, 0),
+        Color::Green => (0, 205, 0),
+        Color::Yellow => (205, 205, 0),
+        Color::Blue => (0, 0, 238),
+        Color::Magenta => (205, 0, 205),
+        Color::Cyan => (0, 205, 205),
+        Color::Gray => (229, 229, 229),
+        Color::DarkGray => (127, 127, 127),
+        Color::LightRed => (255, 0, 0),
+        Color::LightGreen => (0, 255, 0),
+        Color::LightYellow => (255, 255, 0),
+        Color::LightBlue => (92, 92, 255),
+        Color::LightMagenta => (255, 0, 255),
+        Color::LightCyan => (0, 255, 255),
+        Color::White => (255, 255, 255),
+        _ => return None,
+    })
+}
+
+/// Linear blend from `a` (t = 0) to `b` (t = 1); `None` for `Reset` and
+/// indexed colors, which have no fixed RGB value.
+fn mix(a: Color, b: Color, t: f32) -> Option<Color> {
+    let ((r1, g1, b1), (r2, g2, b2)) = (rgb(a)?, rgb(b)?);
+    let lerp = |x: u8, y: u8| (x as f32 + (y as f32 - x as f32) * t).round() as u8;
+    Some(Color::Rgb(lerp(r1, r2), lerp(g1, g2), lerp(b1, b2)))
+}
+
 pub const THEME_NAMES: &[&str] = &[
     "one-dark",
     "github-dark",
@@ -60,28 +103,220 @@ pub const THEME_NAMES: &[&str] = &[
     "catppuccin-mocha",
     "tokyo-night",
     "solarized-dark",
+    "github-light",
 ];
 
 impl Theme {
     pub fn from_name(name: &str) -> Self {
-        match name {
+        match name.to_ascii_lowercase().as_str() {
             "github-dark" => github_dark(),
             "dracula" => dracula(),
             "catppuccin-mocha" => catppuccin_mocha(),
             "tokyo-night" => tokyo_night(),
             "solarized-dark" => solarized_dark(),
+            "github-light" => github_light(),
             _ => one_dark(),
         }
     }
+
+    /// Heuristic: treat the theme as dark when the perceived luma of its
+    /// `surface` color is below the midpoint. Non-RGB surfaces count as dark.
+    pub fn is_dark(&self) -> bool {
+        match self.surface {
+            Color::Rgb(r, g, b) => {
+                let luma = 0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32;
+                luma < 128.0
+            }
+            Color::White | Color::Gray => false,
+            _ => true,
+        }
+    }
+
+    /// Foreground for text drawn on top of `accent` (cursor gutter, context bar badge).
+    /// Dark themes use bright accents, so dark text reads best; light themes the reverse.
+    pub fn on_accent_fg(&self) -> Color {
+        if self.accent == Color::Reset {
+            Color::Reset
+        } else if self.is_dark() {
+            Color::Black
+        } else {
+            Color::White
+        }
+    }
+
+    /// Navigator row background for a file's change size relative to the largest
+    /// file in the diff: `surface` for untouched files, shading towards a mix of
+    /// the add and delete colors (weighted by the file's own split) as it grows.
+    /// `None` when the colors involved have no fixed RGB value.
+    pub fn heat_bg(&self, additions: usize, deletions: usize, max_changes: usize) -> Option<Color> {
+        let changes = additions + deletions;
+        if changes == 0 || max_changes == 0 {
+            return None;
+        }
+        let add_share = additions as f32 / changes as f32;
+        let hot = mix(self.diff_del_fg, self.diff_add_fg, add_share)?;
+        let ratio = (changes as f32 / max_changes as f32).min(1.0);
+        mix(self.surface, hot, ratio * HEAT_MAX_INTENSITY)
+    }
+
+    /// A theme with every color slot set to `Color::Reset`, so the terminal's own
+    /// palette is used throughout. Selected when `NO_COLOR` or `--no-color` is set.
+    pub fn no_color() -> Self {
+        let c = Color::Reset;
+        Theme {
+            name: "no-color".to_string(),
+            accent: c,
+            secondary: c,
+            text: c,
+            text_muted: c,
+            surface: c,
+            selection_bg: c,
+            selection_inactive_bg: c,
+            diff_add_bg: c,
+            diff_del_bg: c,
+            diff_add_char_bg: c,
+            diff_del_char_bg: c,
+            diff_add_fg: c,
+            diff_del_fg: c,
+            diff_context_fg: c,
+            diff_hunk_header_fg: c,
+            visual_select_bg: c,
+            cursor_line_fg: c,
+            cursor_line_bg: c,
+            collapsed_bg: c,
+            search_match_bg: c,
+            success: c,
+            error: c,
+            warning: c,
+            syntax: SyntaxColors {
+                comment: c,
+                keyword: c,
+                string: c,
+                number: c,
+                function: c,
+                type_name: c,
+                variable: c,
+                operator: c,
+                property: c,
+                tag: c,
+                punctuation: c,
+                default_fg: c,
+            },
+        }
+    }
+}
+
+/// How many colors the terminal can display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSupport {
+    /// `NO_COLOR` / `--no-color`: render with the terminal's default colors only.
+    None,
+    /// `COLORTERM` names something other than true color: RGB colors are mapped
+    /// to the xterm 256 palette.
+    Ansi256,
+    TrueColor,
+}
+
+impl ColorSupport {
+    /// Detect color support from CLI flags and the environment. `--color` forces
+    /// true color; `--no-color` or a non-empty `NO_COLOR` disables color entirely.
+    /// Otherwise true color is assumed unless `COLORTERM` says otherwise.
+    pub fn detect(force_color: bool, no_color: bool) -> Self {
+        if force_color {
+            return ColorSupport::TrueColor;
+        }
+        let no_color_env = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+        if no_color || no_color_env {
+            return ColorSupport::None;
+        }
+        match std::env::var("COLORTERM") {
+            Ok(v) if !v.is_empty() && v != "truecolor" && v != "24bit" => ColorSupport::Ansi256,
+            _ => ColorSupport::TrueColor,
+        }
+    }
+
+    /// Adapt a theme to this level of color support.
+    pub fn adapt_theme(self, theme: Theme) -> Theme {
+        match self {
+            ColorSupport::None => Theme::no_color(),
+            ColorSupport::Ansi256 | ColorSupport::TrueColor => theme,
+        }
+    }
+
+    /// Map a single color to this level of color support.
+    pub fn adapt_color(self, color: Color) -> Color {
+        match self {
+            ColorSupport::None => Color::Reset,
+            ColorSupport::Ansi256 => to_ansi256(color),
+            ColorSupport::TrueColor => color,
+        }
+    }
+
+    /// Post-process a rendered frame so colors outside the theme (agent terminal
+    /// output, fixed highlight colors) also respect the terminal's capabilities.
+    pub fn adapt_buffer(self, buf: &mut Buffer) {
+        if self == ColorSupport::TrueColor {
+            return;
+        }
+        for cell in buf.content.iter_mut() {
+            cell.fg = self.adapt_color(cell.fg);
+            cell.bg = self.adapt_color(cell.bg);
+        }
+    }
+}
+
+/// Map an RGB color to the nearest entry of the xterm 256-color palette
+/// (6x6x6 color cube or the 24-step grayscale ramp). Other colors pass through.
+fn to_ansi256(color: Color) -> Color {
+    let Color::Rgb(r, g, b) = color else {
+        return color;
+    };
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    let nearest_level = |v: u8| -> usize {
+        LEVELS
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, &l)| (l as i32 - v as i32).abs())
+            .map(|(i, _)| i)
+            .unwrap_or(0)
+    };
+    let dist = |a: (u8, u8, u8)| -> i32 {
+        let dr = a.0 as i32 - r as i32;
+        let dg = a.1 as i32 - g as i32;
+        let db = a.2 as i32 - b as i32;
+        dr * dr + dg * dg + db * db
+    };
+
+    let (ri, gi, bi) = (nearest_level(r), nearest_level(g), nearest_level(b));
+    let cube = (LEVELS[ri], LEVELS[gi], LEVELS[bi]);
+    let cube_index = 16 + 36 * ri + 6 * gi + bi;
+
+    let avg = (r as u32 + g as u32 + b as u32) / 3;
+    let gray_step = ((avg.saturating_sub(8)) / 10).min(23) as u8;
+    let gray_level = 8 + gray_step * 10;
+    let gray = (gray_level, gray_level, gray_level);
+
+    if dist(gray) < dist(cube) {
+        Color::Indexed(232 + gray_step)
+    } else {
+        Color::Indexed(cube_index as u8)
+    }
+}
+
+fn theme_index(name: &str) -> usize {
+    THEME_NAMES
+        .iter()
+        .position(|n| n.eq_ignore_ascii_case(name))
+        .unwrap_or(0)
 }
 
 pub fn next_theme(current: &str) -> &'static str {
-    let idx = THEME_NAMES.iter().position(|&n| n == current).unwrap_or(0);
+    let idx = theme_index(current);
     THEME_NAMES[(idx + 1) % THEME_NAMES.len()]
 }
 
 pub fn prev_theme(current: &str) -> &'static str {
-    let idx = THEME_NAMES.iter().position(|&n| n == current).unwrap_or(0);
+    let idx = theme_index(current);
     if idx == 0 {
         THEME_NAMES[THEME_NAMES.len() - 1]
     } else {
@@ -100,6 +335,40 @@ pub fn parse_hex_color(hex: &str) -> Option<Color> {
     Some(Color::Rgb(r, g, b))
 }
 
+/// `#rrggbb` for a color, or `default` for the terminal's own colors.
+fn hex(color: Color) -> String {
+    rgb(color).map_or_else(
+        || "default".to_string(),
+        |(r, g, b)| format!("#{r:02x}{g:02x}{b:02x}"),
+    )
+}
+
+/// One line per theme with its accent and diff backgrounds, for `--list-themes`.
+/// Each line starts with a swatch of those three colors unless `color` is
+/// `ColorSupport::None`.
+pub fn format_theme_list(themes: &[Theme], color: ColorSupport) -> String {
+    use crossterm::style::Stylize;
+
+    let name_width = themes.iter().map(|t| t.name.len()).max().unwrap_or(0);
+    let mut out = String::new();
+    for theme in themes {
+        let colors = [theme.accent, theme.diff_add_bg, theme.diff_del_bg];
+        if color != ColorSupport::None {
+            for c in colors {
+                let c: crossterm::style::Color = color.adapt_color(c).into();
+                out.push_str(&format!("{}", "  ".on(c)));
+            }
+            out.push(' ');
+        }
+        let [accent, add_bg, del_bg] = colors.map(hex);
+        out.push_str(&format!(
+            "{:name_width$}  accent={accent}  add_bg={add_bg}  del_bg={del_bg}\n",
+            theme.name
+        ));
+    }
+    out
+}
+
 // ── Serde-compatible override struct ──────────────────────────────
 
 #[derive(Debug, Deserialize, Default, Clone)]
@@ -113,12 +382,15 @@ pub struct ThemeOverrides {
     pub selection_inactive_bg: Option<String>,
     pub diff_add_bg: Option<String>,
     pub diff_del_bg: Option<String>,
+    pub diff_add_char_bg: Option<String>,
+    pub diff_del_char_bg: Option<String>,
     pub diff_add_fg: Option<String>,
     pub diff_del_fg: Option<String>,
     pub diff_context_fg: Option<String>,
     pub diff_hunk_header_fg: Option<String>,
     pub visual_select_bg: Option<String>,
     pub cursor_line_fg: Option<String>,
+    pub cursor_line_bg: Option<String>,
     pub collapsed_bg: Option<String>,
     pub search_match_bg: Option<String>,
     pub success: Option<String>,
@@ -154,7 +426,11 @@ pub fn apply_overrides(theme: &mut Theme, overrides: &ThemeOverrides) {
             }
         };
     }
+    let cursor_follows_accent = theme.cursor_line_bg == theme.accent;
     apply!(accent);
+    if cursor_follows_accent {
+        theme.cursor_line_bg = theme.accent;
+    }
     apply!(secondary);
     apply!(text);
     apply!(text_muted);
@@ -163,12 +439,15 @@ pub fn apply_overrides(theme: &mut Theme, overrides: &ThemeOverrides) {
     apply!(selection_inactive_bg);
     apply!(diff_add_bg);
     apply!(diff_del_bg);
+    apply!(diff_add_char_bg);
+    apply!(diff_del_char_bg);
     apply!(diff_add_fg);
     apply!(diff_del_fg);
     apply!(diff_context_fg);
     apply!(diff_hunk_header_fg);
     apply!(visual_select_bg);
     apply!(cursor_line_fg);
+    apply!(cursor_line_bg);
     apply!(collapsed_bg);
     apply!(search_match_bg);
     apply!(success);
@@ -214,12 +493,15 @@ fn one_dark() -> Theme {
         selection_inactive_bg: Color::Rgb(35, 35, 45),
         diff_add_bg: Color::Rgb(0, 30, 0),
         diff_del_bg: Color::Rgb(40, 0, 0),
+        diff_add_char_bg: Color::Rgb(0, 70, 0),
+        diff_del_char_bg: Color::Rgb(90, 0, 0),
         diff_add_fg: Color::Green,
         diff_del_fg: Color::Red,
         diff_context_fg: Color::Rgb(171, 178, 191),
         diff_hunk_header_fg: Color::Magenta,
         visual_select_bg: Color::Rgb(70, 50, 100),
         cursor_line_fg: Color::Yellow,
+        cursor_line_bg: Color::Cyan,
         collapsed_bg: Color::Rgb(20, 20, 20),
         search_match_bg: Color::Rgb(60, 50, 10),
         success: Color::Green,
@@ -254,12 +536,15 @@ fn github_dark() -> Theme {
         selection_inactive_bg: Color::Rgb(30, 40, 58),
         diff_add_bg: Color::Rgb(18, 40, 24),
         diff_del_bg: Color::Rgb(50, 18, 18),
+        diff_add_char_bg: Color::Rgb(35, 85, 45),
+        diff_del_char_bg: Color::Rgb(105, 35, 35),
         diff_add_fg: Color::Rgb(63, 185, 80),
         diff_del_fg: Color::Rgb(248, 81, 73),
         diff_context_fg: Color::Rgb(230, 237, 243),
         diff_hunk_header_fg: Color::Rgb(188, 140, 255),
         visual_select_bg: Color::Rgb(50, 60, 90),
         cursor_line_fg: Color::Rgb(210, 153, 34),
+        cursor_line_bg: Color::Rgb(88, 166, 255),
         collapsed_bg: Color::Rgb(13, 17, 23),
         search_match_bg: Color::Rgb(50, 40, 10),
         success: Color::Rgb(63, 185, 80),
@@ -294,12 +579,15 @@ fn dracula() -> Theme {
         selection_inactive_bg: Color::Rgb(55, 58, 75),
         diff_add_bg: Color::Rgb(15, 40, 15),
         diff_del_bg: Color::Rgb(45, 10, 10),
+        diff_add_char_bg: Color::Rgb(30, 85, 30),
+        diff_del_char_bg: Color::Rgb(95, 25, 25),
         diff_add_fg: Color::Rgb(80, 250, 123),
         diff_del_fg: Color::Rgb(255, 85, 85),
         diff_context_fg: Color::Rgb(248, 248, 242),
         diff_hunk_header_fg: Color::Rgb(255, 121, 198),
         visual_select_bg: Color::Rgb(80, 60, 120),
         cursor_line_fg: Color::Rgb(241, 250, 140),
+        cursor_line_bg: Color::Rgb(139, 233, 253),
         collapsed_bg: Color::Rgb(30, 31, 40),
         search_match_bg: Color::Rgb(60, 55, 15),
         success: Color::Rgb(80, 250, 123),
@@ -334,12 +622,15 @@ fn catppuccin_mocha() -> Theme {
         selection_inactive_bg: Color::Rgb(40, 40, 58),
         diff_add_bg: Color::Rgb(10, 35, 20),
         diff_del_bg: Color::Rgb(45, 10, 15),
+        diff_add_char_bg: Color::Rgb(25, 75, 40),
+        diff_del_char_bg: Color::Rgb(95, 25, 35),
         diff_add_fg: Color::Rgb(166, 227, 161),
         diff_del_fg: Color::Rgb(243, 139, 168),
         diff_context_fg: Color::Rgb(205, 214, 244),
         diff_hunk_header_fg: Color::Rgb(245, 194, 231),
         visual_select_bg: Color::Rgb(60, 50, 90),
         cursor_line_fg: Color::Rgb(249, 226, 175),
+        cursor_line_bg: Color::Rgb(137, 180, 250),
         collapsed_bg: Color::Rgb(24, 24, 37),
         search_match_bg: Color::Rgb(55, 48, 15),
         success: Color::Rgb(166, 227, 161),
@@ -374,12 +665,15 @@ fn tokyo_night() -> Theme {
         selection_inactive_bg: Color::Rgb(33, 37, 55),
         diff_add_bg: Color::Rgb(10, 35, 15),
         diff_del_bg: Color::Rgb(45, 10, 15),
+        diff_add_char_bg: Color::Rgb(25, 75, 35),
+        diff_del_char_bg: Color::Rgb(95, 25, 35),
         diff_add_fg: Color::Rgb(158, 206, 106),
         diff_del_fg: Color::Rgb(247, 118, 142),
         diff_context_fg: Color::Rgb(192, 202, 245),
         diff_hunk_header_fg: Color::Rgb(187, 154, 247),
         visual_select_bg: Color::Rgb(55, 50, 95),
         cursor_line_fg: Color::Rgb(224, 175, 104),
+        cursor_line_bg: Color::Rgb(122, 162, 247),
         collapsed_bg: Color::Rgb(20, 22, 30),
         search_match_bg: Color::Rgb(50, 42, 12),
         success: Color::Rgb(158, 206, 106),
@@ -414,12 +708,15 @@ fn solarized_dark() -> Theme {
         selection_inactive_bg: Color::Rgb(3, 44, 55),
         diff_add_bg: Color::Rgb(0, 30, 10),
         diff_del_bg: Color::Rgb(40, 5, 5),
+        diff_add_char_bg: Color::Rgb(0, 70, 25),
+        diff_del_char_bg: Color::Rgb(90, 15, 15),
         diff_add_fg: Color::Rgb(133, 153, 0),
         diff_del_fg: Color::Rgb(220, 50, 47),
         diff_context_fg: Color::Rgb(147, 161, 161),
         diff_hunk_header_fg: Color::Rgb(211, 54, 130),
         visual_select_bg: Color::Rgb(30, 60, 80),
         cursor_line_fg: Color::Rgb(181, 137, 0),
+        cursor_line_bg: Color::Rgb(38, 139, 210),
         collapsed_bg: Color::Rgb(0, 26, 33),
         search_match_bg: Color::Rgb(40, 35, 5),
         success: Color::Rgb(133, 153, 0),
@@ -441,3 +738,119 @@ fn solarized_dark() -> Theme {
         },
     }
 }
+
+fn github_light() -> Theme {
+    Theme {
+        name: "github-light".to_string(),
+        accent: Color::Rgb(9, 105, 218),
+        secondary: Color::Rgb(130, 80, 223),
+        text: Color::Rgb(31, 35, 40),
+        text_muted: Color::Rgb(101, 109, 118),
+        surface: Color::Rgb(255, 255, 255),
+        selection_bg: Color::Rgb(221, 234, 252),
+        selection_inactive_bg: Color::Rgb(234, 238, 242),
+        diff_add_bg: Color::Rgb(218, 251, 225),
+        diff_del_bg: Color::Rgb(255, 235, 233),
+        diff_add_char_bg: Color::Rgb(171, 242, 188),
+        diff_del_char_bg: Color::Rgb(255, 192, 192),
+        diff_add_fg: Color::Rgb(26, 127, 55),
+        diff_del_fg: Color::Rgb(207, 34, 46),
+        diff_context_fg: Color::Rgb(31, 35, 40),
+        diff_hunk_header_fg: Color::Rgb(130, 80, 223),
+        visual_select_bg: Color::Rgb(200, 220, 255),
+        cursor_line_fg: Color::Rgb(154, 103, 0),
+        cursor_line_bg: Color::Rgb(9, 105, 218),
+        collapsed_bg: Color::Rgb(246, 248, 250),
+        search_match_bg: Color::Rgb(255, 248, 197),
+        success: Color::Rgb(26, 127, 55),
+        error: Color::Rgb(207, 34, 46),
+        warning: Color::Rgb(154, 103, 0),
+        syntax: SyntaxColors {
+            comment: Color::Rgb(110, 119, 129),
+            keyword: Color::Rgb(207, 34, 46),
+            string: Color::Rgb(10, 48, 105),
+            number: Color::Rgb(5, 80, 174),
+            function: Color::Rgb(130, 80, 223),
+            type_name: Color::Rgb(149, 56, 0),
+            variable: Color::Rgb(31, 35, 40),
+            operator: Color::Rgb(207, 34, 46),
+            property: Color::Rgb(5, 80, 174),
+            tag: Color::Rgb(17, 99, 41),
+            punctuation: Color::Rgb(101, 109, 118),
+            default_fg: Color::Rgb(31, 35, 40),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ansi256_maps_to_cube_and_grayscale() {
+        assert_eq!(to_ansi256(Color::Rgb(255, 0, 0)), Color::Indexed(196));
+        assert_eq!(to_ansi256(Color::Rgb(0, 0, 0)), Color::Indexed(16));
+        assert_eq!(to_ansi256(Color::Rgb(128, 128, 128)), Color::Indexed(244));
+        assert_eq!(to_ansi256(Color::Reset), Color::Reset);
+    }
+
+    #[test]
+    fn lists_themes_with_hex_colors() {
+        let mut light = Theme::from_name("github-light");
+        light.accent = Color::Rgb(1, 2, 255);
+        let out = format_theme_list(&[Theme::from_name("one-dark"), light], ColorSupport::None);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("one-dark      accent=#"));
+        assert!(lines[1].starts_with("github-light  accent=#0102ff  add_bg=#"));
+        assert!(!out.contains('\x1b'));
+    }
+
+    #[test]
+    fn heat_bg_scales_with_change_size() {
+        let theme = Theme::from_name("one-dark");
+        assert_eq!(theme.heat_bg(0, 0, 10), None);
+        assert_eq!(theme.heat_bg(1, 0, 0), None);
+        assert_eq!(
+            mix(Color::Rgb(0, 0, 0), Color::Rgb(200, 100, 0), 0.5),
+            Some(Color::Rgb(100, 50, 0))
+        );
+        assert_eq!(mix(Color::Reset, Color::Rgb(1, 2, 3), 0.5), None);
+
+        let distance = |c: Option<Color>| match (c, theme.surface) {
+            (Some(Color::Rgb(r, g, b)), Color::Rgb(sr, sg, sb)) => {
+                r.abs_diff(sr) as u32 + g.abs_diff(sg) as u32 + b.abs_diff(sb) as u32
+            }
+            _ => panic!("expected RGB colors"),
+        };
+        assert!(distance(theme.heat_bg(10, 0, 10)) > distance(theme.heat_bg(1, 0, 10)));
+        assert!(Theme::no_color().heat_bg(5, 5, 10).is_none());
+    }
+
+    #[test]
+    fn cursor_line_bg_follows_accent_unless_overridden() {
+        let mut theme = Theme::from_name("one-dark");
+        let overrides = ThemeOverrides {
+            accent: Some("#112233".to_string()),
+            ..Default::default()
+        };
+        apply_overrides(&mut theme, &overrides);
+        assert_eq!(theme.cursor_line_bg, Color::Rgb(0x11, 0x22, 0x33));
+
+        let overrides = ThemeOverrides {
+            cursor_line_bg: Some("#202020".to_string()),
+            ..Default::default()
+        };
+        apply_overrides(&mut theme, &overrides);
+        assert_eq!(theme.cursor_line_bg, Color::Rgb(0x20, 0x20, 0x20));
+        assert_eq!(theme.accent, Color::Rgb(0x11, 0x22, 0x33));
+    }
+
+    #[test]
+    fn theme_lookup_is_case_insensitive() {
+        assert_eq!(Theme::from_name("GitHub-Light").name, "github-light");
+        assert!(!Theme::from_name("github-light").is_dark());
+        assert!(Theme::from_name("dracula").is_dark());
+        assert_eq!(next_theme("SOLARIZED-DARK"), "github-light");
+    }
+}